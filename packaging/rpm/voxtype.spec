@@ -18,6 +18,7 @@ Requires:       curl
 Recommends:     wtype
 Recommends:     wl-clipboard
 Suggests:       ydotool
+Suggests:       libxkbcommon
 Suggests:       libnotify
 Suggests:       pipewire
 
@@ -0,0 +1,118 @@
+//! WAV file loading for offline transcription
+//!
+//! Used by `voxtype transcribe` and `voxtype bench`. Any sample format and
+//! channel count that hound understands is converted to mono f32 at 16kHz.
+
+use hound::{SampleFormat, WavReader, WavSpec};
+use std::path::Path;
+
+/// Sample rate expected by the transcription engines
+pub const TARGET_SAMPLE_RATE: u32 = 16000;
+
+/// Read a WAV file as mono f32 samples at its original sample rate
+pub fn read_mono(path: &Path) -> Result<(Vec<f32>, WavSpec), hound::Error> {
+    let reader = WavReader::open(path)?;
+    let spec = reader.spec();
+
+    let samples: Vec<f32> = match spec.sample_format {
+        SampleFormat::Int => {
+            let max_val = (1 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .into_samples::<i32>()
+                .filter_map(|s| s.ok())
+                .map(|s| s as f32 / max_val)
+                .collect()
+        }
+        SampleFormat::Float => reader
+            .into_samples::<f32>()
+            .filter_map(|s| s.ok())
+            .collect(),
+    };
+
+    // Mix to mono if stereo
+    let mono = if spec.channels > 1 {
+        samples
+            .chunks(spec.channels as usize)
+            .map(|chunk| chunk.iter().sum::<f32>() / chunk.len() as f32)
+            .collect()
+    } else {
+        samples
+    };
+
+    Ok((mono, spec))
+}
+
+/// Read a WAV file as mono f32 samples at 16kHz
+pub fn load_16k_mono(path: &Path) -> Result<Vec<f32>, hound::Error> {
+    let (samples, spec) = read_mono(path)?;
+    Ok(resample(&samples, spec.sample_rate, TARGET_SAMPLE_RATE))
+}
+
+/// Simple linear resampling
+pub fn resample(samples: &[f32], from_rate: u32, to_rate: u32) -> Vec<f32> {
+    if from_rate == to_rate {
+        return samples.to_vec();
+    }
+
+    let ratio = to_rate as f64 / from_rate as f64;
+    let new_len = (samples.len() as f64 * ratio).ceil() as usize;
+    let mut output = Vec::with_capacity(new_len);
+
+    for i in 0..new_len {
+        let src_idx = i as f64 / ratio;
+        let idx = src_idx.floor() as usize;
+        let frac = (src_idx - idx as f64) as f32;
+
+        let sample = if idx + 1 < samples.len() {
+            samples[idx] * (1.0 - frac) + samples[idx + 1] * frac
+        } else {
+            samples.get(idx).copied().unwrap_or(0.0)
+        };
+
+        output.push(sample);
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_wav(path: &Path, spec: WavSpec, samples: &[i16]) {
+        let mut writer = hound::WavWriter::create(path, spec).unwrap();
+        for &s in samples {
+            writer.write_sample(s).unwrap();
+        }
+        writer.finalize().unwrap();
+    }
+
+    #[test]
+    fn test_resample_same_rate_is_identity() {
+        let samples = vec![0.1, 0.2, 0.3];
+        assert_eq!(resample(&samples, 16000, 16000), samples);
+    }
+
+    #[test]
+    fn test_resample_halves_length() {
+        let samples = vec![0.0; 32000];
+        assert_eq!(resample(&samples, 32000, 16000).len(), 16000);
+    }
+
+    #[test]
+    fn test_load_stereo_mixes_to_mono() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("stereo.wav");
+        let spec = WavSpec {
+            channels: 2,
+            sample_rate: 16000,
+            bits_per_sample: 16,
+            sample_format: SampleFormat::Int,
+        };
+        write_wav(&path, spec, &[16384, 0, 16384, 0]);
+
+        let samples = load_16k_mono(&path).unwrap();
+        assert_eq!(samples.len(), 2);
+        assert!((samples[0] - 0.25).abs() < 1e-4);
+    }
+}
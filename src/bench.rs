@@ -0,0 +1,528 @@
+//! Benchmark and accuracy evaluation
+//!
+//! Runs a directory of WAV files through one or more engines/models and
+//! reports load time, real-time factor, peak memory and word/character error
+//! rate. Reference transcripts live next to the audio as `<name>.txt`.
+
+use crate::audio::wav;
+use crate::config::{Config, ParakeetConfig, TranscriptionEngine};
+use crate::error::{TranscribeError, VoxtypeError};
+use crate::text::vocabulary::levenshtein;
+use crate::transcribe;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// One audio file from the benchmark dataset
+pub struct BenchSample {
+    /// File name (without directory)
+    pub name: String,
+    /// Mono f32 samples at 16kHz
+    pub audio: Vec<f32>,
+    /// Reference transcript, if a matching .txt file exists
+    pub reference: Option<String>,
+}
+
+impl BenchSample {
+    /// Audio duration in seconds
+    pub fn duration_secs(&self) -> f64 {
+        self.audio.len() as f64 / wav::TARGET_SAMPLE_RATE as f64
+    }
+}
+
+/// An engine/model combination to benchmark
+pub struct BenchTarget {
+    /// Label shown in reports (e.g., "whisper:base.en")
+    pub label: String,
+    /// Configuration used to create the transcriber
+    pub config: Config,
+}
+
+impl BenchTarget {
+    /// Build a target from a spec: a Whisper model name, "whisper:<model>",
+    /// "parakeet" (configured Parakeet model) or "parakeet:<model>"
+    pub fn from_spec(base: &Config, spec: &str) -> Result<Self, VoxtypeError> {
+        let mut config = base.clone();
+        let (engine, model) = match spec.split_once(':') {
+            Some((engine, model))
+                if engine.eq_ignore_ascii_case("whisper")
+                    || engine.eq_ignore_ascii_case("parakeet") =>
+            {
+                (engine.to_lowercase(), Some(model))
+            }
+            Some((engine, _)) => {
+                return Err(VoxtypeError::Config(format!(
+                    "Unknown engine '{}' in '{}' (expected whisper or parakeet)",
+                    engine, spec
+                )));
+            }
+            None if spec.eq_ignore_ascii_case("whisper")
+                || spec.eq_ignore_ascii_case("parakeet") =>
+            {
+                (spec.to_lowercase(), None)
+            }
+            None => ("whisper".to_string(), Some(spec)),
+        };
+
+        if engine == "parakeet" {
+            config.engine = TranscriptionEngine::Parakeet;
+            let parakeet = config.parakeet.get_or_insert_with(ParakeetConfig::default);
+            if let Some(model) = model {
+                parakeet.model = model.to_string();
+            }
+        } else {
+            config.engine = TranscriptionEngine::Whisper;
+            if let Some(model) = model {
+                config.whisper.model = model.to_string();
+            }
+        }
+
+        Ok(Self::from_config(config))
+    }
+
+    /// Build a target for the engine/model selected in the config
+    pub fn from_config(mut config: Config) -> Self {
+        // Measure in-process so load time and peak memory reflect the model
+        config.whisper.gpu_isolation = false;
+
+        let label = format!("{}:{}", engine_name(config.engine), model_name(&config));
+        Self { label, config }
+    }
+}
+
+/// Results for a single audio file
+#[derive(Debug, Clone, Serialize)]
+pub struct SampleReport {
+    pub file: String,
+    pub audio_secs: f64,
+    pub transcribe_secs: f64,
+    pub rtf: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wer: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cer: Option<f64>,
+    pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Aggregated results for one engine/model
+#[derive(Debug, Clone, Serialize)]
+pub struct TargetReport {
+    pub target: String,
+    pub engine: String,
+    pub model: String,
+    pub load_secs: f64,
+    /// Peak resident memory added while loading and running the target (MB), Linux only
+    pub peak_memory_mb: Option<f64>,
+    pub audio_secs: f64,
+    pub transcribe_secs: f64,
+    /// Transcription time divided by audio duration (lower is faster)
+    pub rtf: f64,
+    /// Corpus-level word error rate over files with a reference
+    pub wer: Option<f64>,
+    /// Corpus-level character error rate over files with a reference
+    pub cer: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub samples: Vec<SampleReport>,
+}
+
+/// Full benchmark report (serialized for `--json`)
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchReport {
+    pub dataset: PathBuf,
+    pub files: usize,
+    pub references: usize,
+    pub results: Vec<TargetReport>,
+}
+
+/// Load all WAV files in a directory, sorted by name
+pub fn load_dataset(dir: &Path) -> Result<Vec<BenchSample>, VoxtypeError> {
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("wav"))
+        })
+        .collect();
+    paths.sort();
+
+    if paths.is_empty() {
+        return Err(VoxtypeError::Config(format!(
+            "No .wav files found in {:?}",
+            dir
+        )));
+    }
+
+    paths
+        .iter()
+        .map(|path| -> Result<BenchSample, VoxtypeError> {
+            let audio = wav::load_16k_mono(path).map_err(|e| {
+                TranscribeError::AudioFormat(format!("Failed to read {:?}: {}", path, e))
+            })?;
+            let reference = std::fs::read_to_string(path.with_extension("txt"))
+                .ok()
+                .map(|text| text.trim().to_string());
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            Ok(BenchSample {
+                name,
+                audio,
+                reference,
+            })
+        })
+        .collect()
+}
+
+/// Run every sample through one target
+pub fn run_target(target: &BenchTarget, samples: &[BenchSample]) -> TargetReport {
+    let config = &target.config;
+    let mut report = TargetReport {
+        target: target.label.clone(),
+        engine: engine_name(config.engine).to_string(),
+        model: model_name(config),
+        load_secs: 0.0,
+        peak_memory_mb: None,
+        audio_secs: samples.iter().map(BenchSample::duration_secs).sum(),
+        transcribe_secs: 0.0,
+        rtf: 0.0,
+        wer: None,
+        cer: None,
+        error: None,
+        samples: Vec::with_capacity(samples.len()),
+    };
+
+    let memory = MemoryProbe::start();
+    let load_start = Instant::now();
+    let transcriber = match transcribe::create_transcriber(config) {
+        Ok(t) => t,
+        Err(e) => {
+            report.error = Some(e.to_string());
+            return report;
+        }
+    };
+    transcriber.prepare();
+    report.load_secs = load_start.elapsed().as_secs_f64();
+
+    let mut word_errors = ErrorCounts::default();
+    let mut char_errors = ErrorCounts::default();
+
+    for sample in samples {
+        let audio_secs = sample.duration_secs();
+        let start = Instant::now();
+        let result = transcriber.transcribe(&sample.audio);
+        let elapsed = start.elapsed().as_secs_f64();
+        report.transcribe_secs += elapsed;
+
+        let (text, error) = match result {
+            Ok(text) => (text, None),
+            Err(e) => (String::new(), Some(e.to_string())),
+        };
+
+        let (wer, cer) = match (&sample.reference, &error) {
+            (Some(reference), None) => {
+                let words = word_edits(reference, &text);
+                let chars = char_edits(reference, &text);
+                word_errors.add(words);
+                char_errors.add(chars);
+                (words.rate(), chars.rate())
+            }
+            _ => (None, None),
+        };
+
+        report.samples.push(SampleReport {
+            file: sample.name.clone(),
+            audio_secs,
+            transcribe_secs: elapsed,
+            rtf: ratio(elapsed, audio_secs),
+            wer,
+            cer,
+            text,
+            error,
+        });
+    }
+
+    report.rtf = ratio(report.transcribe_secs, report.audio_secs);
+    report.wer = word_errors.rate();
+    report.cer = char_errors.rate();
+    report.peak_memory_mb = memory
+        .and_then(|memory| memory.peak_bytes())
+        .map(|b| b as f64 / (1024.0 * 1024.0));
+    report
+}
+
+/// Print a human-readable summary table
+pub fn print_report(report: &BenchReport) {
+    println!(
+        "Dataset: {:?} ({} files, {} with reference)\n",
+        report.dataset, report.files, report.references
+    );
+    println!(
+        "{:<32} {:>8} {:>8} {:>10} {:>8} {:>8}",
+        "TARGET", "LOAD", "RTF", "PEAK MEM", "WER", "CER"
+    );
+
+    for result in &report.results {
+        if let Some(ref error) = result.error {
+            println!("{:<32} error: {}", result.target, error);
+            continue;
+        }
+        println!(
+            "{:<32} {:>7.2}s {:>8.3} {:>10} {:>8} {:>8}",
+            result.target,
+            result.load_secs,
+            result.rtf,
+            result
+                .peak_memory_mb
+                .map(|mb| format!("{:.0} MB", mb))
+                .unwrap_or_else(|| "-".to_string()),
+            format_rate(result.wer),
+            format_rate(result.cer),
+        );
+        for sample in result.samples.iter().filter(|s| s.error.is_some()) {
+            println!(
+                "  {}: {}",
+                sample.file,
+                sample.error.as_deref().unwrap_or_default()
+            );
+        }
+    }
+}
+
+/// Edit distance and reference length for error rate computation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ErrorCounts {
+    pub edits: usize,
+    pub reference_len: usize,
+}
+
+impl ErrorCounts {
+    fn add(&mut self, other: ErrorCounts) {
+        self.edits += other.edits;
+        self.reference_len += other.reference_len;
+    }
+
+    /// Error rate, or None if there was nothing to compare against
+    pub fn rate(&self) -> Option<f64> {
+        if self.reference_len == 0 {
+            None
+        } else {
+            Some(self.edits as f64 / self.reference_len as f64)
+        }
+    }
+}
+
+/// Word-level edits between a reference and a hypothesis
+pub fn word_edits(reference: &str, hypothesis: &str) -> ErrorCounts {
+    let reference = normalize_for_scoring(reference);
+    let hypothesis = normalize_for_scoring(hypothesis);
+    let ref_words: Vec<&str> = reference.split(' ').filter(|w| !w.is_empty()).collect();
+    let hyp_words: Vec<&str> = hypothesis.split(' ').filter(|w| !w.is_empty()).collect();
+    ErrorCounts {
+        edits: levenshtein(&ref_words, &hyp_words),
+        reference_len: ref_words.len(),
+    }
+}
+
+/// Character-level edits between a reference and a hypothesis
+pub fn char_edits(reference: &str, hypothesis: &str) -> ErrorCounts {
+    let ref_chars: Vec<char> = normalize_for_scoring(reference).chars().collect();
+    let hyp_chars: Vec<char> = normalize_for_scoring(hypothesis).chars().collect();
+    ErrorCounts {
+        edits: levenshtein(&ref_chars, &hyp_chars),
+        reference_len: ref_chars.len(),
+    }
+}
+
+/// Lowercase, strip punctuation (keeping apostrophes) and collapse whitespace
+pub fn normalize_for_scoring(text: &str) -> String {
+    let cleaned: String = text
+        .chars()
+        .flat_map(char::to_lowercase)
+        .map(|c| {
+            if c.is_alphanumeric() || c == '\'' {
+                c
+            } else {
+                ' '
+            }
+        })
+        .collect();
+    cleaned.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn ratio(numerator: f64, denominator: f64) -> f64 {
+    if denominator > 0.0 {
+        numerator / denominator
+    } else {
+        0.0
+    }
+}
+
+fn format_rate(rate: Option<f64>) -> String {
+    rate.map(|r| format!("{:.1}%", r * 100.0))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn engine_name(engine: TranscriptionEngine) -> &'static str {
+    match engine {
+        TranscriptionEngine::Whisper => "whisper",
+        TranscriptionEngine::Parakeet => "parakeet",
+        TranscriptionEngine::External => "external",
+    }
+}
+
+fn model_name(config: &Config) -> String {
+    match config.engine {
+        TranscriptionEngine::Whisper => config.whisper.model.clone(),
+        TranscriptionEngine::Parakeet => config
+            .parakeet
+            .as_ref()
+            .map(|p| p.model.clone())
+            .unwrap_or_else(|| ParakeetConfig::default().model),
+        TranscriptionEngine::External => config.model_name().to_string(),
+    }
+}
+
+/// Measures the peak memory of one target against what the process held before
+///
+/// Earlier targets and the dataset stay resident, so the process-wide peak
+/// alone would grow with every target benchmarked.
+struct MemoryProbe {
+    baseline: u64,
+}
+
+impl MemoryProbe {
+    /// Reset the kernel's peak RSS counter and note the current RSS
+    /// None if the counter can't be reset (not Linux, or Linux before 4.0)
+    fn start() -> Option<Self> {
+        // Writing "5" to clear_refs resets VmHWM to the current RSS
+        std::fs::write("/proc/self/clear_refs", "5").ok()?;
+        Some(Self {
+            baseline: status_bytes("VmRSS")?,
+        })
+    }
+
+    /// Peak RSS since `start`, above the baseline
+    fn peak_bytes(&self) -> Option<u64> {
+        Some(status_bytes("VmHWM")?.saturating_sub(self.baseline))
+    }
+}
+
+/// A memory field (in kB) of /proc/self/status, in bytes
+fn status_bytes(field: &str) -> Option<u64> {
+    parse_status_bytes(&std::fs::read_to_string("/proc/self/status").ok()?, field)
+}
+
+fn parse_status_bytes(status: &str, field: &str) -> Option<u64> {
+    let line = status
+        .lines()
+        .find(|l| l.split_once(':').is_some_and(|(name, _)| name == field))?;
+    let kb: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kb * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_for_scoring() {
+        assert_eq!(
+            normalize_for_scoring("Hello, World!  It's  fine."),
+            "hello world it's fine"
+        );
+    }
+
+    #[test]
+    fn test_word_edits() {
+        let counts = word_edits("the quick brown fox", "The quick, brown box.");
+        assert_eq!(counts.edits, 1);
+        assert_eq!(counts.reference_len, 4);
+        assert_eq!(counts.rate(), Some(0.25));
+
+        // Deletions and insertions count as edits
+        assert_eq!(word_edits("a b c", "a c").edits, 1);
+        assert_eq!(word_edits("a b", "a b c d").edits, 2);
+    }
+
+    #[test]
+    fn test_char_edits() {
+        let counts = char_edits("fox", "box");
+        assert_eq!(counts.edits, 1);
+        assert_eq!(counts.reference_len, 3);
+    }
+
+    #[test]
+    fn test_empty_reference_has_no_rate() {
+        assert_eq!(word_edits("", "anything").rate(), None);
+    }
+
+    #[test]
+    fn test_target_from_spec() {
+        let base = Config::default();
+
+        let target = BenchTarget::from_spec(&base, "base.en").unwrap();
+        assert_eq!(target.config.engine, TranscriptionEngine::Whisper);
+        assert_eq!(target.config.whisper.model, "base.en");
+        assert_eq!(target.label, "whisper:base.en");
+
+        let target = BenchTarget::from_spec(&base, "parakeet").unwrap();
+        assert_eq!(target.config.engine, TranscriptionEngine::Parakeet);
+        assert_eq!(target.label, "parakeet:parakeet-tdt-0.6b-v3");
+
+        let target = BenchTarget::from_spec(&base, "parakeet:parakeet-tdt-0.6b-v3-int8").unwrap();
+        assert_eq!(
+            target.config.parakeet.as_ref().unwrap().model,
+            "parakeet-tdt-0.6b-v3-int8"
+        );
+
+        let target = BenchTarget::from_spec(&base, "Whisper:small").unwrap();
+        assert_eq!(target.label, "whisper:small");
+
+        // A typo in the engine is an error, not a Whisper model called "parakeat:..."
+        assert!(BenchTarget::from_spec(&base, "parakeat:parakeet-tdt-0.6b-v3").is_err());
+    }
+
+    #[test]
+    fn test_parse_status_bytes() {
+        let status = "Name:\tvoxtype\nVmHWM:\t  524288 kB\nVmRSS:\t  131072 kB\n";
+        assert_eq!(parse_status_bytes(status, "VmHWM"), Some(512 * 1024 * 1024));
+        assert_eq!(parse_status_bytes(status, "VmRSS"), Some(128 * 1024 * 1024));
+        assert_eq!(parse_status_bytes(status, "VmPeak"), None);
+    }
+
+    #[test]
+    fn test_load_dataset_pairs_references() {
+        let dir = tempfile::tempdir().unwrap();
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 16000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        for name in ["b.wav", "a.wav"] {
+            let mut writer = hound::WavWriter::create(dir.path().join(name), spec).unwrap();
+            for _ in 0..16000 {
+                writer.write_sample(0i16).unwrap();
+            }
+            writer.finalize().unwrap();
+        }
+        std::fs::write(dir.path().join("a.txt"), "hello world\n").unwrap();
+
+        let samples = load_dataset(dir.path()).unwrap();
+        assert_eq!(samples.len(), 2);
+        assert_eq!(samples[0].name, "a.wav");
+        assert_eq!(samples[0].reference.as_deref(), Some("hello world"));
+        assert!(samples[1].reference.is_none());
+        assert!((samples[0].duration_secs() - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_load_dataset_empty_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(load_dataset(dir.path()).is_err());
+    }
+}
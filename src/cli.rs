@@ -18,6 +18,7 @@ COMMANDS:
   voxtype record toggle    Toggle recording (for compositor keybindings)
   voxtype record start     Start recording
   voxtype record stop      Stop recording and transcribe
+  voxtype listen           Record once and print the text (for scripts)
   voxtype status           Show daemon status (integrates with Waybar)
   voxtype setup            Check dependencies and download models
   voxtype config           Show current configuration
@@ -68,7 +69,7 @@ pub struct Cli {
     #[arg(long, value_name = "PROMPT")]
     pub initial_prompt: Option<String>,
 
-    /// Override transcription engine: "whisper" (default) or "parakeet" (EXPERIMENTAL)
+    /// Override transcription engine: "whisper" (default), "parakeet" (EXPERIMENTAL) or "external"
     #[arg(long, value_name = "ENGINE")]
     pub engine: Option<String>,
 
@@ -89,7 +90,7 @@ pub struct Cli {
     pub wtype_delay: Option<u32>,
 
     /// Output driver order for type mode (comma-separated)
-    /// Overrides config driver_order. Available: wtype, dotool, ydotool, clipboard
+    /// Overrides config driver_order. Available: wtype, eitype, dotool, ydotool, uinput, clipboard, xclip
     /// Example: --driver=ydotool,wtype,clipboard
     #[arg(long, value_name = "DRIVERS")]
     pub driver: Option<String>,
@@ -125,6 +126,14 @@ pub enum Commands {
         #[arg(long)]
         translate: bool,
 
+        /// Disable translation to English (passed from parent process)
+        #[arg(long, conflicts_with = "translate")]
+        no_translate: bool,
+
+        /// Initial prompt (passed from parent process)
+        #[arg(long)]
+        initial_prompt: Option<String>,
+
         /// Number of threads for inference (passed from parent process)
         #[arg(long)]
         threads: Option<usize>,
@@ -155,7 +164,11 @@ pub enum Commands {
     },
 
     /// Show current configuration
-    Config,
+    Config {
+        /// Show the effective configuration with this profile applied
+        #[arg(long, value_name = "NAME")]
+        profile: Option<String>,
+    },
 
     /// Show daemon status (for Waybar/polybar integration)
     Status {
@@ -182,6 +195,71 @@ pub enum Commands {
         action: RecordAction,
     },
 
+    /// Record once and print the transcription to stdout (for scripts)
+    ///
+    /// Uses the running daemon, or records and transcribes in this process
+    /// when none runs. Press Enter to stop (or pause with --vad), Ctrl+C to cancel.
+    /// Exit status: 0 text printed, 1 error, 3 nothing transcribed, 130 cancelled.
+    Listen {
+        /// Stop recording automatically when you pause
+        #[arg(long)]
+        vad: bool,
+
+        /// Length of the pause that ends the recording with --vad
+        #[arg(long, value_name = "MS", default_value_t = 1000)]
+        silence_ms: u64,
+
+        /// Use a specific model for this transcription (e.g., large-v3-turbo)
+        #[arg(long, value_name = "MODEL")]
+        model: Option<String>,
+
+        /// Use a named profile for post-processing (e.g., --profile slack)
+        #[arg(long, value_name = "NAME")]
+        profile: Option<String>,
+
+        /// Transcription language for this recording (e.g., de, auto, or en,fr)
+        #[arg(long, value_name = "LANG")]
+        language: Option<String>,
+
+        /// Translate this recording to English
+        #[arg(long)]
+        translate: bool,
+    },
+
+    /// Benchmark speed and accuracy of engines/models on a set of WAV files
+    Bench {
+        /// Directory with WAV files and reference transcripts (<name>.txt)
+        #[arg(value_name = "DIR")]
+        dir: std::path::PathBuf,
+
+        /// Model to benchmark (repeatable, default: configured model).
+        /// Whisper model name, "whisper:<model>", "parakeet" or "parakeet:<model>"
+        #[arg(long = "model", value_name = "MODEL")]
+        models: Vec<String>,
+
+        /// Output results as JSON (for tracking regressions)
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Manage custom vocabulary (domain terms for better recognition)
+    Vocab {
+        #[command(subcommand)]
+        action: VocabAction,
+    },
+
+    /// Manage snippets (trigger phrases that expand to text)
+    Snippet {
+        #[command(subcommand)]
+        action: SnippetAction,
+    },
+
+    /// Inspect text processing (spoken punctuation, replacements, rules)
+    Text {
+        #[command(subcommand)]
+        action: TextAction,
+    },
+
     /// Launch the graphical user interface (requires --features gui)
     Ui {
         /// Open the settings window directly
@@ -228,6 +306,27 @@ pub enum RecordAction {
         /// Profiles are defined in config.toml under [profiles.name]
         #[arg(long, value_name = "NAME")]
         profile: Option<String>,
+
+        /// Transcription language for this recording (e.g., de, auto, or en,fr)
+        #[arg(long, value_name = "LANG")]
+        language: Option<String>,
+
+        /// Initial prompt for this recording (hints at terminology or style)
+        #[arg(long, value_name = "PROMPT")]
+        initial_prompt: Option<String>,
+
+        /// Translate this recording to English
+        #[arg(long, group = "translate_mode")]
+        translate: bool,
+
+        /// Don't translate this recording, even if enabled in config or profile
+        #[arg(long, group = "translate_mode")]
+        no_translate: bool,
+
+        /// Match this recording against a command grammar and output the command id
+        /// Grammars are defined in config.toml under [grammars.name]
+        #[arg(long, value_name = "NAME")]
+        grammar: Option<String>,
     },
     /// Stop recording and transcribe (send SIGUSR2 to daemon)
     Stop {
@@ -270,6 +369,27 @@ pub enum RecordAction {
         /// Profiles are defined in config.toml under [profiles.name]
         #[arg(long, value_name = "NAME")]
         profile: Option<String>,
+
+        /// Transcription language for this recording (e.g., de, auto, or en,fr)
+        #[arg(long, value_name = "LANG")]
+        language: Option<String>,
+
+        /// Initial prompt for this recording (hints at terminology or style)
+        #[arg(long, value_name = "PROMPT")]
+        initial_prompt: Option<String>,
+
+        /// Translate this recording to English
+        #[arg(long, group = "translate_mode")]
+        translate: bool,
+
+        /// Don't translate this recording, even if enabled in config or profile
+        #[arg(long, group = "translate_mode")]
+        no_translate: bool,
+
+        /// Match this recording against a command grammar and output the command id
+        /// Grammars are defined in config.toml under [grammars.name]
+        #[arg(long, value_name = "NAME")]
+        grammar: Option<String>,
     },
     /// Cancel current recording or transcription (discard without output)
     Cancel,
@@ -345,6 +465,124 @@ impl RecordAction {
             RecordAction::Stop { .. } | RecordAction::Cancel => None,
         }
     }
+
+    /// Get the command grammar name from --grammar flag
+    pub fn grammar(&self) -> Option<&str> {
+        match self {
+            RecordAction::Start { grammar, .. } => grammar.as_deref(),
+            RecordAction::Toggle { grammar, .. } => grammar.as_deref(),
+            RecordAction::Stop { .. } | RecordAction::Cancel => None,
+        }
+    }
+
+    /// Get the language override from --language flag
+    /// Note: only available on start/toggle (language is selected at recording start)
+    pub fn language_override(&self) -> Option<&str> {
+        match self {
+            RecordAction::Start { language, .. } => language.as_deref(),
+            RecordAction::Toggle { language, .. } => language.as_deref(),
+            RecordAction::Stop { .. } | RecordAction::Cancel => None,
+        }
+    }
+
+    /// Get the initial prompt override from --initial-prompt flag
+    pub fn initial_prompt_override(&self) -> Option<&str> {
+        match self {
+            RecordAction::Start { initial_prompt, .. } => initial_prompt.as_deref(),
+            RecordAction::Toggle { initial_prompt, .. } => initial_prompt.as_deref(),
+            RecordAction::Stop { .. } | RecordAction::Cancel => None,
+        }
+    }
+
+    /// Get the translate override from --translate / --no-translate flags
+    /// Returns None if neither flag was given
+    pub fn translate_override(&self) -> Option<bool> {
+        let (translate, no_translate) = match self {
+            RecordAction::Start {
+                translate,
+                no_translate,
+                ..
+            }
+            | RecordAction::Toggle {
+                translate,
+                no_translate,
+                ..
+            } => (*translate, *no_translate),
+            RecordAction::Stop { .. } | RecordAction::Cancel => return None,
+        };
+
+        if translate {
+            Some(true)
+        } else if no_translate {
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Subcommand)]
+pub enum VocabAction {
+    /// Add terms to the vocabulary file
+    Add {
+        /// Terms to add (quote multi-word terms)
+        #[arg(required = true)]
+        terms: Vec<String>,
+    },
+
+    /// Remove terms from the vocabulary file (case-insensitive)
+    Remove {
+        /// Terms to remove
+        #[arg(required = true)]
+        terms: Vec<String>,
+    },
+
+    /// List vocabulary terms
+    List,
+}
+
+#[derive(Subcommand)]
+pub enum SnippetAction {
+    /// Add a snippet, or replace the snippet with the same name
+    Add {
+        /// Snippet name
+        name: String,
+
+        /// Spoken phrase that inserts the snippet
+        #[arg(long, value_name = "PHRASE")]
+        trigger: String,
+
+        /// Snippet text ("-" reads it from stdin, for multi-line text)
+        /// Placeholders: {date}, {time}, {datetime}, {date:%A}, {clipboard}, {env:NAME}
+        text: String,
+    },
+
+    /// Remove snippets by name
+    Remove {
+        /// Snippet names
+        #[arg(required = true)]
+        names: Vec<String>,
+    },
+
+    /// List snippets and their triggers
+    List,
+}
+
+#[derive(Subcommand)]
+pub enum TextAction {
+    /// Show how each processing stage and rule transforms a sample
+    Test {
+        /// Sample transcription text
+        input: String,
+
+        /// Transcription language for normalization and spoken punctuation (e.g., "fr", "de")
+        #[arg(long, value_name = "LANG")]
+        language: Option<String>,
+
+        /// Apply a named profile's text settings (e.g., normalize = true)
+        #[arg(long, value_name = "NAME")]
+        profile: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -410,6 +648,18 @@ pub enum SetupAction {
         /// Restart the daemon after changing model (use with --set)
         #[arg(long)]
         restart: bool,
+
+        /// Install a model from a local file, directory or tar archive (offline)
+        #[arg(long, value_name = "PATH", conflicts_with_all = ["list", "set"])]
+        import: Option<std::path::PathBuf>,
+
+        /// Expected SHA-256 of the imported file or archive (use with --import)
+        #[arg(long, value_name = "HASH", requires = "import")]
+        sha256: Option<String>,
+
+        /// Re-check installed models against their recorded SHA-256 checksums
+        #[arg(long, conflicts_with_all = ["list", "set", "import"])]
+        verify: bool,
     },
 
     /// Manage GPU acceleration (Vulkan for Whisper, CUDA/ROCm for Parakeet)
@@ -718,6 +968,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_listen() {
+        let cli = Cli::parse_from(["voxtype", "listen"]);
+        match cli.command {
+            Some(Commands::Listen {
+                vad, silence_ms, ..
+            }) => {
+                assert!(!vad);
+                assert_eq!(silence_ms, 1000);
+            }
+            _ => panic!("Expected Listen command"),
+        }
+
+        let cli = Cli::parse_from([
+            "voxtype",
+            "listen",
+            "--vad",
+            "--silence-ms",
+            "700",
+            "--profile",
+            "shell",
+            "--language",
+            "de",
+        ]);
+        match cli.command {
+            Some(Commands::Listen {
+                vad,
+                silence_ms,
+                profile,
+                language,
+                ..
+            }) => {
+                assert!(vad);
+                assert_eq!(silence_ms, 700);
+                assert_eq!(profile.as_deref(), Some("shell"));
+                assert_eq!(language.as_deref(), Some("de"));
+            }
+            _ => panic!("Expected Listen command"),
+        }
+    }
+
     #[test]
     fn test_record_start_no_override() {
         let cli = Cli::parse_from(["voxtype", "record", "start"]);
@@ -1034,6 +1325,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_config_with_profile() {
+        let cli = Cli::parse_from(["voxtype", "config", "--profile", "chat"]);
+        match cli.command {
+            Some(Commands::Config { profile }) => {
+                assert_eq!(profile.as_deref(), Some("chat"));
+            }
+            _ => panic!("Expected Config command"),
+        }
+    }
+
     #[test]
     fn test_record_start_without_profile() {
         let cli = Cli::parse_from(["voxtype", "record", "start"]);
@@ -1045,6 +1347,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_record_start_with_grammar() {
+        let cli = Cli::parse_from(["voxtype", "record", "start", "--grammar", "window"]);
+        match cli.command {
+            Some(Commands::Record { action }) => {
+                assert_eq!(action.grammar(), Some("window"));
+            }
+            _ => panic!("Expected Record command"),
+        }
+    }
+
+    #[test]
+    fn test_record_toggle_without_grammar() {
+        let cli = Cli::parse_from(["voxtype", "record", "toggle"]);
+        match cli.command {
+            Some(Commands::Record { action }) => {
+                assert_eq!(action.grammar(), None);
+            }
+            _ => panic!("Expected Record command"),
+        }
+    }
+
     #[test]
     fn test_record_stop_has_no_profile() {
         // Stop command doesn't have --profile flag
@@ -1081,6 +1405,77 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_record_start_language_override() {
+        let cli = Cli::parse_from(["voxtype", "record", "start", "--language", "de"]);
+        match cli.command {
+            Some(Commands::Record { action }) => {
+                assert_eq!(action.language_override(), Some("de"));
+                assert_eq!(action.initial_prompt_override(), None);
+                assert_eq!(action.translate_override(), None);
+            }
+            _ => panic!("Expected Record command"),
+        }
+    }
+
+    #[test]
+    fn test_record_toggle_prompt_and_translate() {
+        let cli = Cli::parse_from([
+            "voxtype",
+            "record",
+            "toggle",
+            "--initial-prompt",
+            "Kubernetes, Rust",
+            "--translate",
+        ]);
+        match cli.command {
+            Some(Commands::Record { action }) => {
+                assert_eq!(action.initial_prompt_override(), Some("Kubernetes, Rust"));
+                assert_eq!(action.translate_override(), Some(true));
+            }
+            _ => panic!("Expected Record command"),
+        }
+    }
+
+    #[test]
+    fn test_record_start_no_translate() {
+        let cli = Cli::parse_from(["voxtype", "record", "start", "--no-translate"]);
+        match cli.command {
+            Some(Commands::Record { action }) => {
+                assert_eq!(action.translate_override(), Some(false));
+            }
+            _ => panic!("Expected Record command"),
+        }
+    }
+
+    #[test]
+    fn test_record_start_translate_mutually_exclusive() {
+        let result = Cli::try_parse_from([
+            "voxtype",
+            "record",
+            "start",
+            "--translate",
+            "--no-translate",
+        ]);
+        assert!(
+            result.is_err(),
+            "Should not allow both --translate and --no-translate"
+        );
+    }
+
+    #[test]
+    fn test_record_stop_has_no_transcription_overrides() {
+        let cli = Cli::parse_from(["voxtype", "record", "stop"]);
+        match cli.command {
+            Some(Commands::Record { action }) => {
+                assert_eq!(action.language_override(), None);
+                assert_eq!(action.initial_prompt_override(), None);
+                assert_eq!(action.translate_override(), None);
+            }
+            _ => panic!("Expected Record command"),
+        }
+    }
+
     // =========================================================================
     // DMS setup tests
     // =========================================================================
@@ -1170,4 +1565,176 @@ mod tests {
         let cli = Cli::parse_from(["voxtype"]);
         assert!(cli.driver.is_none());
     }
+
+    #[test]
+    fn test_vocab_add_multiple_terms() {
+        let cli = Cli::parse_from(["voxtype", "vocab", "add", "Kubernetes", "Tokio runtime"]);
+        match cli.command {
+            Some(Commands::Vocab {
+                action: VocabAction::Add { terms },
+            }) => {
+                assert_eq!(terms, vec!["Kubernetes", "Tokio runtime"]);
+            }
+            _ => panic!("Expected Vocab Add command"),
+        }
+    }
+
+    #[test]
+    fn test_vocab_add_requires_terms() {
+        let result = Cli::try_parse_from(["voxtype", "vocab", "add"]);
+        assert!(result.is_err(), "vocab add without terms should fail");
+    }
+
+    #[test]
+    fn test_vocab_list() {
+        let cli = Cli::parse_from(["voxtype", "vocab", "list"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Vocab {
+                action: VocabAction::List
+            })
+        ));
+    }
+
+    #[test]
+    fn test_snippet_add() {
+        let cli = Cli::parse_from([
+            "voxtype",
+            "snippet",
+            "add",
+            "sig",
+            "--trigger",
+            "insert signature",
+            "-",
+        ]);
+        match cli.command {
+            Some(Commands::Snippet {
+                action:
+                    SnippetAction::Add {
+                        name,
+                        trigger,
+                        text,
+                    },
+            }) => {
+                assert_eq!(name, "sig");
+                assert_eq!(trigger, "insert signature");
+                assert_eq!(text, "-");
+            }
+            _ => panic!("Expected Snippet Add command"),
+        }
+    }
+
+    #[test]
+    fn test_snippet_add_requires_trigger() {
+        let result = Cli::try_parse_from(["voxtype", "snippet", "add", "sig", "Best regards"]);
+        assert!(result.is_err(), "snippet add without --trigger should fail");
+    }
+
+    #[test]
+    fn test_text_test() {
+        let cli = Cli::parse_from(["voxtype", "text", "test", "fixes ticket 42"]);
+        match cli.command {
+            Some(Commands::Text {
+                action:
+                    TextAction::Test {
+                        input,
+                        language,
+                        profile,
+                    },
+            }) => {
+                assert_eq!(input, "fixes ticket 42");
+                assert_eq!(language, None);
+                assert_eq!(profile, None);
+            }
+            _ => panic!("Expected Text Test command"),
+        }
+
+        let cli = Cli::parse_from([
+            "voxtype",
+            "text",
+            "test",
+            "oui virgule",
+            "--language",
+            "fr",
+            "--profile",
+            "notes",
+        ]);
+        match cli.command {
+            Some(Commands::Text {
+                action:
+                    TextAction::Test {
+                        language, profile, ..
+                    },
+            }) => {
+                assert_eq!(language.as_deref(), Some("fr"));
+                assert_eq!(profile.as_deref(), Some("notes"));
+            }
+            _ => panic!("Expected Text Test command"),
+        }
+    }
+
+    #[test]
+    fn test_bench_multiple_models() {
+        let cli = Cli::parse_from([
+            "voxtype", "bench", "samples/", "--model", "base.en", "--model", "parakeet", "--json",
+        ]);
+        match cli.command {
+            Some(Commands::Bench { dir, models, json }) => {
+                assert_eq!(dir, std::path::PathBuf::from("samples/"));
+                assert_eq!(models, vec!["base.en", "parakeet"]);
+                assert!(json);
+            }
+            _ => panic!("Expected Bench command"),
+        }
+    }
+
+    #[test]
+    fn test_bench_defaults_to_configured_model() {
+        let cli = Cli::parse_from(["voxtype", "bench", "samples/"]);
+        match cli.command {
+            Some(Commands::Bench { models, json, .. }) => {
+                assert!(models.is_empty());
+                assert!(!json);
+            }
+            _ => panic!("Expected Bench command"),
+        }
+    }
+
+    #[test]
+    fn test_setup_model_import_with_sha256() {
+        let cli = Cli::parse_from([
+            "voxtype",
+            "setup",
+            "model",
+            "--import",
+            "/tmp/ggml-base.en.bin",
+            "--sha256",
+            "abc",
+        ]);
+        match cli.command {
+            Some(Commands::Setup {
+                action: Some(SetupAction::Model { import, sha256, .. }),
+                ..
+            }) => {
+                assert_eq!(
+                    import,
+                    Some(std::path::PathBuf::from("/tmp/ggml-base.en.bin"))
+                );
+                assert_eq!(sha256.as_deref(), Some("abc"));
+            }
+            _ => panic!("Expected Setup Model command"),
+        }
+    }
+
+    #[test]
+    fn test_setup_model_sha256_requires_import() {
+        let result = Cli::try_parse_from(["voxtype", "setup", "model", "--sha256", "abc"]);
+        assert!(result.is_err(), "--sha256 without --import should fail");
+    }
+
+    #[test]
+    fn test_setup_model_verify_conflicts_with_list() {
+        let result = Cli::try_parse_from(["voxtype", "setup", "model", "--verify", "--list"]);
+        assert!(result.is_err(), "--verify and --list should conflict");
+    }
 }
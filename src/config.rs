@@ -8,7 +8,7 @@
 
 use crate::error::VoxtypeError;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 
 /// Default configuration file content
@@ -149,7 +149,9 @@ fallback_to_clipboard = true
 # Custom driver order for type mode (optional)
 # Default order: wtype -> dotool -> ydotool -> clipboard
 # Customize to prefer a specific driver or change the fallback order.
-# Available drivers: wtype, dotool, ydotool, clipboard
+# Available drivers: wtype, eitype, dotool, ydotool, uinput, clipboard, xclip
+# uinput is built in (no external tool) but needs write access to /dev/uinput;
+# it is not in the default order, add it to use it
 # Example: prefer ydotool over dotool:
 #   driver_order = ["wtype", "ydotool", "dotool", "clipboard"]
 # Example: use only ydotool, no fallback:
@@ -169,6 +171,10 @@ type_delay_ms = 0
 # Useful for applications where Enter submits (e.g., Cursor IDE, Slack, Discord)
 # shift_enter_newlines = false
 
+# Paste mode: put back what was on the clipboard after pasting
+# paste_restore_clipboard = true
+# paste_restore_delay_ms = 500  # Give the application time to read the paste first
+
 # Pre/post output hooks (optional)
 # Commands to run before and after typing output. Useful for compositor integration.
 # Example: Block modifier keys during typing with Hyprland submap:
@@ -185,6 +191,32 @@ type_delay_ms = 0
 # [output.post_process]
 # command = "ollama run llama3.2:1b 'Clean up this dictation. Fix grammar, remove filler words. Output only the cleaned text:'"
 # timeout_ms = 30000  # 30 second timeout (generous for LLM)
+# protocol = "json"   # Send text with profile, language, duration and model as JSON
+#                     # The command may reply {"text", "output_mode", "auto_submit", "discard"}
+#
+# Or let Voxtype call an LLM API directly (see [llm.*] below):
+# post_process_llm = "cleanup"
+
+# Smart joining (optional)
+# Continue the previous dictation: add a leading space, fix capitalization
+# and avoid doubled punctuation when recordings follow each other.
+#
+# [output.smart_join]
+# enabled = true
+# timeout_secs = 30  # Start fresh after 30 seconds without dictation
+
+# Transcription sinks (optional)
+# With mode = "fifo", "socket" or "webhook", each transcription is sent to
+# another program as one JSON line instead of being typed:
+#   {"text": "...", "profile": null, "language": "en", "duration": 2.1,
+#    "model": "base.en", "app": "firefox", "timestamp": 1760000000}
+#
+# [output.sink]
+# fifo_path = "/run/user/1000/voxtype/transcriptions.fifo"    # mode = "fifo"
+# socket_path = "/run/user/1000/voxtype/transcriptions.sock"  # mode = "socket"
+# webhook_url = "http://localhost:8080/dictation"             # mode = "webhook"
+# webhook_headers = { Authorization = "Bearer secret" }
+# webhook_timeout_ms = 5000
 
 [output.notification]
 # Show notification when recording starts (hotkey pressed)
@@ -205,6 +237,55 @@ on_transcription = true
 # Custom word replacements (case-insensitive)
 # replacements = { "vox type" = "voxtype" }
 
+# [vocabulary]
+# Custom vocabulary for domain terms (product names, colleagues, APIs)
+# Manage terms with: voxtype vocab add "Kubernetes" / vocab remove / vocab list
+#
+# Vocabulary file, one term per line (default: ~/.config/voxtype/vocabulary.txt)
+# file = "/path/to/vocabulary.txt"
+#
+# Append terms to the Whisper initial prompt, within an approximate token budget
+# inject_prompt = true
+# max_prompt_tokens = 150
+#
+# Correct near-miss spellings after transcription (e.g., "kubernetis" -> "Kubernetes")
+# fuzzy_correction = true
+# fuzzy_threshold = 0.8
+
+# [snippets]
+# Snippets: trigger phrases that expand to text (signatures, replies, code)
+# Manage with: voxtype snippet add sig --trigger "insert signature" "Best regards"
+#
+# Snippets file (default: ~/.config/voxtype/snippets.toml)
+# file = "/path/to/snippets.toml"
+
+# [scripts]
+# Rhai scripts that transform the text before output
+# Each *.rhai file defines fn transform(text, meta); reloaded when changed
+#
+# Scripts directory (default: ~/.config/voxtype/scripts)
+# dir = "/path/to/scripts"
+#
+# Maximum operations per script run (stops runaway loops)
+# max_operations = 1000000
+#
+# Output the text unchanged if the scripts take longer (ms)
+# timeout_ms = 1000
+
+# [[pipeline]]
+# Order of text processing stages (default: [text] stages, then post_process)
+# Stages: hallucination_filter, disfluency, normalize, code, spoken_punctuation,
+#         vocabulary, replacements, rules, snippets, post_process, command
+#
+# [[pipeline]]
+# stage = "spoken_punctuation"
+#
+# [[pipeline]]
+# stage = "command"
+# command = "ollama run llama3.2:1b 'Fix grammar:'"
+# timeout_ms = 10000
+# profiles = ["email"]
+
 # [status]
 # Status display icons for Waybar/tray integrations
 #
@@ -230,6 +311,18 @@ on_transcription = true
 # transcribing = "⏳"
 # stopped = ""
 
+# [llm.cleanup]
+# Named LLM post-processors for [output] post_process_llm and profiles
+# Talks to OpenAI-compatible chat completion APIs or Ollama, no script needed
+#
+# provider = "ollama"              # "openai" (default) or "ollama"
+# endpoint = "http://localhost:11434"
+# model = "llama3.2:1b"
+# system_prompt = "Clean up this dictated text. Output only the cleaned text."
+# temperature = 0.2
+# timeout_ms = 30000
+# api_key = ""                     # Or set VOXTYPE_LLM_API_KEY
+
 # [profiles]
 # Named profiles for context-specific post-processing
 # Use with: voxtype record start --profile slack
@@ -240,6 +333,47 @@ on_transcription = true
 # [profiles.code]
 # post_process_command = "ollama run llama3.2:1b 'Format as code comment...'"
 # output_mode = "clipboard"
+#
+# [profiles.email]
+# post_process_llm = "cleanup"
+#
+# Profiles can also override transcription language, prompt and translation:
+# [profiles.german]
+# language = "de"
+# initial_prompt = "Besprechung über Softwareentwicklung."
+#
+# [profiles.to_english]
+# language = "auto"
+# translate = true
+#
+# Or almost any other setting, inheriting the rest from another profile
+# (see the effective settings with: voxtype config --profile chat):
+# [profiles.chat]
+# extends = "email"
+# model = "small.en"
+# auto_submit = true
+# driver_order = ["wtype", "clipboard"]
+# feedback = false
+# replacements = { "vox type" = "voxtype" }
+
+# [auto_profile]
+# Pick a profile from the focused window when recording starts
+# (an explicit --profile still wins)
+#
+# Where window info comes from: "auto" (Hyprland, Sway or niri, detected
+# from the environment) or "hyprland", "sway", "niri", "command"
+# provider = "auto"
+#
+# Command printing {"app_id": "...", "class": "...", "title": "..."} (provider = "command")
+# command = "my-focused-window"
+#
+# [[auto_profile.rules]]
+# app_id = "kitty|foot|Alacritty"  # Regex matching the whole app id (case-insensitive)
+# profile = "terminal"
+#
+# [[auto_profile.rules]]
+# title = "Slack|Discord"          # Regex found anywhere in the title
+# profile = "chat"
 "#;
 
 /// Hotkey activation mode
@@ -262,7 +396,7 @@ pub struct Config {
     pub whisper: WhisperConfig,
     pub output: OutputConfig,
 
-    /// Transcription engine: "whisper" (default) or "parakeet"
+    /// Transcription engine: "whisper" (default), "parakeet" or "external"
     /// Parakeet requires: cargo build --features parakeet
     #[serde(default)]
     pub engine: TranscriptionEngine,
@@ -271,10 +405,31 @@ pub struct Config {
     #[serde(default)]
     pub parakeet: Option<ParakeetConfig>,
 
+    /// External engine configuration (optional, only used when engine = "external")
+    #[serde(default)]
+    pub external: Option<ExternalConfig>,
+
     /// Text processing configuration (replacements, spoken punctuation)
     #[serde(default)]
     pub text: TextConfig,
 
+    /// Custom vocabulary configuration (prompt injection, fuzzy correction)
+    #[serde(default)]
+    pub vocabulary: VocabularyConfig,
+
+    /// Snippet expansion configuration (trigger phrases → boilerplate text)
+    #[serde(default)]
+    pub snippets: SnippetsConfig,
+
+    /// Text transform scripts (Rhai files in the scripts directory)
+    #[serde(default)]
+    pub scripts: ScriptsConfig,
+
+    /// Ordered text processing stages (`[[pipeline]]`)
+    /// Empty: the built-in [text] order, then [output.post_process]
+    #[serde(default)]
+    pub pipeline: Vec<PipelineStage>,
+
     /// Status display configuration (icons for Waybar/tray integrations)
     #[serde(default)]
     pub status: StatusConfig,
@@ -292,6 +447,23 @@ pub struct Config {
     #[serde(default)]
     pub profiles: HashMap<String, Profile>,
 
+    /// Named command grammars for constrained recognition
+    /// Example: [grammars.window] with commands = { close = ["close window"] }
+    /// Use with: `voxtype record start --grammar window` or a profile's `grammar`
+    #[serde(default)]
+    pub grammars: HashMap<String, GrammarConfig>,
+
+    /// Profile selection from the focused window
+    /// Example: [[auto_profile.rules]] with app_id = "kitty", profile = "terminal"
+    #[serde(default)]
+    pub auto_profile: AutoProfileConfig,
+
+    /// Named LLM post-processors
+    /// Example: [llm.cleanup] with provider = "ollama", model = "llama3.2:1b"
+    /// Use with: [output] post_process_llm = "cleanup" or a profile's `post_process_llm`
+    #[serde(default)]
+    pub llm: HashMap<String, LlmConfig>,
+
     /// Update checking configuration
     /// Example: [update] check_enabled = false
     #[serde(default)]
@@ -887,8 +1059,79 @@ impl Default for ParakeetConfig {
     }
 }
 
+fn default_external_startup_timeout() -> u64 {
+    60
+}
+
+fn default_external_timeout() -> u64 {
+    120
+}
+
+/// External speech-to-text engine configuration
+///
+/// Runs a long-lived recognizer process that speaks voxtype's JSON-lines
+/// protocol over stdin/stdout (see docs/EXTERNAL_ENGINE.md).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ExternalConfig {
+    /// Command to run (absolute path or looked up in PATH)
+    pub command: String,
+
+    /// Arguments passed to the command
+    #[serde(default)]
+    pub args: Vec<String>,
+
+    /// Extra environment variables for the engine process
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+
+    /// Model name passed to the engine in the handshake (engine-specific)
+    #[serde(default)]
+    pub model: Option<String>,
+
+    /// Language sent with each request ("auto" lets the engine detect it)
+    #[serde(default)]
+    pub language: LanguageConfig,
+
+    /// Initial prompt sent with each request (if the engine supports it)
+    #[serde(default)]
+    pub initial_prompt: Option<String>,
+
+    /// Request translation to English (if the engine supports it)
+    #[serde(default)]
+    pub translate: bool,
+
+    /// Seconds to wait for the engine's handshake (model loading)
+    #[serde(default = "default_external_startup_timeout")]
+    pub startup_timeout_secs: u64,
+
+    /// Seconds to wait for a transcription result
+    #[serde(default = "default_external_timeout")]
+    pub timeout_secs: u64,
+
+    /// Start the engine when recording starts (true) or keep it running (false)
+    #[serde(default = "default_on_demand_loading")]
+    pub on_demand_loading: bool,
+}
+
+impl Default for ExternalConfig {
+    fn default() -> Self {
+        Self {
+            command: String::new(),
+            args: Vec::new(),
+            env: HashMap::new(),
+            model: None,
+            language: LanguageConfig::default(),
+            initial_prompt: None,
+            translate: false,
+            startup_timeout_secs: default_external_startup_timeout(),
+            timeout_secs: default_external_timeout(),
+            on_demand_loading: false,
+        }
+    }
+}
+
 /// Transcription engine selection (which ASR technology to use)
-#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Hash, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum TranscriptionEngine {
     /// Use Whisper (whisper.cpp via whisper-rs) - default
@@ -897,6 +1140,8 @@ pub enum TranscriptionEngine {
     /// Use Parakeet (NVIDIA's FastConformer via ONNX Runtime)
     /// Requires: cargo build --features parakeet
     Parakeet,
+    /// Use an external recognizer process (JSON-lines protocol)
+    External,
 }
 
 /// Text processing configuration
@@ -906,185 +1151,1194 @@ pub struct TextConfig {
     #[serde(default)]
     pub spoken_punctuation: bool,
 
+    /// Spoken punctuation language (default: follow the transcription language)
+    /// Example: "fr" to always use the French table ("virgule" → ",")
+    #[serde(default)]
+    pub punctuation_language: Option<String>,
+
     /// Custom word replacements (case-insensitive)
     /// Example: { "vox type" = "voxtype" }
     #[serde(default)]
     pub replacements: HashMap<String, String>,
-}
 
-/// Notification configuration
-#[derive(Debug, Clone, Deserialize, Serialize)]
-pub struct NotificationConfig {
-    /// Notify when recording starts (hotkey pressed)
+    /// Ordered replacement rules, applied after `replacements`
+    /// Example: [[text.rules]] pattern = "ticket (\\d+)" replacement = "#$1" regex = true
     #[serde(default)]
-    pub on_recording_start: bool,
+    pub rules: Vec<TextRule>,
 
-    /// Notify when recording stops (hotkey released, transcription starting)
+    /// Voice editing commands ("select all", "press enter", "delete that")
     #[serde(default)]
-    pub on_recording_stop: bool,
-
-    /// Notify with transcribed text after transcription completes
-    #[serde(default = "default_true")]
-    pub on_transcription: bool,
+    pub commands: VoiceCommandsConfig,
 
-    /// Show engine icon in notification title (🦜 for Parakeet, 🗣️ for Whisper)
+    /// Inverse text normalization ("twenty three percent" → "23%")
     #[serde(default)]
-    pub show_engine_icon: bool,
-}
+    pub normalize: NormalizeConfig,
 
-impl Default for NotificationConfig {
-    fn default() -> Self {
-        Self {
-            on_recording_start: false,
-            on_recording_stop: false,
-            on_transcription: true,
-            show_engine_icon: false,
-        }
-    }
-}
+    /// Filler word and stutter removal ("um, I I think" → "I think")
+    #[serde(default)]
+    pub disfluency: DisfluencyConfig,
 
-/// Post-processing command configuration
-///
-/// Pipes transcribed text through an external command for cleanup/formatting.
-/// Commonly used with local LLMs (Ollama, llama.cpp) or text processing tools.
-#[derive(Debug, Clone, Deserialize, Serialize)]
-pub struct PostProcessConfig {
-    /// Shell command to execute
-    /// Receives transcribed text on stdin, outputs processed text on stdout
-    pub command: String,
+    /// Code dictation mode ("camel case get value" → "getValue")
+    #[serde(default)]
+    pub code: CodeModeConfig,
 
-    /// Timeout in milliseconds (default: 30000 = 30 seconds)
-    #[serde(default = "default_post_process_timeout")]
-    pub timeout_ms: u64,
+    /// Drop text Whisper produces on silence ("Thanks for watching!", "[BLANK_AUDIO]")
+    #[serde(default)]
+    pub hallucination_filter: HallucinationFilterConfig,
 }
 
-/// Named profile for context-specific settings
-///
-/// Profiles allow different post-processing commands (and other settings)
-/// for different contexts like Slack, code editors, email, etc.
-///
-/// # Example Configuration
-///
-/// ```toml
-/// [profiles.slack]
-/// post_process_command = "cleanup-for-slack.sh"
-///
-/// [profiles.code]
-/// post_process_command = "cleanup-for-code.sh"
-/// ```
-///
-/// Use with: `voxtype record start --profile slack`
+/// Hallucination filter configuration (`[text.hallucination_filter]`)
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
-pub struct Profile {
-    /// Post-processing command for this profile
-    /// Overrides [output.post_process.command] when the profile is active
+pub struct HallucinationFilterConfig {
+    /// Enable the filter (default: false)
     #[serde(default)]
-    pub post_process_command: Option<String>,
+    pub enabled: bool,
 
-    /// Timeout for post-processing in milliseconds (default: 30000)
+    /// Extra phrases dropped when they are the whole transcription
+    /// Example: ["Okay.", "Subtitles by our team"]
     #[serde(default)]
-    pub post_process_timeout_ms: Option<u64>,
+    pub phrases: Vec<String>,
+}
 
-    /// Output mode override for this profile
+/// Code dictation configuration (`[text.code]`)
+///
+/// Formats dictation as source code: casing commands, "no space",
+/// symbol words and NATO alphabet spelling. Usually enabled per profile.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct CodeModeConfig {
+    /// Enable code dictation for every transcription (default: false)
+    /// Profiles can override this with `code = true/false`
     #[serde(default)]
-    pub output_mode: Option<OutputMode>,
-}
+    pub enabled: bool,
 
-fn default_post_process_timeout() -> u64 {
-    30000 // 30 seconds - generous for LLM processing
+    /// Extra symbol words, added to or replacing the built-in ones
+    /// Spaces around the symbol are kept; an empty symbol removes a word
+    /// Example: { walrus = " := ", "dash" = "" }
+    #[serde(default)]
+    pub symbols: HashMap<String, String>,
 }
 
-/// Text output configuration
+/// Disfluency removal configuration (`[text.disfluency]`)
+///
+/// Removes filler words, discourse markers set off by commas and stutter
+/// repeats, then repairs punctuation and capitalization.
 #[derive(Debug, Clone, Deserialize, Serialize)]
-pub struct OutputConfig {
-    /// Primary output mode
-    pub mode: OutputMode,
+pub struct DisfluencyConfig {
+    /// Enable disfluency removal (default: false)
+    /// Profiles can override this with `disfluency = true/false`
+    #[serde(default)]
+    pub enabled: bool,
 
-    /// Fall back to clipboard if typing fails
+    /// Collapse repeated words and phrases ("I I think" → "I think")
     #[serde(default = "default_true")]
-    pub fallback_to_clipboard: bool,
+    pub collapse_repeats: bool,
 
-    /// Custom driver order for type mode (overrides default: wtype -> dotool -> ydotool -> clipboard)
-    /// Specify which drivers to try and in what order.
-    /// Example: ["ydotool", "wtype"] to prefer ydotool over wtype
+    /// Filler words removed wherever they appear, by language
+    /// A language listed here replaces its built-in list
+    /// Example: { en = ["um", "uh", "er"] }
     #[serde(default)]
-    pub driver_order: Option<Vec<OutputDriver>>,
+    pub fillers: HashMap<String, Vec<String>>,
 
-    /// Notification settings
+    /// Phrases removed only when set off by commas ("it's, you know, fast"),
+    /// by language; a language listed here replaces its built-in list
     #[serde(default)]
-    pub notification: NotificationConfig,
+    pub discourse_markers: HashMap<String, Vec<String>>,
+}
 
-    /// Delay between typed characters (ms), 0 for fastest
-    #[serde(default)]
-    pub type_delay_ms: u32,
+impl Default for DisfluencyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            collapse_repeats: true,
+            fillers: HashMap::new(),
+            discourse_markers: HashMap::new(),
+        }
+    }
+}
 
-    /// Delay before typing starts (ms), allows virtual keyboard to initialize
-    /// Helps prevent first character from being dropped on some compositors
+/// Inverse text normalization configuration (`[text.normalize]`)
+///
+/// Converts spoken numbers, ordinals, currencies, percentages, dates, times
+/// and units into written form. Each category can be turned off.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct NormalizeConfig {
+    /// Enable inverse text normalization (default: false)
+    /// Profiles can override this with `normalize = true/false`
     #[serde(default)]
-    pub pre_type_delay_ms: u32,
+    pub enabled: bool,
 
-    /// DEPRECATED: Use pre_type_delay_ms instead. Kept for backwards compatibility.
+    /// Languages to normalize (default: all languages with built-in rules)
+    /// Example: ["en", "de"]
     #[serde(default)]
-    pub wtype_delay_ms: u32,
+    pub languages: Vec<String>,
 
-    /// Automatically submit (send Enter key) after outputting transcribed text
-    /// Useful for chat applications, command lines, or forms where you want
-    /// to auto-submit after dictation
-    #[serde(default)]
-    pub auto_submit: bool,
+    /// Leave standalone numbers below ten as words ("two cats", "first time")
+    #[serde(default = "default_true")]
+    pub keep_small_numbers: bool,
 
-    /// Convert newlines to Shift+Enter instead of regular Enter
-    /// Useful for applications where Enter submits (e.g., Cursor IDE, Slack, Discord)
-    #[serde(default)]
-    pub shift_enter_newlines: bool,
+    /// Cardinal and decimal numbers ("twenty three point five" → "23.5")
+    #[serde(default = "default_true")]
+    pub numbers: bool,
 
-    /// Command to run when recording starts (e.g., switch to compositor submap)
-    /// Useful for entering a mode where cancel keybindings are effective
-    #[serde(default)]
-    pub pre_recording_command: Option<String>,
+    /// Ordinals ("twenty third" → "23rd")
+    #[serde(default = "default_true")]
+    pub ordinals: bool,
 
-    /// Command to run before typing output (e.g., compositor submap switch)
-    /// Useful for blocking modifier keys at the compositor level
-    #[serde(default)]
-    pub pre_output_command: Option<String>,
+    /// Currency amounts ("five dollars fifty" → "$5.50")
+    #[serde(default = "default_true")]
+    pub currency: bool,
 
-    /// Command to run after typing output (e.g., reset compositor submap)
-    /// Runs even if typing fails, to ensure cleanup
-    #[serde(default)]
-    pub post_output_command: Option<String>,
+    /// Percentages ("ten percent" → "10%")
+    #[serde(default = "default_true")]
+    pub percentages: bool,
 
-    /// Optional post-processing command configuration
-    /// Pipes transcribed text through an external command before output
-    #[serde(default)]
-    pub post_process: Option<PostProcessConfig>,
+    /// Dates ("march third twenty twenty six" → "March 3, 2026")
+    #[serde(default = "default_true")]
+    pub dates: bool,
 
-    /// Keystroke to simulate for paste mode (e.g., "ctrl+v", "shift+insert", "ctrl+shift+v")
-    /// Defaults to "ctrl+v" if not specified
-    #[serde(default)]
-    pub paste_keys: Option<String>,
+    /// Times ("three thirty p.m." → "3:30 p.m.")
+    #[serde(default = "default_true")]
+    pub times: bool,
 
-    /// Keyboard layout for dotool (e.g., "de" for German, "fr" for French)
-    /// Required for non-US keyboard layouts when using dotool
-    #[serde(default)]
-    pub dotool_xkb_layout: Option<String>,
+    /// Units ("five kilometers" → "5 km")
+    #[serde(default = "default_true")]
+    pub units: bool,
 
-    /// Keyboard layout variant for dotool (e.g., "nodeadkeys")
+    /// Date style: "long" (March 3, 2026), "iso" (2026-03-03) or
+    /// "numeric" (the language's numeric form, e.g. 03/03/2026)
     #[serde(default)]
-    pub dotool_xkb_variant: Option<String>,
+    pub date_format: DateFormat,
+}
 
-    /// File path for file output mode (required when mode = "file")
-    /// Also used as default path for --output-file CLI flag
-    #[serde(default)]
-    pub file_path: Option<PathBuf>,
+impl Default for NormalizeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            languages: Vec::new(),
+            keep_small_numbers: true,
+            numbers: true,
+            ordinals: true,
+            currency: true,
+            percentages: true,
+            dates: true,
+            times: true,
+            units: true,
+            date_format: DateFormat::default(),
+        }
+    }
+}
 
-    /// File write mode: "overwrite" (default) or "append"
-    /// Applies to both config-based file output and --output-file CLI flag
-    #[serde(default)]
-    pub file_mode: FileMode,
+/// How inverse text normalization writes dates
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DateFormat {
+    /// Month name in the language's usual order ("March 3, 2026", "3. März 2026")
+    #[default]
+    Long,
+    /// ISO 8601 ("2026-03-03"); dates without a year use the long form
+    Iso,
+    /// Numeric ("03/03/2026", "03.03.2026"); dates without a year use the long form
+    Numeric,
 }
 
-impl OutputConfig {
-    /// Get the effective pre-type delay, handling deprecated wtype_delay_ms
+/// Voice editing commands configuration (`[text.commands]`)
+///
+/// When enabled, spoken commands are turned into key presses instead of
+/// being typed. Key presses need a keyboard driver (wtype, eitype, dotool
+/// or ydotool).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct VoiceCommandsConfig {
+    /// Recognize voice commands (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Include the built-in commands (default: true)
+    #[serde(default = "default_true")]
+    pub builtin: bool,
+
+    /// Custom commands: phrase → space-separated key combinations
+    /// Example: { "save file" = "ctrl+s", "close tab" = "ctrl+w" }
+    #[serde(default)]
+    pub custom: BTreeMap<String, String>,
+}
+
+impl Default for VoiceCommandsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            builtin: true,
+            custom: BTreeMap::new(),
+        }
+    }
+}
+
+/// A literal or regex replacement rule (`[[text.rules]]`)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TextRule {
+    /// Optional name shown in logs and `voxtype text test`
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// Text or regular expression to match
+    pub pattern: String,
+
+    /// Replacement text (regex rules may use $1 or ${name} for capture groups)
+    #[serde(default)]
+    pub replacement: String,
+
+    /// Treat the pattern as a regular expression
+    #[serde(default)]
+    pub regex: bool,
+
+    /// Match case exactly (default: false)
+    #[serde(default)]
+    pub case_sensitive: bool,
+
+    /// Only match whole words (default: true)
+    #[serde(default = "default_true")]
+    pub whole_word: bool,
+
+    /// Apply this rule (default: true)
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+impl TextRule {
+    /// Rule name, or the quoted pattern if unnamed
+    pub fn label(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("{:?}", self.pattern),
+        }
+    }
+}
+
+/// Custom vocabulary configuration
+///
+/// The vocabulary file lists domain terms (product names, people, APIs), one
+/// per line. Terms are injected into the Whisper initial prompt and used to
+/// correct near-miss spellings after transcription.
+/// Manage it with `voxtype vocab add/remove/list`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct VocabularyConfig {
+    /// Enable vocabulary support (default: true, no-op while the file is empty)
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Path to the vocabulary file
+    /// Default: ~/.config/voxtype/vocabulary.txt
+    #[serde(default)]
+    pub file: Option<PathBuf>,
+
+    /// Append vocabulary terms to the Whisper initial prompt
+    #[serde(default = "default_true")]
+    pub inject_prompt: bool,
+
+    /// Approximate token budget for the whole initial prompt
+    /// Whisper accepts at most 224 prompt tokens; terms that don't fit are skipped
+    #[serde(default = "default_vocabulary_max_prompt_tokens")]
+    pub max_prompt_tokens: usize,
+
+    /// Correct near-miss spellings of vocabulary terms after transcription
+    #[serde(default = "default_true")]
+    pub fuzzy_correction: bool,
+
+    /// Minimum similarity (0.0 to 1.0) for a fuzzy correction to be applied
+    #[serde(default = "default_vocabulary_fuzzy_threshold")]
+    pub fuzzy_threshold: f32,
+}
+
+fn default_vocabulary_max_prompt_tokens() -> usize {
+    150
+}
+
+fn default_vocabulary_fuzzy_threshold() -> f32 {
+    0.8
+}
+
+/// Command grammar for constrained recognition
+///
+/// A grammar maps command identifiers to the phrases that trigger them.
+/// While a grammar is active, the transcription is matched against the
+/// phrases and the identifier of the best match is output instead of text.
+///
+/// ```toml
+/// [grammars.window]
+/// threshold = 0.75
+///
+/// [grammars.window.commands]
+/// close = ["close window", "close this"]
+/// maximize = ["maximize", "make it bigger"]
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GrammarConfig {
+    /// Command identifier → phrases that trigger it
+    #[serde(default)]
+    pub commands: BTreeMap<String, Vec<String>>,
+
+    /// Minimum similarity (0.0 to 1.0) for a phrase to match
+    #[serde(default = "default_grammar_threshold")]
+    pub threshold: f32,
+
+    /// Put the phrases into the Whisper initial prompt to bias recognition
+    #[serde(default = "default_true")]
+    pub bias_prompt: bool,
+}
+
+fn default_grammar_threshold() -> f32 {
+    0.75
+}
+
+impl Default for VocabularyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            file: None,
+            inject_prompt: true,
+            max_prompt_tokens: default_vocabulary_max_prompt_tokens(),
+            fuzzy_correction: true,
+            fuzzy_threshold: default_vocabulary_fuzzy_threshold(),
+        }
+    }
+}
+
+impl VocabularyConfig {
+    /// Resolve the vocabulary file path (configured path or default location)
+    pub fn resolve_path(&self) -> Option<PathBuf> {
+        self.file
+            .clone()
+            .or_else(|| Config::config_dir().map(|dir| dir.join("vocabulary.txt")))
+    }
+}
+
+/// One stage of the text pipeline (`[[pipeline]]`)
+///
+/// ```toml
+/// [[pipeline]]
+/// stage = "hallucination_filter"
+///
+/// [[pipeline]]
+/// stage = "command"
+/// name = "grammar"
+/// command = "ollama run llama3.2:1b 'Fix grammar:'"
+/// timeout_ms = 10000
+/// profiles = ["email"]
+///
+/// [[pipeline]]
+/// stage = "replacements"
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PipelineStage {
+    /// What the stage does: a built-in text stage, "post_process" or "command"
+    pub stage: PipelineStageKind,
+
+    /// Name shown in logs and `voxtype text test` (default: the stage type)
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// Shell command for "command" stages (text on stdin, result on stdout)
+    #[serde(default)]
+    pub command: Option<String>,
+
+    /// Timeout for "command" stages in milliseconds (default: 30000)
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+
+    /// Protocol of "command" stages: "text" (default) or "json"
+    #[serde(default)]
+    pub protocol: PostProcessProtocol,
+
+    /// Run the stage (default: true)
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Only run with these profiles (default: always)
+    /// Use "default" for recordings without a profile
+    #[serde(default)]
+    pub profiles: Vec<String>,
+}
+
+impl PipelineStage {
+    /// Name shown in logs: the configured name, or the stage type
+    pub fn label(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| self.stage.as_str().to_string())
+    }
+
+    /// Whether the stage runs for a recording with this profile
+    pub fn applies_to(&self, profile: Option<&str>) -> bool {
+        self.enabled
+            && (self.profiles.is_empty()
+                || self
+                    .profiles
+                    .iter()
+                    .any(|p| p == profile.unwrap_or("default")))
+    }
+}
+
+/// Pipeline stage type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PipelineStageKind {
+    /// Drop Whisper hallucinations ([text.hallucination_filter])
+    HallucinationFilter,
+    /// Remove filler words and stutters ([text.disfluency])
+    Disfluency,
+    /// Inverse text normalization ([text.normalize])
+    Normalize,
+    /// Code dictation ([text.code])
+    Code,
+    /// Spoken punctuation ([text] spoken_punctuation)
+    SpokenPunctuation,
+    /// Vocabulary correction ([vocabulary])
+    Vocabulary,
+    /// Word replacements ([text.replacements])
+    Replacements,
+    /// Replacement rules ([[text.rules]])
+    Rules,
+    /// Snippet expansion ([snippets])
+    Snippets,
+    /// The profile's or the default post-processor (command or LLM)
+    PostProcess,
+    /// A shell command given in the stage
+    Command,
+}
+
+impl PipelineStageKind {
+    /// Stage type as written in the config
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::HallucinationFilter => "hallucination_filter",
+            Self::Disfluency => "disfluency",
+            Self::Normalize => "normalize",
+            Self::Code => "code",
+            Self::SpokenPunctuation => "spoken_punctuation",
+            Self::Vocabulary => "vocabulary",
+            Self::Replacements => "replacements",
+            Self::Rules => "rules",
+            Self::Snippets => "snippets",
+            Self::PostProcess => "post_process",
+            Self::Command => "command",
+        }
+    }
+
+    /// Whether the stage runs an external command
+    pub fn is_external(self) -> bool {
+        matches!(self, Self::PostProcess | Self::Command)
+    }
+}
+
+/// Snippet expansion configuration
+///
+/// The snippets file maps trigger phrases to text with placeholders
+/// ({date}, {time}, {clipboard}, {env:NAME}). A trigger in a transcription
+/// is replaced by its snippet before output.
+/// Manage it with `voxtype snippet add/remove/list`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SnippetsConfig {
+    /// Enable snippet expansion (default: true, no-op while the file is empty)
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Path to the snippets file
+    /// Default: ~/.config/voxtype/snippets.toml
+    #[serde(default)]
+    pub file: Option<PathBuf>,
+}
+
+impl Default for SnippetsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            file: None,
+        }
+    }
+}
+
+impl SnippetsConfig {
+    /// Resolve the snippets file path (configured path or default location)
+    pub fn resolve_path(&self) -> Option<PathBuf> {
+        self.file
+            .clone()
+            .or_else(|| Config::config_dir().map(|dir| dir.join("snippets.toml")))
+    }
+}
+
+/// Text transform script configuration
+///
+/// Every `*.rhai` file in the scripts directory defines a
+/// `transform(text, meta)` function. Scripts run in file name order on the
+/// final text, right before output, and are reloaded when they change.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ScriptsConfig {
+    /// Run scripts (default: true, no-op while the directory is empty or missing)
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Directory containing the scripts
+    /// Default: ~/.config/voxtype/scripts
+    #[serde(default)]
+    pub dir: Option<PathBuf>,
+
+    /// Maximum number of operations a script may run per transcription
+    #[serde(default = "default_script_max_operations")]
+    pub max_operations: u64,
+
+    /// Maximum time to wait for the scripts of one transcription (ms)
+    #[serde(default = "default_script_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+fn default_script_max_operations() -> u64 {
+    1_000_000
+}
+
+fn default_script_timeout_ms() -> u64 {
+    1000
+}
+
+impl Default for ScriptsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            dir: None,
+            max_operations: default_script_max_operations(),
+            timeout_ms: default_script_timeout_ms(),
+        }
+    }
+}
+
+impl ScriptsConfig {
+    /// Resolve the scripts directory (configured path or default location)
+    pub fn resolve_dir(&self) -> Option<PathBuf> {
+        self.dir
+            .clone()
+            .or_else(|| Config::config_dir().map(|dir| dir.join("scripts")))
+    }
+}
+
+/// Automatic profile selection from the focused window
+///
+/// When a recording starts without `--profile`, the focused window is looked
+/// up and the first matching rule's profile is used.
+///
+/// ```toml
+/// [auto_profile]
+/// provider = "auto"
+///
+/// [[auto_profile.rules]]
+/// app_id = "kitty|foot"
+/// profile = "terminal"
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AutoProfileConfig {
+    /// Select profiles automatically (default: true, no-op without rules)
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Where the focused window comes from
+    #[serde(default)]
+    pub provider: WindowProvider,
+
+    /// Command printing the focused window as JSON (provider = "command")
+    #[serde(default)]
+    pub command: Option<String>,
+
+    /// Maximum time to wait for the compositor or command (ms)
+    #[serde(default = "default_window_timeout_ms")]
+    pub timeout_ms: u64,
+
+    /// Rules mapping windows to profiles, first match wins
+    #[serde(default)]
+    pub rules: Vec<WindowRule>,
+}
+
+fn default_window_timeout_ms() -> u64 {
+    500
+}
+
+impl Default for AutoProfileConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            provider: WindowProvider::default(),
+            command: None,
+            timeout_ms: default_window_timeout_ms(),
+            rules: Vec::new(),
+        }
+    }
+}
+
+/// Source of focused window information
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WindowProvider {
+    /// Detect Hyprland, Sway or niri from the environment
+    #[default]
+    Auto,
+    /// Hyprland IPC socket
+    Hyprland,
+    /// Sway (i3) IPC socket
+    Sway,
+    /// niri IPC socket
+    Niri,
+    /// User command printing JSON
+    Command,
+}
+
+/// Rule selecting a profile for matching windows
+/// Every given pattern must match; a rule without patterns matches nothing
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WindowRule {
+    /// Regex matching the whole Wayland app id (case-insensitive)
+    #[serde(default)]
+    pub app_id: Option<String>,
+
+    /// Regex matching the whole X11 class (case-insensitive)
+    #[serde(default)]
+    pub class: Option<String>,
+
+    /// Regex found anywhere in the window title
+    #[serde(default)]
+    pub title: Option<String>,
+
+    /// Profile to use
+    pub profile: String,
+}
+
+/// Notification configuration
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct NotificationConfig {
+    /// Notify when recording starts (hotkey pressed)
+    #[serde(default)]
+    pub on_recording_start: bool,
+
+    /// Notify when recording stops (hotkey released, transcription starting)
+    #[serde(default)]
+    pub on_recording_stop: bool,
+
+    /// Notify with transcribed text after transcription completes
+    #[serde(default = "default_true")]
+    pub on_transcription: bool,
+
+    /// Show engine icon in notification title (🦜 for Parakeet, 🗣️ for Whisper)
+    #[serde(default)]
+    pub show_engine_icon: bool,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            on_recording_start: false,
+            on_recording_stop: false,
+            on_transcription: true,
+            show_engine_icon: false,
+        }
+    }
+}
+
+/// Post-processing command configuration
+///
+/// Pipes transcribed text through an external command for cleanup/formatting.
+/// Commonly used with local LLMs (Ollama, llama.cpp) or text processing tools.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PostProcessConfig {
+    /// Shell command to execute
+    /// Receives transcribed text on stdin, outputs processed text on stdout
+    pub command: String,
+
+    /// Timeout in milliseconds (default: 30000 = 30 seconds)
+    #[serde(default = "default_post_process_timeout")]
+    pub timeout_ms: u64,
+
+    /// What the command reads and writes: plain text (default) or JSON
+    #[serde(default)]
+    pub protocol: PostProcessProtocol,
+}
+
+/// Post-process command input/output format
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PostProcessProtocol {
+    /// Text on stdin, text on stdout
+    #[default]
+    Text,
+    /// A JSON object with the text and recording metadata on stdin; text or
+    /// a JSON object (text, output_mode, auto_submit, discard) on stdout
+    Json,
+}
+
+/// LLM post-processor configuration
+///
+/// Sends the transcription to a chat completion API with a system prompt
+/// and outputs the reply. On any failure, the original text is used.
+///
+/// ```toml
+/// [llm.cleanup]
+/// provider = "ollama"
+/// model = "llama3.2:1b"
+///
+/// [llm.gpt]
+/// endpoint = "https://api.openai.com/v1"
+/// model = "gpt-4o-mini"
+/// api_key = "sk-..."
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LlmConfig {
+    /// API flavor: "openai" (chat completions) or "ollama"
+    #[serde(default)]
+    pub provider: LlmProvider,
+
+    /// API base URL
+    /// Default: https://api.openai.com/v1 (openai), http://localhost:11434 (ollama)
+    #[serde(default)]
+    pub endpoint: Option<String>,
+
+    /// Model name
+    pub model: String,
+
+    /// Instructions sent as the system message
+    /// Default: clean up filler words, grammar and punctuation
+    #[serde(default)]
+    pub system_prompt: Option<String>,
+
+    /// Sampling temperature (default: the server's)
+    #[serde(default)]
+    pub temperature: Option<f32>,
+
+    /// Request timeout in milliseconds (default: 30000)
+    #[serde(default = "default_post_process_timeout")]
+    pub timeout_ms: u64,
+
+    /// API key sent as a bearer token
+    /// Falls back to the VOXTYPE_LLM_API_KEY environment variable
+    #[serde(default)]
+    pub api_key: Option<String>,
+}
+
+/// LLM API flavor
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LlmProvider {
+    /// OpenAI-compatible chat completions (OpenAI, LM Studio, llama.cpp, vLLM, ...)
+    #[default]
+    OpenAi,
+    /// Ollama chat API
+    Ollama,
+}
+
+impl LlmProvider {
+    /// Default API base URL
+    pub fn default_endpoint(self) -> &'static str {
+        match self {
+            Self::OpenAi => "https://api.openai.com/v1",
+            Self::Ollama => "http://localhost:11434",
+        }
+    }
+}
+
+/// Named profile for context-specific settings
+///
+/// Profiles allow different post-processing commands (and other settings)
+/// for different contexts like Slack, code editors, email, etc.
+///
+/// # Example Configuration
+///
+/// ```toml
+/// [profiles.slack]
+/// post_process_command = "cleanup-for-slack.sh"
+///
+/// [profiles.code]
+/// post_process_command = "cleanup-for-code.sh"
+/// ```
+///
+/// Use with: `voxtype record start --profile slack`
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Profile {
+    /// Profile to inherit unset options from
+    #[serde(default)]
+    pub extends: Option<String>,
+
+    /// Post-processing command for this profile
+    /// Overrides [output.post_process.command] when the profile is active
+    #[serde(default)]
+    pub post_process_command: Option<String>,
+
+    /// Timeout for post-processing in milliseconds (default: 30000)
+    #[serde(default)]
+    pub post_process_timeout_ms: Option<u64>,
+
+    /// Protocol of post_process_command: "text" (default) or "json"
+    #[serde(default)]
+    pub post_process_protocol: Option<PostProcessProtocol>,
+
+    /// LLM post-processor for this profile (name from [llm])
+    /// Used when the profile has no post_process_command
+    #[serde(default)]
+    pub post_process_llm: Option<String>,
+
+    /// Output mode override for this profile
+    #[serde(default)]
+    pub output_mode: Option<OutputMode>,
+
+    /// Transcription language override for this profile
+    /// Same format as [whisper] language: "de", "auto", or ["en", "fr"]
+    #[serde(default)]
+    pub language: Option<LanguageConfig>,
+
+    /// Initial prompt override for this profile
+    /// Replaces [whisper] initial_prompt when the profile is active
+    #[serde(default)]
+    pub initial_prompt: Option<String>,
+
+    /// Translate-to-English override for this profile
+    #[serde(default)]
+    pub translate: Option<bool>,
+
+    /// Command grammar to match recordings against (name from [grammars])
+    /// When set, the matched command id is output instead of the transcription
+    #[serde(default)]
+    pub grammar: Option<String>,
+
+    /// Turn inverse text normalization on or off for this profile
+    /// Overrides [text.normalize] enabled when the profile is active
+    #[serde(default)]
+    pub normalize: Option<bool>,
+
+    /// Turn filler word removal on or off for this profile
+    /// Overrides [text.disfluency] enabled when the profile is active
+    #[serde(default)]
+    pub disfluency: Option<bool>,
+
+    /// Turn code dictation on or off for this profile
+    /// Overrides [text.code] enabled when the profile is active
+    #[serde(default)]
+    pub code: Option<bool>,
+
+    /// Transcription engine override for this profile
+    #[serde(default)]
+    pub engine: Option<TranscriptionEngine>,
+
+    /// Whisper model override for this profile (same format as [whisper] model)
+    #[serde(default)]
+    pub model: Option<String>,
+
+    /// Turn spoken punctuation on or off for this profile
+    #[serde(default)]
+    pub spoken_punctuation: Option<bool>,
+
+    /// Word replacements added to [text] replacements (same words override them)
+    #[serde(default)]
+    pub replacements: Option<HashMap<String, String>>,
+
+    /// Press Enter after the text (overrides [output] auto_submit)
+    #[serde(default)]
+    pub auto_submit: Option<bool>,
+
+    /// Output driver order override for this profile
+    #[serde(default)]
+    pub driver_order: Option<Vec<OutputDriver>>,
+
+    /// Overrides [output] pre_recording_command for this profile
+    #[serde(default)]
+    pub pre_recording_command: Option<String>,
+
+    /// Overrides [output] pre_output_command for this profile
+    #[serde(default)]
+    pub pre_output_command: Option<String>,
+
+    /// Overrides [output] post_output_command for this profile
+    #[serde(default)]
+    pub post_output_command: Option<String>,
+
+    /// Turn feedback sounds on or off for this profile
+    /// Overrides [audio.feedback] enabled
+    #[serde(default)]
+    pub feedback: Option<bool>,
+}
+
+impl Profile {
+    /// Fill options this profile leaves unset from a base profile
+    /// Replacements are merged, with this profile's winning
+    fn inherit(&mut self, base: &Profile) {
+        macro_rules! inherit {
+            ($($field:ident),*) => {
+                $(if self.$field.is_none() {
+                    self.$field = base.$field.clone();
+                })*
+            };
+        }
+        // The post-processor is one choice: setting either a command or an LLM
+        // replaces the base profile's, whichever it is
+        if self.post_process_command.is_none() && self.post_process_llm.is_none() {
+            self.post_process_command = base.post_process_command.clone();
+            self.post_process_llm = base.post_process_llm.clone();
+        }
+        inherit!(
+            post_process_timeout_ms,
+            post_process_protocol,
+            output_mode,
+            language,
+            initial_prompt,
+            translate,
+            grammar,
+            normalize,
+            disfluency,
+            code,
+            engine,
+            model,
+            spoken_punctuation,
+            auto_submit,
+            driver_order,
+            pre_recording_command,
+            pre_output_command,
+            post_output_command,
+            feedback
+        );
+        match (&mut self.replacements, &base.replacements) {
+            (Some(replacements), Some(base)) => {
+                for (word, replacement) in base {
+                    replacements
+                        .entry(word.clone())
+                        .or_insert_with(|| replacement.clone());
+                }
+            }
+            (None, Some(base)) => self.replacements = Some(base.clone()),
+            _ => {}
+        }
+    }
+
+    /// Whether text processing differs from the base configuration with this profile
+    /// The language (and translation) pick the spoken punctuation and numbers,
+    /// the engine may change it as well
+    pub fn changes_text_processing(&self) -> bool {
+        self.language.is_some()
+            || self.translate.is_some()
+            || self.engine.is_some()
+            || self.spoken_punctuation.is_some()
+            || self.replacements.is_some()
+            || self.normalize.is_some()
+            || self.disfluency.is_some()
+            || self.code.is_some()
+    }
+}
+
+fn default_post_process_timeout() -> u64 {
+    30000 // 30 seconds - generous for LLM processing
+}
+
+/// Text output configuration
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OutputConfig {
+    /// Primary output mode
+    pub mode: OutputMode,
+
+    /// Fall back to clipboard if typing fails
+    #[serde(default = "default_true")]
+    pub fallback_to_clipboard: bool,
+
+    /// Custom driver order for type mode (overrides default: wtype -> dotool -> ydotool -> clipboard)
+    /// Specify which drivers to try and in what order.
+    /// Example: ["ydotool", "wtype"] to prefer ydotool over wtype
+    #[serde(default)]
+    pub driver_order: Option<Vec<OutputDriver>>,
+
+    /// Notification settings
+    #[serde(default)]
+    pub notification: NotificationConfig,
+
+    /// Delay between typed characters (ms), 0 for fastest
+    #[serde(default)]
+    pub type_delay_ms: u32,
+
+    /// Delay before typing starts (ms), allows virtual keyboard to initialize
+    /// Helps prevent first character from being dropped on some compositors
+    #[serde(default)]
+    pub pre_type_delay_ms: u32,
+
+    /// DEPRECATED: Use pre_type_delay_ms instead. Kept for backwards compatibility.
+    #[serde(default)]
+    pub wtype_delay_ms: u32,
+
+    /// Automatically submit (send Enter key) after outputting transcribed text
+    /// Useful for chat applications, command lines, or forms where you want
+    /// to auto-submit after dictation
+    #[serde(default)]
+    pub auto_submit: bool,
+
+    /// Convert newlines to Shift+Enter instead of regular Enter
+    /// Useful for applications where Enter submits (e.g., Cursor IDE, Slack, Discord)
+    #[serde(default)]
+    pub shift_enter_newlines: bool,
+
+    /// Command to run when recording starts (e.g., switch to compositor submap)
+    /// Useful for entering a mode where cancel keybindings are effective
+    #[serde(default)]
+    pub pre_recording_command: Option<String>,
+
+    /// Command to run before typing output (e.g., compositor submap switch)
+    /// Useful for blocking modifier keys at the compositor level
+    #[serde(default)]
+    pub pre_output_command: Option<String>,
+
+    /// Command to run after typing output (e.g., reset compositor submap)
+    /// Runs even if typing fails, to ensure cleanup
+    #[serde(default)]
+    pub post_output_command: Option<String>,
+
+    /// Optional post-processing command configuration
+    /// Pipes transcribed text through an external command before output
+    #[serde(default)]
+    pub post_process: Option<PostProcessConfig>,
+
+    /// LLM post-processor to use by default (name from [llm])
+    /// Used when [output.post_process] isn't configured
+    #[serde(default)]
+    pub post_process_llm: Option<String>,
+
+    /// Keystroke to simulate for paste mode (e.g., "ctrl+v", "shift+insert", "ctrl+shift+v")
+    /// Defaults to "ctrl+v" if not specified
+    #[serde(default)]
+    pub paste_keys: Option<String>,
+
+    /// Restore the previous clipboard contents after pasting (paste mode)
+    #[serde(default)]
+    pub paste_restore_clipboard: bool,
+
+    /// Delay before restoring the clipboard (ms), so the application reads the paste first
+    #[serde(default = "default_paste_restore_delay_ms")]
+    pub paste_restore_delay_ms: u32,
+
+    /// Keyboard layout for dotool and uinput (e.g., "de" for German, "fr" for French)
+    /// Required for non-US keyboard layouts when using dotool or uinput
+    #[serde(default)]
+    pub dotool_xkb_layout: Option<String>,
+
+    /// Keyboard layout variant for dotool and uinput (e.g., "nodeadkeys")
+    #[serde(default)]
+    pub dotool_xkb_variant: Option<String>,
+
+    /// File path for file output mode (required when mode = "file")
+    /// Also used as default path for --output-file CLI flag
+    #[serde(default)]
+    pub file_path: Option<PathBuf>,
+
+    /// File write mode: "overwrite" (default) or "append"
+    /// Applies to both config-based file output and --output-file CLI flag
+    #[serde(default)]
+    pub file_mode: FileMode,
+
+    /// Join consecutive dictations into continuous text (spacing, capitalization)
+    #[serde(default)]
+    pub smart_join: SmartJoinConfig,
+
+    /// Targets of the fifo, socket and webhook output modes
+    #[serde(default)]
+    pub sink: SinkConfig,
+}
+
+/// Smart joining configuration (`[output.smart_join]`)
+///
+/// Remembers the end of the previous dictation and adjusts the next one to
+/// continue it: a leading space, sentence capitalization, no doubled punctuation.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SmartJoinConfig {
+    /// Enable smart joining (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Seconds after which the next dictation starts fresh (default: 30)
+    #[serde(default = "default_smart_join_timeout")]
+    pub timeout_secs: u64,
+
+    /// Capitalize after a sentence end and lowercase a capital Whisper put
+    /// mid-sentence (default: true)
+    #[serde(default = "default_true")]
+    pub capitalization: bool,
+}
+
+fn default_smart_join_timeout() -> u64 {
+    30
+}
+
+impl Default for SmartJoinConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            timeout_secs: default_smart_join_timeout(),
+            capitalization: true,
+        }
+    }
+}
+
+/// Transcription sink configuration (`[output.sink]`)
+///
+/// The "fifo", "socket" and "webhook" output modes send each transcription
+/// to another program as a JSON object instead of typing it.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SinkConfig {
+    /// Named pipe for mode = "fifo", created if missing
+    /// (default: $XDG_RUNTIME_DIR/voxtype/transcriptions.fifo)
+    #[serde(default)]
+    pub fifo_path: Option<PathBuf>,
+
+    /// Unix socket the daemon listens on for mode = "socket"
+    /// (default: $XDG_RUNTIME_DIR/voxtype/transcriptions.sock)
+    #[serde(default)]
+    pub socket_path: Option<PathBuf>,
+
+    /// URL to POST each transcription to (required for mode = "webhook")
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+
+    /// Extra HTTP headers for the webhook (e.g., Authorization)
+    #[serde(default)]
+    pub webhook_headers: HashMap<String, String>,
+
+    /// Webhook request timeout in milliseconds (default: 5000)
+    #[serde(default = "default_webhook_timeout_ms")]
+    pub webhook_timeout_ms: u64,
+}
+
+fn default_webhook_timeout_ms() -> u64 {
+    5000
+}
+
+impl Default for SinkConfig {
+    fn default() -> Self {
+        Self {
+            fifo_path: None,
+            socket_path: None,
+            webhook_url: None,
+            webhook_headers: HashMap::new(),
+            webhook_timeout_ms: default_webhook_timeout_ms(),
+        }
+    }
+}
+
+impl SinkConfig {
+    /// Named pipe path, or the default in the runtime directory
+    pub fn effective_fifo_path(&self) -> PathBuf {
+        self.fifo_path
+            .clone()
+            .unwrap_or_else(|| Config::runtime_dir().join("transcriptions.fifo"))
+    }
+
+    /// Socket path, or the default in the runtime directory
+    pub fn effective_socket_path(&self) -> PathBuf {
+        self.socket_path
+            .clone()
+            .unwrap_or_else(|| Config::runtime_dir().join("transcriptions.sock"))
+    }
+}
+
+fn default_paste_restore_delay_ms() -> u32 {
+    500
+}
+
+impl OutputConfig {
+    /// Get the effective pre-type delay, handling deprecated wtype_delay_ms
     pub fn effective_pre_type_delay_ms(&self) -> u32 {
         if self.wtype_delay_ms > 0 {
             if self.pre_type_delay_ms > 0 {
@@ -1118,6 +2372,22 @@ pub enum OutputMode {
     Paste,
     /// Write transcription to a file
     File,
+    /// Write each transcription as a JSON line to a named pipe
+    Fifo,
+    /// Send each transcription as a JSON line to the clients of a Unix socket
+    Socket,
+    /// POST each transcription as JSON to an HTTP URL
+    Webhook,
+}
+
+impl OutputMode {
+    /// Modes that hand the transcription to another program ([output.sink])
+    pub fn is_sink(&self) -> bool {
+        matches!(
+            self,
+            OutputMode::Fifo | OutputMode::Socket | OutputMode::Webhook
+        )
+    }
 }
 
 /// Output driver for typing text
@@ -1133,6 +2403,8 @@ pub enum OutputDriver {
     Dotool,
     /// ydotool - Works on X11/Wayland/TTY, requires daemon
     Ydotool,
+    /// Built-in virtual keyboard via /dev/uinput, no external tool or daemon
+    Uinput,
     /// Clipboard via wl-copy (Wayland)
     Clipboard,
     /// Clipboard via xclip (X11)
@@ -1146,6 +2418,7 @@ impl std::fmt::Display for OutputDriver {
             OutputDriver::Eitype => write!(f, "eitype"),
             OutputDriver::Dotool => write!(f, "dotool"),
             OutputDriver::Ydotool => write!(f, "ydotool"),
+            OutputDriver::Uinput => write!(f, "uinput"),
             OutputDriver::Clipboard => write!(f, "clipboard"),
             OutputDriver::Xclip => write!(f, "xclip"),
         }
@@ -1161,10 +2434,11 @@ impl std::str::FromStr for OutputDriver {
             "eitype" => Ok(OutputDriver::Eitype),
             "dotool" => Ok(OutputDriver::Dotool),
             "ydotool" => Ok(OutputDriver::Ydotool),
+            "uinput" => Ok(OutputDriver::Uinput),
             "clipboard" => Ok(OutputDriver::Clipboard),
             "xclip" => Ok(OutputDriver::Xclip),
             _ => Err(format!(
-                "Unknown driver '{}'. Valid options: wtype, eitype, dotool, ydotool, clipboard, xclip",
+                "Unknown driver '{}'. Valid options: wtype, eitype, dotool, ydotool, uinput, clipboard, xclip",
                 s
             )),
         }
@@ -1238,18 +2512,31 @@ impl Default for Config {
                 pre_output_command: None,
                 post_output_command: None,
                 post_process: None,
+                post_process_llm: None,
                 paste_keys: None,
+                paste_restore_clipboard: false,
+                paste_restore_delay_ms: default_paste_restore_delay_ms(),
                 dotool_xkb_layout: None,
                 dotool_xkb_variant: None,
                 file_path: None,
                 file_mode: FileMode::default(),
+                smart_join: SmartJoinConfig::default(),
+                sink: SinkConfig::default(),
             },
             engine: TranscriptionEngine::default(),
             parakeet: None,
+            external: None,
             text: TextConfig::default(),
+            vocabulary: VocabularyConfig::default(),
+            snippets: SnippetsConfig::default(),
+            scripts: ScriptsConfig::default(),
+            pipeline: Vec::new(),
             status: StatusConfig::default(),
             state_file: Some("auto".to_string()),
             profiles: HashMap::new(),
+            grammars: HashMap::new(),
+            auto_profile: AutoProfileConfig::default(),
+            llm: HashMap::new(),
             update: UpdateConfig::default(),
         }
     }
@@ -1328,6 +2615,11 @@ impl Config {
                 .as_ref()
                 .map(|p| p.on_demand_loading)
                 .unwrap_or(false),
+            TranscriptionEngine::External => self
+                .external
+                .as_ref()
+                .map(|e| e.on_demand_loading)
+                .unwrap_or(false),
         }
     }
 
@@ -1340,6 +2632,11 @@ impl Config {
                 .as_ref()
                 .map(|p| p.model.as_str())
                 .unwrap_or("parakeet (not configured)"),
+            TranscriptionEngine::External => self
+                .external
+                .as_ref()
+                .map(|e| e.model.as_deref().unwrap_or(&e.command))
+                .unwrap_or("external (not configured)"),
         }
     }
 
@@ -1353,6 +2650,128 @@ impl Config {
     pub fn profile_names(&self) -> Vec<&String> {
         self.profiles.keys().collect()
     }
+
+    /// Whether an output mode is the default or set by a profile
+    pub fn uses_output_mode(&self, mode: &OutputMode) -> bool {
+        self.output.mode == *mode
+            || self
+                .profiles
+                .values()
+                .any(|profile| profile.output_mode.as_ref() == Some(mode))
+    }
+
+    /// Apply `extends` so every profile holds its inherited options
+    /// Fails on unknown base profiles and inheritance cycles
+    pub fn resolve_profiles(&mut self) -> Result<(), VoxtypeError> {
+        let mut resolved = HashMap::new();
+        for (name, profile) in &self.profiles {
+            let mut profile = profile.clone();
+            let mut chain = vec![name.clone()];
+            let mut next = profile.extends.clone();
+            while let Some(base_name) = next {
+                if chain.contains(&base_name) {
+                    chain.push(base_name);
+                    return Err(VoxtypeError::Config(format!(
+                        "Profile inheritance cycle: {}",
+                        chain.join(" -> ")
+                    )));
+                }
+                let base = self.profiles.get(&base_name).ok_or_else(|| {
+                    VoxtypeError::Config(format!(
+                        "Profile '{}' extends unknown profile '{}'",
+                        chain.last().map(String::as_str).unwrap_or(name),
+                        base_name
+                    ))
+                })?;
+                profile.inherit(base);
+                next = base.extends.clone();
+                chain.push(base_name);
+            }
+            resolved.insert(name.clone(), profile);
+        }
+        self.profiles = resolved;
+        Ok(())
+    }
+
+    /// The effective configuration while a profile is active
+    pub fn with_profile(&self, profile: &Profile) -> Config {
+        let mut config = self.clone();
+        if let Some(engine) = profile.engine {
+            config.engine = engine;
+        }
+        if let Some(ref model) = profile.model {
+            config.whisper.model = model.clone();
+        }
+        if let Some(ref language) = profile.language {
+            config.whisper.language = language.clone();
+        }
+        if let Some(ref prompt) = profile.initial_prompt {
+            config.whisper.initial_prompt = Some(prompt.clone());
+        }
+        if let Some(translate) = profile.translate {
+            config.whisper.translate = translate;
+        }
+
+        if let Some(enabled) = profile.spoken_punctuation {
+            config.text.spoken_punctuation = enabled;
+        }
+        if let Some(ref replacements) = profile.replacements {
+            config.text.replacements.extend(replacements.clone());
+        }
+        if let Some(enabled) = profile.normalize {
+            config.text.normalize.enabled = enabled;
+        }
+        if let Some(enabled) = profile.disfluency {
+            config.text.disfluency.enabled = enabled;
+        }
+        if let Some(enabled) = profile.code {
+            config.text.code.enabled = enabled;
+        }
+
+        // Same priority as PostProcessor::from_config: the profile's command, then its LLM
+        if let Some(ref command) = profile.post_process_command {
+            config.output.post_process = Some(PostProcessConfig {
+                command: command.clone(),
+                timeout_ms: profile.post_process_timeout_ms.unwrap_or(30000),
+                protocol: profile.post_process_protocol.unwrap_or_default(),
+            });
+        } else if let Some(ref llm) = profile.post_process_llm {
+            config.output.post_process = None;
+            config.output.post_process_llm = Some(llm.clone());
+        }
+        if let Some(ref mode) = profile.output_mode {
+            config.output.mode = mode.clone();
+        }
+        if let Some(auto_submit) = profile.auto_submit {
+            config.output.auto_submit = auto_submit;
+        }
+        if let Some(ref driver_order) = profile.driver_order {
+            config.output.driver_order = Some(driver_order.clone());
+        }
+        if let Some(ref command) = profile.pre_recording_command {
+            config.output.pre_recording_command = Some(command.clone());
+        }
+        if let Some(ref command) = profile.pre_output_command {
+            config.output.pre_output_command = Some(command.clone());
+        }
+        if let Some(ref command) = profile.post_output_command {
+            config.output.post_output_command = Some(command.clone());
+        }
+        if let Some(enabled) = profile.feedback {
+            config.audio.feedback.enabled = enabled;
+        }
+        config
+    }
+
+    /// Get a command grammar by name
+    pub fn get_grammar(&self, name: &str) -> Option<&GrammarConfig> {
+        self.grammars.get(name)
+    }
+
+    /// Get an LLM post-processor by name
+    pub fn get_llm(&self, name: &str) -> Option<&LlmConfig> {
+        self.llm.get(name)
+    }
 }
 
 /// Load configuration from file, with defaults for missing values
@@ -1372,6 +2791,7 @@ pub fn load_config(path: Option<&Path>) -> Result<Config, VoxtypeError> {
 
             config = toml::from_str(&contents)
                 .map_err(|e| VoxtypeError::Config(format!("Invalid config: {}", e)))?;
+            config.resolve_profiles()?;
         } else {
             tracing::debug!("Config file not found at {:?}, using defaults", path);
         }
@@ -1979,9 +3399,39 @@ mod tests {
     }
 
     #[test]
-    fn test_parse_engine_parakeet() {
+    fn test_parse_engine_parakeet() {
+        let toml_str = r#"
+            engine = "parakeet"
+
+            [hotkey]
+            key = "SCROLLLOCK"
+
+            [audio]
+            device = "default"
+            sample_rate = 16000
+            max_duration_secs = 60
+
+            [whisper]
+            model = "base.en"
+            language = "en"
+
+            [output]
+            mode = "type"
+
+            [parakeet]
+            model = "parakeet-tdt-0.6b-v3"
+        "#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.engine, TranscriptionEngine::Parakeet);
+        assert!(config.parakeet.is_some());
+        assert_eq!(config.parakeet.as_ref().unwrap().model, "parakeet-tdt-0.6b-v3");
+    }
+
+    #[test]
+    fn test_parse_engine_external() {
         let toml_str = r#"
-            engine = "parakeet"
+            engine = "external"
 
             [hotkey]
             key = "SCROLLLOCK"
@@ -1991,21 +3441,29 @@ mod tests {
             sample_rate = 16000
             max_duration_secs = 60
 
-            [whisper]
-            model = "base.en"
-            language = "en"
-
             [output]
             mode = "type"
 
-            [parakeet]
-            model = "parakeet-tdt-0.6b-v3"
+            [external]
+            command = "my-asr"
+            args = ["--device", "cpu"]
+            model = "small"
+            language = ["en", "de"]
         "#;
 
         let config: Config = toml::from_str(toml_str).unwrap();
-        assert_eq!(config.engine, TranscriptionEngine::Parakeet);
-        assert!(config.parakeet.is_some());
-        assert_eq!(config.parakeet.as_ref().unwrap().model, "parakeet-tdt-0.6b-v3");
+        assert_eq!(config.engine, TranscriptionEngine::External);
+        let external = config.external.as_ref().unwrap();
+        assert_eq!(external.command, "my-asr");
+        assert_eq!(external.args, vec!["--device", "cpu"]);
+        assert_eq!(
+            external.language,
+            LanguageConfig::Multiple(vec!["en".to_string(), "de".to_string()])
+        );
+        assert_eq!(external.startup_timeout_secs, 60);
+        assert_eq!(external.timeout_secs, 120);
+        assert!(!external.on_demand_loading);
+        assert_eq!(config.model_name(), "small");
     }
 
     #[test]
@@ -2036,6 +3494,7 @@ mod tests {
         assert_eq!("wtype".parse::<OutputDriver>().unwrap(), OutputDriver::Wtype);
         assert_eq!("dotool".parse::<OutputDriver>().unwrap(), OutputDriver::Dotool);
         assert_eq!("ydotool".parse::<OutputDriver>().unwrap(), OutputDriver::Ydotool);
+        assert_eq!("uinput".parse::<OutputDriver>().unwrap(), OutputDriver::Uinput);
         assert_eq!("clipboard".parse::<OutputDriver>().unwrap(), OutputDriver::Clipboard);
         assert_eq!("xclip".parse::<OutputDriver>().unwrap(), OutputDriver::Xclip);
         // Case insensitive
@@ -2051,6 +3510,7 @@ mod tests {
         assert_eq!(OutputDriver::Wtype.to_string(), "wtype");
         assert_eq!(OutputDriver::Dotool.to_string(), "dotool");
         assert_eq!(OutputDriver::Ydotool.to_string(), "ydotool");
+        assert_eq!(OutputDriver::Uinput.to_string(), "uinput");
         assert_eq!(OutputDriver::Clipboard.to_string(), "clipboard");
         assert_eq!(OutputDriver::Xclip.to_string(), "xclip");
     }
@@ -2535,6 +3995,333 @@ mod tests {
         let profile = config.get_profile("clipboard_only").unwrap();
         assert!(profile.post_process_command.is_none());
         assert_eq!(profile.output_mode, Some(OutputMode::Clipboard));
+        assert!(!profile.changes_text_processing());
+    }
+
+    #[test]
+    fn test_profile_changes_text_processing() {
+        let config: Config = toml::from_str(
+            r#"
+            [hotkey]
+            key = "SCROLLLOCK"
+
+            [audio]
+            device = "default"
+            sample_rate = 16000
+            max_duration_secs = 60
+
+            [whisper]
+            model = "base.en"
+
+            [output]
+            mode = "type"
+
+            [profiles.german]
+            language = "de"
+
+            [profiles.prose]
+            normalize = false
+
+            [profiles.slack]
+            output_mode = "paste"
+            post_process_command = "cat"
+        "#,
+        )
+        .unwrap();
+        let changes = |name| config.get_profile(name).unwrap().changes_text_processing();
+        assert!(changes("german"));
+        assert!(changes("prose"));
+        assert!(!changes("slack"));
+    }
+
+    #[test]
+    fn test_profile_inheritance() {
+        let toml_str = r#"
+            [hotkey]
+            key = "SCROLLLOCK"
+
+            [audio]
+            device = "default"
+            sample_rate = 16000
+            max_duration_secs = 60
+
+            [audio.feedback]
+            enabled = true
+
+            [whisper]
+            model = "base.en"
+            language = "en"
+
+            [output]
+            mode = "type"
+            driver_order = ["ydotool"]
+
+            [text.replacements]
+            "vox type" = "voxtype"
+            "hyper land" = "Hyprland"
+
+            [profiles.base]
+            model = "small.en"
+            auto_submit = true
+            replacements = { "vox type" = "VoxType", "k eight s" = "k8s" }
+
+            [profiles.chat]
+            extends = "base"
+            output_mode = "paste"
+            feedback = false
+            replacements = { "k eight s" = "kubernetes" }
+
+            [profiles.terminal]
+            extends = "chat"
+            auto_submit = false
+            engine = "parakeet"
+            driver_order = ["wtype", "clipboard"]
+            post_process_command = "cat"
+        "#;
+
+        let mut config: Config = toml::from_str(toml_str).unwrap();
+        config.resolve_profiles().unwrap();
+
+        let terminal = config.get_profile("terminal").unwrap();
+        assert_eq!(terminal.model.as_deref(), Some("small.en"));
+        assert_eq!(terminal.auto_submit, Some(false));
+        assert_eq!(terminal.output_mode, Some(OutputMode::Paste));
+        let replacements = terminal.replacements.as_ref().unwrap();
+        assert_eq!(replacements["vox type"], "VoxType");
+        assert_eq!(replacements["k eight s"], "kubernetes");
+
+        let effective = config.with_profile(terminal);
+        assert_eq!(effective.engine, TranscriptionEngine::Parakeet);
+        assert_eq!(effective.whisper.model, "small.en");
+        assert_eq!(effective.output.mode, OutputMode::Paste);
+        assert!(!effective.output.auto_submit);
+        assert!(!effective.audio.feedback.enabled);
+        assert_eq!(
+            effective.output.driver_order,
+            Some(vec![OutputDriver::Wtype, OutputDriver::Clipboard])
+        );
+        assert_eq!(effective.output.post_process.unwrap().command, "cat");
+        assert_eq!(effective.text.replacements["vox type"], "VoxType");
+        assert_eq!(effective.text.replacements["hyper land"], "Hyprland");
+
+        // The base config is untouched
+        let chat = config.with_profile(config.get_profile("chat").unwrap());
+        assert!(chat.output.auto_submit);
+        assert_eq!(chat.engine, TranscriptionEngine::Whisper);
+        assert!(config.audio.feedback.enabled);
+        assert_eq!(config.text.replacements["vox type"], "voxtype");
+    }
+
+    #[test]
+    fn test_profile_inherits_post_processor_as_one_choice() {
+        let toml_str = r#"
+            [hotkey]
+            key = "SCROLLLOCK"
+
+            [audio]
+            device = "default"
+            sample_rate = 16000
+            max_duration_secs = 60
+
+            [output]
+            mode = "type"
+
+            [profiles.base]
+            post_process_command = "cat"
+            post_process_timeout_ms = 5000
+
+            [profiles.same]
+            extends = "base"
+
+            [profiles.llm]
+            extends = "base"
+            post_process_llm = "cleanup"
+        "#;
+
+        let mut config: Config = toml::from_str(toml_str).unwrap();
+        config.resolve_profiles().unwrap();
+
+        let same = config.get_profile("same").unwrap();
+        assert_eq!(same.post_process_command.as_deref(), Some("cat"));
+        assert_eq!(same.post_process_llm, None);
+
+        // The LLM replaces the base command instead of losing to it
+        let llm = config.get_profile("llm").unwrap();
+        assert_eq!(llm.post_process_command, None);
+        assert_eq!(llm.post_process_llm.as_deref(), Some("cleanup"));
+        assert_eq!(llm.post_process_timeout_ms, Some(5000));
+    }
+
+    #[test]
+    fn test_profile_inheritance_errors() {
+        let base = r#"
+            [hotkey]
+            key = "SCROLLLOCK"
+
+            [audio]
+            device = "default"
+            sample_rate = 16000
+            max_duration_secs = 60
+
+            [output]
+            mode = "type"
+        "#;
+        let parse = |profiles: &str| {
+            let mut config: Config = toml::from_str(&format!("{}\n{}", base, profiles)).unwrap();
+            config.resolve_profiles().map_err(|e| e.to_string())
+        };
+
+        let error = parse("[profiles.a]\nextends = \"missing\"").unwrap_err();
+        assert!(
+            error.contains("'a' extends unknown profile 'missing'"),
+            "{}",
+            error
+        );
+
+        let error = parse(
+            "[profiles.a]\nextends = \"b\"\n[profiles.b]\nextends = \"c\"\n[profiles.c]\nextends = \"a\"",
+        )
+        .unwrap_err();
+        assert!(error.contains("cycle"), "{}", error);
+
+        assert!(parse("[profiles.a]\nextends = \"b\"\n[profiles.b]\nmodel = \"tiny\"").is_ok());
+    }
+
+    #[test]
+    fn test_parse_auto_profile() {
+        let toml_str = r#"
+            [hotkey]
+            key = "SCROLLLOCK"
+
+            [audio]
+            device = "default"
+            sample_rate = 16000
+            max_duration_secs = 60
+
+            [output]
+            mode = "type"
+
+            [auto_profile]
+            provider = "sway"
+            timeout_ms = 200
+
+            [[auto_profile.rules]]
+            app_id = "kitty|foot"
+            profile = "terminal"
+
+            [[auto_profile.rules]]
+            class = "Slack"
+            title = "general"
+            profile = "chat"
+        "#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert!(config.auto_profile.enabled);
+        assert_eq!(config.auto_profile.provider, WindowProvider::Sway);
+        assert_eq!(config.auto_profile.timeout_ms, 200);
+        assert_eq!(config.auto_profile.rules.len(), 2);
+        assert_eq!(
+            config.auto_profile.rules[0].app_id.as_deref(),
+            Some("kitty|foot")
+        );
+        assert_eq!(config.auto_profile.rules[1].class.as_deref(), Some("Slack"));
+        assert_eq!(config.auto_profile.rules[1].profile, "chat");
+
+        let default = Config::default().auto_profile;
+        assert_eq!(default.provider, WindowProvider::Auto);
+        assert!(default.rules.is_empty());
+    }
+
+    #[test]
+    fn test_parse_output_sink() {
+        let toml_str = r#"
+            [hotkey]
+            key = "SCROLLLOCK"
+
+            [audio]
+            device = "default"
+            sample_rate = 16000
+            max_duration_secs = 60
+
+            [output]
+            mode = "webhook"
+
+            [output.sink]
+            socket_path = "/tmp/dictation.sock"
+            webhook_url = "http://localhost:8080/dictation"
+            webhook_headers = { Authorization = "Bearer secret" }
+
+            [profiles.notes]
+            output_mode = "socket"
+        "#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.output.mode, OutputMode::Webhook);
+        assert!(config.output.mode.is_sink());
+        let sink = &config.output.sink;
+        assert_eq!(
+            sink.effective_socket_path(),
+            PathBuf::from("/tmp/dictation.sock")
+        );
+        assert!(sink
+            .effective_fifo_path()
+            .ends_with("voxtype/transcriptions.fifo"));
+        assert_eq!(
+            sink.webhook_url.as_deref(),
+            Some("http://localhost:8080/dictation")
+        );
+        assert_eq!(sink.webhook_headers["Authorization"], "Bearer secret");
+        assert_eq!(sink.webhook_timeout_ms, 5000);
+
+        assert!(config.uses_output_mode(&OutputMode::Socket));
+        assert!(!config.uses_output_mode(&OutputMode::Fifo));
+        assert!(!OutputMode::File.is_sink());
+    }
+
+    #[test]
+    fn test_parse_profile_transcription_overrides() {
+        let toml_str = r#"
+            [hotkey]
+            key = "SCROLLLOCK"
+
+            [audio]
+            device = "default"
+            sample_rate = 16000
+            max_duration_secs = 60
+
+            [whisper]
+            model = "base.en"
+            language = "en"
+
+            [output]
+            mode = "type"
+
+            [profiles.german]
+            language = "de"
+            initial_prompt = "Besprechung"
+
+            [profiles.translate]
+            language = ["fr", "de"]
+            translate = true
+        "#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+
+        let german = config.get_profile("german").unwrap();
+        assert_eq!(german.language, Some(LanguageConfig::Single("de".to_string())));
+        assert_eq!(german.initial_prompt, Some("Besprechung".to_string()));
+        assert_eq!(german.translate, None);
+
+        let translate = config.get_profile("translate").unwrap();
+        assert_eq!(
+            translate.language,
+            Some(LanguageConfig::Multiple(vec![
+                "fr".to_string(),
+                "de".to_string()
+            ]))
+        );
+        assert_eq!(translate.translate, Some(true));
+        assert!(translate.initial_prompt.is_none());
     }
 
     #[test]
@@ -2561,6 +4348,142 @@ mod tests {
         assert!(config.profiles.is_empty());
     }
 
+    #[test]
+    fn test_parse_grammars() {
+        let toml_str = r#"
+            [hotkey]
+            key = "SCROLLLOCK"
+
+            [audio]
+            device = "default"
+            sample_rate = 16000
+            max_duration_secs = 60
+
+            [output]
+            mode = "type"
+
+            [grammars.window]
+            threshold = 0.9
+
+            [grammars.window.commands]
+            close = ["close window", "close this"]
+            maximize = ["maximize"]
+
+            [grammars.media.commands]
+            play = ["play"]
+
+            [profiles.commands]
+            grammar = "window"
+        "#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.grammars.len(), 2);
+
+        let window = config.get_grammar("window").unwrap();
+        assert_eq!(window.threshold, 0.9);
+        assert!(window.bias_prompt);
+        assert_eq!(window.commands["close"], vec!["close window", "close this"]);
+
+        let media = config.get_grammar("media").unwrap();
+        assert_eq!(media.threshold, 0.75);
+        assert!(config.get_grammar("missing").is_none());
+
+        let profile = config.get_profile("commands").unwrap();
+        assert_eq!(profile.grammar.as_deref(), Some("window"));
+    }
+
+    #[test]
+    fn test_vocabulary_config_defaults() {
+        let config = Config::default();
+        assert!(config.vocabulary.enabled);
+        assert!(config.vocabulary.inject_prompt);
+        assert!(config.vocabulary.fuzzy_correction);
+        assert_eq!(config.vocabulary.max_prompt_tokens, 150);
+        assert!(config.vocabulary.file.is_none());
+    }
+
+    #[test]
+    fn test_parse_vocabulary_config() {
+        let toml_str = r#"
+            [hotkey]
+            key = "SCROLLLOCK"
+
+            [audio]
+            device = "default"
+            sample_rate = 16000
+            max_duration_secs = 60
+
+            [output]
+            mode = "type"
+
+            [vocabulary]
+            file = "/tmp/terms.txt"
+            inject_prompt = false
+            fuzzy_threshold = 0.9
+        "#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(
+            config.vocabulary.resolve_path(),
+            Some(PathBuf::from("/tmp/terms.txt"))
+        );
+        assert!(!config.vocabulary.inject_prompt);
+        assert!(config.vocabulary.fuzzy_correction);
+        assert_eq!(config.vocabulary.fuzzy_threshold, 0.9);
+    }
+
+    #[test]
+    fn test_parse_pipeline() {
+        let toml_str = r#"
+            [hotkey]
+            key = "SCROLLLOCK"
+
+            [audio]
+            device = "default"
+            sample_rate = 16000
+            max_duration_secs = 60
+
+            [output]
+            mode = "type"
+
+            [[pipeline]]
+            stage = "hallucination_filter"
+
+            [[pipeline]]
+            stage = "command"
+            name = "llm"
+            command = "cat"
+            timeout_ms = 5000
+            profiles = ["email", "default"]
+            protocol = "json"
+
+            [[pipeline]]
+            stage = "spoken_punctuation"
+            enabled = false
+        "#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let stages = &config.pipeline;
+        assert_eq!(stages.len(), 3);
+        assert_eq!(stages[0].stage, PipelineStageKind::HallucinationFilter);
+        assert_eq!(stages[0].label(), "hallucination_filter");
+        assert!(stages[0].applies_to(Some("email")));
+        assert_eq!(stages[1].label(), "llm");
+        assert_eq!(stages[1].timeout_ms, Some(5000));
+        assert_eq!(stages[1].protocol, PostProcessProtocol::Json);
+        assert_eq!(stages[0].protocol, PostProcessProtocol::Text);
+        assert!(stages[1].applies_to(None));
+        assert!(stages[1].applies_to(Some("email")));
+        assert!(!stages[1].applies_to(Some("code")));
+        assert!(!stages[2].applies_to(None));
+
+        assert!(toml::from_str::<Config>(&format!(
+            "{}\n[[pipeline]]\nstage = \"translate\"",
+            toml_str
+        ))
+        .is_err());
+    }
+
     #[test]
     fn test_parse_driver_order_from_config() {
         let toml_str = r#"
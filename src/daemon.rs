@@ -5,18 +5,31 @@
 
 use crate::audio::feedback::{AudioFeedback, SoundEvent};
 use crate::audio::{self, AudioCapture};
-use crate::config::{ActivationMode, Config, FileMode, OutputMode};
+use crate::config::{ActivationMode, Config, FileMode, OutputMode, Profile, TranscriptionEngine};
 use crate::error::Result;
 use crate::hotkey::{self, HotkeyEvent};
+use crate::listen::{ListenOutcome, ListenRequest, ListenSession};
 use crate::model_manager::ModelManager;
 use crate::output;
-use crate::output::post_process::PostProcessor;
+use crate::output::action::{actions_text, OutputAction};
+use crate::output::post_process::{PostProcessMetadata, PostProcessOutput, PostProcessor};
+use crate::output::sink::{self, SocketSink, TranscriptionEvent};
+use crate::pipeline::Pipeline;
+use crate::script::{ScriptMetadata, ScriptRunner};
 use crate::state::State;
-use crate::text::TextProcessor;
-use crate::transcribe::Transcriber;
+use crate::text::grammar::CommandGrammar;
+use crate::text::join::SmartJoiner;
+use crate::text::snippets::SnippetLibrary;
+use crate::text::vocabulary::{Vocabulary, WHISPER_MAX_PROMPT_TOKENS};
+use crate::text::{ProcessContext, TextProcessor};
+use crate::transcribe::{Transcriber, TranscriptionOverrides};
+use crate::window::{ProfileSelector, WindowInfo};
 use pidlock::Pidlock;
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::process::Stdio;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::process::Command;
@@ -252,6 +265,19 @@ fn cleanup_profile_override() {
     let _ = std::fs::remove_file(&profile_file);
 }
 
+/// Characters typed after the last key press in `actions`
+fn trailing_text_chars(actions: &[OutputAction]) -> usize {
+    actions
+        .iter()
+        .rev()
+        .take_while(|action| !action.is_key())
+        .map(|action| match action {
+            OutputAction::Text(text) => text.chars().count(),
+            OutputAction::Key(_) => 0,
+        })
+        .sum()
+}
+
 /// Write transcription to a file, respecting file_mode (overwrite or append)
 async fn write_transcription_to_file(
     path: &std::path::Path,
@@ -327,8 +353,176 @@ fn cleanup_model_override() {
     let _ = std::fs::remove_file(&override_file);
 }
 
-/// Result type for transcription task
-type TranscriptionResult = std::result::Result<String, crate::error::TranscribeError>;
+/// Read and consume a single-value override file from the runtime dir
+/// Returns the trimmed content, or None if missing or empty
+fn read_override_file(name: &str) -> Option<String> {
+    let override_file = Config::runtime_dir().join(name);
+    if !override_file.exists() {
+        return None;
+    }
+
+    let content = match std::fs::read_to_string(&override_file) {
+        Ok(s) => s,
+        Err(e) => {
+            tracing::warn!("Failed to read {} file: {}", name, e);
+            return None;
+        }
+    };
+
+    // Consume the file (delete it after reading)
+    if let Err(e) = std::fs::remove_file(&override_file) {
+        tracing::warn!("Failed to remove {} file: {}", name, e);
+    }
+
+    let value = content.trim().to_string();
+    if value.is_empty() {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+/// Read the profile override file without consuming it
+/// The profile is consumed later, when post-processing the transcription
+fn peek_profile_override() -> Option<String> {
+    let profile_file = Config::runtime_dir().join("profile_override");
+    std::fs::read_to_string(&profile_file)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Name of the command grammar selected for this recording, without consuming it
+/// The --grammar flag takes precedence over the profile's grammar
+fn peek_grammar(config: &Config) -> Option<String> {
+    std::fs::read_to_string(Config::runtime_dir().join("grammar_override"))
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .or_else(|| {
+            peek_profile_override()
+                .and_then(|name| config.get_profile(&name))
+                .and_then(|profile| profile.grammar.clone())
+        })
+}
+
+/// Parse the content of the translate override file ("true"/"false")
+fn parse_translate_override(value: &str) -> Option<bool> {
+    match value {
+        "true" => Some(true),
+        "false" => Some(false),
+        other => {
+            tracing::warn!("Invalid translate override: {:?}", other);
+            None
+        }
+    }
+}
+
+/// Read and consume the language, initial prompt and translate override files
+/// Unset values are filled from the profile selected for this recording (if any)
+/// Vocabulary terms are appended to an overridden initial prompt
+fn read_transcription_overrides(
+    config: &Config,
+    vocabulary: Option<&Vocabulary>,
+) -> TranscriptionOverrides {
+    let overrides = TranscriptionOverrides {
+        language: read_override_file("language_override")
+            .map(|l| crate::config::LanguageConfig::from_comma_separated(&l)),
+        initial_prompt: read_override_file("initial_prompt_override"),
+        translate: read_override_file("translate_override")
+            .and_then(|t| parse_translate_override(&t)),
+    };
+
+    let mut overrides = match peek_profile_override()
+        .as_deref()
+        .and_then(|name| config.get_profile(name))
+    {
+        Some(profile) => overrides.with_profile_defaults(profile),
+        None => overrides,
+    };
+
+    // The base prompt already includes the vocabulary (see Daemon::new),
+    // but an overridden prompt replaces it entirely
+    if let (Some(vocab), Some(prompt)) = (vocabulary, overrides.initial_prompt.as_deref()) {
+        if config.vocabulary.inject_prompt {
+            overrides.initial_prompt =
+                vocab.build_prompt(Some(prompt), config.vocabulary.max_prompt_tokens);
+        }
+    }
+
+    // A command grammar biases recognition toward its phrases, added after the
+    // prompt and vocabulary terms that apply otherwise
+    if let Some(grammar) = peek_grammar(config)
+        .and_then(|name| config.get_grammar(&name))
+        .filter(|g| g.bias_prompt)
+    {
+        let base = overrides
+            .initial_prompt
+            .as_deref()
+            .or(config.whisper.initial_prompt.as_deref());
+        overrides.initial_prompt =
+            CommandGrammar::new(grammar).prompt(base, WHISPER_MAX_PROMPT_TOKENS);
+    }
+
+    if !overrides.is_empty() {
+        tracing::info!("Using transcription overrides: {:?}", overrides);
+    }
+    overrides
+}
+
+/// Remove the transcription override files if they exist (for cleanup on cancel/error)
+fn cleanup_transcription_overrides() {
+    for name in [
+        "language_override",
+        "initial_prompt_override",
+        "translate_override",
+        "grammar_override",
+    ] {
+        let _ = std::fs::remove_file(Config::runtime_dir().join(name));
+    }
+}
+
+/// Build the text processor for a (possibly profile-adjusted) configuration
+fn build_text_processor(
+    config: &Config,
+    vocabulary: Option<&Vocabulary>,
+    snippets: Option<&SnippetLibrary>,
+) -> TextProcessor {
+    let mut text_processor = TextProcessor::new(&config.text);
+    if let Some(vocab) = vocabulary {
+        if config.vocabulary.fuzzy_correction {
+            text_processor =
+                text_processor.with_vocabulary(vocab, config.vocabulary.fuzzy_threshold);
+        }
+    }
+    if let Some(snippets) = snippets {
+        text_processor = text_processor.with_snippets(snippets);
+    }
+    // Spoken punctuation follows the configured transcription language
+    // until a transcription reports the language it detected
+    if config.whisper.translate {
+        text_processor = text_processor.with_language("en");
+    } else if !config.whisper.language.is_auto() {
+        text_processor = text_processor.with_language(config.whisper.language.primary());
+    }
+    text_processor
+}
+
+/// Result type for transcription task: text and its language, if known
+type TranscriptionResult =
+    std::result::Result<(String, Option<String>), crate::error::TranscribeError>;
+
+/// What the profile and `voxtype record start` flags change about a recording
+struct RecordingOverrides {
+    /// Model to transcribe with instead of the configured one
+    model: Option<String>,
+    /// Configuration to load when the profile picks another engine
+    engine_config: Option<Config>,
+    /// Language, initial prompt and translation
+    transcription: TranscriptionOverrides,
+    /// Hook run once recording has started
+    pre_recording_command: Option<String>,
+}
 
 /// Main daemon that orchestrates all components
 pub struct Daemon {
@@ -338,22 +532,46 @@ pub struct Daemon {
     pid_file_path: Option<PathBuf>,
     audio_feedback: Option<AudioFeedback>,
     text_processor: TextProcessor,
+    // Text processors of profiles with their own language or text options
+    profile_text_processors: HashMap<String, TextProcessor>,
+    vocabulary: Option<Vocabulary>,
     post_processor: Option<PostProcessor>,
+    // Ordered stages from [[pipeline]] (empty: default order)
+    pipeline: Pipeline,
+    // Rhai transform scripts from [scripts]
+    scripts: Option<Arc<ScriptRunner>>,
+    // Profile selection from the focused window ([auto_profile])
+    window_selector: Option<ProfileSelector>,
+    // Window focused when the current recording started
+    active_window: Option<WindowInfo>,
     // Model manager for multi-model support
     model_manager: Option<ModelManager>,
     // Background task for loading model on-demand
     model_load_task: Option<tokio::task::JoinHandle<std::result::Result<Arc<dyn Transcriber>, crate::error::TranscribeError>>>,
+    // Transcribers for profiles with another engine, by engine and model
+    profile_transcribers:
+        Arc<std::sync::Mutex<HashMap<(TranscriptionEngine, String), Arc<dyn Transcriber>>>>,
     // Background task for transcription (allows cancel during transcription)
     transcription_task: Option<tokio::task::JoinHandle<TranscriptionResult>>,
+    // Characters typed by the last dictation (erased by "delete that")
+    last_output_chars: AtomicUsize,
+    // Continues the previous dictation (leading space, capitalization)
+    smart_joiner: SmartJoiner,
+    // Unix socket clients receiving transcriptions (mode = "socket")
+    socket_sink: tokio::sync::Mutex<Option<SocketSink>>,
+    // `voxtype listen` client waiting for the current recording
+    listen: std::sync::Mutex<Option<ListenSession>>,
 }
 
 impl Daemon {
     /// Create a new daemon with the given configuration
-    pub fn new(config: Config, config_path: Option<PathBuf>) -> Self {
+    pub fn new(mut config: Config, config_path: Option<PathBuf>) -> Self {
         let state_file_path = config.resolve_state_file();
 
-        // Initialize audio feedback if enabled
-        let audio_feedback = if config.audio.feedback.enabled {
+        // Initialize audio feedback if enabled (in the config or any profile)
+        let audio_feedback = if config.audio.feedback.enabled
+            || config.profiles.values().any(|p| p.feedback == Some(true))
+        {
             match AudioFeedback::new(&config.audio.feedback) {
                 Ok(feedback) => {
                     tracing::info!(
@@ -372,10 +590,40 @@ impl Daemon {
             None
         };
 
+        // Load custom vocabulary
+        let vocabulary = Vocabulary::from_config(&config.vocabulary).filter(|v| !v.is_empty());
+        if let Some(ref vocab) = vocabulary {
+            tracing::info!("Custom vocabulary loaded: {} terms", vocab.terms().len());
+            if config.vocabulary.inject_prompt {
+                config.whisper.initial_prompt = vocab.build_prompt(
+                    config.whisper.initial_prompt.as_deref(),
+                    config.vocabulary.max_prompt_tokens,
+                );
+            }
+        }
+
         // Initialize text processor
-        let text_processor = TextProcessor::new(&config.text);
+        let snippets = SnippetLibrary::from_config(&config.snippets).filter(|s| !s.is_empty());
+        if let Some(ref snippets) = snippets {
+            tracing::info!("Snippets loaded: {}", snippets.len());
+        }
+        let text_processor = build_text_processor(&config, vocabulary.as_ref(), snippets.as_ref());
+        let profile_text_processors = config
+            .profiles
+            .iter()
+            .filter(|(_, profile)| profile.changes_text_processing())
+            .map(|(name, profile)| {
+                let config = config.with_profile(profile);
+                let processor =
+                    build_text_processor(&config, vocabulary.as_ref(), snippets.as_ref());
+                (name.clone(), processor)
+            })
+            .collect();
         if config.text.spoken_punctuation {
-            tracing::info!("Spoken punctuation enabled");
+            tracing::info!(
+                "Spoken punctuation enabled (language: {})",
+                text_processor.punctuation_language(None)
+            );
         }
         if !config.text.replacements.is_empty() {
             tracing::info!(
@@ -383,16 +631,50 @@ impl Daemon {
                 config.text.replacements.len()
             );
         }
+        if !config.text.rules.is_empty() {
+            tracing::info!("Text rules configured: {}", config.text.rules.len());
+        }
 
         // Initialize post-processor if configured
-        let post_processor = config.output.post_process.as_ref().map(|cfg| {
+        let post_processor = PostProcessor::from_config(&config, None);
+        if let Some(ref post_processor) = post_processor {
+            tracing::info!("Post-processing enabled: {}", post_processor.describe());
+        }
+
+        let pipeline = Pipeline::new(&config.pipeline);
+        if !pipeline.is_empty() {
             tracing::info!(
-                "Post-processing enabled: command={:?}, timeout={}ms",
-                cfg.command,
-                cfg.timeout_ms
+                "Text pipeline: {}",
+                config
+                    .pipeline
+                    .iter()
+                    .map(|stage| stage.label())
+                    .collect::<Vec<_>>()
+                    .join(" → ")
             );
-            PostProcessor::new(cfg)
-        });
+        }
+
+        let scripts = ScriptRunner::from_config(&config.scripts).map(Arc::new);
+        if let Some(ref scripts) = scripts {
+            tracing::debug!("Scripts directory: {:?}", scripts.dir());
+        }
+
+        let window_selector = ProfileSelector::from_config(&config.auto_profile);
+        if let Some(ref selector) = window_selector {
+            tracing::info!(
+                "Automatic profile selection enabled ({}, {} rules)",
+                selector.provider_name(),
+                config.auto_profile.rules.len()
+            );
+        }
+
+        let smart_joiner = SmartJoiner::new(&config.output.smart_join);
+        if config.output.smart_join.enabled {
+            tracing::info!(
+                "Smart joining enabled (timeout: {}s)",
+                config.output.smart_join.timeout_secs
+            );
+        }
 
         Self {
             config,
@@ -401,16 +683,194 @@ impl Daemon {
             pid_file_path: None,
             audio_feedback,
             text_processor,
+            profile_text_processors,
+            vocabulary,
             post_processor,
+            pipeline,
+            scripts,
+            window_selector,
+            active_window: None,
             model_manager: None,
             model_load_task: None,
+            profile_transcribers: Arc::default(),
             transcription_task: None,
+            last_output_chars: AtomicUsize::new(0),
+            smart_joiner,
+            socket_sink: tokio::sync::Mutex::new(None),
+            listen: std::sync::Mutex::new(None),
+        }
+    }
+
+    /// Post-processor for a recording: the profile's command or LLM, else the default
+    fn post_processor_for(&self, profile: Option<&Profile>) -> Option<Cow<'_, PostProcessor>> {
+        match profile.filter(|p| p.post_process_command.is_some() || p.post_process_llm.is_some()) {
+            Some(profile) => {
+                PostProcessor::from_config(&self.config, Some(profile)).map(Cow::Owned)
+            }
+            None => self.post_processor.as_ref().map(Cow::Borrowed),
+        }
+    }
+
+    /// Text processor for a recording: the profile's, if it changes text processing
+    fn text_processor_for(&self, profile_name: Option<&str>) -> &TextProcessor {
+        profile_name
+            .and_then(|name| self.profile_text_processors.get(name))
+            .unwrap_or(&self.text_processor)
+    }
+
+    /// Effective configuration for a recording with this profile
+    fn config_for(&self, profile: Option<&Profile>) -> Cow<'_, Config> {
+        match profile {
+            Some(profile) => Cow::Owned(self.config.with_profile(profile)),
+            None => Cow::Borrowed(&self.config),
         }
     }
 
-    /// Play audio feedback sound if enabled
+    /// Look up the focused window when a recording starts, and pick its profile
+    /// unless one was given with `voxtype record start --profile`
+    /// The selected profile is written as the profile override for this recording
+    async fn select_window_profile(&mut self) -> Option<Profile> {
+        self.active_window = None;
+        let selector = self.window_selector.as_ref()?;
+        let window = selector.active_window().await?;
+        tracing::debug!("Focused window: {:?}", window);
+        let selected = match peek_profile_override() {
+            Some(_) => None,
+            None => selector.select(&window).map(str::to_string),
+        };
+        self.active_window = Some(window);
+
+        let name = selected?;
+        let Some(profile) = self.config.get_profile(&name).cloned() else {
+            tracing::warn!("auto_profile rule selects unknown profile '{}'", name);
+            return None;
+        };
+        let profile_file = Config::runtime_dir().join("profile_override");
+        if let Err(e) = std::fs::write(&profile_file, &name) {
+            tracing::warn!("Failed to write profile override: {}", e);
+            return None;
+        }
+        tracing::info!(
+            "Selected profile '{}' for {}",
+            name,
+            self.active_window
+                .as_ref()
+                .and_then(WindowInfo::app)
+                .unwrap_or("focused window")
+        );
+        Some(profile)
+    }
+
+    /// Select the profile for a recording that is starting and collect what it changes
+    /// The profile comes from `voxtype record start --profile X`, else from the
+    /// focused window; `model_override` (hotkey or --model) takes precedence over its model
+    async fn recording_overrides(&mut self, model_override: Option<String>) -> RecordingOverrides {
+        self.select_window_profile().await;
+        let profile = peek_profile_override().and_then(|name| self.config.get_profile(&name));
+        let model = model_override.or_else(|| profile.and_then(|p| p.model.clone()));
+        let engine_config = profile
+            .filter(|p| p.engine.is_some_and(|engine| engine != self.config.engine))
+            .map(|p| {
+                let mut config = self.config.with_profile(p);
+                if let Some(ref model) = model {
+                    config.whisper.model = model.clone();
+                }
+                config
+            });
+        let pre_recording_command = profile
+            .and_then(|p| p.pre_recording_command.clone())
+            .or_else(|| self.config.output.pre_recording_command.clone());
+
+        RecordingOverrides {
+            model,
+            engine_config,
+            transcription: read_transcription_overrides(&self.config, self.vocabulary.as_ref()),
+            pre_recording_command,
+        }
+    }
+
+    /// Start loading or preparing the model for a recording that is starting
+    fn prepare_recording_model(
+        &mut self,
+        overrides: &RecordingOverrides,
+        transcriber_preloaded: &Option<Arc<dyn Transcriber>>,
+    ) {
+        if let Some(ref config) = overrides.engine_config {
+            // The profile's engine is loaded on first use and kept for later
+            // recordings, unless that engine loads its model on demand
+            let config = config.clone();
+            let transcription = overrides.transcription.clone();
+            let cache = (!config.on_demand_loading()).then(|| self.profile_transcribers.clone());
+            self.model_load_task = Some(tokio::task::spawn_blocking(move || {
+                let key = (config.engine, config.whisper.model.clone());
+                let cached = cache
+                    .as_ref()
+                    .and_then(|cache| cache.lock().unwrap().get(&key).cloned());
+                if let Some(transcriber) = cached {
+                    transcriber.prepare_with_overrides(&transcription);
+                    return Ok(transcriber);
+                }
+                tracing::info!("Loading {:?} engine for profile", config.engine);
+                let transcriber: Arc<dyn Transcriber> =
+                    crate::transcribe::create_transcriber(&config)?.into();
+                if let Some(cache) = cache {
+                    cache.lock().unwrap().insert(key, transcriber.clone());
+                }
+                Ok(transcriber)
+            }));
+        } else if self.config.on_demand_loading() {
+            // Start model loading in background
+            match self.config.engine {
+                crate::config::TranscriptionEngine::Whisper => {
+                    let config = self.config.whisper.clone();
+                    let config_path = self.config_path.clone();
+                    let model_to_load = overrides.model.clone();
+                    self.model_load_task = Some(tokio::task::spawn_blocking(move || {
+                        let mut temp_manager = ModelManager::new(&config, config_path);
+                        temp_manager.get_transcriber(model_to_load.as_deref())
+                    }));
+                }
+                crate::config::TranscriptionEngine::Parakeet
+                | crate::config::TranscriptionEngine::External => {
+                    let config = self.config.clone();
+                    self.model_load_task = Some(tokio::task::spawn_blocking(move || {
+                        crate::transcribe::create_transcriber(&config).map(Arc::from)
+                    }));
+                }
+            }
+            tracing::debug!("Started background model loading");
+        } else {
+            // Prepare model (spawns subprocess for gpu_isolation mode)
+            match self.config.engine {
+                crate::config::TranscriptionEngine::Whisper => {
+                    if let Some(ref mut mm) = self.model_manager {
+                        if let Err(e) =
+                            mm.prepare_model(overrides.model.as_deref(), &overrides.transcription)
+                        {
+                            tracing::warn!("Failed to prepare model: {}", e);
+                        }
+                    }
+                }
+                crate::config::TranscriptionEngine::Parakeet
+                | crate::config::TranscriptionEngine::External => {
+                    if let Some(ref t) = transcriber_preloaded {
+                        let transcriber = t.clone();
+                        tokio::task::spawn_blocking(move || {
+                            transcriber.prepare();
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    /// Play audio feedback sound if enabled (the selected profile can turn it on or off)
     fn play_feedback(&self, event: SoundEvent) {
-        if let Some(ref feedback) = self.audio_feedback {
+        let enabled = peek_profile_override()
+            .and_then(|name| self.config.get_profile(&name))
+            .and_then(|profile| profile.feedback)
+            .unwrap_or(self.config.audio.feedback.enabled);
+        if let Some(feedback) = self.audio_feedback.as_ref().filter(|_| enabled) {
             feedback.play(event);
         }
     }
@@ -437,7 +897,8 @@ impl Daemon {
         model_override: Option<&str>,
         transcriber_preloaded: &Option<Arc<dyn Transcriber>>,
     ) -> std::result::Result<Arc<dyn Transcriber>, ()> {
-        if self.config.on_demand_loading() {
+        // A profile with its own engine loads one for the recording, like on-demand loading
+        let transcriber = if self.config.on_demand_loading() || self.model_load_task.is_some() {
             // Wait for background model load task
             if let Some(task) = self.model_load_task.take() {
                 match task.await {
@@ -464,11 +925,12 @@ impl Daemon {
         } else {
             // Use preloaded transcriber based on engine type
             match self.config.engine {
-                crate::config::TranscriptionEngine::Parakeet => {
+                crate::config::TranscriptionEngine::Parakeet
+                | crate::config::TranscriptionEngine::External => {
                     if let Some(ref t) = transcriber_preloaded {
                         Ok(t.clone())
                     } else {
-                        tracing::error!("Parakeet transcriber not preloaded");
+                        tracing::error!("Transcriber not preloaded");
                         self.play_feedback(SoundEvent::Error);
                         Err(())
                     }
@@ -490,14 +952,55 @@ impl Daemon {
                     }
                 }
             }
+        };
+        if transcriber.is_err() {
+            self.finish_listen(ListenOutcome::error(
+                "Failed to load the transcription model",
+            ));
+        }
+        transcriber
+    }
+
+    /// Stop recording and start transcribing (SIGUSR2, or a pause with `voxtype listen --vad`)
+    async fn stop_recording(
+        &mut self,
+        state: &mut State,
+        audio_capture: &mut Option<Box<dyn AudioCapture>>,
+        transcriber_preloaded: &Option<Arc<dyn Transcriber>>,
+    ) {
+        let State::Recording {
+            model_override,
+            overrides,
+            ..
+        } = state
+        else {
+            return;
+        };
+        let model_override = model_override.clone();
+        let overrides = overrides.clone();
+        match self
+            .get_transcriber_for_recording(model_override.as_deref(), transcriber_preloaded)
+            .await
+        {
+            Ok(transcriber) => {
+                self.start_transcription_task(state, audio_capture, Some(transcriber), overrides)
+                    .await;
+            }
+            Err(()) => {
+                *state = State::Idle;
+                self.update_state("idle");
+            }
         }
     }
 
     /// Reset state to idle and run post_output_command to reset compositor submap
     /// Call this when exiting from recording/transcribing without normal output flow
     async fn reset_to_idle(&self, state: &mut State) {
+        // Errors and cancels report themselves first; anything else produced no text
+        self.finish_listen(ListenOutcome::Empty);
         cleanup_output_mode_override();
         cleanup_model_override();
+        cleanup_transcription_overrides();
         cleanup_profile_override();
         *state = State::Idle;
         self.update_state("idle");
@@ -510,6 +1013,366 @@ impl Daemon {
         }
     }
 
+    /// Hand the outcome to a waiting `voxtype listen`
+    /// Returns false if the recording was not started by one
+    fn finish_listen(&self, outcome: ListenOutcome) -> bool {
+        match self.listen.lock().unwrap().take() {
+            Some(session) => {
+                session.finish(&outcome);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Whether a `voxtype listen --vad` recording heard the speaker pause
+    fn listen_heard_pause(&self, level: f32) -> bool {
+        self.listen
+            .lock()
+            .unwrap()
+            .as_mut()
+            .is_some_and(|session| session.heard_pause(level))
+    }
+
+    /// Run the transform scripts on a blocking thread
+    /// Scripts that take longer than [scripts] timeout_ms are abandoned (they
+    /// stop at max_operations) and the text goes on unchanged
+    async fn run_scripts(
+        &self,
+        scripts: Arc<ScriptRunner>,
+        text: &str,
+        profile: Option<&str>,
+        language: Option<&str>,
+        duration: Duration,
+    ) -> Vec<OutputAction> {
+        let input = text.to_string();
+        let profile = profile.map(str::to_string);
+        let language = language.map(str::to_string);
+        let app = self
+            .active_window
+            .as_ref()
+            .and_then(WindowInfo::app)
+            .map(str::to_string);
+        let task = tokio::task::spawn_blocking(move || {
+            let metadata = ScriptMetadata {
+                profile: profile.as_deref(),
+                language: language.as_deref(),
+                duration,
+                app: app.as_deref(),
+            };
+            scripts.run(&input, &metadata)
+        });
+
+        let timeout = Duration::from_millis(self.config.scripts.timeout_ms);
+        match tokio::time::timeout(timeout, task).await {
+            Ok(Ok(actions)) => actions,
+            Ok(Err(e)) => {
+                tracing::warn!("Scripts panicked: {}", e);
+                vec![OutputAction::Text(text.to_string())]
+            }
+            Err(_) => {
+                tracing::warn!(
+                    "Scripts took longer than {}ms, outputting the text unchanged",
+                    self.config.scripts.timeout_ms
+                );
+                vec![OutputAction::Text(text.to_string())]
+            }
+        }
+    }
+
+    /// Post-process the text of a recording without voice editing key presses
+    /// With [[pipeline]], the stages from the first command on run, in order,
+    /// including the post-process command where it is listed (the built-in
+    /// stages before it already ran); otherwise the post-process command does.
+    async fn post_process_text(
+        &self,
+        text: String,
+        text_processor: &TextProcessor,
+        context: ProcessContext<'_>,
+        profile: Option<&Profile>,
+        metadata: &PostProcessMetadata<'_>,
+    ) -> PostProcessOutput {
+        if !self.pipeline.is_empty() {
+            let post_processor = self.post_processor_for(profile);
+            let result = self
+                .pipeline
+                .run_rest(
+                    &text,
+                    text_processor,
+                    context,
+                    metadata,
+                    post_processor.as_deref(),
+                )
+                .await;
+            tracing::info!("After pipeline: {:?}", result.text);
+            result
+        } else if let Some(post_processor) = self.post_processor_for(profile) {
+            tracing::info!("Post-processing: {:?}", text);
+            let result = post_processor.process_with(&text, metadata).await;
+            tracing::info!("Post-processed: {:?}", result.text);
+            result
+        } else {
+            PostProcessOutput::text(text)
+        }
+    }
+
+    /// Post-process the dictated text between voice editing commands
+    /// Each piece goes through the post-processor on its own; key presses are
+    /// kept. With [[pipeline]], each piece goes through the stages from the
+    /// first command on (the built-in stages before it already ran).
+    /// Returns the actions and the output choices a JSON post-process command made
+    async fn post_process_actions(
+        &self,
+        actions: Vec<OutputAction>,
+        text_processor: &TextProcessor,
+        context: ProcessContext<'_>,
+        profile: Option<&Profile>,
+        metadata: &PostProcessMetadata<'_>,
+    ) -> (Vec<OutputAction>, PostProcessOutput) {
+        let mut choices = PostProcessOutput::default();
+        let post_processor = self.post_processor_for(profile);
+        if self.pipeline.is_empty() && post_processor.is_none() {
+            return (actions, choices);
+        }
+
+        let mut processed = Vec::with_capacity(actions.len());
+        for action in actions {
+            match action {
+                OutputAction::Text(text) => {
+                    let result = match post_processor {
+                        Some(ref post_processor) if self.pipeline.is_empty() => {
+                            post_processor.process_with(&text, metadata).await
+                        }
+                        _ => {
+                            self.pipeline
+                                .run_rest(
+                                    &text,
+                                    text_processor,
+                                    context,
+                                    metadata,
+                                    post_processor.as_deref(),
+                                )
+                                .await
+                        }
+                    };
+                    choices.output_mode = result.output_mode.or(choices.output_mode);
+                    choices.auto_submit = result.auto_submit.or(choices.auto_submit);
+                    if !result.discarded && !result.text.is_empty() {
+                        processed.push(OutputAction::Text(result.text));
+                    }
+                }
+                key => processed.push(key),
+            }
+        }
+        tracing::info!("Post-processed: {:?}", processed);
+        (processed, choices)
+    }
+
+    /// Output the result of a recording: text, or text and key presses from
+    /// voice editing commands
+    /// The mode comes from the CLI override, then the post-process command
+    /// (`choices`) or profile, then the configuration. Only type mode can press
+    /// keys; the other modes get the text alone.
+    async fn output_result(
+        &self,
+        state: &mut State,
+        actions: Vec<OutputAction>,
+        active_profile: Option<&Profile>,
+        choices: &PostProcessOutput,
+        metadata: &PostProcessMetadata<'_>,
+        matched_command: bool,
+    ) {
+        let text = actions_text(&actions);
+        let has_keys = actions.iter().any(OutputAction::is_key);
+
+        // Nothing left to output (e.g., a dropped hallucination)
+        if text.is_empty() && !has_keys {
+            tracing::info!("Nothing to output after text processing");
+            self.reset_to_idle(state).await;
+            return;
+        }
+
+        // `voxtype listen` prints the text instead of outputting it
+        if self.finish_listen(ListenOutcome::Text { text: text.clone() }) {
+            self.reset_to_idle(state).await;
+            return;
+        }
+
+        // Check for output mode override from CLI flags
+        let output_override = read_output_mode_override();
+
+        // Check if a JSON post-process command or the profile specifies output mode
+        let profile_output_mode = choices
+            .output_mode
+            .clone()
+            .or_else(|| active_profile.and_then(|p| p.output_mode.clone()));
+
+        // Determine file output path (if file mode)
+        // Priority: 1. CLI --file=path, 2. CLI --file (config path),
+        // 3. post-process/profile output_mode, 4. config mode=file
+        let file_output_path: Option<PathBuf> = match &output_override {
+            Some(OutputOverride::FileWithPath(path)) => {
+                // CLI --file=path.txt
+                Some(path.clone())
+            }
+            Some(OutputOverride::Mode(OutputMode::File)) => {
+                // CLI --file (no path) - use config's file_path
+                self.config.output.file_path.clone()
+            }
+            None if profile_output_mode == Some(OutputMode::File) => {
+                // Profile specifies file mode
+                self.config.output.file_path.clone()
+            }
+            None if self.config.output.mode == OutputMode::File => {
+                // Config mode = "file" (no CLI override)
+                self.config.output.file_path.clone()
+            }
+            _ => None,
+        };
+
+        // Key presses need a keyboard driver, so the other modes only get the text,
+        // and leave nothing typed for "delete that" to erase
+        if let Some(output_path) = file_output_path {
+            if has_keys {
+                tracing::warn!("Key presses from voice commands are not written to files");
+            }
+            self.last_output_chars.store(0, Ordering::Relaxed);
+            *state = State::Outputting { text: text.clone() };
+
+            let file_mode = &self.config.output.file_mode;
+            match write_transcription_to_file(&output_path, &text, file_mode).await {
+                Ok(()) => {
+                    let mode_str = match file_mode {
+                        FileMode::Overwrite => "wrote",
+                        FileMode::Append => "appended",
+                    };
+                    tracing::info!("{} transcription to {:?}", mode_str, output_path);
+                }
+                Err(e) => {
+                    tracing::error!("Failed to write transcription to {:?}: {}", output_path, e);
+                }
+            }
+
+            *state = State::Idle;
+            self.update_state("idle");
+            return;
+        }
+
+        // Create output chain with potential mode override (for non-file modes)
+        // Priority: 1. CLI override, 2. post-process/profile output_mode, 3. config default
+        // The profile's auto_submit, driver order and hooks apply too
+        let mut output_config = self.config_for(active_profile).output.clone();
+        match output_override {
+            Some(OutputOverride::Mode(mode)) => output_config.mode = mode,
+            _ => {
+                if let Some(mode) = profile_output_mode {
+                    output_config.mode = mode;
+                }
+            }
+        }
+        if let Some(auto_submit) = choices.auto_submit {
+            output_config.auto_submit = auto_submit;
+        }
+
+        // Fifo, socket and webhook modes hand the text to another program
+        if output_config.mode.is_sink() {
+            if has_keys {
+                tracing::warn!(
+                    "Key presses from voice commands are not sent to {:?} output",
+                    output_config.mode
+                );
+            }
+            self.last_output_chars.store(0, Ordering::Relaxed);
+            *state = State::Outputting { text: text.clone() };
+            let event = TranscriptionEvent::new(
+                &text,
+                metadata,
+                self.active_window.as_ref().and_then(WindowInfo::app),
+            );
+            let mut socket = self.socket_sink.lock().await;
+            if let Err(e) = sink::send_event(
+                &output_config.mode,
+                &output_config.sink,
+                &mut socket,
+                &event,
+            )
+            .await
+            {
+                tracing::error!("{}", e);
+            }
+
+            *state = State::Idle;
+            self.update_state("idle");
+            return;
+        }
+
+        let mut actions = if has_keys && output_config.mode != OutputMode::Type {
+            tracing::warn!(
+                "Key presses from voice commands need type mode, outputting only the text"
+            );
+            if text.is_empty() {
+                self.reset_to_idle(state).await;
+                return;
+            }
+            vec![OutputAction::Text(text)]
+        } else {
+            actions
+        };
+        let output_chain = output::create_output_chain(&output_config);
+
+        // Continue the previous dictation (leading space, capitalization)
+        if let [OutputAction::Text(text)] = actions.as_mut_slice() {
+            if !matched_command {
+                *text = self.smart_joiner.join(text);
+            }
+        }
+        let text = actions_text(&actions);
+        let has_keys = actions.iter().any(OutputAction::is_key);
+        if has_keys {
+            tracing::info!("Voice command actions: {:?}", actions);
+        }
+
+        *state = State::Outputting { text: text.clone() };
+
+        let output_options = output::OutputOptions {
+            pre_output_command: output_config.pre_output_command.as_deref(),
+            post_output_command: output_config.post_output_command.as_deref(),
+        };
+
+        match output::output_actions_with_fallback(&output_chain, &actions, output_options).await {
+            Err(e) => tracing::error!("Output failed: {}", e),
+            Ok(typed) => {
+                // "delete that" can only take back text typed after the last key
+                // press, and nothing once Enter submitted it
+                let erasable = if typed && !output_config.auto_submit {
+                    trailing_text_chars(&actions)
+                } else {
+                    0
+                };
+                self.last_output_chars.store(erasable, Ordering::Relaxed);
+                // Only text typed into the focused field can be continued; key presses
+                // may have moved the cursor, and after Enter the next dictation starts afresh
+                if typed && !has_keys && !matched_command && !output_config.auto_submit {
+                    self.smart_joiner.record(&text);
+                } else {
+                    self.smart_joiner.reset();
+                }
+                if self.config.output.notification.on_transcription && !text.is_empty() {
+                    // Send notification on successful output
+                    output::send_transcription_notification(
+                        &text,
+                        self.config.output.notification.show_engine_icon,
+                        self.config.engine,
+                    )
+                    .await;
+                }
+            }
+        }
+
+        *state = State::Idle;
+        self.update_state("idle");
+    }
+
     /// Start transcription task (non-blocking, stores JoinHandle for later completion)
     /// Returns true if transcription was started, false if skipped (too short)
     async fn start_transcription_task(
@@ -517,6 +1380,7 @@ impl Daemon {
         state: &mut State,
         audio_capture: &mut Option<Box<dyn AudioCapture>>,
         transcriber: Option<Arc<dyn Transcriber>>,
+        overrides: TranscriptionOverrides,
     ) -> bool {
         let duration = state.recording_duration().unwrap_or_default();
         tracing::info!("Recording stopped ({:.1}s)", duration.as_secs_f32());
@@ -550,11 +1414,25 @@ impl Daemon {
 
                     // Spawn transcription task (non-blocking)
                     if let Some(t) = transcriber {
-                        self.transcription_task =
-                            Some(tokio::task::spawn_blocking(move || t.transcribe(&samples)));
+                        // Engines that don't report a language fall back to a
+                        // single-language override
+                        let override_language = if overrides.translate == Some(true) {
+                            Some("en".to_string())
+                        } else {
+                            overrides
+                                .language
+                                .as_ref()
+                                .filter(|l| !l.is_auto() && !l.is_multiple())
+                                .map(|l| l.primary().to_string())
+                        };
+                        self.transcription_task = Some(tokio::task::spawn_blocking(move || {
+                            let text = t.transcribe_with_overrides(&samples, &overrides)?;
+                            Ok((text, t.last_language().or(override_language)))
+                        }));
                         return true;
                     } else {
                         tracing::error!("No transcriber available");
+                        self.finish_listen(ListenOutcome::error("No transcriber available"));
                         self.play_feedback(SoundEvent::Error);
                         self.reset_to_idle(state).await;
                         return false;
@@ -562,6 +1440,7 @@ impl Daemon {
                 }
                 Err(e) => {
                     tracing::warn!("Recording error: {}", e);
+                    self.finish_listen(ListenOutcome::error(format!("Recording error: {}", e)));
                     self.reset_to_idle(state).await;
                     return false;
                 }
@@ -579,18 +1458,18 @@ impl Daemon {
         result: std::result::Result<TranscriptionResult, tokio::task::JoinError>,
     ) {
         match result {
-            Ok(Ok(text)) => {
+            Ok(Ok((text, language))) => {
                 if text.is_empty() {
                     tracing::debug!("Transcription was empty");
                     self.reset_to_idle(state).await;
                 } else {
                     tracing::info!("Transcribed: {:?}", text);
-
-                    // Apply text processing (replacements, punctuation)
-                    let processed_text = self.text_processor.process(&text);
-                    if processed_text != text {
-                        tracing::debug!("After text processing: {:?}", processed_text);
-                    }
+                    let audio_duration = match state {
+                        State::Transcribing { audio } => {
+                            Duration::from_secs_f32(audio.len() as f32 / 16000.0)
+                        }
+                        _ => Duration::ZERO,
+                    };
 
                     // Check for profile override from CLI flags
                     let profile_override = read_profile_override();
@@ -607,163 +1486,171 @@ impl Daemon {
                         }
                     }
 
-                    // Apply post-processing command (profile overrides default)
-                    let final_text = if let Some(profile) = active_profile {
-                        if let Some(ref cmd) = profile.post_process_command {
-                            let timeout_ms = profile
-                                .post_process_timeout_ms
-                                .unwrap_or(30000);
-                            let profile_config = crate::config::PostProcessConfig {
-                                command: cmd.clone(),
-                                timeout_ms,
-                            };
-                            let profile_processor = PostProcessor::new(&profile_config);
-                            tracing::info!(
-                                "Post-processing with profile: {:?}",
-                                profile_override.as_ref().unwrap()
-                            );
-                            let result = profile_processor.process(&processed_text).await;
-                            tracing::info!("Post-processed: {:?}", result);
-                            result
-                        } else {
-                            // Profile exists but has no post_process_command, use default
-                            if let Some(ref post_processor) = self.post_processor {
-                                tracing::info!("Post-processing: {:?}", processed_text);
-                                let result = post_processor.process(&processed_text).await;
-                                tracing::info!("Post-processed: {:?}", result);
-                                result
-                            } else {
-                                processed_text
-                            }
-                        }
-                    } else if let Some(ref post_processor) = self.post_processor {
-                        tracing::info!("Post-processing: {:?}", processed_text);
-                        let result = post_processor.process(&processed_text).await;
-                        tracing::info!("Post-processed: {:?}", result);
-                        result
-                    } else {
-                        processed_text
-                    };
-
-                    // Check for output mode override from CLI flags
-                    let output_override = read_output_mode_override();
-
-                    // Check if profile specifies output mode override
-                    let profile_output_mode = active_profile.and_then(|p| p.output_mode.clone());
-
-                    // Determine file output path (if file mode)
-                    // Priority: 1. CLI --file=path, 2. CLI --file (config path), 3. profile output_mode, 4. config mode=file
-                    let file_output_path: Option<PathBuf> = match &output_override {
-                        Some(OutputOverride::FileWithPath(path)) => {
-                            // CLI --file=path.txt
-                            Some(path.clone())
-                        }
-                        Some(OutputOverride::Mode(OutputMode::File)) => {
-                            // CLI --file (no path) - use config's file_path
-                            self.config.output.file_path.clone()
-                        }
-                        None if profile_output_mode == Some(OutputMode::File) => {
-                            // Profile specifies file mode
-                            self.config.output.file_path.clone()
-                        }
-                        None if self.config.output.mode == OutputMode::File => {
-                            // Config mode = "file" (no CLI override)
-                            self.config.output.file_path.clone()
-                        }
-                        _ => None,
+                    // Apply text processing (normalization, punctuation, replacements)
+                    // and recognize voice editing commands
+                    let context = ProcessContext {
+                        language: language.as_deref(),
+                        normalize: active_profile.and_then(|p| p.normalize),
+                        disfluency: active_profile.and_then(|p| p.disfluency),
+                        code: active_profile.and_then(|p| p.code),
+                        spoken_punctuation: active_profile.and_then(|p| p.spoken_punctuation),
                     };
+                    let profile_name = profile_override
+                        .as_deref()
+                        .filter(|_| active_profile.is_some());
+                    let text_processor = self.text_processor_for(profile_name);
+                    let previous_output_chars = self.last_output_chars.load(Ordering::Relaxed);
+                    let actions = self.pipeline.process_actions(
+                        &text,
+                        text_processor,
+                        context,
+                        profile_name,
+                        previous_output_chars,
+                    );
+                    let processed_text = actions_text(&actions);
+                    if processed_text != text {
+                        tracing::debug!("After text processing: {:?}", processed_text);
+                    }
 
-                    if let Some(output_path) = file_output_path {
-                        *state = State::Outputting {
-                            text: final_text.clone(),
-                        };
-
-                        let file_mode = &self.config.output.file_mode;
-                        match write_transcription_to_file(&output_path, &final_text, file_mode).await
-                        {
-                            Ok(()) => {
-                                let mode_str = match file_mode {
-                                    FileMode::Overwrite => "wrote",
-                                    FileMode::Append => "appended",
-                                };
-                                tracing::info!(
-                                    "{} transcription to {:?}",
-                                    mode_str,
-                                    output_path
-                                );
+                    // Command grammar mode: output the matched command id instead of text
+                    // Priority: 1. CLI --grammar, 2. profile grammar
+                    let grammar_name = read_override_file("grammar_override")
+                        .or_else(|| active_profile.and_then(|p| p.grammar.clone()));
+                    let command_id = match grammar_name {
+                        Some(name) => match self.config.get_grammar(&name) {
+                            Some(grammar) => {
+                                match CommandGrammar::new(grammar).match_command(&text) {
+                                    Some(m) => {
+                                        tracing::info!(
+                                            "Matched command {:?} (phrase {:?}, score {:.2})",
+                                            m.id,
+                                            m.phrase,
+                                            m.score
+                                        );
+                                        Some(m.id)
+                                    }
+                                    None => {
+                                        tracing::info!(
+                                            "No command in grammar '{}' matched {:?}",
+                                            name,
+                                            text
+                                        );
+                                        self.play_feedback(SoundEvent::Error);
+                                        self.reset_to_idle(state).await;
+                                        return;
+                                    }
+                                }
                             }
-                            Err(e) => {
-                                tracing::error!(
-                                    "Failed to write transcription to {:?}: {}",
-                                    output_path,
-                                    e
+                            None => {
+                                tracing::warn!(
+                                    "Grammar '{}' not found in config, outputting text",
+                                    name
                                 );
+                                None
                             }
-                        }
+                        },
+                        None => None,
+                    };
 
-                        *state = State::Idle;
-                        self.update_state("idle");
+                    let matched_command = command_id.is_some();
+                    let post_process_metadata = PostProcessMetadata {
+                        profile: profile_name,
+                        language: language.as_deref(),
+                        duration: audio_duration,
+                        model: Some(self.config.model_name()),
+                    };
+
+                    // Voice editing commands: the dictated text between key presses
+                    // is post-processed piece by piece, then output like any other text
+                    if !matched_command && actions.iter().any(OutputAction::is_key) {
+                        let (actions, choices) = self
+                            .post_process_actions(
+                                actions,
+                                text_processor,
+                                context,
+                                active_profile,
+                                &post_process_metadata,
+                            )
+                            .await;
+                        self.output_result(
+                            state,
+                            actions,
+                            active_profile,
+                            &choices,
+                            &post_process_metadata,
+                            false,
+                        )
+                        .await;
                         return;
                     }
 
-                    // Create output chain with potential mode override (for non-file modes)
-                    // Priority: 1. CLI override, 2. profile output_mode, 3. config default
-                    let output_config = match output_override {
-                        Some(OutputOverride::Mode(mode)) => {
-                            let mut config = self.config.output.clone();
-                            config.mode = mode;
-                            config
-                        }
-                        _ => {
-                            if let Some(mode) = profile_output_mode {
-                                let mut config = self.config.output.clone();
-                                config.mode = mode;
-                                config
-                            } else {
-                                self.config.output.clone()
+                    // Apply the pipeline or post-processing command (profile overrides default)
+                    // Matched commands are output as-is
+                    // JSON post-process commands may also pick the output mode or discard
+                    let mut post_processed = if let Some(id) = command_id {
+                        PostProcessOutput::text(id)
+                    } else {
+                        self.post_process_text(
+                            processed_text,
+                            text_processor,
+                            context,
+                            active_profile,
+                            &post_process_metadata,
+                        )
+                        .await
+                    };
+                    if post_processed.discarded {
+                        self.reset_to_idle(state).await;
+                        return;
+                    }
+                    let final_text = std::mem::take(&mut post_processed.text);
+
+                    // Transform scripts get the final text and may replace it,
+                    // press keys or discard it
+                    let actions = match self.scripts {
+                        Some(ref scripts) if !matched_command => {
+                            let actions = self
+                                .run_scripts(
+                                    scripts.clone(),
+                                    &final_text,
+                                    profile_name,
+                                    language.as_deref(),
+                                    audio_duration,
+                                )
+                                .await;
+                            let result = actions_text(&actions);
+                            if result != final_text || actions.iter().any(OutputAction::is_key) {
+                                tracing::info!("After scripts: {:?}", actions);
                             }
+                            actions
                         }
+                        _ => vec![OutputAction::Text(final_text)],
                     };
-                    let output_chain = output::create_output_chain(&output_config);
-
-                    // Output the text
-                    *state = State::Outputting {
-                        text: final_text.clone(),
-                    };
-
-                    let output_options = output::OutputOptions {
-                        pre_output_command: output_config.pre_output_command.as_deref(),
-                        post_output_command: output_config.post_output_command.as_deref(),
-                    };
-
-                    if let Err(e) =
-                        output::output_with_fallback(&output_chain, &final_text, output_options)
-                            .await
-                    {
-                        tracing::error!("Output failed: {}", e);
-                    } else if self.config.output.notification.on_transcription {
-                        // Send notification on successful output
-                        output::send_transcription_notification(
-                            &final_text,
-                            self.config.output.notification.show_engine_icon,
-                            self.config.engine,
-                        ).await;
-                    }
 
-                    *state = State::Idle;
-                    self.update_state("idle");
+                    self.output_result(
+                        state,
+                        actions,
+                        active_profile,
+                        &post_processed,
+                        &post_process_metadata,
+                        matched_command,
+                    )
+                    .await;
                 }
             }
             Ok(Err(e)) => {
                 tracing::error!("Transcription failed: {}", e);
+                self.finish_listen(ListenOutcome::error(format!("Transcription failed: {}", e)));
                 self.reset_to_idle(state).await;
             }
             Err(e) => {
                 // JoinError - task was cancelled or panicked
                 if e.is_cancelled() {
                     tracing::debug!("Transcription task was cancelled");
+                    self.finish_listen(ListenOutcome::Cancelled);
                 } else {
                     tracing::error!("Transcription task panicked: {}", e);
+                    self.finish_listen(ListenOutcome::error("Transcription task panicked"));
                 }
                 self.reset_to_idle(state).await;
             }
@@ -774,8 +1661,9 @@ impl Daemon {
     pub async fn run(&mut self) -> Result<()> {
         tracing::info!("Starting voxtype daemon");
 
-        // Clean up any stale cancel file from previous runs
+        // Clean up any stale cancel file and listen requests from previous runs
         cleanup_cancel_file();
+        ListenRequest::withdraw_all();
 
         // Write PID file for external control via signals
         self.pid_file_path = write_pid_file();
@@ -819,6 +1707,18 @@ impl Daemon {
 
         tracing::info!("Output mode: {:?}", self.config.output.mode);
 
+        // Listen for transcription socket clients before the first recording
+        if self.config.uses_output_mode(&OutputMode::Socket) {
+            let path = self.config.output.sink.effective_socket_path();
+            match SocketSink::bind(&path) {
+                Ok(socket) => {
+                    tracing::info!("Transcription socket: {:?}", path);
+                    *self.socket_sink.get_mut() = Some(socket);
+                }
+                Err(e) => tracing::warn!("{}", e),
+            }
+        }
+
         // Log state file if configured
         if let Some(ref path) = self.state_file_path {
             tracing::info!("State file: {:?}", path);
@@ -863,8 +1763,9 @@ impl Daemon {
                         return Err(crate::error::VoxtypeError::Transcribe(e));
                     }
                 }
-                crate::config::TranscriptionEngine::Parakeet => {
-                    // Parakeet uses its own model loading
+                crate::config::TranscriptionEngine::Parakeet
+                | crate::config::TranscriptionEngine::External => {
+                    // Parakeet and external engines load their own model
                     transcriber_preloaded = Some(Arc::from(crate::transcribe::create_transcriber(&self.config)?));
                 }
             }
@@ -932,53 +1833,14 @@ impl Daemon {
                                 state.is_idle(), model_override);
                             if state.is_idle() {
                                 tracing::info!("Recording started");
+                                let overrides = self.recording_overrides(model_override).await;
 
                                 // Send notification if enabled
                                 if self.config.output.notification.on_recording_start {
                                     send_notification("Push to Talk Active", "Recording...", self.config.output.notification.show_engine_icon, self.config.engine).await;
                                 }
 
-                                // Prepare model for transcription
-                                if self.config.on_demand_loading() {
-                                    // Start model loading in background
-                                    match self.config.engine {
-                                        crate::config::TranscriptionEngine::Whisper => {
-                                            let config = self.config.whisper.clone();
-                                            let config_path = self.config_path.clone();
-                                            let model_to_load = model_override.clone();
-                                            self.model_load_task = Some(tokio::task::spawn_blocking(move || {
-                                                let mut temp_manager = ModelManager::new(&config, config_path);
-                                                temp_manager.get_transcriber(model_to_load.as_deref())
-                                            }));
-                                        }
-                                        crate::config::TranscriptionEngine::Parakeet => {
-                                            let config = self.config.clone();
-                                            self.model_load_task = Some(tokio::task::spawn_blocking(move || {
-                                                crate::transcribe::create_transcriber(&config).map(Arc::from)
-                                            }));
-                                        }
-                                    }
-                                    tracing::debug!("Started background model loading");
-                                } else {
-                                    // Prepare model (spawns subprocess for gpu_isolation mode)
-                                    match self.config.engine {
-                                        crate::config::TranscriptionEngine::Whisper => {
-                                            if let Some(ref mut mm) = self.model_manager {
-                                                if let Err(e) = mm.prepare_model(model_override.as_deref()) {
-                                                    tracing::warn!("Failed to prepare model: {}", e);
-                                                }
-                                            }
-                                        }
-                                        crate::config::TranscriptionEngine::Parakeet => {
-                                            if let Some(ref t) = transcriber_preloaded {
-                                                let transcriber = t.clone();
-                                                tokio::task::spawn_blocking(move || {
-                                                    transcriber.prepare();
-                                                });
-                                            }
-                                        }
-                                    }
-                                }
+                                self.prepare_recording_model(&overrides, &transcriber_preloaded);
 
                                 // Create and start audio capture
                                 tracing::debug!("Creating audio capture with device: {}", self.config.audio.device);
@@ -987,19 +1849,21 @@ impl Daemon {
                                         tracing::debug!("Audio capture created, starting...");
                                         if let Err(e) = capture.start().await {
                                             tracing::error!("Failed to start audio: {}", e);
+                                            cleanup_profile_override();
                                             continue;
                                         }
                                         tracing::debug!("Audio capture started successfully");
                                         audio_capture = Some(capture);
                                         state = State::Recording {
                                             started_at: std::time::Instant::now(),
-                                            model_override: model_override.clone(),
+                                            model_override: overrides.model.clone(),
+                                            overrides: overrides.transcription.clone(),
                                         };
                                         self.update_state("recording");
                                         self.play_feedback(SoundEvent::RecordingStart);
 
                                         // Run pre-recording hook (e.g., enter compositor submap for cancel)
-                                        if let Some(cmd) = &self.config.output.pre_recording_command {
+                                        if let Some(cmd) = &overrides.pre_recording_command {
                                             if let Err(e) = output::run_hook(cmd, "pre_recording").await {
                                                 tracing::warn!("{}", e);
                                             }
@@ -1007,6 +1871,7 @@ impl Daemon {
                                     }
                                     Err(e) => {
                                         tracing::error!("Failed to create audio capture: {}", e);
+                                        cleanup_profile_override();
                                         self.play_feedback(SoundEvent::Error);
                                     }
                                 }
@@ -1015,7 +1880,8 @@ impl Daemon {
 
                         (HotkeyEvent::Released, ActivationMode::PushToTalk) => {
                             tracing::debug!("Received HotkeyEvent::Released (push-to-talk), state.is_recording() = {}", state.is_recording());
-                            if let State::Recording { model_override, .. } = &state {
+                            if let State::Recording { model_override, overrides, .. } = &state {
+                                let overrides = overrides.clone();
                                 let transcriber = match self.get_transcriber_for_recording(
                                     model_override.as_deref(),
                                     &transcriber_preloaded,
@@ -1032,6 +1898,7 @@ impl Daemon {
                                     &mut state,
                                     &mut audio_capture,
                                     transcriber,
+                                    overrides,
                                 ).await;
                             }
                         }
@@ -1044,70 +1911,33 @@ impl Daemon {
                             if state.is_idle() {
                                 // Start recording
                                 tracing::info!("Recording started (toggle mode)");
+                                let overrides = self.recording_overrides(model_override).await;
 
                                 if self.config.output.notification.on_recording_start {
                                     send_notification("Recording Started", "Press hotkey again to stop", self.config.output.notification.show_engine_icon, self.config.engine).await;
                                 }
 
-                                // Prepare model for transcription
-                                if self.config.on_demand_loading() {
-                                    // Start model loading in background
-                                    match self.config.engine {
-                                        crate::config::TranscriptionEngine::Whisper => {
-                                            let config = self.config.whisper.clone();
-                                            let config_path = self.config_path.clone();
-                                            let model_to_load = model_override.clone();
-                                            self.model_load_task = Some(tokio::task::spawn_blocking(move || {
-                                                let mut temp_manager = ModelManager::new(&config, config_path);
-                                                temp_manager.get_transcriber(model_to_load.as_deref())
-                                            }));
-                                        }
-                                        crate::config::TranscriptionEngine::Parakeet => {
-                                            let config = self.config.clone();
-                                            self.model_load_task = Some(tokio::task::spawn_blocking(move || {
-                                                crate::transcribe::create_transcriber(&config).map(Arc::from)
-                                            }));
-                                        }
-                                    }
-                                    tracing::debug!("Started background model loading");
-                                } else {
-                                    // Prepare model (spawns subprocess for gpu_isolation mode)
-                                    match self.config.engine {
-                                        crate::config::TranscriptionEngine::Whisper => {
-                                            if let Some(ref mut mm) = self.model_manager {
-                                                if let Err(e) = mm.prepare_model(model_override.as_deref()) {
-                                                    tracing::warn!("Failed to prepare model: {}", e);
-                                                }
-                                            }
-                                        }
-                                        crate::config::TranscriptionEngine::Parakeet => {
-                                            if let Some(ref t) = transcriber_preloaded {
-                                                let transcriber = t.clone();
-                                                tokio::task::spawn_blocking(move || {
-                                                    transcriber.prepare();
-                                                });
-                                            }
-                                        }
-                                    }
-                                }
+                                self.prepare_recording_model(&overrides, &transcriber_preloaded);
 
                                 match audio::create_capture(&self.config.audio) {
                                     Ok(mut capture) => {
                                         if let Err(e) = capture.start().await {
                                             tracing::error!("Failed to start audio: {}", e);
+                                            cleanup_profile_override();
                                             self.play_feedback(SoundEvent::Error);
                                             continue;
                                         }
                                         audio_capture = Some(capture);
                                         state = State::Recording {
                                             started_at: std::time::Instant::now(),
-                                            model_override: model_override.clone(),
+                                            model_override: overrides.model.clone(),
+                                            overrides: overrides.transcription.clone(),
                                         };
                                         self.update_state("recording");
                                         self.play_feedback(SoundEvent::RecordingStart);
 
                                         // Run pre-recording hook (e.g., enter compositor submap for cancel)
-                                        if let Some(cmd) = &self.config.output.pre_recording_command {
+                                        if let Some(cmd) = &overrides.pre_recording_command {
                                             if let Err(e) = output::run_hook(cmd, "pre_recording").await {
                                                 tracing::warn!("{}", e);
                                             }
@@ -1115,10 +1945,12 @@ impl Daemon {
                                     }
                                     Err(e) => {
                                         tracing::error!("Failed to create audio capture: {}", e);
+                                        cleanup_profile_override();
                                         self.play_feedback(SoundEvent::Error);
                                     }
                                 }
-                            } else if let State::Recording { model_override: current_model_override, .. } = &state {
+                            } else if let State::Recording { model_override: current_model_override, overrides, .. } = &state {
+                                let overrides = overrides.clone();
                                 let transcriber = match self.get_transcriber_for_recording(
                                     current_model_override.as_deref(),
                                     &transcriber_preloaded,
@@ -1136,6 +1968,7 @@ impl Daemon {
                                     &mut state,
                                     &mut audio_capture,
                                     transcriber,
+                                    overrides,
                                 ).await;
                             }
                         }
@@ -1164,7 +1997,9 @@ impl Daemon {
 
                                 cleanup_output_mode_override();
                                 cleanup_model_override();
+                                cleanup_transcription_overrides();
                                 cleanup_profile_override();
+                                self.finish_listen(ListenOutcome::Cancelled);
                                 state = State::Idle;
                                 self.update_state("idle");
                                 self.play_feedback(SoundEvent::Cancelled);
@@ -1189,7 +2024,9 @@ impl Daemon {
 
                                 cleanup_output_mode_override();
                                 cleanup_model_override();
+                                cleanup_transcription_overrides();
                                 cleanup_profile_override();
+                                self.finish_listen(ListenOutcome::Cancelled);
                                 state = State::Idle;
                                 self.update_state("idle");
                                 self.play_feedback(SoundEvent::Cancelled);
@@ -1235,7 +2072,9 @@ impl Daemon {
 
                         cleanup_output_mode_override();
                         cleanup_model_override();
+                        cleanup_transcription_overrides();
                         cleanup_profile_override();
+                        self.finish_listen(ListenOutcome::Cancelled);
                         state = State::Idle;
                         self.update_state("idle");
                         self.play_feedback(SoundEvent::Cancelled);
@@ -1266,8 +2105,13 @@ impl Daemon {
                             if let Some(mut capture) = audio_capture.take() {
                                 let _ = capture.stop().await;
                             }
+                            self.finish_listen(ListenOutcome::error(format!(
+                                "Recording timeout ({:.0}s limit)",
+                                max_duration.as_secs_f32()
+                            )));
                             cleanup_output_mode_override();
                             cleanup_model_override();
+                            cleanup_transcription_overrides();
                             cleanup_profile_override();
                             state = State::Idle;
                             self.update_state("idle");
@@ -1280,76 +2124,50 @@ impl Daemon {
                             }
                         }
                     }
+
+                    // `voxtype listen --vad` stops once the speaker pauses
+                    let level = audio_capture.as_ref().map(|capture| capture.current_level());
+                    if state.is_recording() && level.is_some_and(|level| self.listen_heard_pause(level)) {
+                        tracing::info!("Pause in speech, stopping");
+                        self.stop_recording(&mut state, &mut audio_capture, &transcriber_preloaded).await;
+                    }
                 }
 
                 // Handle SIGUSR1 - start recording (for compositor keybindings)
                 _ = sigusr1.recv() => {
                     tracing::debug!("Received SIGUSR1 (start recording)");
+                    // A `voxtype listen` client waiting for this recording
+                    let listen = ListenSession::take();
                     if state.is_idle() {
-                        // Read model override from file (set by `voxtype record start --model X`)
-                        let model_override = read_model_override();
-                        tracing::info!("Recording started (external trigger), model_override = {:?}", model_override);
+                        *self.listen.get_mut().unwrap() = listen;
+                        // Read model override from file (set by `voxtype record start --model X`),
+                        // and language/prompt/translate overrides (set by `voxtype record start --language X`)
+                        let overrides = self.recording_overrides(read_model_override()).await;
+                        tracing::info!("Recording started (external trigger), model_override = {:?}", overrides.model);
 
                         if self.config.output.notification.on_recording_start {
                             send_notification("Recording Started", "External trigger", self.config.output.notification.show_engine_icon, self.config.engine).await;
                         }
 
-                        // Prepare model for transcription
-                        if self.config.on_demand_loading() {
-                            // Start model loading in background
-                            match self.config.engine {
-                                crate::config::TranscriptionEngine::Whisper => {
-                                    let config = self.config.whisper.clone();
-                                    let config_path = self.config_path.clone();
-                                    let model_to_load = model_override.clone();
-                                    self.model_load_task = Some(tokio::task::spawn_blocking(move || {
-                                        let mut temp_manager = ModelManager::new(&config, config_path);
-                                        temp_manager.get_transcriber(model_to_load.as_deref())
-                                    }));
-                                }
-                                crate::config::TranscriptionEngine::Parakeet => {
-                                    let config = self.config.clone();
-                                    self.model_load_task = Some(tokio::task::spawn_blocking(move || {
-                                        crate::transcribe::create_transcriber(&config).map(Arc::from)
-                                    }));
-                                }
-                            }
-                        } else {
-                            // Prepare model (spawns subprocess for gpu_isolation mode)
-                            match self.config.engine {
-                                crate::config::TranscriptionEngine::Whisper => {
-                                    if let Some(ref mut mm) = self.model_manager {
-                                        if let Err(e) = mm.prepare_model(model_override.as_deref()) {
-                                            tracing::warn!("Failed to prepare model: {}", e);
-                                        }
-                                    }
-                                }
-                                crate::config::TranscriptionEngine::Parakeet => {
-                                    if let Some(ref t) = transcriber_preloaded {
-                                        let transcriber = t.clone();
-                                        tokio::task::spawn_blocking(move || {
-                                            transcriber.prepare();
-                                        });
-                                    }
-                                }
-                            }
-                        }
+                        self.prepare_recording_model(&overrides, &transcriber_preloaded);
 
                         match audio::create_capture(&self.config.audio) {
                             Ok(mut capture) => {
                                 if let Err(e) = capture.start().await {
                                     tracing::error!("Failed to start audio: {}", e);
+                                    self.finish_listen(ListenOutcome::error(format!("Failed to start audio: {}", e)));
                                 } else {
                                     audio_capture = Some(capture);
                                     state = State::Recording {
                                         started_at: std::time::Instant::now(),
-                                        model_override,
+                                        model_override: overrides.model.clone(),
+                                        overrides: overrides.transcription.clone(),
                                     };
                                     self.update_state("recording");
                                     self.play_feedback(SoundEvent::RecordingStart);
 
                                     // Run pre-recording hook (e.g., enter compositor submap for cancel)
-                                    if let Some(cmd) = &self.config.output.pre_recording_command {
+                                    if let Some(cmd) = &overrides.pre_recording_command {
                                         if let Err(e) = output::run_hook(cmd, "pre_recording").await {
                                             tracing::warn!("{}", e);
                                         }
@@ -1358,34 +2176,19 @@ impl Daemon {
                             }
                             Err(e) => {
                                 tracing::error!("Failed to create audio capture: {}", e);
+                                self.finish_listen(ListenOutcome::error(format!("Failed to create audio capture: {}", e)));
                                 self.play_feedback(SoundEvent::Error);
                             }
                         }
+                    } else if let Some(session) = listen {
+                        session.finish(&ListenOutcome::error("Voxtype is already recording or transcribing"));
                     }
                 }
 
                 // Handle SIGUSR2 - stop recording (for compositor keybindings)
                 _ = sigusr2.recv() => {
                     tracing::debug!("Received SIGUSR2 (stop recording)");
-                    if let State::Recording { model_override, .. } = &state {
-                        let transcriber = match self.get_transcriber_for_recording(
-                            model_override.as_deref(),
-                            &transcriber_preloaded,
-                        ).await {
-                            Ok(t) => Some(t),
-                            Err(()) => {
-                                state = State::Idle;
-                                self.update_state("idle");
-                                continue;
-                            }
-                        };
-
-                        self.start_transcription_task(
-                            &mut state,
-                            &mut audio_capture,
-                            transcriber,
-                        ).await;
-                    }
+                    self.stop_recording(&mut state, &mut audio_capture, &transcriber_preloaded).await;
                 }
 
                 // Handle transcription task completion
@@ -1411,7 +2214,9 @@ impl Daemon {
 
                         cleanup_output_mode_override();
                         cleanup_model_override();
+                        cleanup_transcription_overrides();
                         cleanup_profile_override();
+                        self.finish_listen(ListenOutcome::Cancelled);
                         state = State::Idle;
                         self.update_state("idle");
                         self.play_feedback(SoundEvent::Cancelled);
@@ -1488,6 +2293,7 @@ impl Daemon {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::{PipelineStage, PipelineStageKind, PostProcessProtocol};
     use std::fs;
     use tempfile::TempDir;
 
@@ -1650,6 +2456,13 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_parse_translate_override() {
+        assert_eq!(parse_translate_override("true"), Some(true));
+        assert_eq!(parse_translate_override("false"), Some(false));
+        assert_eq!(parse_translate_override("yes"), None);
+    }
+
     #[test]
     fn test_cleanup_output_mode_override() {
         with_test_runtime_dir(|dir| {
@@ -1725,4 +2538,57 @@ mod tests {
             );
         });
     }
+
+    /// Text processing and post-processing of a transcription without key presses,
+    /// as handle_transcription_result does them
+    async fn process_transcription(daemon: &Daemon, text: &str) -> String {
+        let context = ProcessContext::default();
+        let actions =
+            daemon
+                .pipeline
+                .process_actions(text, &daemon.text_processor, context, None, 0);
+        daemon
+            .post_process_text(
+                actions_text(&actions),
+                &daemon.text_processor,
+                context,
+                None,
+                &PostProcessMetadata::default(),
+            )
+            .await
+            .text
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_runs_once_with_voice_commands() {
+        let stage = |stage, command: Option<&str>| PipelineStage {
+            stage,
+            name: None,
+            command: command.map(str::to_string),
+            timeout_ms: None,
+            protocol: PostProcessProtocol::Text,
+            enabled: true,
+            profiles: Vec::new(),
+        };
+        let mut config = Config::default();
+        config.text.commands.enabled = true;
+        config
+            .text
+            .replacements
+            .insert("vox type".to_string(), "voxtype".to_string());
+        config.pipeline = vec![
+            stage(PipelineStageKind::Replacements, None),
+            stage(PipelineStageKind::Command, Some("tr a-z A-Z")),
+        ];
+        let daemon = Daemon::new(config, None);
+
+        assert_eq!(
+            process_transcription(&daemon, "hello vox type new line world").await,
+            "HELLO VOXTYPE\nWORLD"
+        );
+        assert_eq!(
+            process_transcription(&daemon, "foo. Delete that. bar").await,
+            "BAR"
+        );
+    }
 }
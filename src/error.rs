@@ -123,10 +123,32 @@ pub enum OutputError {
     #[error("Ctrl+V simulation failed: {0}")]
     CtrlVFailed(String),
 
+    #[error("{0} cannot send key presses")]
+    KeysUnsupported(String),
+
+    #[error("Sending transcription to {0} failed: {1}")]
+    SinkFailed(&'static str, String),
+
     #[error("All output methods failed. Ensure wtype, dotool, ydotool, wl-copy, or xclip is available.")]
     AllMethodsFailed,
 }
 
+/// Errors related to looking up the focused window
+#[derive(Error, Debug)]
+pub enum WindowError {
+    #[error("{0} IPC socket not found. Is the compositor running?")]
+    NoSocket(&'static str),
+
+    #[error("{0} IPC failed: {1}")]
+    Ipc(&'static str, String),
+
+    #[error("Invalid window info from {0}: {1}")]
+    InvalidReply(&'static str, String),
+
+    #[error("Window command failed: {0}")]
+    Command(String),
+}
+
 /// Result type alias using VoxtypeError
 pub type Result<T> = std::result::Result<T, VoxtypeError>;
 
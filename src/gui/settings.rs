@@ -528,10 +528,8 @@ fn build_transcription_page() -> adw::PreferencesPage {
         .build();
 
     // Model selection — load from config
-    let models_list = [
-        "tiny", "tiny.en", "base", "base.en", "small", "small.en",
-        "medium", "medium.en", "large-v3", "large-v3-turbo",
-    ];
+    let models_list =
+        crate::model_registry::registry().names(crate::config::TranscriptionEngine::Whisper);
     let current_model = whisper
         .and_then(|w| w.get("model"))
         .and_then(|v| v.as_str())
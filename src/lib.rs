@@ -69,6 +69,7 @@
 //! ```
 
 pub mod audio;
+pub mod bench;
 pub mod cli;
 pub mod config;
 pub mod cpu;
@@ -77,14 +78,22 @@ pub mod error;
 #[cfg(feature = "gui")]
 pub mod gui;
 pub mod hotkey;
+pub mod listen;
 pub mod model_manager;
+pub mod model_registry;
 pub mod output;
+pub mod pipeline;
+pub mod script;
 pub mod setup;
 pub mod state;
 pub mod text;
 pub mod transcribe;
+pub mod window;
 
-pub use cli::{Cli, Commands, CompositorType, OutputModeOverride, RecordAction, SetupAction};
+pub use cli::{
+    Cli, Commands, CompositorType, OutputModeOverride, RecordAction, SetupAction, SnippetAction,
+    TextAction, VocabAction,
+};
 pub use config::Config;
 pub use daemon::Daemon;
 pub use error::{Result, VoxtypeError};
@@ -0,0 +1,346 @@
+//! One-shot transcriptions for `voxtype listen`
+//!
+//! The client writes a request to the runtime directory and starts a recording
+//! with SIGUSR1. The daemon takes the request when the recording starts and,
+//! instead of outputting the transcription, writes the outcome to the file the
+//! request names. The client waits for that file and prints the text.
+//!
+//! Request and result files carry the client's process ID, so clients started
+//! at the same time don't overwrite each other; the daemon takes the oldest
+//! request first.
+
+use crate::config::Config;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Request files in the runtime directory (`listen_request.<client pid>`),
+/// consumed when a recording starts
+const REQUEST_PREFIX: &str = "listen_request.";
+
+/// RMS level above which the microphone is taken to hear speech
+const SPEECH_LEVEL: f32 = 0.015;
+
+/// Exit code when nothing was transcribed
+pub const EXIT_EMPTY: i32 = 3;
+/// Exit code when the recording or transcription was cancelled
+pub const EXIT_CANCELLED: i32 = 130;
+
+/// What `voxtype listen` asks of the daemon
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ListenRequest {
+    /// Where to write the outcome
+    pub result_path: PathBuf,
+    /// Stop after this long without speech (--vad)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub silence_ms: Option<u64>,
+}
+
+impl ListenRequest {
+    /// A request from this process, with its own result file
+    pub fn new(silence_ms: Option<u64>) -> Self {
+        Self {
+            result_path: Config::runtime_dir().join(format!("listen-{}.json", std::process::id())),
+            silence_ms,
+        }
+    }
+
+    /// This process's request file
+    fn own_path(dir: &Path) -> PathBuf {
+        dir.join(format!("{}{}", REQUEST_PREFIX, std::process::id()))
+    }
+
+    /// Write the request for the next recording the daemon starts
+    pub fn write(&self) -> std::io::Result<()> {
+        self.write_in(&Config::runtime_dir())
+    }
+
+    fn write_in(&self, dir: &Path) -> std::io::Result<()> {
+        let json = serde_json::to_string(self).map_err(std::io::Error::other)?;
+        // Renamed into place, so the daemon never reads half a request
+        let path = Self::own_path(dir);
+        let tmp = dir.join(format!("{}{}.tmp", REQUEST_PREFIX, std::process::id()));
+        std::fs::write(&tmp, json)?;
+        std::fs::rename(&tmp, path)
+    }
+
+    /// Whether the daemon has not taken this process's request yet
+    pub fn is_pending() -> bool {
+        Self::own_path(&Config::runtime_dir()).exists()
+    }
+
+    /// Withdraw this process's request if the daemon never took it
+    pub fn withdraw() {
+        let _ = std::fs::remove_file(Self::own_path(&Config::runtime_dir()));
+    }
+
+    /// Remove every request, e.g. left over from a previous daemon
+    pub fn withdraw_all() {
+        for (_, path) in request_files(&Config::runtime_dir()) {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+
+    /// Read and consume the oldest request, if there is one
+    pub fn take() -> Option<Self> {
+        Self::take_from(&Config::runtime_dir())
+    }
+
+    fn take_from(dir: &Path) -> Option<Self> {
+        let (_, path) = request_files(dir).into_iter().min()?;
+        let content = std::fs::read_to_string(&path).ok()?;
+        let _ = std::fs::remove_file(&path);
+        match serde_json::from_str(&content) {
+            Ok(request) => Some(request),
+            Err(e) => {
+                tracing::warn!("Invalid listen request {:?}: {}", path, e);
+                None
+            }
+        }
+    }
+}
+
+/// Request files in `dir`, with the time each was written
+fn request_files(dir: &Path) -> Vec<(SystemTime, PathBuf)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.strip_prefix(REQUEST_PREFIX))
+                .is_some_and(|pid| pid.parse::<u32>().is_ok())
+        })
+        .map(|entry| {
+            let written = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .unwrap_or(SystemTime::UNIX_EPOCH);
+            (written, entry.path())
+        })
+        .collect()
+}
+
+/// How a one-shot transcription ended
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "lowercase")]
+pub enum ListenOutcome {
+    /// Transcribed text, after text processing and post-processing
+    #[serde(rename = "ok")]
+    Text { text: String },
+    /// Nothing was said, or nothing was left after processing
+    Empty,
+    /// Cancelled before any text was produced
+    Cancelled,
+    /// Recording or transcription failed
+    Error { message: String },
+}
+
+impl ListenOutcome {
+    /// Shorthand for an error outcome
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::Error {
+            message: message.into(),
+        }
+    }
+
+    /// Process exit code for the outcome
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Text { .. } => 0,
+            Self::Error { .. } => 1,
+            Self::Empty => EXIT_EMPTY,
+            Self::Cancelled => EXIT_CANCELLED,
+        }
+    }
+
+    /// Write the outcome, replacing the file at once so readers never see half of it
+    pub fn write(&self, path: &Path) -> std::io::Result<()> {
+        let json = serde_json::to_string(self).map_err(std::io::Error::other)?;
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, json)?;
+        std::fs::rename(&tmp, path)
+    }
+
+    /// Read and remove an outcome, once it has been written
+    pub fn take(path: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        let _ = std::fs::remove_file(path);
+        Some(
+            serde_json::from_str(&content)
+                .unwrap_or_else(|e| Self::error(format!("Invalid listen result: {}", e))),
+        )
+    }
+}
+
+/// Notices the pause that ends an utterance, from the microphone level
+#[derive(Debug, Clone)]
+pub struct PauseDetector {
+    silence: Duration,
+    heard_speech: bool,
+    quiet_since: Option<Instant>,
+}
+
+impl PauseDetector {
+    /// Detect pauses of at least `silence`
+    pub fn new(silence: Duration) -> Self {
+        Self {
+            silence,
+            heard_speech: false,
+            quiet_since: None,
+        }
+    }
+
+    /// Feed the current level; true once speech was followed by a long enough pause
+    pub fn update(&mut self, level: f32, now: Instant) -> bool {
+        if level >= SPEECH_LEVEL {
+            self.heard_speech = true;
+            self.quiet_since = None;
+            return false;
+        }
+        if !self.heard_speech {
+            return false;
+        }
+        let quiet_since = *self.quiet_since.get_or_insert(now);
+        now.duration_since(quiet_since) >= self.silence
+    }
+}
+
+/// A recording whose outcome a `voxtype listen` client is waiting for
+#[derive(Debug)]
+pub struct ListenSession {
+    result_path: PathBuf,
+    pause: Option<PauseDetector>,
+}
+
+impl ListenSession {
+    /// Start a session from the pending request, if there is one
+    pub fn take() -> Option<Self> {
+        ListenRequest::take().map(|request| Self {
+            result_path: request.result_path,
+            pause: request
+                .silence_ms
+                .map(|ms| PauseDetector::new(Duration::from_millis(ms))),
+        })
+    }
+
+    /// Whether the speaker paused long enough to stop (only with --vad)
+    pub fn heard_pause(&mut self, level: f32) -> bool {
+        self.pause
+            .as_mut()
+            .is_some_and(|pause| pause.update(level, Instant::now()))
+    }
+
+    /// Hand the outcome to the client
+    pub fn finish(self, outcome: &ListenOutcome) {
+        tracing::debug!("Listen outcome: {:?}", outcome);
+        if let Err(e) = outcome.write(&self.result_path) {
+            tracing::warn!(
+                "Failed to write listen result to {:?}: {}",
+                self.result_path,
+                e
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outcome_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("result.json");
+        assert_eq!(ListenOutcome::take(&path), None);
+
+        let outcome = ListenOutcome::Text {
+            text: "hello world".to_string(),
+        };
+        outcome.write(&path).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            r#"{"status":"ok","text":"hello world"}"#
+        );
+        assert_eq!(ListenOutcome::take(&path), Some(outcome));
+        assert!(!path.exists());
+
+        ListenOutcome::Cancelled.write(&path).unwrap();
+        assert_eq!(ListenOutcome::take(&path), Some(ListenOutcome::Cancelled));
+
+        std::fs::write(&path, "garbage").unwrap();
+        assert!(matches!(
+            ListenOutcome::take(&path),
+            Some(ListenOutcome::Error { .. })
+        ));
+    }
+
+    #[test]
+    fn test_requests_from_two_clients() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(ListenRequest::take_from(dir.path()), None);
+
+        // Another client's request, written earlier
+        let other = ListenRequest {
+            result_path: dir.path().join("listen-1.json"),
+            silence_ms: None,
+        };
+        let other_path = dir.path().join(format!("{}1", REQUEST_PREFIX));
+        std::fs::write(&other_path, serde_json::to_string(&other).unwrap()).unwrap();
+        let earlier = SystemTime::now() - Duration::from_secs(5);
+        std::fs::File::options()
+            .write(true)
+            .open(&other_path)
+            .unwrap()
+            .set_modified(earlier)
+            .unwrap();
+
+        let own = ListenRequest {
+            result_path: dir.path().join("listen-2.json"),
+            silence_ms: Some(800),
+        };
+        own.write_in(dir.path()).unwrap();
+        std::fs::write(dir.path().join("listen_request.3.tmp"), "partial").unwrap();
+
+        // Both requests survive, and the oldest is taken first
+        assert_eq!(ListenRequest::take_from(dir.path()), Some(other));
+        assert_eq!(ListenRequest::take_from(dir.path()), Some(own));
+        assert_eq!(ListenRequest::take_from(dir.path()), None);
+    }
+
+    #[test]
+    fn test_exit_codes() {
+        let text = ListenOutcome::Text {
+            text: "hi".to_string(),
+        };
+        assert_eq!(text.exit_code(), 0);
+        assert_eq!(ListenOutcome::error("no mic").exit_code(), 1);
+        assert_eq!(ListenOutcome::Empty.exit_code(), EXIT_EMPTY);
+        assert_eq!(ListenOutcome::Cancelled.exit_code(), EXIT_CANCELLED);
+    }
+
+    #[test]
+    fn test_pause_detector() {
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        let mut pause = PauseDetector::new(Duration::from_millis(500));
+
+        // Silence before the first word never stops the recording
+        assert!(!pause.update(0.001, at(0)));
+        assert!(!pause.update(0.001, at(2000)));
+
+        // Short gaps between words don't either
+        assert!(!pause.update(0.1, at(2100)));
+        assert!(!pause.update(0.002, at(2200)));
+        assert!(!pause.update(0.002, at(2600)));
+        assert!(!pause.update(0.08, at(2650)));
+
+        // A long enough pause after speech does
+        assert!(!pause.update(0.002, at(2700)));
+        assert!(!pause.update(0.002, at(3100)));
+        assert!(pause.update(0.002, at(3200)));
+    }
+}
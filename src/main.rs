@@ -8,7 +8,19 @@ use clap::Parser;
 use std::path::PathBuf;
 use std::process::Command;
 use tracing_subscriber::EnvFilter;
-use voxtype::{config, cpu, daemon, setup, transcribe, Cli, Commands, RecordAction, SetupAction};
+use voxtype::listen::{ListenOutcome, ListenRequest, PauseDetector};
+use voxtype::output::action::{actions_text, OutputAction};
+use voxtype::output::post_process::{PostProcessMetadata, PostProcessOutput, PostProcessor};
+use voxtype::pipeline::Pipeline;
+use voxtype::script::{ScriptMetadata, ScriptRunner};
+use voxtype::text::rules::CompiledRule;
+use voxtype::text::snippets::{Snippet, SnippetLibrary};
+use voxtype::text::vocabulary::Vocabulary;
+use voxtype::text::{ProcessContext, TextProcessor};
+use voxtype::{
+    config, cpu, daemon, setup, transcribe, Cli, Commands, RecordAction, SetupAction,
+    SnippetAction, TextAction, VocabAction,
+};
 
 /// Parse a comma-separated list of driver names into OutputDriver vec
 fn parse_driver_order(s: &str) -> Result<Vec<config::OutputDriver>, String> {
@@ -99,8 +111,12 @@ async fn main() -> anyhow::Result<()> {
         match engine.to_lowercase().as_str() {
             "whisper" => config.engine = config::TranscriptionEngine::Whisper,
             "parakeet" => config.engine = config::TranscriptionEngine::Parakeet,
+            "external" => config.engine = config::TranscriptionEngine::External,
             _ => {
-                eprintln!("Error: Invalid engine '{}'. Valid options: whisper, parakeet", engine);
+                eprintln!(
+                    "Error: Invalid engine '{}'. Valid options: whisper, parakeet, external",
+                    engine
+                );
                 std::process::exit(1);
             }
         }
@@ -151,6 +167,8 @@ async fn main() -> anyhow::Result<()> {
             model,
             language,
             translate,
+            no_translate,
+            initial_prompt,
             threads,
         } => {
             // Internal command: run transcription worker process
@@ -167,6 +185,12 @@ async fn main() -> anyhow::Result<()> {
             if translate {
                 whisper_config.translate = true;
             }
+            if no_translate {
+                whisper_config.translate = false;
+            }
+            if let Some(p) = initial_prompt {
+                whisper_config.initial_prompt = Some(p);
+            }
             if let Some(t) = threads {
                 whisper_config.threads = Some(t);
             }
@@ -226,8 +250,19 @@ async fn main() -> anyhow::Result<()> {
                         setup::dms::print_config();
                     }
                 }
-                Some(SetupAction::Model { list, set, restart }) => {
-                    if list {
+                Some(SetupAction::Model {
+                    list,
+                    set,
+                    restart,
+                    import,
+                    sha256,
+                    verify,
+                }) => {
+                    if verify {
+                        setup::model::verify_installed()?;
+                    } else if let Some(path) = import {
+                        setup::model::import_model(&path, sha256.as_deref())?;
+                    } else if list {
                         setup::model::list_installed();
                     } else if let Some(model_name) = set {
                         setup::model::set_model(&model_name, restart).await?;
@@ -274,9 +309,15 @@ async fn main() -> anyhow::Result<()> {
             }
         }
 
-        Commands::Config => {
-            show_config(&config).await?;
-        }
+        Commands::Config { profile } => match profile {
+            Some(name) => {
+                let profile = config
+                    .get_profile(&name)
+                    .ok_or_else(|| anyhow::anyhow!("Profile '{}' not found in config", name))?;
+                show_config(&config.with_profile(profile), Some(&name)).await?;
+            }
+            None => show_config(&config, None).await?,
+        },
 
         Commands::Status {
             follow,
@@ -291,6 +332,77 @@ async fn main() -> anyhow::Result<()> {
             send_record_command(&config, action)?;
         }
 
+        Commands::Listen {
+            vad,
+            silence_ms,
+            model,
+            profile,
+            language,
+            translate,
+        } => {
+            let silence_ms = vad.then_some(silence_ms);
+            let outcome = if is_daemon_running() {
+                let action = RecordAction::Start {
+                    type_mode: false,
+                    clipboard: false,
+                    paste: false,
+                    file: None,
+                    model,
+                    profile,
+                    language,
+                    initial_prompt: None,
+                    translate,
+                    no_translate: false,
+                    grammar: None,
+                };
+                listen_with_daemon(&config, action, silence_ms).await?
+            } else {
+                let mut config = match profile.as_deref() {
+                    Some(name) => {
+                        config.with_profile(config.get_profile(name).ok_or_else(|| {
+                            anyhow::anyhow!("Profile '{}' not found in config", name)
+                        })?)
+                    }
+                    None => config,
+                };
+                if let Some(model) = model {
+                    config.whisper.model = model;
+                }
+                if let Some(language) = language {
+                    config.whisper.language =
+                        config::LanguageConfig::from_comma_separated(&language);
+                }
+                if translate {
+                    config.whisper.translate = true;
+                }
+                listen_standalone(&config, profile.as_deref(), silence_ms).await?
+            };
+
+            match &outcome {
+                ListenOutcome::Text { text } => println!("{}", text),
+                ListenOutcome::Empty => eprintln!("Nothing transcribed"),
+                ListenOutcome::Cancelled => eprintln!("Cancelled"),
+                ListenOutcome::Error { message } => eprintln!("Error: {}", message),
+            }
+            std::process::exit(outcome.exit_code());
+        }
+
+        Commands::Bench { dir, models, json } => {
+            run_bench(&config, &dir, &models, json)?;
+        }
+
+        Commands::Vocab { action } => {
+            run_vocab_command(&config, action)?;
+        }
+
+        Commands::Snippet { action } => {
+            run_snippet_command(&config, action)?;
+        }
+
+        Commands::Text { action } => {
+            run_text_command(&config, action).await?;
+        }
+
         Commands::Ui { settings } => {
             #[cfg(feature = "gui")]
             {
@@ -309,19 +421,316 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
-/// Send a record command to the running daemon via Unix signals or file triggers
-fn send_record_command(config: &config::Config, action: RecordAction) -> anyhow::Result<()> {
-    use nix::sys::signal::{kill, Signal};
+/// Benchmark engines/models on a directory of WAV files
+fn run_bench(
+    config: &config::Config,
+    dir: &std::path::Path,
+    models: &[String],
+    json: bool,
+) -> anyhow::Result<()> {
+    use voxtype::bench;
+
+    let samples = bench::load_dataset(dir)?;
+    let references = samples.iter().filter(|s| s.reference.is_some()).count();
+
+    let targets: Vec<bench::BenchTarget> = if models.is_empty() {
+        vec![bench::BenchTarget::from_config(config.clone())]
+    } else {
+        models
+            .iter()
+            .map(|spec| bench::BenchTarget::from_spec(config, spec))
+            .collect::<Result<_, _>>()?
+    };
+
+    let mut results = Vec::with_capacity(targets.len());
+    for target in &targets {
+        if !json {
+            eprintln!("Benchmarking {} ({} files)...", target.label, samples.len());
+        }
+        results.push(bench::run_target(target, &samples));
+    }
+
+    let report = bench::BenchReport {
+        dataset: dir.to_path_buf(),
+        files: samples.len(),
+        references,
+        results,
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        bench::print_report(&report);
+    }
+
+    if report.results.iter().any(|r| r.error.is_some()) {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Inspect text processing on a sample
+async fn run_text_command(config: &config::Config, action: TextAction) -> anyhow::Result<()> {
+    match action {
+        TextAction::Test {
+            input,
+            language,
+            profile,
+        } => {
+            // Report rules the daemon would skip
+            for rule in &config.text.rules {
+                if !rule.enabled {
+                    println!("Disabled rule {}", rule.label());
+                } else if let Err(e) = CompiledRule::compile(rule) {
+                    println!("Invalid rule {}: {}", rule.label(), e);
+                }
+            }
+
+            // Same processor the daemon builds, with the profile's replacements
+            let text_config = match profile.as_deref().and_then(|name| config.get_profile(name)) {
+                Some(profile) => config.with_profile(profile).text,
+                None => config.text.clone(),
+            };
+            let mut processor = TextProcessor::new(&text_config);
+            if config.vocabulary.fuzzy_correction {
+                if let Some(vocab) =
+                    Vocabulary::from_config(&config.vocabulary).filter(|v| !v.is_empty())
+                {
+                    processor =
+                        processor.with_vocabulary(&vocab, config.vocabulary.fuzzy_threshold);
+                }
+            }
+            if let Some(snippets) = SnippetLibrary::from_config(&config.snippets) {
+                processor = processor.with_snippets(&snippets);
+            }
+            if config.whisper.translate {
+                processor = processor.with_language("en");
+            } else if !config.whisper.language.is_auto() {
+                processor = processor.with_language(config.whisper.language.primary());
+            }
+
+            let profile_name = profile.clone();
+            let profile = profile
+                .map(|name| {
+                    config
+                        .get_profile(&name)
+                        .ok_or_else(|| anyhow::anyhow!("Profile '{}' not found in config", name))
+                })
+                .transpose()?;
+            let context = ProcessContext {
+                language: language.as_deref(),
+                normalize: profile.and_then(|p| p.normalize),
+                disfluency: profile.and_then(|p| p.disfluency),
+                code: profile.and_then(|p| p.code),
+                spoken_punctuation: profile.and_then(|p| p.spoken_punctuation),
+            };
+
+            println!("{:<24} {:?}", "input", input);
+            let mut previous = input.clone();
+            let pipeline = Pipeline::new(&config.pipeline);
+            if pipeline.is_empty() {
+                for (stage, output) in processor.trace(&input, context) {
+                    if output == previous {
+                        println!("{:<24} (unchanged)", stage);
+                    } else {
+                        println!("{:<24} {:?}", stage, output);
+                    }
+                    previous = output;
+                }
+            } else {
+                // Same post-processor the daemon picks: the profile's, then the default
+                let post_processor = PostProcessor::from_config(config, profile);
+                let metadata = PostProcessMetadata {
+                    profile: profile_name.as_deref(),
+                    language: language.as_deref(),
+                    model: Some(config.model_name()),
+                    ..Default::default()
+                };
+                let steps = pipeline
+                    .trace(
+                        &input,
+                        &processor,
+                        context,
+                        &metadata,
+                        post_processor.as_ref(),
+                    )
+                    .await;
+                for (stage, output, elapsed) in steps {
+                    let stage = format!("{} ({} ms)", stage, elapsed.as_millis());
+                    if output == previous {
+                        println!("{:<24} (unchanged)", stage);
+                    } else {
+                        println!("{:<24} {:?}", stage, output);
+                    }
+                    previous = output;
+                }
+            }
+
+            // Scripts run last, on the final text
+            if let Some(scripts) = ScriptRunner::from_config(&config.scripts) {
+                let metadata = ScriptMetadata {
+                    profile: profile_name.as_deref(),
+                    language: language.as_deref(),
+                    ..Default::default()
+                };
+                for (name, actions) in scripts.trace(&previous, &metadata) {
+                    let stage = format!("script {}", name);
+                    let output = actions_text(&actions);
+                    if actions.iter().any(OutputAction::is_key) {
+                        println!("{:<24} {:?}", stage, actions);
+                    } else if output == previous {
+                        println!("{:<24} (unchanged)", stage);
+                    } else {
+                        println!("{:<24} {:?}", stage, output);
+                    }
+                    previous = output;
+                }
+            }
+            println!("{:<24} {:?}", "output", previous);
+        }
+    }
+
+    Ok(())
+}
+
+/// Add, remove or list custom vocabulary terms
+fn run_vocab_command(config: &config::Config, action: VocabAction) -> anyhow::Result<()> {
+    let path = config
+        .vocabulary
+        .resolve_path()
+        .ok_or_else(|| anyhow::anyhow!("Could not determine vocabulary file location"))?;
+    let mut vocab = Vocabulary::load(&path)
+        .map_err(|e| anyhow::anyhow!("Failed to read vocabulary file {:?}: {}", path, e))?;
+
+    let changed = match action {
+        VocabAction::List => {
+            if vocab.is_empty() {
+                eprintln!("No vocabulary terms ({:?})", path);
+            }
+            for term in vocab.terms() {
+                println!("{}", term);
+            }
+            return Ok(());
+        }
+        VocabAction::Add { terms } => {
+            let mut changed = false;
+            for term in &terms {
+                if vocab.add(term) {
+                    println!("Added: {}", term.trim());
+                    changed = true;
+                } else {
+                    println!("Already present: {}", term.trim());
+                }
+            }
+            changed
+        }
+        VocabAction::Remove { terms } => {
+            let mut changed = false;
+            for term in &terms {
+                if vocab.remove(term) {
+                    println!("Removed: {}", term.trim());
+                    changed = true;
+                } else {
+                    println!("Not found: {}", term.trim());
+                }
+            }
+            changed
+        }
+    };
+
+    if changed {
+        vocab
+            .save(&path)
+            .map_err(|e| anyhow::anyhow!("Failed to write vocabulary file {:?}: {}", path, e))?;
+        if !config.vocabulary.enabled {
+            eprintln!("Note: vocabulary is disabled in config ([vocabulary] enabled = false)");
+        } else if is_daemon_running() {
+            eprintln!("Restart the daemon to apply: systemctl --user restart voxtype");
+        }
+    }
+
+    Ok(())
+}
+
+/// Add, remove or list snippets
+fn run_snippet_command(config: &config::Config, action: SnippetAction) -> anyhow::Result<()> {
+    let path = config
+        .snippets
+        .resolve_path()
+        .ok_or_else(|| anyhow::anyhow!("Could not determine snippets file location"))?;
+    let mut library = SnippetLibrary::load(&path)
+        .map_err(|e| anyhow::anyhow!("Failed to read snippets file {:?}: {}", path, e))?;
+
+    match action {
+        SnippetAction::List => {
+            if library.is_empty() {
+                eprintln!("No snippets ({:?})", path);
+            }
+            for (name, snippet) in library.iter() {
+                println!("{:<16} {:?} → {:?}", name, snippet.trigger, snippet.text);
+            }
+            return Ok(());
+        }
+        SnippetAction::Add {
+            name,
+            trigger,
+            text,
+        } => {
+            let text = if text == "-" {
+                let mut text = String::new();
+                std::io::Read::read_to_string(&mut std::io::stdin(), &mut text)?;
+                text.trim_end_matches('\n').to_string()
+            } else {
+                text
+            };
+            if trigger.trim().is_empty() {
+                anyhow::bail!("Snippet trigger must not be empty");
+            }
+            let snippet = Snippet { trigger, text };
+            if library.insert(&name, snippet).is_some() {
+                println!("Replaced: {}", name);
+            } else {
+                println!("Added: {}", name);
+            }
+        }
+        SnippetAction::Remove { names } => {
+            let mut changed = false;
+            for name in &names {
+                if library.remove(name) {
+                    println!("Removed: {}", name);
+                    changed = true;
+                } else {
+                    println!("Not found: {}", name);
+                }
+            }
+            if !changed {
+                return Ok(());
+            }
+        }
+    }
+
+    library
+        .save(&path)
+        .map_err(|e| anyhow::anyhow!("Failed to write snippets file {:?}: {}", path, e))?;
+    if !config.snippets.enabled {
+        eprintln!("Note: snippets are disabled in config ([snippets] enabled = false)");
+    } else if is_daemon_running() {
+        eprintln!("Restart the daemon to apply: systemctl --user restart voxtype");
+    }
+
+    Ok(())
+}
+
+/// PID of the running daemon, from the pid file
+fn daemon_pid() -> anyhow::Result<i32> {
+    use nix::sys::signal::kill;
     use nix::unistd::Pid;
-    use voxtype::OutputModeOverride;
 
     // Read PID from the pid file
     let pid_file = config::Config::runtime_dir().join("pid");
 
     if !pid_file.exists() {
-        eprintln!("Error: Voxtype daemon is not running.");
-        eprintln!("Start it with: voxtype daemon");
-        std::process::exit(1);
+        anyhow::bail!("Voxtype daemon is not running.\nStart it with: voxtype daemon");
     }
 
     let pid_str = std::fs::read_to_string(&pid_file)
@@ -336,11 +745,93 @@ fn send_record_command(config: &config::Config, action: RecordAction) -> anyhow:
     if kill(Pid::from_raw(pid), None).is_err() {
         // Process doesn't exist, clean up stale PID file
         let _ = std::fs::remove_file(&pid_file);
-        eprintln!("Error: Voxtype daemon is not running (stale PID file removed).");
-        eprintln!("Start it with: voxtype daemon");
-        std::process::exit(1);
+        anyhow::bail!(
+            "Voxtype daemon is not running (stale PID file removed).\n\
+             Start it with: voxtype daemon"
+        );
+    }
+
+    Ok(pid)
+}
+
+/// State file the daemon writes, which `voxtype record toggle` reads
+fn toggle_state_file(config: &config::Config) -> anyhow::Result<std::path::PathBuf> {
+    config.resolve_state_file().ok_or_else(|| {
+        anyhow::anyhow!(
+            "Cannot toggle recording without state_file configured.\n\n\
+             Add to your config.toml:\n  \
+             state_file = \"auto\"\n\n\
+             Or use explicit start/stop commands:\n  \
+             voxtype record start\n  \
+             voxtype record stop"
+        )
+    })
+}
+
+/// Check the grammar and profile a record command asks for, and that a
+/// toggle can tell whether the daemon is recording
+fn check_record_command(config: &config::Config, action: &RecordAction) -> anyhow::Result<()> {
+    if let Some(grammar_name) = action.grammar() {
+        if config.get_grammar(grammar_name).is_none() {
+            let mut available: Vec<&str> = config.grammars.keys().map(|s| s.as_str()).collect();
+            if available.is_empty() {
+                anyhow::bail!(
+                    "Grammar '{0}' not found.\n\n\
+                     No grammars are configured. Add one to your config.toml:\n\n  \
+                     [grammars.{0}.commands]\n  \
+                     close = [\"close window\", \"close this\"]",
+                    grammar_name
+                );
+            }
+            available.sort();
+            anyhow::bail!(
+                "Grammar '{}' not found.\n\nAvailable grammars: {}",
+                grammar_name,
+                available.join(", ")
+            );
+        }
     }
 
+    if let Some(profile_name) = action.profile() {
+        if config.get_profile(profile_name).is_none() {
+            let available = config.profile_names();
+            if available.is_empty() {
+                anyhow::bail!(
+                    "Profile '{0}' not found.\n\n\
+                     No profiles are configured. Add profiles to your config.toml:\n\n  \
+                     [profiles.{0}]\n  \
+                     post_process_command = \"your-command-here\"",
+                    profile_name
+                );
+            }
+            anyhow::bail!(
+                "Profile '{}' not found.\n\nAvailable profiles: {}",
+                profile_name,
+                available
+                    .iter()
+                    .map(|s| s.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+    }
+
+    if matches!(action, RecordAction::Toggle { .. }) {
+        toggle_state_file(config)?;
+    }
+
+    Ok(())
+}
+
+/// Send a record command to the running daemon via Unix signals or file triggers
+fn send_record_command(config: &config::Config, action: RecordAction) -> anyhow::Result<()> {
+    use nix::sys::signal::{kill, Signal};
+    use nix::unistd::Pid;
+    use voxtype::OutputModeOverride;
+
+    let pid = daemon_pid()?;
+    check_record_command(config, &action)?;
+
     // Handle cancel separately (uses file trigger instead of signal)
     if matches!(action, RecordAction::Cancel) {
         let cancel_file = config::Config::runtime_dir().join("cancel");
@@ -376,26 +867,33 @@ fn send_record_command(config: &config::Config, action: RecordAction) -> anyhow:
             .map_err(|e| anyhow::anyhow!("Failed to write model override: {}", e))?;
     }
 
+    // Write transcription override files if specified
+    // (language, initial prompt, translate are applied for this recording only)
+    if let Some(language) = action.language_override() {
+        let override_file = config::Config::runtime_dir().join("language_override");
+        std::fs::write(&override_file, language)
+            .map_err(|e| anyhow::anyhow!("Failed to write language override: {}", e))?;
+    }
+    if let Some(prompt) = action.initial_prompt_override() {
+        let override_file = config::Config::runtime_dir().join("initial_prompt_override");
+        std::fs::write(&override_file, prompt)
+            .map_err(|e| anyhow::anyhow!("Failed to write initial prompt override: {}", e))?;
+    }
+    if let Some(translate) = action.translate_override() {
+        let override_file = config::Config::runtime_dir().join("translate_override");
+        std::fs::write(&override_file, translate.to_string())
+            .map_err(|e| anyhow::anyhow!("Failed to write translate override: {}", e))?;
+    }
+
+    // Write grammar override file if specified
+    if let Some(grammar_name) = action.grammar() {
+        let grammar_file = config::Config::runtime_dir().join("grammar_override");
+        std::fs::write(&grammar_file, grammar_name)
+            .map_err(|e| anyhow::anyhow!("Failed to write grammar override: {}", e))?;
+    }
+
     // Write profile override file if specified
     if let Some(profile_name) = action.profile() {
-        // Validate that the profile exists in config
-        if config.get_profile(profile_name).is_none() {
-            let available = config.profile_names();
-            if available.is_empty() {
-                eprintln!("Error: Profile '{}' not found.", profile_name);
-                eprintln!();
-                eprintln!("No profiles are configured. Add profiles to your config.toml:");
-                eprintln!();
-                eprintln!("  [profiles.{}]", profile_name);
-                eprintln!("  post_process_command = \"your-command-here\"");
-            } else {
-                eprintln!("Error: Profile '{}' not found.", profile_name);
-                eprintln!();
-                eprintln!("Available profiles: {}", available.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(", "));
-            }
-            std::process::exit(1);
-        }
-
         let profile_file = config::Config::runtime_dir().join("profile_override");
         std::fs::write(&profile_file, profile_name)
             .map_err(|e| anyhow::anyhow!("Failed to write profile override: {}", e))?;
@@ -407,20 +905,7 @@ fn send_record_command(config: &config::Config, action: RecordAction) -> anyhow:
         RecordAction::Stop { .. } => Signal::SIGUSR2,
         RecordAction::Toggle { .. } => {
             // Read current state to determine action
-            let state_file = match config.resolve_state_file() {
-                Some(path) => path,
-                None => {
-                    eprintln!("Error: Cannot toggle recording without state_file configured.");
-                    eprintln!();
-                    eprintln!("Add to your config.toml:");
-                    eprintln!("  state_file = \"auto\"");
-                    eprintln!();
-                    eprintln!("Or use explicit start/stop commands:");
-                    eprintln!("  voxtype record start");
-                    eprintln!("  voxtype record stop");
-                    std::process::exit(1);
-                }
-            };
+            let state_file = toggle_state_file(config)?;
 
             let current_state =
                 std::fs::read_to_string(&state_file).unwrap_or_else(|_| "idle".to_string());
@@ -440,50 +925,268 @@ fn send_record_command(config: &config::Config, action: RecordAction) -> anyhow:
     Ok(())
 }
 
+/// Lines typed on the terminal, which stop a `voxtype listen` recording
+/// (never any when stdin is not a terminal)
+fn spawn_enter_listener() -> tokio::sync::mpsc::UnboundedReceiver<()> {
+    use std::io::IsTerminal;
+
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+    if std::io::stdin().is_terminal() {
+        eprintln!("Listening... press Enter to stop, Ctrl+C to cancel");
+        std::thread::spawn(move || {
+            let mut line = String::new();
+            while std::io::stdin().read_line(&mut line).is_ok_and(|n| n > 0) {
+                line.clear();
+                if tx.send(()).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    rx
+}
+
+/// Record once with the running daemon and wait for the outcome
+async fn listen_with_daemon(
+    config: &config::Config,
+    action: RecordAction,
+    silence_ms: Option<u64>,
+) -> anyhow::Result<ListenOutcome> {
+    use std::time::{Duration, Instant};
+    use tokio::signal::unix::{signal, SignalKind};
+
+    // The daemon takes the request as soon as it gets the start signal
+    const REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+
+    /// Withdraws the request however listening ends; harmless once the daemon took it
+    struct WithdrawOnDrop;
+
+    impl Drop for WithdrawOnDrop {
+        fn drop(&mut self) {
+            ListenRequest::withdraw();
+        }
+    }
+
+    // Fail before writing the request, so it can't wait for the next recording
+    daemon_pid()?;
+    check_record_command(config, &action)?;
+
+    let request = ListenRequest::new(silence_ms);
+    let result_path = request.result_path.clone();
+    let _ = std::fs::remove_file(&result_path);
+    request
+        .write()
+        .map_err(|e| anyhow::anyhow!("Failed to write listen request: {}", e))?;
+    let _withdraw = WithdrawOnDrop;
+    let mut interrupt = signal(SignalKind::interrupt())?;
+    send_record_command(config, action)?;
+
+    let started = Instant::now();
+    let mut enter = spawn_enter_listener();
+    let mut cancelling = false;
+    loop {
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_millis(50)) => {
+                if let Some(outcome) = ListenOutcome::take(&result_path) {
+                    return Ok(outcome);
+                }
+                if ListenRequest::is_pending() && started.elapsed() > REQUEST_TIMEOUT {
+                    anyhow::bail!("The daemon did not start recording");
+                }
+                if !is_daemon_running() {
+                    anyhow::bail!("The daemon stopped before the transcription finished");
+                }
+            }
+            Some(()) = enter.recv() => {
+                let stop = RecordAction::Stop {
+                    type_mode: false,
+                    clipboard: false,
+                    paste: false,
+                };
+                send_record_command(config, stop)?;
+            }
+            _ = interrupt.recv() => {
+                // A second Ctrl+C stops waiting for the daemon to confirm
+                if cancelling {
+                    return Ok(ListenOutcome::Cancelled);
+                }
+                cancelling = true;
+                send_record_command(config, RecordAction::Cancel)?;
+            }
+        }
+    }
+}
+
+/// Record and transcribe in this process, for `voxtype listen` without a daemon
+async fn listen_standalone(
+    config: &config::Config,
+    profile_name: Option<&str>,
+    silence_ms: Option<u64>,
+) -> anyhow::Result<ListenOutcome> {
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+    use tokio::signal::unix::{signal, SignalKind};
+    use voxtype::audio;
+
+    let profile = profile_name.and_then(|name| config.get_profile(name));
+
+    // Load the model first, so recording starts when the prompt appears
+    let mut transcribe_config = config.clone();
+    if config.vocabulary.inject_prompt {
+        if let Some(vocab) = Vocabulary::from_config(&config.vocabulary) {
+            transcribe_config.whisper.initial_prompt = vocab.build_prompt(
+                config.whisper.initial_prompt.as_deref(),
+                config.vocabulary.max_prompt_tokens,
+            );
+        }
+    }
+    let transcriber: Arc<dyn transcribe::Transcriber> =
+        Arc::from(transcribe::create_transcriber(&transcribe_config)?);
+
+    let mut interrupt = signal(SignalKind::interrupt())?;
+    let mut capture = audio::create_capture(&config.audio)?;
+    capture.start().await?;
+
+    let max_duration = Duration::from_secs(config.audio.max_duration_secs as u64);
+    let mut pause = silence_ms.map(|ms| PauseDetector::new(Duration::from_millis(ms)));
+    let started = Instant::now();
+    let mut enter = spawn_enter_listener();
+    loop {
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_millis(50)) => {
+                let level = capture.current_level();
+                if pause.as_mut().is_some_and(|p| p.update(level, Instant::now())) {
+                    break;
+                }
+                if started.elapsed() > max_duration {
+                    let _ = capture.stop().await;
+                    return Ok(ListenOutcome::error(format!(
+                        "Recording timeout ({:.0}s limit)",
+                        max_duration.as_secs_f32()
+                    )));
+                }
+            }
+            Some(()) = enter.recv() => break,
+            _ = interrupt.recv() => {
+                let _ = capture.stop().await;
+                return Ok(ListenOutcome::Cancelled);
+            }
+        }
+    }
+
+    // Same minimum length as the daemon
+    let samples = capture.stop().await?;
+    let duration = Duration::from_secs_f32(samples.len() as f32 / 16000.0);
+    if duration < Duration::from_millis(300) {
+        return Ok(ListenOutcome::Empty);
+    }
+
+    let task = {
+        let transcriber = transcriber.clone();
+        tokio::task::spawn_blocking(move || transcriber.transcribe(&samples))
+    };
+    let text = tokio::select! {
+        result = task => match result? {
+            Ok(text) => text,
+            Err(e) => return Ok(ListenOutcome::error(format!("Transcription failed: {}", e))),
+        },
+        _ = interrupt.recv() => return Ok(ListenOutcome::Cancelled),
+    };
+    if text.is_empty() {
+        return Ok(ListenOutcome::Empty);
+    }
+
+    // Text processing, post-processing and scripts, as the daemon does
+    let language = transcriber.last_language().or_else(|| {
+        if config.whisper.translate {
+            Some("en".to_string())
+        } else {
+            (!config.whisper.language.is_auto() && !config.whisper.language.is_multiple())
+                .then(|| config.whisper.language.primary().to_string())
+        }
+    });
+    let mut processor = TextProcessor::new(&config.text);
+    if config.vocabulary.fuzzy_correction {
+        if let Some(vocab) = Vocabulary::from_config(&config.vocabulary).filter(|v| !v.is_empty()) {
+            processor = processor.with_vocabulary(&vocab, config.vocabulary.fuzzy_threshold);
+        }
+    }
+    if let Some(snippets) = SnippetLibrary::from_config(&config.snippets) {
+        processor = processor.with_snippets(&snippets);
+    }
+    let context = ProcessContext {
+        language: language.as_deref(),
+        normalize: profile.and_then(|p| p.normalize),
+        disfluency: profile.and_then(|p| p.disfluency),
+        code: profile.and_then(|p| p.code),
+        spoken_punctuation: profile.and_then(|p| p.spoken_punctuation),
+    };
+    let metadata = PostProcessMetadata {
+        profile: profile_name,
+        language: language.as_deref(),
+        duration,
+        model: Some(config.model_name()),
+    };
+    let post_processor = PostProcessor::from_config(config, profile);
+    let pipeline = Pipeline::new(&config.pipeline);
+    let processed =
+        actions_text(&pipeline.process_actions(&text, &processor, context, profile_name, 0));
+    let output = if pipeline.is_empty() {
+        match &post_processor {
+            Some(post_processor) => post_processor.process_with(&processed, &metadata).await,
+            None => PostProcessOutput::text(processed),
+        }
+    } else {
+        pipeline
+            .run_rest(
+                &processed,
+                &processor,
+                context,
+                &metadata,
+                post_processor.as_ref(),
+            )
+            .await
+    };
+    if output.discarded {
+        return Ok(ListenOutcome::Empty);
+    }
+
+    let text = match ScriptRunner::from_config(&config.scripts) {
+        Some(scripts) => {
+            let metadata = ScriptMetadata {
+                profile: profile_name,
+                language: language.as_deref(),
+                duration,
+                app: None,
+            };
+            actions_text(&scripts.run(&output.text, &metadata))
+        }
+        None => output.text,
+    };
+    Ok(if text.is_empty() {
+        ListenOutcome::Empty
+    } else {
+        ListenOutcome::Text { text }
+    })
+}
+
 /// Transcribe an audio file
 fn transcribe_file(config: &config::Config, path: &PathBuf) -> anyhow::Result<()> {
-    use hound::WavReader;
+    use voxtype::audio::wav;
 
     println!("Loading audio file: {:?}", path);
 
-    let reader = WavReader::open(path)?;
-    let spec = reader.spec();
+    let (mono_samples, spec) = wav::read_mono(path)?;
 
     println!(
         "Audio format: {} Hz, {} channel(s), {:?}",
         spec.sample_rate, spec.channels, spec.sample_format
     );
 
-    // Convert samples to f32 mono at 16kHz
-    let samples: Vec<f32> = match spec.sample_format {
-        hound::SampleFormat::Int => {
-            let max_val = (1 << (spec.bits_per_sample - 1)) as f32;
-            reader
-                .into_samples::<i32>()
-                .filter_map(|s| s.ok())
-                .map(|s| s as f32 / max_val)
-                .collect()
-        }
-        hound::SampleFormat::Float => reader
-            .into_samples::<f32>()
-            .filter_map(|s| s.ok())
-            .collect(),
-    };
-
-    // Mix to mono if stereo
-    let mono_samples: Vec<f32> = if spec.channels > 1 {
-        samples
-            .chunks(spec.channels as usize)
-            .map(|chunk| chunk.iter().sum::<f32>() / chunk.len() as f32)
-            .collect()
-    } else {
-        samples
-    };
-
     // Resample to 16kHz if needed
-    let final_samples = if spec.sample_rate != 16000 {
+    let final_samples = if spec.sample_rate != wav::TARGET_SAMPLE_RATE {
         println!("Resampling from {} Hz to 16000 Hz...", spec.sample_rate);
-        resample(&mono_samples, spec.sample_rate, 16000)
+        wav::resample(&mono_samples, spec.sample_rate, wav::TARGET_SAMPLE_RATE)
     } else {
         mono_samples
     };
@@ -494,6 +1197,17 @@ fn transcribe_file(config: &config::Config, path: &PathBuf) -> anyhow::Result<()
         final_samples.len() as f32 / 16000.0
     );
 
+    // Bias recognition toward custom vocabulary terms
+    let mut config = config.clone();
+    if config.vocabulary.inject_prompt {
+        if let Some(vocab) = Vocabulary::from_config(&config.vocabulary) {
+            config.whisper.initial_prompt = vocab.build_prompt(
+                config.whisper.initial_prompt.as_deref(),
+                config.vocabulary.max_prompt_tokens,
+            );
+        }
+    }
+
     // Create transcriber and transcribe
     let transcriber = transcribe::create_transcriber(&config)?;
     let text = transcriber.transcribe(&final_samples)?;
@@ -502,33 +1216,6 @@ fn transcribe_file(config: &config::Config, path: &PathBuf) -> anyhow::Result<()
     Ok(())
 }
 
-/// Simple linear resampling
-fn resample(samples: &[f32], from_rate: u32, to_rate: u32) -> Vec<f32> {
-    if from_rate == to_rate {
-        return samples.to_vec();
-    }
-
-    let ratio = to_rate as f64 / from_rate as f64;
-    let new_len = (samples.len() as f64 * ratio).ceil() as usize;
-    let mut output = Vec::with_capacity(new_len);
-
-    for i in 0..new_len {
-        let src_idx = i as f64 / ratio;
-        let idx = src_idx.floor() as usize;
-        let frac = (src_idx - idx as f64) as f32;
-
-        let sample = if idx + 1 < samples.len() {
-            samples[idx] * (1.0 - frac) + samples[idx + 1] * frac
-        } else {
-            samples.get(idx).copied().unwrap_or(0.0)
-        };
-
-        output.push(sample);
-    }
-
-    output
-}
-
 /// JSON output for Waybar consumption.
 ///
 /// STABILITY: These field names MUST NOT change between minor versions (NFR2).
@@ -871,9 +1558,18 @@ fn format_state_json_with_level(
 }
 
 /// Show current configuration
-async fn show_config(config: &config::Config) -> anyhow::Result<()> {
-    println!("Current Configuration\n");
-    println!("=====================\n");
+async fn show_config(config: &config::Config, profile: Option<&str>) -> anyhow::Result<()> {
+    match profile {
+        Some(name) => {
+            let title = format!("Effective Configuration (profile: {})", name);
+            println!("{}\n", title);
+            println!("{}\n", "=".repeat(title.len()));
+        }
+        None => {
+            println!("Current Configuration\n");
+            println!("=====================\n");
+        }
+    }
 
     println!("[hotkey]");
     println!("  key = {:?}", config.hotkey.key);
@@ -940,6 +1636,22 @@ async fn show_config(config: &config::Config) -> anyhow::Result<()> {
         println!("  available models: {}", parakeet_models.join(", "));
     }
 
+    if let Some(ref external_config) = config.external {
+        println!("\n[external]");
+        println!("  command = {:?}", external_config.command);
+        if !external_config.args.is_empty() {
+            println!("  args = {:?}", external_config.args);
+        }
+        if let Some(ref model) = external_config.model {
+            println!("  model = {:?}", model);
+        }
+        println!("  language = {:?}", external_config.language);
+        println!(
+            "  on_demand_loading = {}",
+            external_config.on_demand_loading
+        );
+    }
+
     println!("\n[output]");
     println!("  mode = {:?}", config.output.mode);
     println!(
@@ -960,6 +1672,42 @@ async fn show_config(config: &config::Config) -> anyhow::Result<()> {
     }
     println!("  type_delay_ms = {}", config.output.type_delay_ms);
     println!("  pre_type_delay_ms = {}", config.output.pre_type_delay_ms);
+    println!("  auto_submit = {}", config.output.auto_submit);
+    for (hook, command) in [
+        (
+            "pre_recording_command",
+            &config.output.pre_recording_command,
+        ),
+        ("pre_output_command", &config.output.pre_output_command),
+        ("post_output_command", &config.output.post_output_command),
+    ] {
+        if let Some(command) = command {
+            println!("  {} = {:?}", hook, command);
+        }
+    }
+    if let Some(post_processor) = PostProcessor::from_config(config, None) {
+        println!("  post_process = {}", post_processor.describe());
+    }
+
+    println!("\n[output.smart_join]");
+    println!("  enabled = {}", config.output.smart_join.enabled);
+    println!("  timeout_secs = {}", config.output.smart_join.timeout_secs);
+    println!(
+        "  capitalization = {}",
+        config.output.smart_join.capitalization
+    );
+
+    if config.output.mode.is_sink() {
+        let sink = &config.output.sink;
+        println!("\n[output.sink]");
+        println!("  fifo_path = {:?}", sink.effective_fifo_path());
+        println!("  socket_path = {:?}", sink.effective_socket_path());
+        match sink.webhook_url {
+            Some(ref url) => println!("  webhook_url = {:?}", url),
+            None => println!("  webhook_url = (not set)"),
+        }
+        println!("  webhook_timeout_ms = {}", sink.webhook_timeout_ms);
+    }
 
     println!("\n[output.notification]");
     println!(
@@ -983,6 +1731,132 @@ async fn show_config(config: &config::Config) -> anyhow::Result<()> {
         icons.idle, icons.recording, icons.transcribing, icons.stopped
     );
 
+    println!("\n[text]");
+    println!("  spoken_punctuation = {}", config.text.spoken_punctuation);
+    println!("  replacements = {} rules", config.text.replacements.len());
+    println!("  normalize = {}", config.text.normalize.enabled);
+    println!("  disfluency = {}", config.text.disfluency.enabled);
+    println!("  code = {}", config.text.code.enabled);
+
+    println!("\n[vocabulary]");
+    println!("  enabled = {}", config.vocabulary.enabled);
+    if let Some(path) = config.vocabulary.resolve_path() {
+        let count = Vocabulary::load(&path)
+            .map(|v| v.terms().len())
+            .unwrap_or(0);
+        println!("  file = {:?} ({} terms)", path, count);
+    }
+    println!("  inject_prompt = {}", config.vocabulary.inject_prompt);
+    println!(
+        "  max_prompt_tokens = {}",
+        config.vocabulary.max_prompt_tokens
+    );
+    println!(
+        "  fuzzy_correction = {}",
+        config.vocabulary.fuzzy_correction
+    );
+    println!("  fuzzy_threshold = {}", config.vocabulary.fuzzy_threshold);
+
+    println!("\n[snippets]");
+    println!("  enabled = {}", config.snippets.enabled);
+    if let Some(path) = config.snippets.resolve_path() {
+        match SnippetLibrary::load(&path) {
+            Ok(library) => println!("  file = {:?} ({} snippets)", path, library.len()),
+            Err(e) => println!("  file = {:?} (invalid: {})", path, e),
+        }
+    }
+
+    println!("\n[scripts]");
+    println!("  enabled = {}", config.scripts.enabled);
+    println!("  max_operations = {}", config.scripts.max_operations);
+    println!("  timeout_ms = {}", config.scripts.timeout_ms);
+    if let Some(scripts) = ScriptRunner::from_config(&config.scripts) {
+        let names = scripts.script_names();
+        if names.is_empty() {
+            println!("  dir = {:?} (no scripts)", scripts.dir());
+        } else {
+            println!("  dir = {:?} ({})", scripts.dir(), names.join(", "));
+        }
+    }
+
+    println!("\n[[pipeline]]");
+    if config.pipeline.is_empty() {
+        println!("  (default: [text] stages, then [output.post_process])");
+    }
+    for stage in &config.pipeline {
+        let mut notes = Vec::new();
+        if !stage.enabled {
+            notes.push("disabled".to_string());
+        }
+        if !stage.profiles.is_empty() {
+            notes.push(format!("profiles: {}", stage.profiles.join(", ")));
+        }
+        if stage.protocol == config::PostProcessProtocol::Json {
+            notes.push("JSON protocol".to_string());
+        }
+        if notes.is_empty() {
+            println!("  {}", stage.label());
+        } else {
+            println!("  {} ({})", stage.label(), notes.join("; "));
+        }
+    }
+
+    let mut llm_names: Vec<&String> = config.llm.keys().collect();
+    llm_names.sort();
+    for name in llm_names {
+        let llm = &config.llm[name];
+        println!("\n[llm.{}]", name);
+        println!("  provider = {:?}", llm.provider);
+        println!(
+            "  endpoint = {}",
+            llm.endpoint
+                .as_deref()
+                .unwrap_or(llm.provider.default_endpoint())
+        );
+        println!("  model = {}", llm.model);
+        if let Some(temperature) = llm.temperature {
+            println!("  temperature = {}", temperature);
+        }
+        println!("  timeout_ms = {}", llm.timeout_ms);
+    }
+
+    let mut profile_names = config.profile_names();
+    profile_names.sort();
+    if !profile_names.is_empty() {
+        println!("\n[profiles]");
+        for name in profile_names {
+            match config.profiles[name].extends {
+                Some(ref base) => println!("  {} (extends {})", name, base),
+                None => println!("  {}", name),
+            }
+        }
+    }
+
+    if !config.auto_profile.rules.is_empty() {
+        let auto_profile = &config.auto_profile;
+        println!("\n[auto_profile]");
+        println!("  enabled = {}", auto_profile.enabled);
+        match voxtype::window::create_provider(auto_profile) {
+            Some(provider) => println!(
+                "  provider = {:?} (using {})",
+                auto_profile.provider,
+                provider.name()
+            ),
+            None => println!("  provider = {:?} (not available)", auto_profile.provider),
+        }
+        for rule in &auto_profile.rules {
+            let patterns: Vec<String> = [
+                ("app_id", &rule.app_id),
+                ("class", &rule.class),
+                ("title", &rule.title),
+            ]
+            .iter()
+            .filter_map(|(field, pattern)| pattern.as_ref().map(|p| format!("{} = {:?}", field, p)))
+            .collect();
+            println!("  {} -> {}", patterns.join(", "), rule.profile);
+        }
+    }
+
     if let Some(ref state_file) = config.state_file {
         println!("\n[integration]");
         println!("  state_file = {:?}", state_file);
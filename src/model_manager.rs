@@ -8,7 +8,7 @@
 
 use crate::config::{WhisperMode, WhisperConfig};
 use crate::error::TranscribeError;
-use crate::transcribe::{self, Transcriber};
+use crate::transcribe::{self, Transcriber, TranscriptionOverrides};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -248,8 +248,13 @@ impl ModelManager {
     /// Prepare a model for transcription (called when recording starts)
     ///
     /// For subprocess mode, this spawns the worker early so it can load
-    /// the model while the user is speaking.
-    pub fn prepare_model(&mut self, model: Option<&str>) -> Result<(), TranscribeError> {
+    /// the model while the user is speaking. Per-recording overrides are
+    /// passed to the worker so it doesn't need to be respawned.
+    pub fn prepare_model(
+        &mut self,
+        model: Option<&str>,
+        overrides: &TranscriptionOverrides,
+    ) -> Result<(), TranscribeError> {
         let model_name = model
             .map(|s| s.to_string())
             .unwrap_or_else(|| self.config.model.clone());
@@ -267,7 +272,7 @@ impl ModelManager {
         if self.config.gpu_isolation && self.config.effective_mode() == WhisperMode::Local {
             // Create and prepare subprocess transcriber
             let transcriber = self.create_subprocess_transcriber(&model_name)?;
-            transcriber.prepare();
+            transcriber.prepare_with_overrides(overrides);
             // Store it temporarily for the upcoming transcription
             self.loaded_models.insert(
                 format!("_prepared_{}", model_name),
@@ -0,0 +1,485 @@
+//! Data-driven model registry
+//!
+//! The list of known models is loaded from a built-in manifest
+//! (`model_registry.toml`) merged with the user's `~/.config/voxtype/models.toml`.
+//! The registry drives model name resolution, downloading, and the listing and
+//! validation in `voxtype setup model`, so quantized, distilled and
+//! fine-tuned models can be used by name without code changes.
+//!
+//! Example user entry:
+//!
+//! ```toml
+//! [[models]]
+//! name = "distil-large-v3"
+//! description = "Distilled large-v3, English"
+//! size_mb = 1520
+//! languages = ["en"]
+//! files = [
+//!     { name = "ggml-distil-large-v3.bin", url = "https://huggingface.co/distil-whisper/distil-large-v3-ggml/resolve/main/ggml-distil-large-v3.bin" },
+//! ]
+//! ```
+
+use crate::config::{Config, TranscriptionEngine};
+use crate::setup::download::is_sha256;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Built-in manifest shipped with voxtype
+const BUILTIN_MANIFEST: &str = include_str!("model_registry.toml");
+
+/// User manifest file name in the config directory
+pub const USER_MANIFEST_FILE: &str = "models.toml";
+
+/// Default download location for whisper.cpp models
+const WHISPER_BASE_URL: &str = "https://huggingface.co/ggerganov/whisper.cpp/resolve/main";
+
+/// A single file belonging to a model
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct ModelFile {
+    /// File name (relative to the model's install location)
+    pub name: String,
+
+    /// Download URL (defaults to the model's base_url joined with the name)
+    #[serde(default)]
+    pub url: Option<String>,
+
+    /// Expected size in bytes
+    #[serde(default)]
+    pub size: Option<u64>,
+
+    /// Expected SHA-256 checksum (hex)
+    #[serde(default)]
+    pub sha256: Option<String>,
+}
+
+/// A model known to the registry
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct ModelEntry {
+    /// Model name used in config (e.g., "base.en")
+    pub name: String,
+
+    /// Transcription engine that runs this model
+    #[serde(default)]
+    pub engine: TranscriptionEngine,
+
+    /// Short description shown by `voxtype setup model`
+    #[serde(default)]
+    pub description: String,
+
+    /// Approximate download size in MB
+    #[serde(default)]
+    pub size_mb: u32,
+
+    /// Supported language codes (empty = multilingual)
+    #[serde(default)]
+    pub languages: Vec<String>,
+
+    /// Alternative names that resolve to this model
+    #[serde(default)]
+    pub aliases: Vec<String>,
+
+    /// Resolvable by name but not offered by `voxtype setup model`
+    #[serde(default)]
+    pub hidden: bool,
+
+    /// URL prefix for files without an explicit url
+    #[serde(default)]
+    pub base_url: Option<String>,
+
+    /// Files that make up the model
+    pub files: Vec<ModelFile>,
+}
+
+impl ModelEntry {
+    /// Check whether the model only supports English
+    pub fn is_english_only(&self) -> bool {
+        self.languages.len() == 1 && self.languages[0] == "en"
+    }
+
+    /// Check whether the model can transcribe a language ("auto" always passes)
+    pub fn supports_language(&self, language: &str) -> bool {
+        self.languages.is_empty()
+            || language == "auto"
+            || self.languages.iter().any(|l| l == language)
+    }
+
+    /// Download URL for one of the model's files
+    pub fn file_url(&self, file: &ModelFile) -> String {
+        if let Some(ref url) = file.url {
+            return url.clone();
+        }
+        let base = self.base_url.as_deref().unwrap_or(match self.engine {
+            TranscriptionEngine::Whisper => WHISPER_BASE_URL,
+            TranscriptionEngine::Parakeet | TranscriptionEngine::External => "",
+        });
+        format!("{}/{}", base.trim_end_matches('/'), file.name)
+    }
+
+    /// Path of a file relative to the models directory
+    ///
+    /// Whisper model files live directly in the models directory; other
+    /// engines get a directory named after the model.
+    pub fn relative_path(&self, file: &ModelFile) -> String {
+        match self.engine {
+            TranscriptionEngine::Whisper => file.name.clone(),
+            TranscriptionEngine::Parakeet | TranscriptionEngine::External => {
+                format!("{}/{}", self.name, file.name)
+            }
+        }
+    }
+
+    /// Path the model is loaded from (model file for Whisper, directory otherwise)
+    pub fn install_path(&self, models_dir: &Path) -> PathBuf {
+        match self.engine {
+            TranscriptionEngine::Whisper => models_dir.join(self.primary_file()),
+            TranscriptionEngine::Parakeet | TranscriptionEngine::External => {
+                models_dir.join(&self.name)
+            }
+        }
+    }
+
+    /// File name of the main model file
+    pub fn primary_file(&self) -> &str {
+        self.files
+            .first()
+            .map(|f| f.name.as_str())
+            .unwrap_or(&self.name)
+    }
+
+    /// Check whether all of the model's files are present in the models directory
+    pub fn is_installed(&self, models_dir: &Path) -> bool {
+        !self.files.is_empty()
+            && self
+                .files
+                .iter()
+                .all(|f| models_dir.join(self.relative_path(f)).exists())
+    }
+
+    /// Check whether a name refers to this model
+    fn matches(&self, name: &str) -> bool {
+        self.name == name || self.aliases.iter().any(|a| a == name)
+    }
+}
+
+/// Manifest file layout
+#[derive(Debug, Deserialize)]
+struct Manifest {
+    #[serde(default)]
+    models: Vec<ModelEntry>,
+}
+
+/// Registry of known models
+#[derive(Debug, Clone, Default)]
+pub struct ModelRegistry {
+    models: Vec<ModelEntry>,
+}
+
+impl ModelRegistry {
+    /// Registry containing only the built-in models
+    pub fn builtin() -> Self {
+        Self::parse(BUILTIN_MANIFEST).expect("built-in model registry is valid")
+    }
+
+    /// Built-in models merged with the user's models.toml
+    pub fn load() -> Self {
+        let mut registry = Self::builtin();
+        let Some(path) = Self::user_manifest_path() else {
+            return registry;
+        };
+        if !path.exists() {
+            return registry;
+        }
+
+        match std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|contents| Self::parse(&contents))
+        {
+            Ok(user) => registry.merge(user),
+            Err(e) => tracing::warn!("Ignoring {:?}: {}", path, e),
+        }
+        registry
+    }
+
+    /// Path of the user manifest (~/.config/voxtype/models.toml)
+    pub fn user_manifest_path() -> Option<PathBuf> {
+        Config::config_dir().map(|dir| dir.join(USER_MANIFEST_FILE))
+    }
+
+    /// Parse a manifest
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let manifest: Manifest = toml::from_str(contents).map_err(|e| e.to_string())?;
+        for model in &manifest.models {
+            if model.name.is_empty() {
+                return Err("model entry without a name".to_string());
+            }
+            if model.files.is_empty() {
+                return Err(format!("model '{}' has no files", model.name));
+            }
+            for file in &model.files {
+                if let Some(sha256) = file.sha256.as_deref().filter(|s| !is_sha256(s)) {
+                    return Err(format!(
+                        "model '{}': invalid sha256 for {}: {:?}",
+                        model.name, file.name, sha256
+                    ));
+                }
+            }
+        }
+        Ok(Self {
+            models: manifest.models,
+        })
+    }
+
+    /// Add models from another registry, replacing entries with the same name
+    pub fn merge(&mut self, other: ModelRegistry) {
+        for model in other.models {
+            match self.models.iter_mut().find(|m| m.name == model.name) {
+                Some(existing) => *existing = model,
+                None => self.models.push(model),
+            }
+        }
+    }
+
+    /// Look up a model by name or alias
+    pub fn get(&self, name: &str) -> Option<&ModelEntry> {
+        self.models
+            .iter()
+            .find(|m| m.name == name)
+            .or_else(|| self.models.iter().find(|m| m.matches(name)))
+    }
+
+    /// Models for an engine that `voxtype setup model` offers (excludes hidden)
+    pub fn models(&self, engine: TranscriptionEngine) -> impl Iterator<Item = &ModelEntry> {
+        self.models
+            .iter()
+            .filter(move |m| m.engine == engine && !m.hidden)
+    }
+
+    /// Names of the models offered for an engine
+    pub fn names(&self, engine: TranscriptionEngine) -> Vec<&str> {
+        self.models(engine).map(|m| m.name.as_str()).collect()
+    }
+
+    /// Find the model whose primary file has the given name
+    pub fn find_by_file(&self, file_name: &str) -> Option<&ModelEntry> {
+        self.models
+            .iter()
+            .find(|m| m.engine == TranscriptionEngine::Whisper && m.primary_file() == file_name)
+    }
+
+    /// All models, including hidden ones
+    pub fn all(&self) -> &[ModelEntry] {
+        &self.models
+    }
+}
+
+/// Process-wide registry (built-in plus user models.toml), loaded on first use
+pub fn registry() -> &'static ModelRegistry {
+    static REGISTRY: OnceLock<ModelRegistry> = OnceLock::new();
+    REGISTRY.get_or_init(ModelRegistry::load)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_registry_parses() {
+        let registry = ModelRegistry::builtin();
+        let whisper = registry.names(TranscriptionEngine::Whisper);
+        assert!(whisper.contains(&"base.en"));
+        assert!(whisper.contains(&"large-v3-turbo"));
+        assert!(whisper.contains(&"large-v3-turbo-q5_0"));
+
+        let parakeet = registry.names(TranscriptionEngine::Parakeet);
+        assert!(parakeet.contains(&"parakeet-tdt-0.6b-v3"));
+        assert!(parakeet.contains(&"parakeet-tdt-0.6b-v3-int8"));
+    }
+
+    /// Built-in models whose published SHA-256 is not pinned yet, so they are
+    /// downloaded unverified (remove entries as their hashes are added)
+    const UNPINNED: &[&str] = &[
+        "base.en-q5_1",
+        "small.en-q5_1",
+        "medium.en-q5_0",
+        "large-v3-q5_0",
+        "large-v3-turbo-q5_0",
+        "large-v3-turbo-q8_0",
+        "large-v1",
+        "large-v2",
+        "parakeet-tdt-0.6b-v3",
+        "parakeet-tdt-0.6b-v3-int8",
+    ];
+
+    #[test]
+    fn test_builtin_checksums_pinned() {
+        let registry = ModelRegistry::builtin();
+        for model in registry.all() {
+            let unpinned = UNPINNED.contains(&model.name.as_str());
+            for file in &model.files {
+                match &file.sha256 {
+                    Some(_) => assert!(
+                        !unpinned,
+                        "{} is pinned, remove it from UNPINNED",
+                        model.name
+                    ),
+                    None => assert!(unpinned, "{} has no pinned sha256", file.name),
+                }
+            }
+        }
+
+        // The standard Whisper models all download verified
+        for name in [
+            "tiny",
+            "base.en",
+            "small",
+            "medium.en",
+            "large-v3",
+            "large-v3-turbo",
+        ] {
+            assert!(registry.get(name).unwrap().files[0].sha256.is_some());
+        }
+    }
+
+    #[test]
+    fn test_names_are_unique() {
+        let registry = ModelRegistry::builtin();
+        let mut names: Vec<&str> = registry.all().iter().map(|m| m.name.as_str()).collect();
+        names.sort();
+        let count = names.len();
+        names.dedup();
+        assert_eq!(names.len(), count);
+    }
+
+    #[test]
+    fn test_aliases_and_hidden_models() {
+        let registry = ModelRegistry::builtin();
+        let large = registry.get("large").unwrap();
+        assert_eq!(large.name, "large-v1");
+        assert_eq!(large.primary_file(), "ggml-large-v1.bin");
+        assert!(!registry
+            .names(TranscriptionEngine::Whisper)
+            .contains(&"large-v1"));
+        assert!(registry.get("large-v2").is_some());
+    }
+
+    #[test]
+    fn test_file_urls() {
+        let registry = ModelRegistry::builtin();
+        let base = registry.get("base.en").unwrap();
+        assert_eq!(
+            base.file_url(&base.files[0]),
+            "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-base.en.bin"
+        );
+
+        let parakeet = registry.get("parakeet-tdt-0.6b-v3").unwrap();
+        let vocab = parakeet
+            .files
+            .iter()
+            .find(|f| f.name == "vocab.txt")
+            .unwrap();
+        assert_eq!(
+            parakeet.file_url(vocab),
+            "https://huggingface.co/istupakov/parakeet-tdt-0.6b-v3-onnx/resolve/main/vocab.txt"
+        );
+        assert_eq!(
+            parakeet.relative_path(vocab),
+            "parakeet-tdt-0.6b-v3/vocab.txt"
+        );
+    }
+
+    #[test]
+    fn test_language_support() {
+        let registry = ModelRegistry::builtin();
+        let en = registry.get("base.en").unwrap();
+        assert!(en.is_english_only());
+        assert!(en.supports_language("en"));
+        assert!(en.supports_language("auto"));
+        assert!(!en.supports_language("de"));
+
+        let multi = registry.get("base").unwrap();
+        assert!(!multi.is_english_only());
+        assert!(multi.supports_language("de"));
+    }
+
+    #[test]
+    fn test_user_entries_override_and_extend() {
+        let mut registry = ModelRegistry::builtin();
+        let user = ModelRegistry::parse(
+            r#"
+            [[models]]
+            name = "base.en"
+            description = "Local mirror"
+            files = [{ name = "ggml-base.en.bin", url = "https://mirror.example/ggml-base.en.bin" }]
+
+            [[models]]
+            name = "distil-large-v3"
+            languages = ["en"]
+            files = [{ name = "ggml-distil-large-v3.bin", sha256 = "9a4bd4c2ed6c9ff5f1e5b9b2ac2f3e4c6bd3d4e3b5f1c1b4f0e2d8a7c6b5a493" }]
+            "#,
+        )
+        .unwrap();
+        registry.merge(user);
+
+        let base = registry.get("base.en").unwrap();
+        assert_eq!(base.description, "Local mirror");
+        assert_eq!(
+            base.file_url(&base.files[0]),
+            "https://mirror.example/ggml-base.en.bin"
+        );
+
+        let distil = registry.get("distil-large-v3").unwrap();
+        assert_eq!(distil.engine, TranscriptionEngine::Whisper);
+        assert!(registry
+            .names(TranscriptionEngine::Whisper)
+            .contains(&"distil-large-v3"));
+        assert_eq!(
+            registry
+                .find_by_file("ggml-distil-large-v3.bin")
+                .unwrap()
+                .name,
+            "distil-large-v3"
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_entries_without_files() {
+        assert!(ModelRegistry::parse("[[models]]\nname = \"x\"\nfiles = []\n").is_err());
+        assert!(ModelRegistry::parse("[[models]]\nname = \"x\"\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_sha256() {
+        let entry = |sha256: &str| {
+            format!(
+                "[[models]]\nname = \"x\"\nfiles = [{{ name = \"x.bin\", sha256 = \"{}\" }}]\n",
+                sha256
+            )
+        };
+        assert!(ModelRegistry::parse(&entry("abc")).is_err());
+        assert!(ModelRegistry::parse(&entry(&"z".repeat(64))).is_err());
+        assert!(ModelRegistry::parse(&entry(&"a1".repeat(32))).is_ok());
+    }
+
+    #[test]
+    fn test_is_installed() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = ModelRegistry::builtin();
+
+        let base = registry.get("base.en").unwrap();
+        assert!(!base.is_installed(dir.path()));
+        std::fs::write(dir.path().join("ggml-base.en.bin"), "x").unwrap();
+        assert!(base.is_installed(dir.path()));
+        assert_eq!(
+            base.install_path(dir.path()),
+            dir.path().join("ggml-base.en.bin")
+        );
+
+        let parakeet = registry.get("parakeet-tdt-0.6b-v3-int8").unwrap();
+        assert_eq!(
+            parakeet.install_path(dir.path()),
+            dir.path().join("parakeet-tdt-0.6b-v3-int8")
+        );
+        assert!(!parakeet.is_installed(dir.path()));
+    }
+}
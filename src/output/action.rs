@@ -0,0 +1,395 @@
+//! Output actions: text to type and keys to press
+//!
+//! Voice editing commands ("select all", "press enter") produce a sequence
+//! of actions instead of plain text. Keyboard drivers that can send key
+//! events (wtype, eitype, dotool, ydotool) translate each key combination
+//! to their own key names.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// A single output step
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OutputAction {
+    /// Type text
+    Text(String),
+    /// Press a key combination
+    Key(KeyCombo),
+}
+
+impl OutputAction {
+    /// Whether this action presses a key
+    pub fn is_key(&self) -> bool {
+        matches!(self, OutputAction::Key(_))
+    }
+}
+
+/// Concatenated text of all text actions (keys are skipped)
+pub fn actions_text(actions: &[OutputAction]) -> String {
+    actions
+        .iter()
+        .filter_map(|a| match a {
+            OutputAction::Text(text) => Some(text.as_str()),
+            OutputAction::Key(_) => None,
+        })
+        .collect()
+}
+
+/// Modifier key held during a key press
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Modifier {
+    Ctrl,
+    Shift,
+    Alt,
+    Super,
+}
+
+impl Modifier {
+    /// Parse a modifier name ("ctrl", "control", "super", ...)
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "ctrl" | "control" => Some(Modifier::Ctrl),
+            "shift" => Some(Modifier::Shift),
+            "alt" => Some(Modifier::Alt),
+            "super" | "meta" | "logo" | "win" | "windows" => Some(Modifier::Super),
+            _ => None,
+        }
+    }
+
+    /// Modifier name for wtype -M/-m
+    pub fn wtype_name(self) -> &'static str {
+        match self {
+            Modifier::Ctrl => "ctrl",
+            Modifier::Shift => "shift",
+            Modifier::Alt => "alt",
+            Modifier::Super => "logo",
+        }
+    }
+
+    /// Modifier name for eitype -M and dotool key chords
+    pub fn name(self) -> &'static str {
+        match self {
+            Modifier::Ctrl => "ctrl",
+            Modifier::Shift => "shift",
+            Modifier::Alt => "alt",
+            Modifier::Super => "super",
+        }
+    }
+
+    /// Linux evdev key code (left-hand key) for ydotool
+    pub fn evdev_code(self) -> u16 {
+        match self {
+            Modifier::Ctrl => 29,
+            Modifier::Shift => 42,
+            Modifier::Alt => 56,
+            Modifier::Super => 125,
+        }
+    }
+}
+
+/// A non-modifier key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Enter,
+    Tab,
+    Escape,
+    Backspace,
+    Delete,
+    Space,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    /// Letter a-z or digit 0-9
+    Char(char),
+    /// Function key F1-F12
+    F(u8),
+}
+
+impl Key {
+    /// Parse a key name ("enter", "page_up", "a", "f5")
+    pub fn parse(name: &str) -> Option<Self> {
+        let name = name.to_lowercase().replace(['_', '-', ' '], "");
+        let key = match name.as_str() {
+            "enter" | "return" => Key::Enter,
+            "tab" => Key::Tab,
+            "escape" | "esc" => Key::Escape,
+            "backspace" => Key::Backspace,
+            "delete" | "del" => Key::Delete,
+            "space" => Key::Space,
+            "left" => Key::Left,
+            "right" => Key::Right,
+            "up" => Key::Up,
+            "down" => Key::Down,
+            "home" => Key::Home,
+            "end" => Key::End,
+            "pageup" => Key::PageUp,
+            "pagedown" => Key::PageDown,
+            _ => {
+                let mut chars = name.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) if c.is_ascii_alphanumeric() => Key::Char(c),
+                    (Some('f'), Some(_)) => match name[1..].parse::<u8>() {
+                        Ok(n @ 1..=12) => Key::F(n),
+                        _ => return None,
+                    },
+                    _ => return None,
+                }
+            }
+        };
+        Some(key)
+    }
+
+    /// XKB keysym name (used by wtype -k)
+    pub fn keysym(self) -> String {
+        match self {
+            Key::Enter => "Return".to_string(),
+            Key::Tab => "Tab".to_string(),
+            Key::Escape => "Escape".to_string(),
+            Key::Backspace => "BackSpace".to_string(),
+            Key::Delete => "Delete".to_string(),
+            Key::Space => "space".to_string(),
+            Key::Left => "Left".to_string(),
+            Key::Right => "Right".to_string(),
+            Key::Up => "Up".to_string(),
+            Key::Down => "Down".to_string(),
+            Key::Home => "Home".to_string(),
+            Key::End => "End".to_string(),
+            Key::PageUp => "Prior".to_string(),
+            Key::PageDown => "Next".to_string(),
+            Key::Char(c) => c.to_string(),
+            Key::F(n) => format!("F{}", n),
+        }
+    }
+
+    /// Linux input key name without KEY_ prefix, lowercase (used by dotool)
+    pub fn linux_name(self) -> String {
+        match self {
+            Key::Enter => "enter".to_string(),
+            Key::Tab => "tab".to_string(),
+            Key::Escape => "esc".to_string(),
+            Key::Backspace => "backspace".to_string(),
+            Key::Delete => "delete".to_string(),
+            Key::Space => "space".to_string(),
+            Key::Left => "left".to_string(),
+            Key::Right => "right".to_string(),
+            Key::Up => "up".to_string(),
+            Key::Down => "down".to_string(),
+            Key::Home => "home".to_string(),
+            Key::End => "end".to_string(),
+            Key::PageUp => "pageup".to_string(),
+            Key::PageDown => "pagedown".to_string(),
+            Key::Char(c) => c.to_string(),
+            Key::F(n) => format!("f{}", n),
+        }
+    }
+
+    /// Linux evdev key code (used by ydotool)
+    ///
+    /// Letters and digits map to the key at their US-layout position, so on
+    /// other layouts (AZERTY, QWERTZ, ...) a shortcut like "control z" presses
+    /// whatever that key produces there. The uinput driver resolves letters
+    /// through the XKB layout instead.
+    pub fn evdev_code(self) -> u16 {
+        match self {
+            Key::Enter => 28,
+            Key::Tab => 15,
+            Key::Escape => 1,
+            Key::Backspace => 14,
+            Key::Delete => 111,
+            Key::Space => 57,
+            Key::Left => 105,
+            Key::Right => 106,
+            Key::Up => 103,
+            Key::Down => 108,
+            Key::Home => 102,
+            Key::End => 107,
+            Key::PageUp => 104,
+            Key::PageDown => 109,
+            Key::Char(c) => char_evdev_code(c),
+            Key::F(n @ 1..=10) => 58 + n as u16,
+            Key::F(11) => 87,
+            Key::F(_) => 88,
+        }
+    }
+}
+
+/// evdev key code for a letter or digit, by its US-layout position
+fn char_evdev_code(c: char) -> u16 {
+    const ROW_Q: &str = "qwertyuiop";
+    const ROW_A: &str = "asdfghjkl";
+    const ROW_Z: &str = "zxcvbnm";
+    if let Some(i) = ROW_Q.find(c) {
+        16 + i as u16
+    } else if let Some(i) = ROW_A.find(c) {
+        30 + i as u16
+    } else if let Some(i) = ROW_Z.find(c) {
+        44 + i as u16
+    } else if c == '0' {
+        11
+    } else {
+        // '1'..='9' map to codes 2..=10
+        c.to_digit(10).map(|d| d as u16 + 1).unwrap_or(0)
+    }
+}
+
+/// A key with optional modifiers, written like "ctrl+shift+z"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyCombo {
+    pub modifiers: Vec<Modifier>,
+    pub key: Key,
+}
+
+impl KeyCombo {
+    /// A key without modifiers
+    pub fn key(key: Key) -> Self {
+        Self {
+            modifiers: Vec::new(),
+            key,
+        }
+    }
+
+    /// A key with modifiers
+    pub fn with(modifiers: &[Modifier], key: Key) -> Self {
+        let mut modifiers = modifiers.to_vec();
+        modifiers.sort();
+        modifiers.dedup();
+        Self { modifiers, key }
+    }
+
+    /// Key chord for dotool's `key` command (e.g., "ctrl+shift+z")
+    pub fn dotool_chord(&self) -> String {
+        let mut parts: Vec<String> = self
+            .modifiers
+            .iter()
+            .map(|m| m.name().to_string())
+            .collect();
+        parts.push(self.key.linux_name());
+        parts.join("+")
+    }
+
+    /// Arguments for `ydotool key`: modifiers down, key down/up, modifiers up
+    pub fn ydotool_args(&self) -> Vec<String> {
+        let mut args: Vec<String> = self
+            .modifiers
+            .iter()
+            .map(|m| format!("{}:1", m.evdev_code()))
+            .collect();
+        let code = self.key.evdev_code();
+        args.push(format!("{}:1", code));
+        args.push(format!("{}:0", code));
+        args.extend(
+            self.modifiers
+                .iter()
+                .rev()
+                .map(|m| format!("{}:0", m.evdev_code())),
+        );
+        args
+    }
+}
+
+impl FromStr for KeyCombo {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<String> = s.split('+').map(|p| p.trim().to_lowercase()).collect();
+        let (key_name, modifier_names) = parts
+            .split_last()
+            .ok_or_else(|| format!("Empty key combination: {:?}", s))?;
+
+        let modifiers = modifier_names
+            .iter()
+            .map(|name| {
+                Modifier::parse(name)
+                    .ok_or_else(|| format!("Unknown modifier {:?} in {:?}", name, s))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let key =
+            Key::parse(key_name).ok_or_else(|| format!("Unknown key {:?} in {:?}", key_name, s))?;
+
+        Ok(Self::with(&modifiers, key))
+    }
+}
+
+impl fmt::Display for KeyCombo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.dotool_chord())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_key_combo() {
+        let combo: KeyCombo = "Ctrl+Shift+Z".parse().unwrap();
+        assert_eq!(combo.modifiers, vec![Modifier::Ctrl, Modifier::Shift]);
+        assert_eq!(combo.key, Key::Char('z'));
+
+        let combo: KeyCombo = "enter".parse().unwrap();
+        assert!(combo.modifiers.is_empty());
+        assert_eq!(combo.key, Key::Enter);
+
+        assert_eq!("super+f5".parse::<KeyCombo>().unwrap().key, Key::F(5));
+        assert_eq!("page_up".parse::<KeyCombo>().unwrap().key, Key::PageUp);
+    }
+
+    #[test]
+    fn test_parse_key_combo_errors() {
+        assert!("hyper+a".parse::<KeyCombo>().is_err());
+        assert!("ctrl+nokey".parse::<KeyCombo>().is_err());
+        assert!("f13".parse::<KeyCombo>().is_err());
+        assert!("".parse::<KeyCombo>().is_err());
+    }
+
+    #[test]
+    fn test_dotool_chord() {
+        let combo: KeyCombo = "shift+ctrl+z".parse().unwrap();
+        assert_eq!(combo.dotool_chord(), "ctrl+shift+z");
+        assert_eq!(KeyCombo::key(Key::Escape).dotool_chord(), "esc");
+    }
+
+    #[test]
+    fn test_ydotool_args() {
+        let combo: KeyCombo = "ctrl+a".parse().unwrap();
+        assert_eq!(combo.ydotool_args(), vec!["29:1", "30:1", "30:0", "29:0"]);
+        assert_eq!(
+            KeyCombo::key(Key::Enter).ydotool_args(),
+            vec!["28:1", "28:0"]
+        );
+    }
+
+    #[test]
+    fn test_evdev_codes() {
+        assert_eq!(Key::Char('q').evdev_code(), 16);
+        assert_eq!(Key::Char('z').evdev_code(), 44);
+        assert_eq!(Key::Char('m').evdev_code(), 50);
+        assert_eq!(Key::Char('1').evdev_code(), 2);
+        assert_eq!(Key::Char('0').evdev_code(), 11);
+        assert_eq!(Key::F(1).evdev_code(), 59);
+        assert_eq!(Key::F(12).evdev_code(), 88);
+    }
+
+    #[test]
+    fn test_keysym() {
+        assert_eq!(Key::Backspace.keysym(), "BackSpace");
+        assert_eq!(Key::PageDown.keysym(), "Next");
+        assert_eq!(Key::Char('a').keysym(), "a");
+    }
+
+    #[test]
+    fn test_actions_text() {
+        let actions = vec![
+            OutputAction::Text("Hello".to_string()),
+            OutputAction::Key(KeyCombo::key(Key::Enter)),
+            OutputAction::Text(" world".to_string()),
+        ];
+        assert_eq!(actions_text(&actions), "Hello world");
+        assert!(actions[1].is_key());
+    }
+}
@@ -0,0 +1,308 @@
+//! Clipboard snapshots for paste mode
+//!
+//! Paste mode puts the transcription on the clipboard. With
+//! `paste_restore_clipboard`, the previous contents are read first and put
+//! back after the paste.
+//!
+//! wl-copy and xclip serve one type at a time, so the snapshot keeps the most
+//! useful one: an image if there was one, else plain text (with the usual
+//! text aliases), else the owner's first type.
+//!
+//! Requires wl-clipboard (Wayland) or xclip (X11).
+
+use crate::error::OutputError;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+/// Maximum time to wait for the clipboard owner to send one type
+const READ_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// X11 selection targets that describe the selection rather than hold data
+const META_TARGETS: &[&str] = &["TARGETS", "TIMESTAMP", "MULTIPLE", "SAVE_TARGETS", "DELETE"];
+
+/// Plain text types, most preferred first
+const TEXT_TYPES: &[&str] = &[
+    "text/plain;charset=utf-8",
+    "UTF8_STRING",
+    "text/plain",
+    "STRING",
+    "TEXT",
+];
+
+/// Command-line clipboard tool
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipboardTool {
+    /// wl-copy and wl-paste (Wayland)
+    WlClipboard,
+    /// xclip (X11)
+    Xclip,
+}
+
+impl ClipboardTool {
+    /// Tool for the running session: wl-clipboard on Wayland, xclip on X11
+    pub fn detect() -> Option<Self> {
+        if std::env::var("WAYLAND_DISPLAY").is_ok() {
+            Some(Self::WlClipboard)
+        } else if std::env::var("DISPLAY").is_ok() {
+            Some(Self::Xclip)
+        } else {
+            None
+        }
+    }
+
+    /// Program that writes the clipboard
+    pub fn copy_program(self) -> &'static str {
+        match self {
+            Self::WlClipboard => "wl-copy",
+            Self::Xclip => "xclip",
+        }
+    }
+
+    /// Put data on the clipboard, as `mime` or (None) as text
+    pub async fn copy(self, mime: Option<&str>, data: &[u8]) -> Result<(), OutputError> {
+        let mut command = Command::new(self.copy_program());
+        match self {
+            Self::WlClipboard => {
+                if let Some(mime) = mime {
+                    command.args(["--type", mime]);
+                }
+            }
+            Self::Xclip => {
+                command.args(["-selection", "clipboard"]);
+                if let Some(mime) = mime {
+                    command.args(["-t", mime]);
+                }
+            }
+        }
+
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::NotFound => match self {
+                    Self::WlClipboard => OutputError::WlCopyNotFound,
+                    Self::Xclip => OutputError::XclipNotFound,
+                },
+                _ => OutputError::InjectionFailed(e.to_string()),
+            })?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(data)
+                .await
+                .map_err(|e| OutputError::InjectionFailed(e.to_string()))?;
+            // Close stdin to signal EOF
+            drop(stdin);
+        }
+
+        let status = child
+            .wait()
+            .await
+            .map_err(|e| OutputError::InjectionFailed(e.to_string()))?;
+        if !status.success() {
+            return Err(OutputError::InjectionFailed(format!(
+                "{} exited with error",
+                self.copy_program()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Empty the clipboard
+    async fn clear(self) -> Result<(), OutputError> {
+        match self {
+            Self::WlClipboard => {
+                let status = Command::new("wl-copy")
+                    .arg("--clear")
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .status()
+                    .await
+                    .map_err(|e| OutputError::InjectionFailed(e.to_string()))?;
+                if !status.success() {
+                    return Err(OutputError::InjectionFailed(
+                        "wl-copy --clear exited with error".to_string(),
+                    ));
+                }
+                Ok(())
+            }
+            // xclip cannot give up the selection, an empty string is the closest
+            Self::Xclip => self.copy(None, b"").await,
+        }
+    }
+
+    /// Types offered by the clipboard owner; empty if the clipboard is empty
+    async fn list_types(self) -> Vec<String> {
+        let output = match self {
+            Self::WlClipboard => read_output("wl-paste", &["--list-types"]).await,
+            Self::Xclip => {
+                read_output("xclip", &["-selection", "clipboard", "-o", "-t", "TARGETS"]).await
+            }
+        };
+        output
+            .map(|stdout| parse_types(&String::from_utf8_lossy(&stdout)))
+            .unwrap_or_default()
+    }
+
+    /// Clipboard contents as `mime`, or (None) as text
+    async fn read(self, mime: Option<&str>) -> Option<Vec<u8>> {
+        match self {
+            Self::WlClipboard => {
+                let mut args = vec!["--no-newline"];
+                if let Some(mime) = mime {
+                    args.extend(["--type", mime]);
+                }
+                read_output("wl-paste", &args).await
+            }
+            Self::Xclip => {
+                let mut args = vec!["-selection", "clipboard", "-o"];
+                if let Some(mime) = mime {
+                    args.extend(["-t", mime]);
+                }
+                read_output("xclip", &args).await
+            }
+        }
+    }
+}
+
+/// Run a clipboard reader, returning its stdout if it succeeds in time
+async fn read_output(program: &str, args: &[&str]) -> Option<Vec<u8>> {
+    let output = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    match tokio::time::timeout(READ_TIMEOUT, output).await {
+        Ok(Ok(output)) if output.status.success() => Some(output.stdout),
+        Ok(Ok(_)) => None,
+        Ok(Err(e)) => {
+            tracing::debug!("{} unavailable: {}", program, e);
+            None
+        }
+        Err(_) => {
+            tracing::debug!("{} {} timed out", program, args.join(" "));
+            None
+        }
+    }
+}
+
+/// Parse a type list, one per line, without X11 meta targets or duplicates
+fn parse_types(list: &str) -> Vec<String> {
+    let mut types: Vec<String> = Vec::new();
+    for mime in list.lines().map(str::trim) {
+        if !mime.is_empty() && !META_TARGETS.contains(&mime) && !types.iter().any(|t| t == mime) {
+            types.push(mime.to_string());
+        }
+    }
+    types
+}
+
+/// Index of the type to restore: an image, else plain text, else the first
+fn preferred_type<S: AsRef<str>>(types: &[S]) -> Option<usize> {
+    types
+        .iter()
+        .position(|t| t.as_ref().starts_with("image/"))
+        .or_else(|| {
+            TEXT_TYPES
+                .iter()
+                .find_map(|text| types.iter().position(|t| t.as_ref() == *text))
+        })
+        .or((!types.is_empty()).then_some(0))
+}
+
+/// Clipboard contents saved before a paste
+#[derive(Debug)]
+pub struct ClipboardSnapshot {
+    tool: ClipboardTool,
+    /// (type, data) to restore; None if the clipboard was empty or unreadable
+    contents: Option<(String, Vec<u8>)>,
+    /// Whether the clipboard was empty
+    empty: bool,
+}
+
+impl ClipboardSnapshot {
+    /// Read the clipboard in the type to restore
+    pub async fn take(tool: ClipboardTool) -> Self {
+        let types = tool.list_types().await;
+        let contents = match preferred_type(&types) {
+            Some(index) => {
+                let mime = types[index].clone();
+                match tool.read(Some(&mime)).await {
+                    Some(data) => {
+                        tracing::debug!("Clipboard snapshot: {} ({} bytes)", mime, data.len());
+                        Some((mime, data))
+                    }
+                    None => {
+                        tracing::debug!("Could not read clipboard as {}", mime);
+                        None
+                    }
+                }
+            }
+            None => None,
+        };
+        Self {
+            tool,
+            contents,
+            empty: types.is_empty(),
+        }
+    }
+
+    /// Put the saved contents back, unless something other than `pasted`
+    /// was copied in the meantime
+    pub async fn restore(self, pasted: &str) -> Result<(), OutputError> {
+        if let Some(current) = self.tool.read(None).await {
+            if current != pasted.as_bytes() {
+                tracing::debug!("Clipboard changed since pasting, not restoring it");
+                return Ok(());
+            }
+        }
+
+        match self.contents {
+            Some((mime, data)) => {
+                let mime = (!TEXT_TYPES.contains(&mime.as_str())).then_some(mime.as_str());
+                self.tool.copy(mime, &data).await?;
+                tracing::debug!("Clipboard restored ({})", mime.unwrap_or("text"));
+            }
+            None if self.empty => {
+                self.tool.clear().await?;
+                tracing::debug!("Clipboard cleared (it was empty before pasting)");
+            }
+            None => tracing::debug!("Clipboard could not be saved, not restoring it"),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_types() {
+        let x11 = "TIMESTAMP\nTARGETS\nMULTIPLE\nSAVE_TARGETS\ntext/html\nUTF8_STRING\nSTRING\nUTF8_STRING\n";
+        assert_eq!(parse_types(x11), ["text/html", "UTF8_STRING", "STRING"]);
+
+        let wayland = "image/png\ntext/uri-list\n";
+        assert_eq!(parse_types(wayland), ["image/png", "text/uri-list"]);
+        assert!(parse_types("").is_empty());
+    }
+
+    #[test]
+    fn test_preferred_type() {
+        // A browser offers HTML first, but plain text pastes everywhere
+        let types = ["text/html", "text/plain", "text/plain;charset=utf-8"];
+        assert_eq!(preferred_type(&types), Some(2));
+        // Images win over their file names
+        let types = ["text/uri-list", "UTF8_STRING", "image/png"];
+        assert_eq!(preferred_type(&types), Some(2));
+        // Anything else: the owner's first type
+        let types = ["application/x-kde-cutselection", "application/x-qt-image"];
+        assert_eq!(preferred_type(&types), Some(0));
+        assert_eq!(preferred_type::<&str>(&[]), None);
+    }
+}
@@ -8,6 +8,7 @@
 //! - User in 'input' group for uinput access
 //! - DOTOOL_XKB_LAYOUT set for non-US layouts
 
+use super::action::{actions_text, OutputAction};
 use super::TextOutput;
 use crate::error::OutputError;
 use std::process::Stdio;
@@ -98,30 +99,44 @@ impl DotoolOutput {
 
         commands
     }
-}
 
-#[async_trait::async_trait]
-impl TextOutput for DotoolOutput {
-    async fn output(&self, text: &str) -> Result<(), OutputError> {
-        if text.is_empty() {
-            return Ok(());
+    /// Build the dotool command string for a sequence of text and key actions
+    /// Each line of text gets its own `type` command, newlines become Enter
+    fn build_action_commands(&self, actions: &[OutputAction]) -> String {
+        let mut commands = String::new();
+
+        if self.type_delay_ms > 0 {
+            commands.push_str(&format!("typedelay {}\n", self.type_delay_ms));
+            commands.push_str(&format!("typehold {}\n", self.type_delay_ms));
         }
 
-        // Pre-typing delay if configured
-        if self.pre_type_delay_ms > 0 {
-            tracing::debug!(
-                "dotool: sleeping {}ms before typing",
-                self.pre_type_delay_ms
-            );
-            tokio::time::sleep(Duration::from_millis(self.pre_type_delay_ms as u64)).await;
+        for action in actions {
+            match action {
+                OutputAction::Text(text) => {
+                    for (i, line) in text.split('\n').enumerate() {
+                        if i > 0 {
+                            commands.push_str("key enter\n");
+                        }
+                        if !line.is_empty() {
+                            commands.push_str(&format!("type {}\n", line));
+                        }
+                    }
+                }
+                OutputAction::Key(combo) => {
+                    commands.push_str(&format!("key {}\n", combo.dotool_chord()));
+                }
+            }
         }
 
-        let commands = self.build_commands(text);
-        tracing::debug!(
-            "dotool: sending commands for text: \"{}\"",
-            text.chars().take(20).collect::<String>()
-        );
+        if self.auto_submit {
+            commands.push_str("key enter\n");
+        }
+
+        commands
+    }
 
+    /// Run dotool with the given commands on stdin
+    async fn run_commands(&self, commands: &str) -> Result<(), OutputError> {
         // Spawn dotool with stdin pipe
         let mut cmd = Command::new("dotool");
         cmd.stdin(Stdio::piped())
@@ -174,6 +189,33 @@ impl TextOutput for DotoolOutput {
             )));
         }
 
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl TextOutput for DotoolOutput {
+    async fn output(&self, text: &str) -> Result<(), OutputError> {
+        if text.is_empty() {
+            return Ok(());
+        }
+
+        // Pre-typing delay if configured
+        if self.pre_type_delay_ms > 0 {
+            tracing::debug!(
+                "dotool: sleeping {}ms before typing",
+                self.pre_type_delay_ms
+            );
+            tokio::time::sleep(Duration::from_millis(self.pre_type_delay_ms as u64)).await;
+        }
+
+        let commands = self.build_commands(text);
+        tracing::debug!(
+            "dotool: sending commands for text: \"{}\"",
+            text.chars().take(20).collect::<String>()
+        );
+        self.run_commands(&commands).await?;
+
         tracing::info!("Text typed via dotool ({} chars)", text.len());
 
         // Send notification if enabled
@@ -184,6 +226,29 @@ impl TextOutput for DotoolOutput {
         Ok(())
     }
 
+    async fn output_actions(&self, actions: &[OutputAction]) -> Result<(), OutputError> {
+        if actions.is_empty() {
+            return Ok(());
+        }
+
+        // Pre-typing delay if configured
+        if self.pre_type_delay_ms > 0 {
+            tokio::time::sleep(Duration::from_millis(self.pre_type_delay_ms as u64)).await;
+        }
+
+        let commands = self.build_action_commands(actions);
+        tracing::debug!("dotool: sending {} actions", actions.len());
+        self.run_commands(&commands).await?;
+
+        // Send notification if enabled
+        let text = actions_text(actions);
+        if self.notify && !text.is_empty() {
+            self.send_notification(&text).await;
+        }
+
+        Ok(())
+    }
+
     async fn is_available(&self) -> bool {
         // Check if dotool exists in PATH
         Command::new("which")
@@ -199,6 +264,10 @@ impl TextOutput for DotoolOutput {
     fn name(&self) -> &'static str {
         "dotool"
     }
+
+    fn types_text(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
@@ -238,4 +307,20 @@ mod tests {
         assert!(cmds.contains("type Test"));
         assert!(cmds.contains("key enter"));
     }
+
+    #[test]
+    fn test_build_action_commands() {
+        use crate::output::action::KeyCombo;
+
+        let output = DotoolOutput::new(0, 0, false, false, None, None);
+        let actions = vec![
+            OutputAction::Text("Hello\nworld".to_string()),
+            OutputAction::Key("ctrl+a".parse::<KeyCombo>().unwrap()),
+            OutputAction::Key("backspace".parse::<KeyCombo>().unwrap()),
+        ];
+        assert_eq!(
+            output.build_action_commands(&actions),
+            "type Hello\nkey enter\ntype world\nkey ctrl+a\nkey backspace\n"
+        );
+    }
 }
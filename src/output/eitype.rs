@@ -8,6 +8,7 @@
 //! - eitype installed
 //! - Compositor with EI protocol support (GNOME, KDE, Sway with libei)
 
+use super::action::{KeyCombo, OutputAction};
 use super::TextOutput;
 use crate::error::OutputError;
 use std::process::Stdio;
@@ -131,6 +132,49 @@ impl EitypeOutput {
         Ok(())
     }
 
+    /// Press a key combination using eitype
+    async fn send_key(&self, combo: &KeyCombo) -> Result<(), OutputError> {
+        let mut cmd = Command::new("eitype");
+        for m in &combo.modifiers {
+            cmd.arg("-M").arg(m.name());
+        }
+        cmd.arg("-k").arg(combo.key.keysym().to_lowercase());
+
+        tracing::debug!("Running: eitype key {}", combo);
+
+        let output = cmd
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .output()
+            .await
+            .map_err(|e| {
+                if e.kind() == std::io::ErrorKind::NotFound {
+                    OutputError::EitypeNotFound
+                } else {
+                    OutputError::InjectionFailed(e.to_string())
+                }
+            })?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(OutputError::InjectionFailed(format!(
+                "eitype key {} failed: {}",
+                combo, stderr
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Type text, converting newlines to Shift+Enter if configured
+    async fn type_segment(&self, text: &str) -> Result<(), OutputError> {
+        if self.shift_enter_newlines && text.contains('\n') {
+            self.output_with_shift_enter_newlines(text).await
+        } else {
+            self.type_text(text).await
+        }
+    }
+
     /// Output text with newlines converted to Shift+Enter
     async fn output_with_shift_enter_newlines(&self, text: &str) -> Result<(), OutputError> {
         let segments: Vec<&str> = text.split('\n').collect();
@@ -159,10 +203,22 @@ impl TextOutput for EitypeOutput {
         }
 
         // If shift_enter_newlines is enabled, process text with Shift+Enter for newlines
-        if self.shift_enter_newlines && text.contains('\n') {
-            self.output_with_shift_enter_newlines(text).await?;
-        } else {
-            self.type_text(text).await?;
+        self.type_segment(text).await?;
+
+        // Send Enter key if auto_submit is configured
+        if self.auto_submit {
+            self.send_enter().await?;
+        }
+
+        Ok(())
+    }
+
+    async fn output_actions(&self, actions: &[OutputAction]) -> Result<(), OutputError> {
+        for action in actions {
+            match action {
+                OutputAction::Text(text) => self.type_segment(text).await?,
+                OutputAction::Key(combo) => self.send_key(combo).await?,
+            }
         }
 
         // Send Enter key if auto_submit is configured
@@ -188,6 +244,10 @@ impl TextOutput for EitypeOutput {
     fn name(&self) -> &'static str {
         "eitype"
     }
+
+    fn types_text(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
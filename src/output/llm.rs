@@ -0,0 +1,242 @@
+//! LLM post-processing over HTTP
+//!
+//! Sends the transcription to a chat completion API with a system prompt and
+//! returns the model's reply, without a curl script in between. Two API
+//! flavors are supported:
+//! - OpenAI-compatible `/chat/completions` (OpenAI, LM Studio, llama.cpp
+//!   server, vLLM, OpenRouter, ...)
+//! - Ollama `/api/chat`
+//!
+//! # Example Configuration
+//!
+//! ```toml
+//! [llm.cleanup]
+//! provider = "ollama"
+//! model = "llama3.2:1b"
+//! temperature = 0.2
+//!
+//! [output]
+//! post_process_llm = "cleanup"
+//! ```
+//!
+//! Requests are blocking (ureq), so [`super::post_process::PostProcessor`]
+//! runs them on the blocking thread pool.
+
+use super::post_process::PostProcessError;
+use crate::config::{LlmConfig, LlmProvider};
+use regex::Regex;
+use std::sync::OnceLock;
+use std::time::Duration;
+use ureq::serde_json::{json, Value};
+
+/// System prompt used when none is configured
+pub const DEFAULT_SYSTEM_PROMPT: &str = "You clean up dictated text. Remove filler words \
+(um, uh, like), fix grammar, spelling and punctuation, and keep the meaning and language. \
+Output ONLY the cleaned text - no quotes, no emojis, no explanations.";
+
+/// Client for one configured LLM
+#[derive(Debug, Clone)]
+pub struct LlmClient {
+    provider: LlmProvider,
+    /// Full request URL
+    url: String,
+    model: String,
+    system_prompt: String,
+    temperature: Option<f32>,
+    api_key: Option<String>,
+}
+
+impl LlmClient {
+    pub fn new(config: &LlmConfig) -> Self {
+        let endpoint = config
+            .endpoint
+            .as_deref()
+            .unwrap_or(config.provider.default_endpoint())
+            .trim_end_matches('/');
+        let url = match config.provider {
+            LlmProvider::OpenAi => format!("{}/chat/completions", endpoint),
+            LlmProvider::Ollama => format!("{}/api/chat", endpoint),
+        };
+
+        Self {
+            provider: config.provider,
+            url,
+            model: config.model.clone(),
+            system_prompt: config
+                .system_prompt
+                .clone()
+                .unwrap_or_else(|| DEFAULT_SYSTEM_PROMPT.to_string()),
+            temperature: config.temperature,
+            api_key: config
+                .api_key
+                .clone()
+                .filter(|key| !key.is_empty())
+                .or_else(|| std::env::var("VOXTYPE_LLM_API_KEY").ok()),
+        }
+    }
+
+    /// Model name, for logging
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// Send the text to the model and return its cleaned-up reply (blocking)
+    pub fn complete(&self, text: &str, timeout: Duration) -> Result<String, PostProcessError> {
+        let mut request = ureq::post(&self.url).timeout(timeout);
+        if let Some(ref key) = self.api_key {
+            request = request.set("Authorization", &format!("Bearer {}", key));
+        }
+
+        let response = request
+            .send_json(self.request_body(text))
+            .map_err(|e| match e {
+                ureq::Error::Status(code, resp) => {
+                    let body = resp.into_string().unwrap_or_default();
+                    PostProcessError::RequestFailed(format!(
+                        "server returned {}: {}",
+                        code,
+                        body.trim()
+                    ))
+                }
+                ureq::Error::Transport(t) => PostProcessError::RequestFailed(t.to_string()),
+            })?;
+        let json: Value = response
+            .into_json()
+            .map_err(|e| PostProcessError::InvalidResponse(e.to_string()))?;
+
+        let reply = match self.provider {
+            LlmProvider::OpenAi => json.pointer("/choices/0/message/content"),
+            LlmProvider::Ollama => json.pointer("/message/content"),
+        }
+        .and_then(Value::as_str)
+        .ok_or_else(|| PostProcessError::InvalidResponse(format!("no reply in {}", json)))?;
+
+        Ok(clean_reply(reply))
+    }
+
+    fn request_body(&self, text: &str) -> Value {
+        let mut body = json!({
+            "model": self.model,
+            "messages": [
+                { "role": "system", "content": self.system_prompt },
+                { "role": "user", "content": text },
+            ],
+        });
+        match self.provider {
+            LlmProvider::OpenAi => {
+                if let Some(temperature) = self.temperature {
+                    body["temperature"] = json!(temperature);
+                }
+            }
+            LlmProvider::Ollama => {
+                body["stream"] = Value::Bool(false);
+                if let Some(temperature) = self.temperature {
+                    body["options"] = json!({ "temperature": temperature });
+                }
+            }
+        }
+        body
+    }
+}
+
+/// Strip reasoning blocks and the quotes models like to wrap replies in
+fn clean_reply(reply: &str) -> String {
+    static THINK: OnceLock<Regex> = OnceLock::new();
+    let think =
+        THINK.get_or_init(|| Regex::new(r"(?s)<think>.*?</think>").expect("valid think regex"));
+
+    let reply = think.replace_all(reply, "");
+    let reply = reply.trim();
+    reply
+        .strip_prefix('"')
+        .and_then(|r| r.strip_suffix('"'))
+        .filter(|r| !r.contains('"'))
+        .unwrap_or(reply)
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::stand_in::stand_in_server;
+
+    fn config(provider: LlmProvider, endpoint: &str) -> LlmConfig {
+        LlmConfig {
+            provider,
+            endpoint: Some(format!("{}/", endpoint)),
+            model: "test-model".to_string(),
+            system_prompt: Some("Fix it.".to_string()),
+            temperature: Some(0.5),
+            timeout_ms: 5000,
+            api_key: Some("secret".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_openai_request() {
+        let (endpoint, server) = stand_in_server(
+            "200 OK",
+            &json!({ "choices": [{ "message": { "role": "assistant", "content": "\"Hello, world.\"\n" } }] }).to_string(),
+        );
+        let client = LlmClient::new(&config(LlmProvider::OpenAi, &endpoint));
+        let reply = client.complete("um hello world", Duration::from_secs(5));
+        assert_eq!(reply.unwrap(), "Hello, world.");
+
+        let (head, request) = server.join().unwrap();
+        let request: Value = ureq::serde_json::from_slice(&request).unwrap();
+        assert!(head.starts_with("POST /chat/completions "));
+        assert!(head.contains("Authorization: Bearer secret"));
+        assert_eq!(request["model"], "test-model");
+        assert_eq!(request["temperature"], 0.5);
+        assert_eq!(request["messages"][0]["content"], "Fix it.");
+        assert_eq!(request["messages"][1]["role"], "user");
+        assert_eq!(request["messages"][1]["content"], "um hello world");
+    }
+
+    #[test]
+    fn test_ollama_request() {
+        let (endpoint, server) = stand_in_server(
+            "200 OK",
+            &json!({ "message": { "role": "assistant", "content": "<think>\nhmm\n</think>\nHello." }, "done": true }).to_string(),
+        );
+        let client = LlmClient::new(&config(LlmProvider::Ollama, &endpoint));
+        let reply = client.complete("hello", Duration::from_secs(5));
+        assert_eq!(reply.unwrap(), "Hello.");
+
+        let (head, request) = server.join().unwrap();
+        let request: Value = ureq::serde_json::from_slice(&request).unwrap();
+        assert!(head.starts_with("POST /api/chat "));
+        assert_eq!(request["stream"], false);
+        assert_eq!(request["options"]["temperature"], 0.5);
+    }
+
+    #[test]
+    fn test_errors() {
+        let (endpoint, server) = stand_in_server(
+            "500 Internal Server Error",
+            &json!({ "error": "model not loaded" }).to_string(),
+        );
+        let client = LlmClient::new(&config(LlmProvider::OpenAi, &endpoint));
+        let error = client
+            .complete("hello", Duration::from_secs(5))
+            .unwrap_err();
+        assert!(error.to_string().contains("model not loaded"));
+        server.join().unwrap();
+
+        let (endpoint, server) = stand_in_server("200 OK", &json!({ "choices": [] }).to_string());
+        let client = LlmClient::new(&config(LlmProvider::OpenAi, &endpoint));
+        assert!(matches!(
+            client.complete("hello", Duration::from_secs(5)),
+            Err(PostProcessError::InvalidResponse(_))
+        ));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_clean_reply() {
+        assert_eq!(clean_reply("  plain text \n"), "plain text");
+        assert_eq!(clean_reply("\"quoted\""), "quoted");
+        assert_eq!(clean_reply("\"one\" and \"two\""), "\"one\" and \"two\"");
+    }
+}
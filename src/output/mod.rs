@@ -10,19 +10,37 @@
 //! 5. clipboard (wl-copy) - Wayland clipboard fallback
 //! 6. xclip - X11 clipboard fallback
 //!
+//! The built-in `uinput` driver types through a virtual keyboard without any
+//! external tool; it is opt-in via `driver_order`.
+//!
 //! Paste mode (clipboard + Ctrl+V) helps with system with non US keyboard layouts.
+//!
+//! The fifo, socket and webhook modes send transcriptions to other programs
+//! instead, see [`sink`].
+//!
+//! Voice editing commands produce [`action::OutputAction`] sequences mixing
+//! text and key presses; only the keyboard drivers can send the key presses.
 
+pub mod action;
 pub mod clipboard;
+pub mod clipboard_snapshot;
 pub mod dotool;
 pub mod eitype;
+pub mod llm;
 pub mod paste;
 pub mod post_process;
+pub mod sink;
+#[cfg(test)]
+mod stand_in;
+pub mod uinput;
 pub mod wtype;
 pub mod xclip;
+pub mod xkb;
 pub mod ydotool;
 
 use crate::config::{OutputConfig, OutputDriver};
 use crate::error::OutputError;
+use action::{actions_text, OutputAction};
 use std::borrow::Cow;
 use std::process::Stdio;
 use std::fs;
@@ -91,11 +109,12 @@ pub fn is_parakeet_binary_active() -> bool {
 }
 
 /// Get the engine icon for notifications based on configured engine
-/// Returns 🦜 for Parakeet, 🗣️ for Whisper
+/// Returns 🦜 for Parakeet, 🗣️ for Whisper, 🔌 for external engines
 pub fn engine_icon(engine: crate::config::TranscriptionEngine) -> &'static str {
     match engine {
         crate::config::TranscriptionEngine::Parakeet => "🦜",
         crate::config::TranscriptionEngine::Whisper => "🗣️",
+        crate::config::TranscriptionEngine::External => "🔌",
     }
 }
 
@@ -134,11 +153,26 @@ pub trait TextOutput: Send + Sync {
     /// Output text (type it or copy to clipboard)
     async fn output(&self, text: &str) -> Result<(), OutputError>;
 
+    /// Output a sequence of text and key presses
+    /// The default outputs text only and fails if the sequence contains keys
+    async fn output_actions(&self, actions: &[OutputAction]) -> Result<(), OutputError> {
+        if actions.iter().any(OutputAction::is_key) {
+            return Err(OutputError::KeysUnsupported(self.name().to_string()));
+        }
+        self.output(&actions_text(actions)).await
+    }
+
     /// Check if this output method is available
     async fn is_available(&self) -> bool;
 
     /// Human-readable name for logging
     fn name(&self) -> &'static str;
+
+    /// Whether the text is typed into the focused window with key presses
+    /// (rather than only copied to the clipboard or pasted)
+    fn types_text(&self) -> bool {
+        false
+    }
 }
 
 /// Default driver order for type mode
@@ -188,6 +222,14 @@ fn create_driver_output(
             show_notification,
             config.auto_submit,
         )),
+        OutputDriver::Uinput => Box::new(uinput::UinputOutput::new(
+            config.auto_submit,
+            config.type_delay_ms,
+            pre_type_delay_ms,
+            config.shift_enter_newlines,
+            config.dotool_xkb_layout.clone(),
+            config.dotool_xkb_variant.clone(),
+        )),
         OutputDriver::Clipboard => Box::new(clipboard::ClipboardOutput::new(show_notification)),
         OutputDriver::Xclip => Box::new(xclip::XclipOutput::new(show_notification)),
     }
@@ -264,6 +306,9 @@ pub fn create_output_chain_with_override(
                 config.paste_keys.clone(),
                 config.type_delay_ms,
                 pre_type_delay_ms,
+                config
+                    .paste_restore_clipboard
+                    .then_some(config.paste_restore_delay_ms),
             )));
         }
         crate::config::OutputMode::File => {
@@ -276,6 +321,11 @@ pub fn create_output_chain_with_override(
                 config.notification.on_transcription,
             )));
         }
+        crate::config::OutputMode::Fifo
+        | crate::config::OutputMode::Socket
+        | crate::config::OutputMode::Webhook => {
+            // Sinks receive the transcription in the daemon, nothing is typed
+        }
     }
 
     chain
@@ -311,13 +361,31 @@ pub struct OutputOptions<'a> {
 
 /// Try each output method in the chain until one succeeds
 /// Pre/post output commands are run before and after typing (for compositor integration).
+/// Returns whether the text was typed into the focused window ([`TextOutput::types_text`]).
 pub async fn output_with_fallback(
     chain: &[Box<dyn TextOutput>],
     text: &str,
     options: OutputOptions<'_>,
-) -> Result<(), OutputError> {
+) -> Result<bool, OutputError> {
+    let actions = [OutputAction::Text(text.to_string())];
+    output_actions_with_fallback(chain, &actions, options).await
+}
+
+/// Like [`output_with_fallback`], for a sequence of text and key presses
+/// Drivers that can't press keys (clipboard, paste) fail and the next is tried.
+pub async fn output_actions_with_fallback(
+    chain: &[Box<dyn TextOutput>],
+    actions: &[OutputAction],
+    options: OutputOptions<'_>,
+) -> Result<bool, OutputError> {
     // Normalize curly quotes to ASCII to prevent line break issues with keyboard tools
-    let normalized_text = normalize_quotes(text);
+    let normalized_actions: Vec<OutputAction> = actions
+        .iter()
+        .map(|action| match action {
+            OutputAction::Text(text) => OutputAction::Text(normalize_quotes(text).into_owned()),
+            key => key.clone(),
+        })
+        .collect();
 
     // Run pre-output hook if configured (e.g., switch to modifier-suppressing submap)
     if let Some(cmd) = options.pre_output_command {
@@ -335,10 +403,10 @@ pub async fn output_with_fallback(
             continue;
         }
 
-        match output.output(&normalized_text).await {
+        match output.output_actions(&normalized_actions).await {
             Ok(()) => {
                 tracing::debug!("Text output via {}", output.name());
-                result = Ok(());
+                result = Ok(output.types_text());
                 break;
             }
             Err(e) => {
@@ -1,18 +1,21 @@
 //! Paste-based text output
 //!
-//! Uses wl-copy to copy text to clipboard, then simulates a paste keystroke.
-//! This works around non-US keyboard layout issues by avoiding direct typing.
+//! Uses wl-copy (or xclip on X11) to copy text to clipboard, then simulates a
+//! paste keystroke. This works around non-US keyboard layout issues by
+//! avoiding direct typing. The previous clipboard contents can be restored
+//! afterwards (see [`super::clipboard_snapshot`]).
 //!
 //! Requires:
-//! - wl-copy installed (for clipboard access)
+//! - wl-copy installed (for clipboard access), or xclip on X11
 //! - wtype OR ydotool installed (for keystroke simulation)
 //!   - wtype: Wayland-native, no daemon needed (preferred)
 //!   - ydotool: Works on X11/Wayland/TTY, requires ydotoold daemon
 
+use super::clipboard_snapshot::{ClipboardSnapshot, ClipboardTool};
 use super::TextOutput;
 use crate::error::OutputError;
 use std::process::Stdio;
-use tokio::io::AsyncWriteExt;
+use std::time::Duration;
 use tokio::process::Command;
 
 /// Parsed paste keystroke (modifiers + key)
@@ -166,6 +169,8 @@ pub struct PasteOutput {
     type_delay_ms: u32,
     /// Delay before pasting (after clipboard copy) in milliseconds
     pre_type_delay_ms: u32,
+    /// Restore the previous clipboard this long after pasting (None: keep the text)
+    restore_delay_ms: Option<u32>,
 }
 
 impl PasteOutput {
@@ -175,6 +180,7 @@ impl PasteOutput {
         paste_keys: Option<String>,
         type_delay_ms: u32,
         pre_type_delay_ms: u32,
+        restore_delay_ms: Option<u32>,
     ) -> Self {
         let keystroke_str = paste_keys.as_deref().unwrap_or("ctrl+v");
         let keystroke = ParsedKeystroke::parse(keystroke_str).unwrap_or_else(|e| {
@@ -193,49 +199,13 @@ impl PasteOutput {
             keystroke,
             type_delay_ms,
             pre_type_delay_ms,
+            restore_delay_ms,
         }
     }
 
-    /// Copy text to clipboard using wl-copy
-    async fn copy_to_clipboard(&self, text: &str) -> Result<(), OutputError> {
-        // Spawn wl-copy with stdin pipe
-        let mut child = Command::new("wl-copy")
-            .stdin(Stdio::piped())
-            .stdout(Stdio::null())
-            .stderr(Stdio::piped())
-            .spawn()
-            .map_err(|e| {
-                if e.kind() == std::io::ErrorKind::NotFound {
-                    OutputError::WlCopyNotFound
-                } else {
-                    OutputError::InjectionFailed(e.to_string())
-                }
-            })?;
-
-        // Write text to stdin
-        if let Some(mut stdin) = child.stdin.take() {
-            stdin
-                .write_all(text.as_bytes())
-                .await
-                .map_err(|e| OutputError::InjectionFailed(e.to_string()))?;
-
-            // Close stdin to signal EOF
-            drop(stdin);
-        }
-
-        // Wait for completion
-        let status = child
-            .wait()
-            .await
-            .map_err(|e| OutputError::InjectionFailed(e.to_string()))?;
-
-        if !status.success() {
-            return Err(OutputError::InjectionFailed(
-                "wl-copy exited with error".to_string(),
-            ));
-        }
-
-        Ok(())
+    /// Clipboard tool for this session (wl-copy unless only X11 is available)
+    fn clipboard_tool(&self) -> ClipboardTool {
+        ClipboardTool::detect().unwrap_or(ClipboardTool::WlClipboard)
     }
 
     /// Check if wtype is available
@@ -445,8 +415,15 @@ impl TextOutput for PasteOutput {
             return Ok(());
         }
 
+        // Save what the user had copied, to put it back after pasting
+        let tool = self.clipboard_tool();
+        let snapshot = match self.restore_delay_ms {
+            Some(_) => Some(ClipboardSnapshot::take(tool).await),
+            None => None,
+        };
+
         // Step 1: Copy to clipboard
-        self.copy_to_clipboard(text).await?;
+        tool.copy(None, text.as_bytes()).await?;
 
         // Pre-paste delay to ensure clipboard is set before pasting
         // Default to 100ms if not configured (minimum needed for reliability)
@@ -455,7 +432,7 @@ impl TextOutput for PasteOutput {
         } else {
             100
         };
-        tokio::time::sleep(std::time::Duration::from_millis(delay as u64)).await;
+        tokio::time::sleep(Duration::from_millis(delay as u64)).await;
 
         // Step 2: Simulate paste keystroke
         self.simulate_paste_keystroke().await?;
@@ -473,16 +450,25 @@ impl TextOutput for PasteOutput {
                 } else {
                     ""
                 }
-                + &self.keystroke.key,
+                + self.keystroke.key.as_str(),
             text.len()
         );
+
+        // Step 3: Restore the previous clipboard once the application has read the paste
+        if let (Some(snapshot), Some(delay)) = (snapshot, self.restore_delay_ms) {
+            tokio::time::sleep(Duration::from_millis(delay as u64)).await;
+            if let Err(e) = snapshot.restore(text).await {
+                tracing::warn!("Failed to restore clipboard: {}", e);
+            }
+        }
         Ok(())
     }
 
     async fn is_available(&self) -> bool {
-        // Check if wl-copy exists (required for clipboard)
-        let wl_copy_available = Command::new("which")
-            .arg("wl-copy")
+        // Check if wl-copy (or xclip on X11) exists (required for clipboard)
+        let copy_program = self.clipboard_tool().copy_program();
+        let clipboard_available = Command::new("which")
+            .arg(copy_program)
             .stdout(Stdio::null())
             .stderr(Stdio::null())
             .status()
@@ -490,8 +476,8 @@ impl TextOutput for PasteOutput {
             .map(|s| s.success())
             .unwrap_or(false);
 
-        if !wl_copy_available {
-            tracing::debug!("paste mode unavailable: wl-copy not found");
+        if !clipboard_available {
+            tracing::debug!("paste mode unavailable: {} not found", copy_program);
             return false;
         }
 
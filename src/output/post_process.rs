@@ -12,18 +12,100 @@
 //! ```
 //!
 //! The command receives the transcribed text on stdin and should output
-//! the processed text on stdout. On any failure, the original text is used.
+//! the processed text on stdout. Instead of a command, an LLM from `[llm]`
+//! can be called directly (see [`super::llm`]). On any failure, the original
+//! text is used.
+//!
+//! With `protocol = "json"`, the command receives a JSON object instead:
+//!
+//! ```json
+//! {"text": "hello world", "profile": "chat", "language": "en", "duration": 2.4, "model": "base.en"}
+//! ```
+//!
+//! and may reply with plain text or a JSON object, all fields optional:
+//!
+//! ```json
+//! {"text": "Hello world!", "output_mode": "clipboard", "auto_submit": true}
+//! {"discard": true}
+//! ```
 
-use crate::config::PostProcessConfig;
+use super::llm::LlmClient;
+use crate::config::{
+    Config, LlmConfig, OutputMode, PostProcessConfig, PostProcessProtocol, Profile,
+};
+use serde::Deserialize;
 use std::process::Stdio;
 use std::time::Duration;
 use tokio::io::AsyncWriteExt;
 use tokio::process::Command;
 use tokio::time::timeout;
 
-/// Post-processor that runs an external command on transcribed text
+/// What processes the text
+#[derive(Debug, Clone)]
+enum Backend {
+    /// Shell command: text (or JSON) on stdin, result on stdout
+    Command {
+        command: String,
+        protocol: PostProcessProtocol,
+    },
+    /// Chat completion API
+    Llm(LlmClient),
+}
+
+/// Recording details sent to JSON post-process commands
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PostProcessMetadata<'a> {
+    /// Active profile name
+    pub profile: Option<&'a str>,
+    /// Transcription language
+    pub language: Option<&'a str>,
+    /// Length of the recorded audio
+    pub duration: Duration,
+    /// Transcription model
+    pub model: Option<&'a str>,
+}
+
+/// Post-processed text, and how a JSON command wants it output
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PostProcessOutput {
+    /// Text to output (empty when discarded)
+    pub text: String,
+    /// Output mode for this transcription
+    pub output_mode: Option<OutputMode>,
+    /// Press Enter after the text (or not) for this transcription
+    pub auto_submit: Option<bool>,
+    /// The command discarded the transcription
+    pub discarded: bool,
+}
+
+impl PostProcessOutput {
+    /// Plain text with no output choices
+    pub fn text(text: String) -> Self {
+        Self {
+            text,
+            ..Default::default()
+        }
+    }
+}
+
+/// Reply of a JSON post-process command
+/// Unknown fields are ignored, so commands can echo their input back
+#[derive(Debug, Deserialize)]
+struct JsonReply {
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    output_mode: Option<OutputMode>,
+    #[serde(default)]
+    auto_submit: Option<bool>,
+    #[serde(default)]
+    discard: bool,
+}
+
+/// Post-processor that runs an external command or LLM on transcribed text
+#[derive(Debug, Clone)]
 pub struct PostProcessor {
-    command: String,
+    backend: Backend,
     timeout: Duration,
 }
 
@@ -31,55 +113,186 @@ impl PostProcessor {
     /// Create a new post-processor from configuration
     pub fn new(config: &PostProcessConfig) -> Self {
         Self {
-            command: config.command.clone(),
+            backend: Backend::Command {
+                command: config.command.clone(),
+                protocol: config.protocol,
+            },
+            timeout: Duration::from_millis(config.timeout_ms),
+        }
+    }
+
+    /// Create a post-processor that calls an LLM API
+    pub fn llm(config: &LlmConfig) -> Self {
+        Self {
+            backend: Backend::Llm(LlmClient::new(config)),
             timeout: Duration::from_millis(config.timeout_ms),
         }
     }
 
+    /// Post-processor for a recording
+    ///
+    /// Priority: the profile's post_process_command, the profile's
+    /// post_process_llm, [output.post_process], [output] post_process_llm.
+    /// Returns None when nothing is configured (or the LLM doesn't exist).
+    pub fn from_config(config: &Config, profile: Option<&Profile>) -> Option<Self> {
+        if let Some(profile) = profile {
+            if let Some(ref command) = profile.post_process_command {
+                return Some(Self::new(&PostProcessConfig {
+                    command: command.clone(),
+                    timeout_ms: profile.post_process_timeout_ms.unwrap_or(30000),
+                    protocol: profile.post_process_protocol.unwrap_or_default(),
+                }));
+            }
+            if let Some(ref name) = profile.post_process_llm {
+                let processor = Self::named_llm(config, name)?;
+                return Some(match profile.post_process_timeout_ms {
+                    Some(ms) => processor.with_timeout(Duration::from_millis(ms)),
+                    None => processor,
+                });
+            }
+        }
+        match config.output.post_process {
+            Some(ref post_process) => Some(Self::new(post_process)),
+            None => config
+                .output
+                .post_process_llm
+                .as_deref()
+                .and_then(|name| Self::named_llm(config, name)),
+        }
+    }
+
+    fn named_llm(config: &Config, name: &str) -> Option<Self> {
+        match config.get_llm(name) {
+            Some(llm) => Some(Self::llm(llm)),
+            None => {
+                tracing::warn!("LLM '{}' not found in config ([llm.{}])", name, name);
+                None
+            }
+        }
+    }
+
+    /// Same command or LLM with a different timeout
+    pub fn with_timeout(&self, timeout: Duration) -> Self {
+        Self {
+            backend: self.backend.clone(),
+            timeout,
+        }
+    }
+
+    /// Short description for logs
+    pub fn describe(&self) -> String {
+        match self.backend {
+            Backend::Command {
+                ref command,
+                protocol: PostProcessProtocol::Text,
+            } => format!("command {:?}", command),
+            Backend::Command {
+                ref command,
+                protocol: PostProcessProtocol::Json,
+            } => format!("JSON command {:?}", command),
+            Backend::Llm(ref client) => format!("LLM {}", client.model()),
+        }
+    }
+
     /// Process text through the external command
     ///
     /// Returns the processed text on success, or the original text on any failure.
     /// This ensures voice-to-text always produces output even when post-processing fails.
     pub async fn process(&self, text: &str) -> String {
-        match self.execute_command(text).await {
-            Ok(processed) => {
-                if processed.is_empty() {
-                    tracing::warn!(
-                        "Post-process command returned empty output, using original text"
-                    );
-                    text.to_string()
+        self.process_with(text, &PostProcessMetadata::default())
+            .await
+            .text
+    }
+
+    /// Process text, passing recording details to JSON commands
+    ///
+    /// Like [`Self::process`], falls back to the original text on any failure.
+    /// The text is only empty when a JSON command discarded it.
+    pub async fn process_with(
+        &self,
+        text: &str,
+        metadata: &PostProcessMetadata<'_>,
+    ) -> PostProcessOutput {
+        match self.execute(text, metadata).await {
+            Ok(output) if output.discarded => {
+                tracing::info!("Post-process command discarded the transcription");
+                output
+            }
+            Ok(output) => {
+                if output.text.is_empty() {
+                    tracing::warn!("Post-processing returned empty output, using original text");
+                    PostProcessOutput {
+                        text: text.to_string(),
+                        ..output
+                    }
                 } else {
                     tracing::debug!(
                         "Post-processed ({} -> {} chars)",
                         text.len(),
-                        processed.len()
+                        output.text.len()
                     );
-                    processed
+                    output
                 }
             }
             Err(e) => {
-                tracing::warn!("Post-process command failed: {}, using original text", e);
-                text.to_string()
+                tracing::warn!("Post-processing failed: {}, using original text", e);
+                PostProcessOutput::text(text.to_string())
             }
         }
     }
 
-    async fn execute_command(&self, text: &str) -> Result<String, PostProcessError> {
+    async fn execute(
+        &self,
+        text: &str,
+        metadata: &PostProcessMetadata<'_>,
+    ) -> Result<PostProcessOutput, PostProcessError> {
+        match self.backend {
+            Backend::Command {
+                ref command,
+                protocol: PostProcessProtocol::Text,
+            } => self
+                .execute_command(command, text)
+                .await
+                .map(PostProcessOutput::text),
+            Backend::Command {
+                ref command,
+                protocol: PostProcessProtocol::Json,
+            } => {
+                let input = json_input(text, metadata);
+                let reply = self.execute_command(command, &input).await?;
+                parse_json_reply(&reply, text)
+            }
+            Backend::Llm(ref client) => {
+                // ureq blocks, and applies the timeout itself
+                let client = client.clone();
+                let text = text.to_string();
+                let timeout = self.timeout;
+                tokio::task::spawn_blocking(move || client.complete(&text, timeout))
+                    .await
+                    .map_err(|e| PostProcessError::RequestFailed(e.to_string()))?
+                    .map(PostProcessOutput::text)
+            }
+        }
+    }
+
+    async fn execute_command(&self, command: &str, text: &str) -> Result<String, PostProcessError> {
         // Spawn command via shell for proper parsing of complex commands
         let mut child = Command::new("sh")
-            .args(["-c", &self.command])
+            .args(["-c", command])
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()
             .map_err(|e| PostProcessError::SpawnFailed(e.to_string()))?;
 
-        // Write text to stdin
+        // Write text to stdin; commands that exit without reading it close the pipe early
         if let Some(mut stdin) = child.stdin.take() {
-            stdin
-                .write_all(text.as_bytes())
-                .await
-                .map_err(|e| PostProcessError::WriteFailed(e.to_string()))?;
+            match stdin.write_all(text.as_bytes()).await {
+                Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => {
+                    return Err(PostProcessError::WriteFailed(e.to_string()));
+                }
+                _ => {}
+            }
             // Close stdin to signal EOF
             drop(stdin);
         }
@@ -107,6 +320,40 @@ impl PostProcessor {
     }
 }
 
+/// JSON object sent to JSON commands
+fn json_input(text: &str, metadata: &PostProcessMetadata) -> String {
+    serde_json::json!({
+        "text": text,
+        "profile": metadata.profile,
+        "language": metadata.language,
+        "duration": metadata.duration.as_secs_f64(),
+        "model": metadata.model,
+    })
+    .to_string()
+}
+
+/// Read a JSON command's reply: a JSON object, or plain text
+/// A reply without "text" keeps the original text
+fn parse_json_reply(reply: &str, original: &str) -> Result<PostProcessOutput, PostProcessError> {
+    if !reply.starts_with('{') {
+        return Ok(PostProcessOutput::text(reply.to_string()));
+    }
+    let reply: JsonReply =
+        serde_json::from_str(reply).map_err(|e| PostProcessError::InvalidJson(e.to_string()))?;
+    if reply.discard {
+        return Ok(PostProcessOutput {
+            discarded: true,
+            ..Default::default()
+        });
+    }
+    Ok(PostProcessOutput {
+        text: reply.text.unwrap_or_else(|| original.to_string()),
+        output_mode: reply.output_mode,
+        auto_submit: reply.auto_submit,
+        discarded: false,
+    })
+}
+
 /// Errors that can occur during post-processing
 #[derive(Debug)]
 pub enum PostProcessError {
@@ -122,6 +369,12 @@ pub enum PostProcessError {
     NonZeroExit { code: Option<i32>, stderr: String },
     /// Command output was not valid UTF-8
     InvalidUtf8(String),
+    /// JSON command replied with an invalid JSON object
+    InvalidJson(String),
+    /// LLM request failed (connection, HTTP error status)
+    RequestFailed(String),
+    /// LLM response had no reply
+    InvalidResponse(String),
 }
 
 impl std::fmt::Display for PostProcessError {
@@ -139,6 +392,9 @@ impl std::fmt::Display for PostProcessError {
                 }
             }
             Self::InvalidUtf8(e) => write!(f, "output is not valid UTF-8: {}", e),
+            Self::InvalidJson(e) => write!(f, "invalid JSON reply: {}", e),
+            Self::RequestFailed(e) => write!(f, "LLM request failed: {}", e),
+            Self::InvalidResponse(e) => write!(f, "unexpected LLM response: {}", e),
         }
     }
 }
@@ -153,6 +409,7 @@ mod tests {
         PostProcessConfig {
             command: command.to_string(),
             timeout_ms,
+            protocol: PostProcessProtocol::Text,
         }
     }
 
@@ -238,6 +495,142 @@ mod tests {
         assert_eq!(result, "hello");
     }
 
+    fn json_processor(command: &str) -> PostProcessor {
+        PostProcessor::new(&PostProcessConfig {
+            protocol: PostProcessProtocol::Json,
+            ..make_config(command, 5000)
+        })
+    }
+
+    #[tokio::test]
+    async fn test_json_protocol() {
+        let metadata = PostProcessMetadata {
+            profile: Some("chat"),
+            language: Some("en"),
+            duration: Duration::from_millis(2500),
+            model: Some("base.en"),
+        };
+
+        // The command sees the text and recording details
+        let input: serde_json::Value =
+            serde_json::from_str(&json_input("hello", &metadata)).unwrap();
+        assert_eq!(input["text"], "hello");
+        assert_eq!(input["profile"], "chat");
+        assert_eq!(input["language"], "en");
+        assert_eq!(input["duration"], 2.5);
+        assert_eq!(input["model"], "base.en");
+        let processor = json_processor(r#"sed 's/"text":"hello"/"text":"hi"/'"#);
+        assert_eq!(processor.process_with("hello", &metadata).await.text, "hi");
+
+        let processor = json_processor(
+            r#"cat >/dev/null; echo '{"text": "Hello!", "output_mode": "clipboard", "auto_submit": true}'"#,
+        );
+        assert_eq!(
+            processor.process_with("hello", &metadata).await,
+            PostProcessOutput {
+                text: "Hello!".to_string(),
+                output_mode: Some(OutputMode::Clipboard),
+                auto_submit: Some(true),
+                discarded: false,
+            }
+        );
+
+        // Without "text" the original is kept; plain text replies still work
+        let processor = json_processor(r#"cat >/dev/null; echo '{"output_mode": "paste"}'"#);
+        let output = processor.process_with("hello", &metadata).await;
+        assert_eq!(output.text, "hello");
+        assert_eq!(output.output_mode, Some(OutputMode::Paste));
+        let processor = json_processor("cat >/dev/null; echo plain");
+        assert_eq!(processor.process("hello").await, "plain");
+    }
+
+    #[tokio::test]
+    async fn test_json_discard_and_errors() {
+        let metadata = PostProcessMetadata::default();
+
+        let processor = json_processor(r#"cat >/dev/null; echo '{"discard": true}'"#);
+        let output = processor.process_with("hello", &metadata).await;
+        assert!(output.discarded);
+        assert_eq!(output.text, "");
+
+        // Invalid replies fall back to the original text
+        for reply in [r#"{"text": 3}"#, r#"{"output_mode": "telepathy"}"#, "{oops"] {
+            let processor = json_processor(&format!("cat >/dev/null; echo '{}'", reply));
+            let output = processor.process_with("hello", &metadata).await;
+            assert_eq!(output, PostProcessOutput::text("hello".to_string()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_llm_failure_fallback() {
+        // Nothing listens on the port once the listener is dropped
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let processor = PostProcessor::llm(&LlmConfig {
+            provider: Default::default(),
+            endpoint: Some(endpoint),
+            model: "test-model".to_string(),
+            system_prompt: None,
+            temperature: None,
+            timeout_ms: 5000,
+            api_key: None,
+        });
+        let result = processor.process("original text").await;
+        assert_eq!(result, "original text"); // Falls back to original
+    }
+
+    #[test]
+    fn test_from_config_priority() {
+        let config: Config = toml::from_str(
+            r#"
+            [hotkey]
+            key = "SCROLLLOCK"
+
+            [audio]
+            device = "default"
+            sample_rate = 16000
+            max_duration_secs = 60
+
+            [whisper]
+            model = "base.en"
+            language = "en"
+
+            [output]
+            mode = "type"
+            post_process_llm = "cleanup"
+
+            [llm.cleanup]
+            provider = "ollama"
+            model = "llama3.2:1b"
+
+            [llm.gpt]
+            model = "gpt-4o-mini"
+
+            [profiles.chat]
+            post_process_llm = "gpt"
+
+            [profiles.code]
+            post_process_command = "cat"
+            post_process_llm = "gpt"
+
+            [profiles.broken]
+            post_process_llm = "missing"
+            "#,
+        )
+        .unwrap();
+        let describe = |profile: Option<&str>| {
+            let profile = profile.and_then(|name| config.get_profile(name));
+            PostProcessor::from_config(&config, profile).map(|p| p.describe())
+        };
+
+        assert_eq!(describe(None).as_deref(), Some("LLM llama3.2:1b"));
+        assert_eq!(describe(Some("chat")).as_deref(), Some("LLM gpt-4o-mini"));
+        assert_eq!(describe(Some("code")).as_deref(), Some("command \"cat\""));
+        assert_eq!(describe(Some("broken")), None);
+    }
+
     #[tokio::test]
     async fn test_complex_shell_command() {
         // Test that complex shell commands work (pipes, quotes, etc.)
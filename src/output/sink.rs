@@ -0,0 +1,407 @@
+//! Transcription sinks for other programs
+//!
+//! The "fifo", "socket" and "webhook" output modes hand each transcription to
+//! another program (a chat client, an editor plugin, a note app) instead of
+//! typing it. Every sink sends the same JSON object, one per line:
+//!
+//! ```json
+//! {"text":"Hello world.","profile":null,"language":"en","duration":1.8,"model":"base.en","app":"firefox","timestamp":1760000000}
+//! ```
+//!
+//! - fifo: written to a named pipe, created if missing. Skipped when no
+//!   program has the pipe open for reading, so the daemon never blocks.
+//! - socket: sent to every client connected to a Unix socket the daemon
+//!   listens on. Clients only receive transcriptions made while connected.
+//! - webhook: POSTed to a URL with `Content-Type: application/json`.
+
+use super::post_process::PostProcessMetadata;
+use crate::config::SinkConfig;
+use crate::error::OutputError;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::Write;
+use std::os::unix::fs::{FileTypeExt, OpenOptionsExt};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+
+/// Transcriptions buffered for a socket client that is not reading
+const SOCKET_BACKLOG: usize = 64;
+
+/// A transcription and what is known about it, as sent to sinks
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptionEvent<'a> {
+    /// Final text, after text processing and post-processing
+    pub text: &'a str,
+    /// Active profile name
+    pub profile: Option<&'a str>,
+    /// Transcription language
+    pub language: Option<&'a str>,
+    /// Length of the recorded audio in seconds
+    pub duration: f64,
+    /// Transcription model
+    pub model: Option<&'a str>,
+    /// Application of the focused window when recording started
+    pub app: Option<&'a str>,
+    /// Unix time the transcription was output
+    pub timestamp: u64,
+}
+
+impl<'a> TranscriptionEvent<'a> {
+    /// Event for the final text, with the recording's metadata
+    pub fn new(text: &'a str, metadata: &PostProcessMetadata<'a>, app: Option<&'a str>) -> Self {
+        Self {
+            text,
+            profile: metadata.profile,
+            language: metadata.language,
+            duration: metadata.duration.as_secs_f64(),
+            model: metadata.model,
+            app,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+        }
+    }
+
+    /// The event as one line of JSON, with the trailing newline
+    pub fn to_json_line(&self) -> String {
+        let mut line = serde_json::to_string(self).expect("event serializes");
+        line.push('\n');
+        line
+    }
+}
+
+/// Write a line to a named pipe, creating the pipe if needed
+/// Returns false when no program is reading the pipe
+pub fn write_fifo(path: &Path, line: &str) -> Result<bool, OutputError> {
+    let fail = |message: String| OutputError::SinkFailed("FIFO", message);
+
+    match std::fs::metadata(path) {
+        Ok(metadata) if !metadata.file_type().is_fifo() => {
+            return Err(fail(format!("{:?} exists and is not a named pipe", path)));
+        }
+        Ok(_) => {}
+        Err(_) => create_fifo(path).map_err(|e| fail(format!("creating {:?}: {}", path, e)))?,
+    }
+
+    // Non-blocking open fails with ENXIO instead of waiting for a reader
+    let mut fifo = match std::fs::OpenOptions::new()
+        .write(true)
+        .custom_flags(libc::O_NONBLOCK)
+        .open(path)
+    {
+        Ok(fifo) => fifo,
+        Err(e) if e.raw_os_error() == Some(libc::ENXIO) => return Ok(false),
+        Err(e) => return Err(fail(format!("opening {:?}: {}", path, e))),
+    };
+    fifo.write_all(line.as_bytes())
+        .map_err(|e| fail(format!("writing {:?}: {}", path, e)))?;
+    Ok(true)
+}
+
+fn create_fifo(path: &Path) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let c_path = std::ffi::CString::new(path.as_os_str().as_encoded_bytes())
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+    // SAFETY: c_path is a valid NUL-terminated string
+    if unsafe { libc::mkfifo(c_path.as_ptr(), 0o600) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Unix socket that sends each transcription to all connected clients
+pub struct SocketSink {
+    path: PathBuf,
+    sender: broadcast::Sender<Arc<str>>,
+    accept_task: JoinHandle<()>,
+}
+
+impl SocketSink {
+    /// Listen on a socket path, replacing a stale socket file
+    pub fn bind(path: &Path) -> Result<Self, OutputError> {
+        let fail =
+            |e: std::io::Error| OutputError::SinkFailed("socket", format!("{:?}: {}", path, e));
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(fail)?;
+        }
+        if std::fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_socket()) {
+            std::fs::remove_file(path).map_err(fail)?;
+        }
+        let listener = UnixListener::bind(path).map_err(fail)?;
+
+        let (sender, _) = broadcast::channel(SOCKET_BACKLOG);
+        let accept_sender = sender.clone();
+        let accept_task = tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((stream, _)) => {
+                        tracing::debug!("Transcription socket client connected");
+                        tokio::spawn(serve_client(stream, accept_sender.subscribe()));
+                    }
+                    Err(e) => {
+                        tracing::warn!("Transcription socket accept failed: {}", e);
+                        tokio::time::sleep(Duration::from_millis(100)).await;
+                    }
+                }
+            }
+        });
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            sender,
+            accept_task,
+        })
+    }
+
+    /// Socket path
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Send a line to every connected client, returning how many there are
+    pub fn send(&self, line: &str) -> usize {
+        self.sender.send(Arc::from(line)).unwrap_or(0)
+    }
+}
+
+impl Drop for SocketSink {
+    fn drop(&mut self) {
+        self.accept_task.abort();
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Forward transcriptions to one client until it disconnects
+async fn serve_client(stream: UnixStream, mut lines: broadcast::Receiver<Arc<str>>) {
+    let (mut reader, mut writer) = stream.into_split();
+    let mut buf = [0u8; 256];
+    loop {
+        tokio::select! {
+            line = lines.recv() => match line {
+                Ok(line) => {
+                    if writer.write_all(line.as_bytes()).await.is_err() {
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(missed)) => {
+                    tracing::warn!("Transcription socket client missed {} transcriptions", missed);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            // Clients don't send anything; a read only returns when they hang up
+            read = reader.read(&mut buf) => {
+                if matches!(read, Ok(0) | Err(_)) {
+                    break;
+                }
+            }
+        }
+    }
+    tracing::debug!("Transcription socket client disconnected");
+}
+
+/// POST a JSON body to a URL
+pub async fn post_webhook(
+    url: &str,
+    headers: &HashMap<String, String>,
+    body: String,
+    timeout: Duration,
+) -> Result<(), OutputError> {
+    let mut request = ureq::post(url)
+        .timeout(timeout)
+        .set("Content-Type", "application/json");
+    for (name, value) in headers {
+        request = request.set(name, value);
+    }
+
+    tokio::task::spawn_blocking(move || match request.send_string(&body) {
+        Ok(_) => Ok(()),
+        Err(ureq::Error::Status(code, _)) => Err(format!("server returned {}", code)),
+        Err(ureq::Error::Transport(t)) => Err(t.to_string()),
+    })
+    .await
+    .map_err(|e| e.to_string())
+    .and_then(|result| result)
+    .map_err(|message| OutputError::SinkFailed("webhook", message))
+}
+
+/// Send an event to the sink of a fifo, socket or webhook output mode
+/// The socket sink is created on first use if the daemon did not start it
+pub async fn send_event(
+    mode: &crate::config::OutputMode,
+    config: &SinkConfig,
+    socket: &mut Option<SocketSink>,
+    event: &TranscriptionEvent<'_>,
+) -> Result<(), OutputError> {
+    use crate::config::OutputMode;
+
+    let line = event.to_json_line();
+    match mode {
+        OutputMode::Fifo => {
+            let path = config.effective_fifo_path();
+            if write_fifo(&path, &line)? {
+                tracing::info!("Wrote transcription to {:?}", path);
+            } else {
+                tracing::info!("No reader on {:?}, transcription dropped", path);
+            }
+        }
+        OutputMode::Socket => {
+            let sink = match socket {
+                Some(sink) => sink,
+                None => socket.insert(SocketSink::bind(&config.effective_socket_path())?),
+            };
+            let clients = sink.send(&line);
+            tracing::info!(
+                "Sent transcription to {} client(s) of {:?}",
+                clients,
+                sink.path()
+            );
+        }
+        OutputMode::Webhook => {
+            let url = config.webhook_url.as_deref().ok_or_else(|| {
+                OutputError::SinkFailed("webhook", "webhook_url is not set".to_string())
+            })?;
+            let timeout = Duration::from_millis(config.webhook_timeout_ms);
+            post_webhook(url, &config.webhook_headers, line, timeout).await?;
+            tracing::info!("Posted transcription to {}", url);
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::stand_in::stand_in_server;
+    use std::io::{BufRead, BufReader};
+    use tokio::io::AsyncBufReadExt;
+
+    fn event(text: &str) -> TranscriptionEvent<'_> {
+        let metadata = PostProcessMetadata {
+            profile: Some("notes"),
+            language: Some("en"),
+            duration: Duration::from_millis(1500),
+            model: Some("base.en"),
+        };
+        TranscriptionEvent::new(text, &metadata, Some("firefox"))
+    }
+
+    #[test]
+    fn test_event_json_line() {
+        let line = event("Hello world.").to_json_line();
+        assert!(line.ends_with('\n'));
+        assert_eq!(line.matches('\n').count(), 1);
+
+        let json: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(json["text"], "Hello world.");
+        assert_eq!(json["profile"], "notes");
+        assert_eq!(json["language"], "en");
+        assert_eq!(json["duration"], 1.5);
+        assert_eq!(json["model"], "base.en");
+        assert_eq!(json["app"], "firefox");
+        assert!(json["timestamp"].as_u64().unwrap() > 0);
+
+        // Newlines in the text stay inside the JSON string
+        let line = event("one\ntwo").to_json_line();
+        assert_eq!(line.matches('\n').count(), 1);
+    }
+
+    #[test]
+    fn test_fifo() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sink/transcriptions.fifo");
+
+        // No reader: the pipe is created and the line dropped
+        assert!(!write_fifo(&path, "dropped\n").unwrap());
+        assert!(std::fs::metadata(&path).unwrap().file_type().is_fifo());
+
+        let reader = std::fs::OpenOptions::new()
+            .read(true)
+            .custom_flags(libc::O_NONBLOCK)
+            .open(&path)
+            .unwrap();
+        assert!(write_fifo(&path, "{\"text\":\"hi\"}\n").unwrap());
+        let mut line = String::new();
+        BufReader::new(reader).read_line(&mut line).unwrap();
+        assert_eq!(line, "{\"text\":\"hi\"}\n");
+
+        // A regular file is not replaced
+        let file = dir.path().join("file");
+        std::fs::write(&file, "").unwrap();
+        assert!(write_fifo(&file, "x\n").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_socket_broadcast() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("transcriptions.sock");
+        let sink = SocketSink::bind(&path).unwrap();
+        assert_eq!(sink.send("nobody\n"), 0);
+
+        let mut clients = Vec::new();
+        for _ in 0..2 {
+            let stream = UnixStream::connect(&path).await.unwrap();
+            clients.push(tokio::io::BufReader::new(stream));
+        }
+        // Wait for the daemon side to accept both
+        for _ in 0..100 {
+            if sink.sender.receiver_count() == 2 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        assert_eq!(sink.send("{\"text\":\"hi\"}\n"), 2);
+        for client in &mut clients {
+            let mut line = String::new();
+            client.read_line(&mut line).await.unwrap();
+            assert_eq!(line, "{\"text\":\"hi\"}\n");
+        }
+
+        // Binding again replaces the stale socket; dropping removes it
+        drop(sink);
+        assert!(!path.exists());
+        let sink = SocketSink::bind(&path).unwrap();
+        assert!(path.exists());
+        drop(sink);
+    }
+
+    #[tokio::test]
+    async fn test_webhook() {
+        let (endpoint, server) = stand_in_server("200 OK", "{}");
+        let url = format!("{}/dictation", endpoint);
+
+        let headers = HashMap::from([("Authorization".to_string(), "Bearer secret".to_string())]);
+        let line = event("Hello world.").to_json_line();
+        post_webhook(&url, &headers, line.clone(), Duration::from_secs(5))
+            .await
+            .unwrap();
+
+        let (head, body) = server.join().unwrap();
+        assert!(head.starts_with("POST /dictation "));
+        assert!(head.contains("Content-Type: application/json"));
+        assert!(head.contains("Authorization: Bearer secret"));
+        assert_eq!(String::from_utf8(body).unwrap(), line);
+    }
+
+    #[tokio::test]
+    async fn test_webhook_requires_url() {
+        let mut socket = None;
+        let result = send_event(
+            &crate::config::OutputMode::Webhook,
+            &SinkConfig::default(),
+            &mut socket,
+            &event("hi"),
+        )
+        .await;
+        assert!(matches!(result, Err(OutputError::SinkFailed("webhook", _))));
+    }
+}
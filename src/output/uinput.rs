@@ -0,0 +1,373 @@
+//! Native uinput text output
+//!
+//! Creates a virtual keyboard through /dev/uinput and types by sending key
+//! codes, without an external tool or daemon. Characters are mapped to keys
+//! with the XKB layout from `dotool_xkb_layout`/`dotool_xkb_variant`, which
+//! must match the layout the compositor uses. Characters the layout cannot
+//! type directly (dead keys, other scripts) are skipped with a warning.
+//!
+//! Requires:
+//! - Write access to /dev/uinput (usually membership in the 'input' group)
+//! - libxkbcommon (loaded at runtime)
+
+use super::action::{Key, KeyCombo, OutputAction};
+use super::xkb::{KeyStroke, Keymap, KEY_LEFTSHIFT};
+use super::TextOutput;
+use crate::error::OutputError;
+use evdev::uinput::{VirtualDevice, VirtualDeviceBuilder};
+use evdev::{AttributeSet, EventType, InputEvent};
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+const UINPUT_PATH: &str = "/dev/uinput";
+const DEVICE_NAME: &str = "voxtype virtual keyboard";
+/// Time for the compositor to pick up a newly created device
+const DEVICE_SETTLE: Duration = Duration::from_millis(200);
+
+/// The virtual keyboard, created on first use and kept for the daemon's lifetime
+/// so the compositor does not see a new device for every transcription
+static DEVICE: Mutex<Option<VirtualDevice>> = Mutex::new(None);
+
+/// One key event: evdev code and whether it is a press
+type KeyEvent = (u16, bool);
+
+/// uinput-based text output
+pub struct UinputOutput {
+    /// Whether to send Enter key after output
+    auto_submit: bool,
+    /// Delay between key events in milliseconds
+    type_delay_ms: u32,
+    /// Delay before typing starts (ms), allows virtual keyboard to initialize
+    pre_type_delay_ms: u32,
+    /// Convert newlines to Shift+Enter (for apps where Enter submits)
+    shift_enter_newlines: bool,
+    /// XKB layout used to map characters to keys (e.g., "de", "fr")
+    xkb_layout: Option<String>,
+    /// XKB layout variant (e.g., "nodeadkeys")
+    xkb_variant: Option<String>,
+}
+
+impl UinputOutput {
+    /// Create a new uinput output
+    pub fn new(
+        auto_submit: bool,
+        type_delay_ms: u32,
+        pre_type_delay_ms: u32,
+        shift_enter_newlines: bool,
+        xkb_layout: Option<String>,
+        xkb_variant: Option<String>,
+    ) -> Self {
+        Self {
+            auto_submit,
+            type_delay_ms,
+            pre_type_delay_ms,
+            shift_enter_newlines,
+            xkb_layout,
+            xkb_variant,
+        }
+    }
+
+    /// Key events for a sequence of actions, plus Enter if auto_submit is set
+    fn plan(&self, actions: &[OutputAction], keymap: &Keymap) -> Vec<KeyEvent> {
+        let mut events = Vec::new();
+        let mut skipped = String::new();
+        for action in actions {
+            match action {
+                OutputAction::Text(text) => {
+                    for c in text.chars() {
+                        match c {
+                            '\n' => self.push_newline(&mut events),
+                            '\r' => {}
+                            _ => match keymap.stroke(c) {
+                                Some(stroke) => push_stroke(&mut events, stroke),
+                                None => skipped.push(c),
+                            },
+                        }
+                    }
+                }
+                OutputAction::Key(combo) => push_stroke(&mut events, &combo_stroke(combo, keymap)),
+            }
+        }
+        if self.auto_submit {
+            push_stroke(&mut events, &key_stroke(&[], Key::Enter.evdev_code()));
+        }
+
+        if !skipped.is_empty() {
+            tracing::warn!(
+                "uinput: layout '{}' cannot type {:?}, skipped",
+                self.xkb_layout.as_deref().unwrap_or("default"),
+                skipped
+            );
+        }
+        events
+    }
+
+    /// Enter, or Shift+Enter if configured
+    fn push_newline(&self, events: &mut Vec<KeyEvent>) {
+        let modifiers: &[u16] = if self.shift_enter_newlines {
+            &[KEY_LEFTSHIFT]
+        } else {
+            &[]
+        };
+        push_stroke(events, &key_stroke(modifiers, Key::Enter.evdev_code()));
+    }
+
+    /// Map the actions to key events and send them
+    async fn send(&self, actions: &[OutputAction]) -> Result<(), OutputError> {
+        let keymap = Keymap::new(self.xkb_layout.as_deref(), self.xkb_variant.as_deref())?;
+        let events = self.plan(actions, &keymap);
+        if events.is_empty() {
+            return Ok(());
+        }
+
+        tracing::debug!(
+            "uinput: sending {} key events (layout {}, {} characters mapped)",
+            events.len(),
+            self.xkb_layout.as_deref().unwrap_or("default"),
+            keymap.len()
+        );
+
+        let pre_type_delay = Duration::from_millis(self.pre_type_delay_ms as u64);
+        let type_delay = Duration::from_millis(self.type_delay_ms as u64);
+        tokio::task::spawn_blocking(move || emit_events(&events, pre_type_delay, type_delay))
+            .await
+            .map_err(|e| OutputError::InjectionFailed(format!("uinput task failed: {}", e)))?
+    }
+}
+
+fn key_stroke(modifiers: &[u16], code: u16) -> KeyStroke {
+    KeyStroke {
+        modifiers: modifiers.to_vec(),
+        code,
+    }
+}
+
+/// Keys for a combo; letters follow the layout (Ctrl+Z is Ctrl+Y's key on QWERTZ)
+fn combo_stroke(combo: &KeyCombo, keymap: &Keymap) -> KeyStroke {
+    let code = match combo.key {
+        Key::Char(c) => keymap
+            .stroke(c)
+            .filter(|stroke| stroke.modifiers.is_empty())
+            .map_or(combo.key.evdev_code(), |stroke| stroke.code),
+        key => key.evdev_code(),
+    };
+    let modifiers: Vec<u16> = combo.modifiers.iter().map(|m| m.evdev_code()).collect();
+    key_stroke(&modifiers, code)
+}
+
+/// Press the modifiers, tap the key, release the modifiers in reverse
+fn push_stroke(events: &mut Vec<KeyEvent>, stroke: &KeyStroke) {
+    events.extend(stroke.modifiers.iter().map(|&m| (m, true)));
+    events.push((stroke.code, true));
+    events.push((stroke.code, false));
+    events.extend(stroke.modifiers.iter().rev().map(|&m| (m, false)));
+}
+
+/// Create the virtual keyboard with every key of the main block
+fn create_device() -> Result<VirtualDevice, OutputError> {
+    let uinput_error = |e: std::io::Error| {
+        if e.kind() == std::io::ErrorKind::PermissionDenied {
+            OutputError::InjectionFailed(format!(
+                "No write access to {}. Add your user to the 'input' group (or add a udev rule) and log in again",
+                UINPUT_PATH
+            ))
+        } else {
+            OutputError::InjectionFailed(format!("Failed to create uinput device: {}", e))
+        }
+    };
+
+    let mut keys = AttributeSet::<evdev::Key>::new();
+    for code in 1..=248 {
+        keys.insert(evdev::Key::new(code));
+    }
+    VirtualDeviceBuilder::new()
+        .map_err(uinput_error)?
+        .name(DEVICE_NAME)
+        .with_keys(&keys)
+        .map_err(uinput_error)?
+        .build()
+        .map_err(uinput_error)
+}
+
+/// Send key events through the shared virtual keyboard (blocking)
+fn emit_events(
+    events: &[KeyEvent],
+    pre_type_delay: Duration,
+    type_delay: Duration,
+) -> Result<(), OutputError> {
+    let mut device = DEVICE.lock().unwrap_or_else(PoisonError::into_inner);
+    if device.is_none() {
+        *device = Some(create_device()?);
+        tracing::debug!("Created uinput device '{}'", DEVICE_NAME);
+        std::thread::sleep(DEVICE_SETTLE);
+    }
+    let device = device.as_mut().expect("device was just created");
+
+    std::thread::sleep(pre_type_delay);
+    for &(code, pressed) in events {
+        // emit() appends the SYN_REPORT that makes the compositor apply the event
+        device
+            .emit(&[InputEvent::new(EventType::KEY, code, pressed as i32)])
+            .map_err(|e| OutputError::InjectionFailed(format!("uinput write failed: {}", e)))?;
+        if !type_delay.is_zero() {
+            std::thread::sleep(type_delay);
+        }
+    }
+    Ok(())
+}
+
+#[async_trait::async_trait]
+impl TextOutput for UinputOutput {
+    async fn output(&self, text: &str) -> Result<(), OutputError> {
+        if text.is_empty() {
+            return Ok(());
+        }
+        self.send(&[OutputAction::Text(text.to_string())]).await
+    }
+
+    async fn output_actions(&self, actions: &[OutputAction]) -> Result<(), OutputError> {
+        self.send(actions).await
+    }
+
+    async fn is_available(&self) -> bool {
+        let writable = std::fs::OpenOptions::new()
+            .write(true)
+            .open(UINPUT_PATH)
+            .is_ok();
+        writable && xkbcommon_dl::xkbcommon_option().is_some()
+    }
+
+    fn name(&self) -> &'static str {
+        "uinput"
+    }
+
+    fn types_text(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::action::Modifier;
+    use std::collections::HashMap;
+
+    const KEY_ENTER: u16 = 28;
+    const KEY_RIGHTALT: u16 = 100;
+
+    /// German-like layout: 'z' on the Y key, '@' on AltGr+Q
+    fn keymap() -> Keymap {
+        Keymap::from_strokes(HashMap::from([
+            ('h', key_stroke(&[], 35)),
+            ('H', key_stroke(&[KEY_LEFTSHIFT], 35)),
+            ('i', key_stroke(&[], 23)),
+            ('z', key_stroke(&[], 21)),
+            ('@', key_stroke(&[KEY_RIGHTALT], 16)),
+        ]))
+    }
+
+    fn text(text: &str) -> Vec<OutputAction> {
+        vec![OutputAction::Text(text.to_string())]
+    }
+
+    fn output(auto_submit: bool, shift_enter_newlines: bool) -> UinputOutput {
+        UinputOutput::new(auto_submit, 0, 0, shift_enter_newlines, None, None)
+    }
+
+    #[test]
+    fn test_new() {
+        let output = UinputOutput::new(true, 5, 100, true, Some("de".to_string()), None);
+        assert!(output.auto_submit);
+        assert_eq!(output.type_delay_ms, 5);
+        assert_eq!(output.pre_type_delay_ms, 100);
+        assert!(output.shift_enter_newlines);
+        assert_eq!(output.xkb_layout.as_deref(), Some("de"));
+        assert_eq!(output.xkb_variant, None);
+    }
+
+    #[test]
+    fn test_plan_text() {
+        let events = output(false, false).plan(&text("Hi@"), &keymap());
+        assert_eq!(
+            events,
+            [
+                (KEY_LEFTSHIFT, true),
+                (35, true),
+                (35, false),
+                (KEY_LEFTSHIFT, false),
+                (23, true),
+                (23, false),
+                (KEY_RIGHTALT, true),
+                (16, true),
+                (16, false),
+                (KEY_RIGHTALT, false),
+            ]
+        );
+
+        // Unmappable characters are skipped
+        let events = output(false, false).plan(&text("h€i"), &keymap());
+        assert_eq!(events, [(35, true), (35, false), (23, true), (23, false)]);
+    }
+
+    #[test]
+    fn test_plan_newlines_and_submit() {
+        let events = output(false, false).plan(&text("h\r\ni"), &keymap());
+        assert_eq!(
+            events,
+            [
+                (35, true),
+                (35, false),
+                (KEY_ENTER, true),
+                (KEY_ENTER, false),
+                (23, true),
+                (23, false),
+            ]
+        );
+
+        let events = output(true, true).plan(&text("h\n"), &keymap());
+        assert_eq!(
+            events,
+            [
+                (35, true),
+                (35, false),
+                (KEY_LEFTSHIFT, true),
+                (KEY_ENTER, true),
+                (KEY_ENTER, false),
+                (KEY_LEFTSHIFT, false),
+                (KEY_ENTER, true),
+                (KEY_ENTER, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_key_combo() {
+        // Ctrl+Z uses the layout's Z key, Ctrl+Tab has a fixed code
+        let actions = [
+            OutputAction::Key(KeyCombo {
+                modifiers: vec![Modifier::Ctrl],
+                key: Key::Char('z'),
+            }),
+            OutputAction::Key(KeyCombo {
+                modifiers: vec![Modifier::Ctrl, Modifier::Shift],
+                key: Key::Tab,
+            }),
+        ];
+        let events = output(false, false).plan(&actions, &keymap());
+        assert_eq!(
+            events,
+            [
+                (29, true),
+                (21, true),
+                (21, false),
+                (29, false),
+                (29, true),
+                (KEY_LEFTSHIFT, true),
+                (15, true),
+                (15, false),
+                (KEY_LEFTSHIFT, false),
+                (29, false),
+            ]
+        );
+    }
+}
@@ -0,0 +1,236 @@
+//! Character to key code mapping for an XKB keyboard layout
+//!
+//! The uinput driver sends key codes, and the compositor turns them into
+//! characters with its own keymap. To type a character, we look up which key,
+//! with or without Shift and AltGr, produces it in the same layout.
+//!
+//! libxkbcommon is loaded at runtime, so it is not a build dependency.
+
+use crate::error::OutputError;
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::ptr;
+use xkbcommon_dl::{
+    xkb_context_flags, xkb_key_direction, xkb_keycode_t, xkb_keymap, xkb_keymap_compile_flags,
+    xkb_rule_names, xkb_state, xkbcommon_option, XkbCommon,
+};
+
+/// evdev code of the left Shift key
+pub const KEY_LEFTSHIFT: u16 = 42;
+/// evdev code of the right Alt key, AltGr on most layouts
+const KEY_RIGHTALT: u16 = 100;
+/// XKB key codes are evdev codes plus 8
+const EVDEV_OFFSET: xkb_keycode_t = 8;
+/// Highest evdev code considered (the main keyboard block)
+const MAX_EVDEV_CODE: u16 = 248;
+/// Keysym of the AltGr (third level) modifier
+const ISO_LEVEL3_SHIFT: u32 = 0xfe03;
+
+/// Keys to press for one character
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyStroke {
+    /// Modifier keys to hold (evdev codes)
+    pub modifiers: Vec<u16>,
+    /// Key to tap (evdev code)
+    pub code: u16,
+}
+
+/// Characters a keyboard layout can type without dead keys
+#[derive(Debug, Default)]
+pub struct Keymap {
+    strokes: HashMap<char, KeyStroke>,
+}
+
+impl Keymap {
+    /// Compile a layout ("de", "fr", ...); None uses the XKB defaults (usually "us")
+    pub fn new(layout: Option<&str>, variant: Option<&str>) -> Result<Self, OutputError> {
+        let xkb = xkbcommon_option().ok_or_else(|| {
+            OutputError::InjectionFailed("libxkbcommon could not be loaded".to_string())
+        })?;
+        let c_string = |name: Option<&str>| {
+            name.map(CString::new).transpose().map_err(|_| {
+                OutputError::InjectionFailed("Invalid keyboard layout name".to_string())
+            })
+        };
+        let layout_name = c_string(layout)?;
+        let variant_name = c_string(variant)?;
+        let names = xkb_rule_names {
+            rules: ptr::null(),
+            model: ptr::null(),
+            layout: layout_name.as_ref().map_or(ptr::null(), |s| s.as_ptr()),
+            variant: variant_name.as_ref().map_or(ptr::null(), |s| s.as_ptr()),
+            options: ptr::null(),
+        };
+
+        // SAFETY: the names outlive the call, and the keymap keeps its own
+        // reference to the context
+        let keymap = unsafe {
+            let context = (xkb.xkb_context_new)(xkb_context_flags::XKB_CONTEXT_NO_FLAGS);
+            if context.is_null() {
+                return Err(OutputError::InjectionFailed(
+                    "Failed to create XKB context".to_string(),
+                ));
+            }
+            let keymap = (xkb.xkb_keymap_new_from_names)(
+                context,
+                &names,
+                xkb_keymap_compile_flags::XKB_KEYMAP_COMPILE_NO_FLAGS,
+            );
+            (xkb.xkb_context_unref)(context);
+            keymap
+        };
+        if keymap.is_null() {
+            return Err(OutputError::InjectionFailed(format!(
+                "Unknown keyboard layout '{}'{}",
+                layout.unwrap_or("default"),
+                variant
+                    .map(|v| format!(" (variant '{}')", v))
+                    .unwrap_or_default()
+            )));
+        }
+
+        let strokes = collect_strokes(xkb, keymap);
+        // SAFETY: we own the only reference
+        unsafe { (xkb.xkb_keymap_unref)(keymap) };
+        Ok(Self { strokes })
+    }
+
+    /// Keys producing a character, if the layout has it
+    pub fn stroke(&self, c: char) -> Option<&KeyStroke> {
+        self.strokes.get(&c)
+    }
+
+    /// Number of characters the layout can type
+    pub fn len(&self) -> usize {
+        self.strokes.len()
+    }
+
+    /// Whether the layout types nothing at all
+    pub fn is_empty(&self) -> bool {
+        self.strokes.is_empty()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn from_strokes(strokes: HashMap<char, KeyStroke>) -> Self {
+        Self { strokes }
+    }
+}
+
+/// Keyboard state with some keys held down
+struct HeldKeys<'a> {
+    xkb: &'a XkbCommon,
+    state: *mut xkb_state,
+}
+
+impl<'a> HeldKeys<'a> {
+    fn new(xkb: &'a XkbCommon, keymap: *mut xkb_keymap, held: &[u16]) -> Option<Self> {
+        // SAFETY: keymap is a valid keymap for the lifetime of the state
+        let state = unsafe { (xkb.xkb_state_new)(keymap) };
+        if state.is_null() {
+            return None;
+        }
+        for &code in held {
+            // SAFETY: state is valid; unknown key codes are ignored
+            unsafe {
+                (xkb.xkb_state_update_key)(
+                    state,
+                    code as xkb_keycode_t + EVDEV_OFFSET,
+                    xkb_key_direction::XKB_KEY_DOWN,
+                )
+            };
+        }
+        Some(Self { xkb, state })
+    }
+
+    /// Character typed by a key, if any
+    fn char(&self, code: u16) -> Option<char> {
+        // SAFETY: state is valid
+        let utf32 =
+            unsafe { (self.xkb.xkb_state_key_get_utf32)(self.state, code as u32 + EVDEV_OFFSET) };
+        char::from_u32(utf32).filter(|&c| c != '\0')
+    }
+
+    /// Keysym of a key
+    fn keysym(&self, code: u16) -> u32 {
+        // SAFETY: state is valid
+        unsafe { (self.xkb.xkb_state_key_get_one_sym)(self.state, code as u32 + EVDEV_OFFSET) }
+    }
+}
+
+impl Drop for HeldKeys<'_> {
+    fn drop(&mut self) {
+        // SAFETY: we own the only reference
+        unsafe { (self.xkb.xkb_state_unref)(self.state) };
+    }
+}
+
+/// Find the keys for every character, preferring fewer modifiers and lower codes
+fn collect_strokes(xkb: &XkbCommon, keymap: *mut xkb_keymap) -> HashMap<char, KeyStroke> {
+    let mut strokes = HashMap::new();
+    let Some(base) = HeldKeys::new(xkb, keymap, &[]) else {
+        return strokes;
+    };
+    let codes = 1..=MAX_EVDEV_CODE;
+
+    // AltGr is usually right Alt, but layouts can put it elsewhere
+    let level3 = std::iter::once(KEY_RIGHTALT)
+        .chain(codes.clone())
+        .find(|&code| base.keysym(code) == ISO_LEVEL3_SHIFT);
+    let mut combinations = vec![vec![], vec![KEY_LEFTSHIFT]];
+    if let Some(level3) = level3 {
+        combinations.push(vec![level3]);
+        combinations.push(vec![KEY_LEFTSHIFT, level3]);
+    }
+
+    for modifiers in combinations {
+        let Some(state) = HeldKeys::new(xkb, keymap, &modifiers) else {
+            continue;
+        };
+        for code in codes.clone() {
+            if let Some(c) = state.char(code).filter(|c| !c.is_control() || *c == '\t') {
+                strokes.entry(c).or_insert_with(|| KeyStroke {
+                    modifiers: modifiers.clone(),
+                    code,
+                });
+            }
+        }
+    }
+    strokes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stroke(modifiers: &[u16], code: u16) -> KeyStroke {
+        KeyStroke {
+            modifiers: modifiers.to_vec(),
+            code,
+        }
+    }
+
+    #[test]
+    fn test_layouts() {
+        // Needs libxkbcommon and the XKB data files at runtime
+        if xkbcommon_option().is_none() || Keymap::new(Some("us"), None).is_err() {
+            eprintln!("libxkbcommon unavailable, skipping");
+            return;
+        }
+
+        let us = Keymap::new(Some("us"), None).unwrap();
+        assert_eq!(us.stroke('a'), Some(&stroke(&[], 30)));
+        assert_eq!(us.stroke('A'), Some(&stroke(&[KEY_LEFTSHIFT], 30)));
+        assert_eq!(us.stroke('z'), Some(&stroke(&[], 44)));
+        assert_eq!(us.stroke(' '), Some(&stroke(&[], 57)));
+        assert_eq!(us.stroke('\t'), Some(&stroke(&[], 15)));
+        assert_eq!(us.stroke('ä'), None);
+
+        // German: Y and Z swapped, umlauts, @ on AltGr+Q
+        let de = Keymap::new(Some("de"), None).unwrap();
+        assert_eq!(de.stroke('z'), Some(&stroke(&[], 21)));
+        assert_eq!(de.stroke('ä'), Some(&stroke(&[], 40)));
+        assert_eq!(de.stroke('@'), Some(&stroke(&[KEY_RIGHTALT], 16)));
+
+        assert!(Keymap::new(Some("no-such-layout"), None).is_err());
+    }
+}
@@ -8,6 +8,7 @@
 //! - ydotoold daemon running (systemctl --user start ydotool)
 //! - User in 'input' group
 
+use super::action::{actions_text, KeyCombo, OutputAction};
 use super::TextOutput;
 use crate::error::OutputError;
 use std::process::Stdio;
@@ -91,24 +92,9 @@ impl YdotoolOutput {
             .status()
             .await;
     }
-}
-
-#[async_trait::async_trait]
-impl TextOutput for YdotoolOutput {
-    async fn output(&self, text: &str) -> Result<(), OutputError> {
-        if text.is_empty() {
-            return Ok(());
-        }
-
-        // Pre-typing delay if configured
-        if self.pre_type_delay_ms > 0 {
-            tracing::debug!(
-                "ydotool: sleeping {}ms before typing",
-                self.pre_type_delay_ms
-            );
-            tokio::time::sleep(Duration::from_millis(self.pre_type_delay_ms as u64)).await;
-        }
 
+    /// Type text using ydotool type
+    async fn type_text(&self, text: &str) -> Result<(), OutputError> {
         let mut cmd = Command::new("ydotool");
         cmd.arg("type");
 
@@ -159,7 +145,44 @@ impl TextOutput for YdotoolOutput {
             return Err(OutputError::InjectionFailed(stderr.to_string()));
         }
 
-        // Send Enter key if configured
+        Ok(())
+    }
+
+    /// Press a key combination using ydotool key (evdev key codes)
+    ///
+    /// Letter keys are sent by their US-layout position; see `Key::evdev_code`.
+    async fn send_key(&self, combo: &KeyCombo) -> Result<(), OutputError> {
+        let args = combo.ydotool_args();
+        tracing::debug!("Running: ydotool key {}", args.join(" "));
+
+        let output = Command::new("ydotool")
+            .arg("key")
+            .args(&args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .output()
+            .await
+            .map_err(|e| {
+                if e.kind() == std::io::ErrorKind::NotFound {
+                    OutputError::YdotoolNotFound
+                } else {
+                    OutputError::InjectionFailed(e.to_string())
+                }
+            })?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(OutputError::InjectionFailed(format!(
+                "ydotool key {} failed: {}",
+                combo, stderr
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Send Enter key if auto_submit is configured
+    async fn submit(&self) -> Result<(), OutputError> {
         // ydotool key uses evdev key codes: 28 is KEY_ENTER
         // Format: keycode:press (1) then keycode:release (0)
         if self.auto_submit {
@@ -179,6 +202,29 @@ impl TextOutput for YdotoolOutput {
             }
         }
 
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl TextOutput for YdotoolOutput {
+    async fn output(&self, text: &str) -> Result<(), OutputError> {
+        if text.is_empty() {
+            return Ok(());
+        }
+
+        // Pre-typing delay if configured
+        if self.pre_type_delay_ms > 0 {
+            tracing::debug!(
+                "ydotool: sleeping {}ms before typing",
+                self.pre_type_delay_ms
+            );
+            tokio::time::sleep(Duration::from_millis(self.pre_type_delay_ms as u64)).await;
+        }
+
+        self.type_text(text).await?;
+        self.submit().await?;
+
         // Send notification if enabled
         if self.notify {
             self.send_notification(text).await;
@@ -187,6 +233,34 @@ impl TextOutput for YdotoolOutput {
         Ok(())
     }
 
+    async fn output_actions(&self, actions: &[OutputAction]) -> Result<(), OutputError> {
+        if actions.is_empty() {
+            return Ok(());
+        }
+
+        // Pre-typing delay if configured
+        if self.pre_type_delay_ms > 0 {
+            tokio::time::sleep(Duration::from_millis(self.pre_type_delay_ms as u64)).await;
+        }
+
+        for action in actions {
+            match action {
+                OutputAction::Text(text) if text.is_empty() => {}
+                OutputAction::Text(text) => self.type_text(text).await?,
+                OutputAction::Key(combo) => self.send_key(combo).await?,
+            }
+        }
+        self.submit().await?;
+
+        // Send notification if enabled
+        let text = actions_text(actions);
+        if self.notify && !text.is_empty() {
+            self.send_notification(&text).await;
+        }
+
+        Ok(())
+    }
+
     async fn is_available(&self) -> bool {
         // Check if ydotool exists in PATH
         let which_result = Command::new("which")
@@ -215,6 +289,10 @@ impl TextOutput for YdotoolOutput {
     fn name(&self) -> &'static str {
         "ydotool"
     }
+
+    fn types_text(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,473 @@
+//! Configurable text pipeline
+//!
+//! `[[pipeline]]` lists the stages a transcription goes through, in order:
+//! built-in text stages (spoken punctuation, replacements, hallucination
+//! filter, normalization, ...), the profile's post-process command and
+//! extra shell commands. Stages can be limited to profiles.
+//!
+//! Every stage is timed. A stage that fails passes its input on: commands
+//! that fail, time out or print nothing are skipped, like the post-process
+//! command always has been.
+//!
+//! Commands using the JSON protocol can also pick the output mode or
+//! discard the transcription; later stages override earlier choices, and a
+//! discard stops the pipeline.
+//!
+//! Without `[[pipeline]]`, text goes through the built-in stages in their
+//! default order, then the post-process command.
+
+use crate::config::{PipelineStage, PipelineStageKind, PostProcessConfig};
+use crate::output::action::OutputAction;
+use crate::output::post_process::{PostProcessMetadata, PostProcessOutput, PostProcessor};
+use crate::text::{ProcessContext, TextProcessor};
+use std::time::{Duration, Instant};
+
+/// Default timeout for command stages
+const DEFAULT_COMMAND_TIMEOUT_MS: u64 = 30000;
+
+/// A configured stage, with its command ready to run
+struct Stage {
+    config: PipelineStage,
+    /// Shell command of a "command" stage
+    command: Option<PostProcessor>,
+}
+
+/// Ordered text processing stages from `[[pipeline]]`
+pub struct Pipeline {
+    stages: Vec<Stage>,
+}
+
+impl Pipeline {
+    /// Build the pipeline; "command" stages without a command are skipped with a warning
+    pub fn new(stages: &[PipelineStage]) -> Self {
+        let stages = stages
+            .iter()
+            .filter_map(|stage| {
+                let command = match (stage.stage, &stage.command) {
+                    (PipelineStageKind::Command, Some(command)) => {
+                        Some(PostProcessor::new(&PostProcessConfig {
+                            command: command.clone(),
+                            timeout_ms: stage.timeout_ms.unwrap_or(DEFAULT_COMMAND_TIMEOUT_MS),
+                            protocol: stage.protocol,
+                        }))
+                    }
+                    (PipelineStageKind::Command, None) => {
+                        tracing::warn!("Skipping pipeline stage {}: no command", stage.label());
+                        return None;
+                    }
+                    (_, Some(_)) => {
+                        tracing::warn!(
+                            "Pipeline stage {}: command is only used by \"command\" stages",
+                            stage.label()
+                        );
+                        None
+                    }
+                    (_, None) => None,
+                };
+                Some(Stage {
+                    config: stage.clone(),
+                    command,
+                })
+            })
+            .collect();
+        Self { stages }
+    }
+
+    /// Whether no stages are configured (the default order applies)
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Recognize voice editing commands, running the leading built-in stages
+    /// (see [`run_leading`](Self::run_leading)) on each piece of dictated text
+    /// Without stages, dictated text gets the default text processing
+    pub fn process_actions(
+        &self,
+        text: &str,
+        processor: &TextProcessor,
+        context: ProcessContext,
+        profile: Option<&str>,
+        previous_output_chars: usize,
+    ) -> Vec<OutputAction> {
+        if self.is_empty() {
+            return processor.process_actions(text, context, previous_output_chars);
+        }
+        processor.process_actions_with(text, previous_output_chars, |t| {
+            self.run_leading(t, processor, context, profile)
+        })
+    }
+
+    /// Run the built-in stages listed before the first command or
+    /// post-process stage; [`run_rest`](Self::run_rest) runs the others
+    pub fn run_leading(
+        &self,
+        text: &str,
+        processor: &TextProcessor,
+        context: ProcessContext,
+        profile: Option<&str>,
+    ) -> String {
+        self.active(profile)
+            .take_while(|stage| !stage.config.stage.is_external())
+            .fold(text.to_string(), |result, stage| {
+                processor.apply_stage(stage.config.stage, &result, context, &mut |_, _| {})
+            })
+    }
+
+    /// Run every stage in order
+    /// `post_processor` is used by "post_process" stages (the profile's or the default)
+    pub async fn run(
+        &self,
+        text: &str,
+        processor: &TextProcessor,
+        context: ProcessContext<'_>,
+        metadata: &PostProcessMetadata<'_>,
+        post_processor: Option<&PostProcessor>,
+    ) -> PostProcessOutput {
+        self.run_observed(
+            text,
+            &|stage, text| processor.apply_stage(stage, text, context, &mut |_, _| {}),
+            metadata,
+            post_processor,
+            false,
+            &mut |_, _, _| {},
+        )
+        .await
+    }
+
+    /// Run the stages from the first command or post-process stage on, in
+    /// order, on text that [`run_leading`](Self::run_leading) already processed
+    pub async fn run_rest(
+        &self,
+        text: &str,
+        processor: &TextProcessor,
+        context: ProcessContext<'_>,
+        metadata: &PostProcessMetadata<'_>,
+        post_processor: Option<&PostProcessor>,
+    ) -> PostProcessOutput {
+        self.run_observed(
+            text,
+            &|stage, text| processor.apply_stage(stage, text, context, &mut |_, _| {}),
+            metadata,
+            post_processor,
+            true,
+            &mut |_, _, _| {},
+        )
+        .await
+    }
+
+    /// Run every stage in order and record the output and duration of each
+    /// Returns (stage label, text after the stage, duration) triples, in order
+    pub async fn trace(
+        &self,
+        text: &str,
+        processor: &TextProcessor,
+        context: ProcessContext<'_>,
+        metadata: &PostProcessMetadata<'_>,
+        post_processor: Option<&PostProcessor>,
+    ) -> Vec<(String, String, Duration)> {
+        let mut steps = Vec::new();
+        self.run_observed(
+            text,
+            &|stage, text| processor.apply_stage(stage, text, context, &mut |_, _| {}),
+            metadata,
+            post_processor,
+            false,
+            &mut |label, output, elapsed| {
+                steps.push((label.to_string(), output.to_string(), elapsed))
+            },
+        )
+        .await;
+        steps
+    }
+
+    /// Run the stages in order, built-in ones with `apply`; `skip_leading`
+    /// skips the ones [`run_leading`](Self::run_leading) runs
+    async fn run_observed(
+        &self,
+        text: &str,
+        apply: &(dyn Fn(PipelineStageKind, &str) -> String + Sync),
+        metadata: &PostProcessMetadata<'_>,
+        post_processor: Option<&PostProcessor>,
+        skip_leading: bool,
+        observe: &mut (dyn FnMut(&str, &str, Duration) + Send),
+    ) -> PostProcessOutput {
+        let mut result = PostProcessOutput::text(text.to_string());
+        let stages = self
+            .active(metadata.profile)
+            .skip_while(|stage| skip_leading && !stage.config.stage.is_external());
+        for stage in stages {
+            let label = stage.config.label();
+            let started = Instant::now();
+            let output = match stage.config.stage {
+                PipelineStageKind::Command => match stage.command {
+                    Some(ref command) => command.process_with(&result.text, metadata).await,
+                    None => PostProcessOutput::text(result.text.clone()),
+                },
+                PipelineStageKind::PostProcess => match post_processor {
+                    Some(post_processor) => match stage.config.timeout_ms {
+                        Some(ms) => {
+                            post_processor
+                                .with_timeout(Duration::from_millis(ms))
+                                .process_with(&result.text, metadata)
+                                .await
+                        }
+                        None => post_processor.process_with(&result.text, metadata).await,
+                    },
+                    None => {
+                        tracing::debug!("Pipeline stage {}: no post-process command", label);
+                        PostProcessOutput::text(result.text.clone())
+                    }
+                },
+                builtin => PostProcessOutput::text(apply(builtin, &result.text)),
+            };
+            let elapsed = started.elapsed();
+            tracing::debug!(
+                "Pipeline stage {} ({} ms): {:?}",
+                label,
+                elapsed.as_millis(),
+                output.text
+            );
+            observe(&label, &output.text, elapsed);
+            result = PostProcessOutput {
+                text: output.text,
+                output_mode: output.output_mode.or(result.output_mode),
+                auto_submit: output.auto_submit.or(result.auto_submit),
+                discarded: output.discarded,
+            };
+            if result.discarded {
+                break;
+            }
+        }
+        result
+    }
+
+    /// Stages that run for a recording with this profile
+    fn active<'a>(&'a self, profile: Option<&'a str>) -> impl Iterator<Item = &'a Stage> + 'a {
+        self.stages
+            .iter()
+            .filter(move |stage| stage.config.applies_to(profile))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{OutputMode, PostProcessProtocol, TextConfig};
+    use crate::output::action::actions_text;
+
+    fn stage(kind: PipelineStageKind) -> PipelineStage {
+        PipelineStage {
+            stage: kind,
+            name: None,
+            command: None,
+            timeout_ms: None,
+            enabled: true,
+            profiles: Vec::new(),
+            protocol: PostProcessProtocol::Text,
+        }
+    }
+
+    fn command(command: &str) -> PipelineStage {
+        PipelineStage {
+            command: Some(command.to_string()),
+            ..stage(PipelineStageKind::Command)
+        }
+    }
+
+    fn processor() -> TextProcessor {
+        let mut config = TextConfig::default();
+        config
+            .replacements
+            .insert("vox type".to_string(), "voxtype".to_string());
+        TextProcessor::new(&config)
+    }
+
+    #[tokio::test]
+    async fn test_stages_run_in_order() {
+        let processor = processor();
+        let context = ProcessContext::default();
+        let meta = PostProcessMetadata::default();
+
+        // Spoken punctuation runs even though [text] leaves it disabled
+        let pipeline = Pipeline::new(&[
+            command("tr a-z A-Z"),
+            stage(PipelineStageKind::Replacements),
+            stage(PipelineStageKind::SpokenPunctuation),
+        ]);
+        assert_eq!(
+            pipeline
+                .run("vox type rocks period", &processor, context, &meta, None)
+                .await
+                .text,
+            "voxtype ROCKS."
+        );
+
+        let pipeline = Pipeline::new(&[
+            stage(PipelineStageKind::Replacements),
+            command("tr a-z A-Z"),
+        ]);
+        assert_eq!(
+            pipeline
+                .run("vox type rocks", &processor, context, &meta, None)
+                .await
+                .text,
+            "VOXTYPE ROCKS"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_stage_passes_input_on() {
+        let processor = processor();
+        let pipeline = Pipeline::new(&[
+            command("exit 3"),
+            PipelineStage {
+                timeout_ms: Some(100),
+                ..command("sleep 10")
+            },
+            stage(PipelineStageKind::Replacements),
+        ]);
+        let steps = pipeline
+            .trace(
+                "vox type",
+                &processor,
+                ProcessContext::default(),
+                &PostProcessMetadata::default(),
+                None,
+            )
+            .await;
+        let outputs: Vec<&str> = steps.iter().map(|(_, out, _)| out.as_str()).collect();
+        assert_eq!(outputs, ["vox type", "vox type", "voxtype"]);
+        assert!(steps[1].2 < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_profiles_and_post_process() {
+        let processor = processor();
+        let pipeline = Pipeline::new(&[
+            PipelineStage {
+                profiles: vec!["shout".to_string()],
+                ..command("tr a-z A-Z")
+            },
+            PipelineStage {
+                profiles: vec!["default".to_string()],
+                ..stage(PipelineStageKind::Replacements)
+            },
+            stage(PipelineStageKind::PostProcess),
+        ]);
+        let post_processor = PostProcessor::new(&PostProcessConfig {
+            command: "sed 's/$/!/'".to_string(),
+            timeout_ms: 5000,
+            protocol: PostProcessProtocol::Text,
+        });
+        let context = ProcessContext::default();
+        let shout = PostProcessMetadata {
+            profile: Some("shout"),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            pipeline
+                .run(
+                    "vox type",
+                    &processor,
+                    context,
+                    &PostProcessMetadata::default(),
+                    Some(&post_processor)
+                )
+                .await
+                .text,
+            "voxtype!"
+        );
+        assert_eq!(
+            pipeline
+                .run("vox type", &processor, context, &shout, None)
+                .await
+                .text,
+            "VOX TYPE"
+        );
+        // Only the built-in stages before the first command run between voice commands
+        assert_eq!(
+            pipeline.run_leading("vox type", &processor, context, Some("shout")),
+            "vox type"
+        );
+        assert_eq!(
+            pipeline.run_leading("vox type", &processor, context, None),
+            "voxtype"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_voice_commands_run_each_stage_once() {
+        let mut config = TextConfig::default();
+        config.commands.enabled = true;
+        config
+            .replacements
+            .insert("vox type".to_string(), "voxtype".to_string());
+        let processor = TextProcessor::new(&config);
+        let context = ProcessContext::default();
+        let meta = PostProcessMetadata::default();
+        let pipeline = Pipeline::new(&[
+            stage(PipelineStageKind::Replacements),
+            command("sed 's/rocks/vox type/'"),
+            stage(PipelineStageKind::Replacements),
+        ]);
+
+        let actions =
+            pipeline.process_actions("vox type new line rocks", &processor, context, None, 0);
+        let text = actions_text(&actions);
+        assert_eq!(text, "voxtype\nrocks");
+        let output = pipeline
+            .run_rest(&text, &processor, context, &meta, None)
+            .await;
+        assert_eq!(output.text, "voxtype\nvoxtype");
+
+        let actions =
+            pipeline.process_actions("foo. Delete that. bar", &processor, context, None, 0);
+        assert_eq!(actions_text(&actions), "bar");
+    }
+
+    #[tokio::test]
+    async fn test_json_stages() {
+        let processor = processor();
+        // JSON commands read their input before replying
+        let json = |command: &str| PipelineStage {
+            protocol: PostProcessProtocol::Json,
+            ..self::command(command)
+        };
+        let context = ProcessContext::default();
+        let meta = PostProcessMetadata {
+            profile: Some("chat"),
+            ..Default::default()
+        };
+
+        // Choices carry over to later stages, which can override them
+        let pipeline = Pipeline::new(&[
+            json(r#"cat >/dev/null; echo '{"output_mode": "clipboard", "auto_submit": true}'"#),
+            json(r#"cat >/dev/null; echo '{"text": "done", "auto_submit": false}'"#),
+            stage(PipelineStageKind::Replacements),
+        ]);
+        let output = pipeline
+            .run("vox type", &processor, context, &meta, None)
+            .await;
+        assert_eq!(output.text, "done");
+        assert_eq!(output.output_mode, Some(OutputMode::Clipboard));
+        assert_eq!(output.auto_submit, Some(false));
+
+        // A discard stops the pipeline
+        let pipeline = Pipeline::new(&[
+            json(r#"cat >/dev/null; echo '{"discard": true}'"#),
+            command("echo never"),
+        ]);
+        let output = pipeline
+            .run("vox type", &processor, context, &meta, None)
+            .await;
+        assert!(output.discarded);
+        assert_eq!(output.text, "");
+    }
+
+    #[test]
+    fn test_command_stage_without_command_is_skipped() {
+        let pipeline = Pipeline::new(&[stage(PipelineStageKind::Command)]);
+        assert!(pipeline.is_empty());
+    }
+}
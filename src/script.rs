@@ -0,0 +1,422 @@
+//! Text transform scripts
+//!
+//! Small transforms don't need a post-process command (a process per
+//! dictation, and shell skills). Scripts are Rhai files in the scripts
+//! directory, each defining a `transform` function:
+//!
+//! ```rhai
+//! fn transform(text, meta) {
+//!     if meta.profile == "chat" {
+//!         return text.to_lower();
+//!     }
+//!     text
+//! }
+//! ```
+//!
+//! `meta` has the recording's `profile`, `language`, `duration` (seconds) and
+//! focused `app`; unknown values are `()`. A script returns:
+//! - a string: the new text, passed on to the next script
+//! - `()`: the text unchanged
+//! - an array of strings (text to type) and `#{ key: "ctrl+enter" }` maps
+//!   (key presses); the actions are output as they are and later scripts
+//!   are skipped
+//!
+//! An empty string discards the transcription, also skipping later scripts.
+//! Scripts run in file name order and are reloaded when a file changes. They
+//! can't touch files or run commands, and a script that fails or runs too
+//! long passes its input on.
+
+use crate::config::ScriptsConfig;
+use crate::output::action::{actions_text, OutputAction};
+use rhai::{Dynamic, Engine, Map, Scope, AST};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+/// Function each script defines
+const ENTRY_POINT: &str = "transform";
+
+/// What scripts know about a recording
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ScriptMetadata<'a> {
+    /// Active profile name
+    pub profile: Option<&'a str>,
+    /// Transcription language
+    pub language: Option<&'a str>,
+    /// Length of the recorded audio
+    pub duration: Duration,
+    /// Application of the focused window, known when [auto_profile] rules are set
+    pub app: Option<&'a str>,
+}
+
+impl ScriptMetadata<'_> {
+    fn to_map(self) -> Map {
+        let optional = |value: Option<&str>| value.map_or(Dynamic::UNIT, |v| v.into());
+        let mut map = Map::new();
+        map.insert("profile".into(), optional(self.profile));
+        map.insert("language".into(), optional(self.language));
+        map.insert(
+            "duration".into(),
+            Dynamic::from_float(self.duration.as_secs_f64() as rhai::FLOAT),
+        );
+        map.insert("app".into(), optional(self.app));
+        map
+    }
+}
+
+/// Compiled scripts and the file state they were compiled from
+#[derive(Default)]
+struct Loaded {
+    /// Script files and their modification times, in name order
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+    /// Script name and compiled script, for scripts that compiled
+    scripts: Vec<(String, AST)>,
+}
+
+/// Runs the transform scripts in a directory
+pub struct ScriptRunner {
+    dir: PathBuf,
+    engine: Engine,
+    loaded: Mutex<Loaded>,
+}
+
+impl ScriptRunner {
+    /// Create a runner for the scripts in `dir`
+    /// Scripts are loaded on first use, so the directory may not exist yet
+    pub fn new(dir: PathBuf, max_operations: u64) -> Self {
+        let mut engine = Engine::new();
+        engine.set_max_operations(max_operations);
+        engine.set_max_call_levels(32);
+        engine.set_max_string_size(1 << 20);
+        engine.set_max_array_size(10_000);
+        engine.set_max_map_size(10_000);
+        engine.disable_symbol("eval");
+        engine.on_print(|message| tracing::info!("Script: {}", message));
+        engine.on_debug(|message, source, position| {
+            tracing::debug!("Script {} {}: {}", source.unwrap_or(""), position, message)
+        });
+
+        Self {
+            dir,
+            engine,
+            loaded: Mutex::new(Loaded::default()),
+        }
+    }
+
+    /// Create the runner configured in `[scripts]`
+    ///
+    /// Returns None when scripts are disabled.
+    pub fn from_config(config: &ScriptsConfig) -> Option<Self> {
+        if !config.enabled {
+            return None;
+        }
+        let dir = config.resolve_dir()?;
+        Some(Self::new(dir, config.max_operations))
+    }
+
+    /// Scripts directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Names of the scripts that compiled, in the order they run
+    pub fn script_names(&self) -> Vec<String> {
+        let mut loaded = self.loaded.lock().unwrap();
+        self.refresh(&mut loaded);
+        loaded
+            .scripts
+            .iter()
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Run every script on the text
+    /// Returns the actions to output; no actions (or empty text) discards it
+    pub fn run(&self, text: &str, metadata: &ScriptMetadata) -> Vec<OutputAction> {
+        self.run_observed(text, metadata, &mut |_, _| {})
+    }
+
+    /// Run every script and record the result of each
+    /// Returns (script name, result) pairs, in order
+    pub fn trace(&self, text: &str, metadata: &ScriptMetadata) -> Vec<(String, Vec<OutputAction>)> {
+        let mut steps = Vec::new();
+        self.run_observed(text, metadata, &mut |name, actions| {
+            steps.push((name.to_string(), actions.to_vec()))
+        });
+        steps
+    }
+
+    fn run_observed(
+        &self,
+        text: &str,
+        metadata: &ScriptMetadata,
+        observe: &mut dyn FnMut(&str, &[OutputAction]),
+    ) -> Vec<OutputAction> {
+        let mut loaded = self.loaded.lock().unwrap();
+        self.refresh(&mut loaded);
+
+        let mut result = vec![OutputAction::Text(text.to_string())];
+        for (name, ast) in &loaded.scripts {
+            let input = actions_text(&result);
+            let output = self.call(ast, &input, metadata);
+            let actions = match output {
+                Ok(Some(actions)) => actions,
+                Ok(None) => result.clone(),
+                Err(e) => {
+                    tracing::warn!("Script {} failed: {}", name, e);
+                    result.clone()
+                }
+            };
+            observe(name, &actions);
+            // Key presses and discarded text end the chain
+            let stop =
+                actions.iter().any(OutputAction::is_key) || actions_text(&actions).is_empty();
+            result = actions;
+            if stop {
+                break;
+            }
+        }
+        result.retain(|action| !matches!(action, OutputAction::Text(text) if text.is_empty()));
+        result
+    }
+
+    /// Call a script's transform function
+    /// Returns None when the script leaves the text unchanged
+    fn call(
+        &self,
+        ast: &AST,
+        text: &str,
+        metadata: &ScriptMetadata,
+    ) -> Result<Option<Vec<OutputAction>>, String> {
+        let mut scope = Scope::new();
+        let value: Dynamic = self
+            .engine
+            .call_fn(
+                &mut scope,
+                ast,
+                ENTRY_POINT,
+                (text.to_string(), metadata.to_map()),
+            )
+            .map_err(|e| e.to_string())?;
+        parse_result(value)
+    }
+
+    /// Recompile the scripts if files were added, removed or changed
+    fn refresh(&self, loaded: &mut Loaded) {
+        let files = script_files(&self.dir);
+        if files == loaded.files {
+            return;
+        }
+
+        let mut scripts = Vec::new();
+        for (path, _) in &files {
+            let name = path
+                .file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let ast = std::fs::read_to_string(path)
+                .map_err(|e| e.to_string())
+                .and_then(|source| self.engine.compile(source).map_err(|e| e.to_string()));
+            match ast {
+                Ok(ast) if ast.iter_functions().any(|f| f.name == ENTRY_POINT) => {
+                    scripts.push((name, ast))
+                }
+                Ok(_) => tracing::warn!("Script {:?} has no {} function", path, ENTRY_POINT),
+                Err(e) => tracing::warn!("Failed to load script {:?}: {}", path, e),
+            }
+        }
+        if !files.is_empty() || !loaded.files.is_empty() {
+            tracing::info!(
+                "Scripts loaded from {:?}: {}",
+                self.dir,
+                scripts
+                    .iter()
+                    .map(|(name, _)| name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        *loaded = Loaded { files, scripts };
+    }
+}
+
+/// `*.rhai` files in a directory with their modification times, in name order
+fn script_files(dir: &Path) -> Vec<(PathBuf, Option<SystemTime>)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<(PathBuf, Option<SystemTime>)> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rhai") && path.is_file())
+        .map(|path| {
+            let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// Turn a script's return value into output actions
+fn parse_result(value: Dynamic) -> Result<Option<Vec<OutputAction>>, String> {
+    if value.is_unit() {
+        return Ok(None);
+    }
+    if value.is_string() {
+        let text = value.into_string().map_err(|t| t.to_string())?;
+        return Ok(Some(vec![OutputAction::Text(text)]));
+    }
+    if value.is_array() {
+        let items = value.into_array().map_err(|t| t.to_string())?;
+        return items
+            .into_iter()
+            .map(parse_action)
+            .collect::<Result<Vec<_>, _>>()
+            .map(Some);
+    }
+    Err(format!(
+        "transform returned {}, expected a string, () or an array",
+        value.type_name()
+    ))
+}
+
+/// Parse one array item: a string to type or a `#{ key: "..." }` map
+fn parse_action(item: Dynamic) -> Result<OutputAction, String> {
+    if item.is_string() {
+        return Ok(OutputAction::Text(item.to_string()));
+    }
+    let type_name = item.type_name();
+    let key = item
+        .try_cast::<Map>()
+        .and_then(|map| map.get("key").cloned())
+        .filter(|key| key.is_string())
+        .ok_or_else(|| {
+            format!(
+                "unexpected action {}, expected a string or #{{ key: ... }}",
+                type_name
+            )
+        })?;
+    key.to_string().parse().map(OutputAction::Key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::action::KeyCombo;
+
+    fn runner(scripts: &[(&str, &str)]) -> (tempfile::TempDir, ScriptRunner) {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, source) in scripts {
+            std::fs::write(dir.path().join(name), source).unwrap();
+        }
+        let runner = ScriptRunner::new(dir.path().to_path_buf(), 10_000);
+        (dir, runner)
+    }
+
+    fn text(actions: Vec<OutputAction>) -> String {
+        actions_text(&actions)
+    }
+
+    #[test]
+    fn test_scripts_run_in_order_with_metadata() {
+        let (_dir, runner) = runner(&[
+            (
+                "20-suffix.rhai",
+                r#"fn transform(text, meta) {
+                    if meta.profile == () { return; }
+                    text + " (" + meta.profile + ", " + meta.language + ", " + meta.duration + ")"
+                }"#,
+            ),
+            (
+                "10-upper.rhai",
+                "fn transform(text, meta) { text.to_upper() }",
+            ),
+            ("notes.txt", "not a script"),
+        ]);
+        assert_eq!(runner.script_names(), ["10-upper", "20-suffix"]);
+
+        let metadata = ScriptMetadata {
+            profile: Some("chat"),
+            language: Some("en"),
+            duration: Duration::from_millis(2500),
+            app: None,
+        };
+        assert_eq!(
+            text(runner.run("hello", &metadata)),
+            "HELLO (chat, en, 2.5)"
+        );
+        assert_eq!(
+            text(runner.run("hello", &ScriptMetadata::default())),
+            "HELLO"
+        );
+    }
+
+    #[test]
+    fn test_actions_and_discard() {
+        let (_dir, runner) = runner(&[
+            (
+                "a.rhai",
+                r#"fn transform(text, meta) {
+                    if text == "noise" { return ""; }
+                    if text.ends_with(" send") {
+                        text.crop(0, text.len() - 5);
+                        return [text, #{ key: "enter" }];
+                    }
+                    text
+                }"#,
+            ),
+            ("b.rhai", r#"fn transform(text, meta) { text + "!" }"#),
+        ]);
+        let metadata = ScriptMetadata::default();
+        assert_eq!(
+            runner.run("see you send", &metadata),
+            [
+                OutputAction::Text("see you".to_string()),
+                OutputAction::Key("enter".parse::<KeyCombo>().unwrap())
+            ]
+        );
+        assert!(runner.run("noise", &metadata).is_empty());
+        assert_eq!(text(runner.run("hi", &metadata)), "hi!");
+    }
+
+    #[test]
+    fn test_failing_scripts_pass_text_on() {
+        let (_dir, runner) = runner(&[
+            ("1-syntax.rhai", "fn transform(text, meta) { text +"),
+            ("2-missing.rhai", "fn other(text) { text }"),
+            ("3-error.rhai", "fn transform(text, meta) { text.nope() }"),
+            ("4-loop.rhai", "fn transform(text, meta) { loop {} }"),
+            ("5-type.rhai", "fn transform(text, meta) { 42 }"),
+            ("6-eval.rhai", r#"fn transform(text, meta) { eval("1") }"#),
+            ("7-ok.rhai", r#"fn transform(text, meta) { text + "." }"#),
+        ]);
+        assert_eq!(
+            runner.script_names(),
+            ["3-error", "4-loop", "5-type", "7-ok"]
+        );
+        let steps = runner.trace("hi", &ScriptMetadata::default());
+        let outputs: Vec<String> = steps.into_iter().map(|(_, a)| text(a)).collect();
+        assert_eq!(outputs, ["hi", "hi", "hi", "hi."]);
+    }
+
+    #[test]
+    fn test_reload_on_change() {
+        let (dir, runner) = runner(&[("a.rhai", r#"fn transform(text, meta) { text + "1" }"#)]);
+        let metadata = ScriptMetadata::default();
+        assert_eq!(text(runner.run("v", &metadata)), "v1");
+
+        let path = dir.path().join("a.rhai");
+        std::fs::write(&path, r#"fn transform(text, meta) { text + "2" }"#).unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(10))
+            .unwrap();
+        assert_eq!(text(runner.run("v", &metadata)), "v2");
+
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(text(runner.run("v", &metadata)), "v");
+    }
+}
@@ -0,0 +1,315 @@
+//! Model download, checksum and integrity helpers
+//!
+//! Downloads are written to `<file>.part` and resumed with `curl -C -` when
+//! interrupted, then checked against the SHA-256 pinned in the model registry.
+//! Every installed model file also has its SHA-256 recorded in `SHA256SUMS`
+//! (sha256sum format) in the models directory. `voxtype setup model --verify`
+//! re-checks files against the registry, or for models without a pinned hash
+//! against the recorded one, which only catches changes made after install.
+
+use super::print_info;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Checksum manifest file name in the models directory
+pub const CHECKSUM_FILE: &str = "SHA256SUMS";
+
+/// Recorded SHA-256 checksums of installed model files
+///
+/// Keys are paths relative to the models directory
+/// (e.g., "ggml-base.en.bin" or "parakeet-tdt-0.6b-v3/vocab.txt").
+#[derive(Debug, Default)]
+pub struct ChecksumManifest {
+    path: PathBuf,
+    entries: BTreeMap<String, String>,
+}
+
+impl ChecksumManifest {
+    /// Load the manifest from a models directory (empty if it doesn't exist)
+    pub fn load(models_dir: &Path) -> Self {
+        let path = models_dir.join(CHECKSUM_FILE);
+        let entries = std::fs::read_to_string(&path)
+            .map(|contents| parse_checksums(&contents))
+            .unwrap_or_default();
+        Self { path, entries }
+    }
+
+    /// Recorded checksum for a file
+    pub fn get(&self, relative_path: &str) -> Option<&str> {
+        self.entries.get(relative_path).map(String::as_str)
+    }
+
+    /// Record the checksum of a file
+    pub fn set(&mut self, relative_path: &str, sha256: &str) {
+        self.entries
+            .insert(relative_path.to_string(), sha256.to_lowercase());
+    }
+
+    /// All recorded (path, checksum) entries
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Write the manifest back to disk
+    pub fn save(&self) -> anyhow::Result<()> {
+        let mut contents = String::new();
+        for (path, sha256) in &self.entries {
+            contents.push_str(&format!("{}  {}\n", sha256, path));
+        }
+        std::fs::write(&self.path, contents)?;
+        Ok(())
+    }
+}
+
+/// Parse sha256sum-format lines ("<hash>  <path>")
+fn parse_checksums(contents: &str) -> BTreeMap<String, String> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let (hash, path) = line.trim().split_once(char::is_whitespace)?;
+            // sha256sum marks binary mode with a leading '*'
+            let path = path.trim_start().trim_start_matches('*');
+            if is_sha256(hash) && !path.is_empty() {
+                Some((path.to_string(), hash.to_lowercase()))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Check whether a string is a hex-encoded SHA-256 digest
+pub fn is_sha256(value: &str) -> bool {
+    value.len() == 64 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Compute the SHA-256 of a file
+pub fn sha256_file(path: &Path) -> anyhow::Result<String> {
+    let mut file = std::fs::File::open(path)
+        .map_err(|e| anyhow::anyhow!("Failed to open {:?}: {}", path, e))?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)
+        .map_err(|e| anyhow::anyhow!("Failed to read {:?}: {}", path, e))?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Download a file with resume support and optional checksum verification
+///
+/// Partial data is kept in `<dest>.part` so an interrupted download can be
+/// resumed by running the same command again. Returns the file's SHA-256.
+pub fn download_file(
+    url: &str,
+    dest: &Path,
+    expected_sha256: Option<&str>,
+) -> anyhow::Result<String> {
+    let part = part_path(dest);
+
+    if part.exists() {
+        println!("Resuming partial download ({})...", format_size(&part));
+    }
+
+    // Use curl for downloading - it handles progress display, redirects and resume
+    let status = Command::new("curl")
+        .args([
+            "-L",             // Follow redirects
+            "--progress-bar", // Show progress bar
+            "-C",
+            "-", // Resume from the end of the partial file
+            "-o",
+        ])
+        .arg(&part)
+        .arg(url)
+        .status()
+        .map_err(|e| {
+            print_info("Please ensure curl is installed (e.g., 'sudo pacman -S curl')");
+            anyhow::anyhow!("curl not available: {}", e)
+        })?;
+
+    if !status.success() {
+        // Keep the partial file so the next attempt can resume
+        print_info("Run the same command again to resume the download");
+        anyhow::bail!(
+            "Download failed: curl exited with code {}",
+            status.code().unwrap_or(-1)
+        );
+    }
+
+    let actual = sha256_file(&part)?;
+    if let Some(expected) = expected_sha256 {
+        if !actual.eq_ignore_ascii_case(expected) {
+            let _ = std::fs::remove_file(&part);
+            anyhow::bail!(
+                "Checksum mismatch for {:?}\n  expected: {}\n  actual:   {}",
+                dest.file_name().unwrap_or_default(),
+                expected,
+                actual
+            );
+        }
+    }
+
+    std::fs::rename(&part, dest)?;
+    Ok(actual)
+}
+
+/// Copy a file into place atomically (via a .part file) and return its SHA-256
+pub fn install_file(source: &Path, dest: &Path) -> anyhow::Result<String> {
+    let part = part_path(dest);
+    std::fs::copy(source, &part)?;
+    let sha256 = sha256_file(&part)?;
+    std::fs::rename(&part, dest)?;
+    Ok(sha256)
+}
+
+/// Result of checking an installed file against its recorded checksum
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerifyStatus {
+    /// Checksum matches the recorded value
+    Ok,
+    /// Checksum differs from the recorded value
+    Mismatch { expected: String, actual: String },
+    /// No checksum recorded for this file
+    Unrecorded,
+    /// File is recorded but missing on disk
+    Missing,
+}
+
+/// Check an installed file against its expected checksum
+///
+/// The expected value comes from the model registry or the manifest.
+pub fn verify_file(
+    models_dir: &Path,
+    expected: Option<&str>,
+    relative_path: &str,
+) -> anyhow::Result<VerifyStatus> {
+    let path = models_dir.join(relative_path);
+    if !path.exists() {
+        return Ok(VerifyStatus::Missing);
+    }
+    let Some(expected) = expected else {
+        return Ok(VerifyStatus::Unrecorded);
+    };
+    let actual = sha256_file(&path)?;
+    if actual.eq_ignore_ascii_case(expected) {
+        Ok(VerifyStatus::Ok)
+    } else {
+        Ok(VerifyStatus::Mismatch {
+            expected: expected.to_string(),
+            actual,
+        })
+    }
+}
+
+/// Check whether a file starts with a GGML or GGUF header (whisper.cpp model)
+pub fn is_ggml_model(path: &Path) -> bool {
+    let mut magic = [0u8; 4];
+    std::fs::File::open(path)
+        .and_then(|mut f| f.read_exact(&mut magic))
+        .map(|_| &magic == b"lmgg" || &magic == b"GGUF")
+        .unwrap_or(false)
+}
+
+/// Path of the partial download for a destination file
+fn part_path(dest: &Path) -> PathBuf {
+    let mut name = dest.file_name().unwrap_or_default().to_os_string();
+    name.push(".part");
+    dest.with_file_name(name)
+}
+
+fn format_size(path: &Path) -> String {
+    let bytes = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    format!("{:.0} MB", bytes as f64 / 1024.0 / 1024.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HASH: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+    #[test]
+    fn test_is_sha256() {
+        assert!(is_sha256(HASH));
+        assert!(!is_sha256("abc"));
+        assert!(!is_sha256(&HASH.replace('a', "z")));
+    }
+
+    #[test]
+    fn test_parse_checksums() {
+        let contents = format!(
+            "{}  ggml-base.en.bin\n{} *parakeet/vocab.txt\ngarbage line\n",
+            HASH,
+            HASH.to_uppercase()
+        );
+        let entries = parse_checksums(&contents);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries["ggml-base.en.bin"], HASH);
+        assert_eq!(entries["parakeet/vocab.txt"], HASH);
+    }
+
+    #[test]
+    fn test_sha256_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("model.bin");
+        std::fs::write(&path, "test").unwrap();
+        assert_eq!(sha256_file(&path).unwrap(), HASH);
+        assert!(sha256_file(&dir.path().join("missing.bin")).is_err());
+    }
+
+    #[test]
+    fn test_manifest_roundtrip_and_verify() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("model.bin"), "test").unwrap();
+
+        let mut manifest = ChecksumManifest::load(dir.path());
+        assert_eq!(
+            verify_file(dir.path(), manifest.get("model.bin"), "model.bin").unwrap(),
+            VerifyStatus::Unrecorded
+        );
+
+        // SHA-256 of "test"
+        manifest.set("model.bin", HASH);
+        manifest.save().unwrap();
+
+        let manifest = ChecksumManifest::load(dir.path());
+        assert_eq!(manifest.get("model.bin"), Some(HASH));
+        assert_eq!(
+            verify_file(dir.path(), manifest.get("model.bin"), "model.bin").unwrap(),
+            VerifyStatus::Ok
+        );
+
+        std::fs::write(dir.path().join("model.bin"), "tampered").unwrap();
+        assert!(matches!(
+            verify_file(dir.path(), manifest.get("model.bin"), "model.bin").unwrap(),
+            VerifyStatus::Mismatch { .. }
+        ));
+
+        std::fs::remove_file(dir.path().join("model.bin")).unwrap();
+        assert_eq!(
+            verify_file(dir.path(), manifest.get("model.bin"), "model.bin").unwrap(),
+            VerifyStatus::Missing
+        );
+    }
+
+    #[test]
+    fn test_is_ggml_model() {
+        let dir = tempfile::tempdir().unwrap();
+        let ggml = dir.path().join("ggml.bin");
+        let other = dir.path().join("other.bin");
+        std::fs::write(&ggml, b"lmgg\x00\x00").unwrap();
+        std::fs::write(&other, b"<html>").unwrap();
+
+        assert!(is_ggml_model(&ggml));
+        assert!(!is_ggml_model(&other));
+    }
+
+    #[test]
+    fn test_part_path() {
+        assert_eq!(
+            part_path(Path::new("/models/ggml-base.bin")),
+            PathBuf::from("/models/ggml-base.bin.part")
+        );
+    }
+}
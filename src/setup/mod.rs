@@ -4,6 +4,7 @@
 //! - systemd service installation
 //! - Waybar configuration generation
 //! - Interactive model selection
+//! - Model download, import and checksum verification
 //! - Output chain detection
 //! - GPU backend management
 //! - Parakeet backend management
@@ -11,6 +12,7 @@
 
 pub mod compositor;
 pub mod dms;
+pub mod download;
 pub mod gpu;
 pub mod model;
 pub mod parakeet;
@@ -729,6 +731,25 @@ pub async fn run_checks(config: &Config) -> anyhow::Result<()> {
         all_ok = false;
     }
 
+    // Check the configured language against the model's language support
+    if let Some(entry) = crate::model_registry::registry().get(model_name) {
+        let unsupported: Vec<String> = config
+            .whisper
+            .language
+            .as_vec()
+            .into_iter()
+            .filter(|lang| !entry.supports_language(lang))
+            .collect();
+        if !unsupported.is_empty() {
+            print_warning(&format!(
+                "Model '{}' does not support language: {}",
+                model_name,
+                unsupported.join(", ")
+            ));
+            println!("       Use a multilingual model (without the .en suffix)");
+        }
+    }
+
     // Check Parakeet models (experimental)
     println!("\nParakeet Models (EXPERIMENTAL):");
 
@@ -789,6 +810,30 @@ pub async fn run_checks(config: &Config) -> anyhow::Result<()> {
         }
     }
 
+    // Check the external engine command
+    if config.engine == crate::config::TranscriptionEngine::External {
+        println!("\nExternal Engine:");
+        match config.external {
+            Some(ref external_config) if which::which(&external_config.command).is_ok() => {
+                print_success(&format!(
+                    "Engine command '{}' found",
+                    external_config.command
+                ));
+            }
+            Some(ref external_config) => {
+                print_failure(&format!(
+                    "Engine command '{}' not found",
+                    external_config.command
+                ));
+                all_ok = false;
+            }
+            None => {
+                print_failure("Engine set to 'external' but [external] config section is missing");
+                all_ok = false;
+            }
+        }
+    }
+
     // Summary
     println!("\n---");
     if all_ok {
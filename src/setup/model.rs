@@ -1,141 +1,28 @@
 //! Interactive model selection and download
 
+use super::download::{self, ChecksumManifest, VerifyStatus};
 use super::{print_failure, print_info, print_success, print_warning};
 use crate::config::{Config, TranscriptionEngine};
-use crate::transcribe::whisper::{get_model_filename, get_model_url};
+use crate::model_registry::{registry, ModelEntry};
+use crate::transcribe::whisper::get_model_filename;
 use std::io::{self, Write};
 use std::path::Path;
 use std::process::Command;
 
-/// Model information for display
-struct ModelInfo {
-    name: &'static str,
-    size_mb: u32,
-    description: &'static str,
-    english_only: bool,
-}
-
-const MODELS: &[ModelInfo] = &[
-    // Tiny models
-    ModelInfo {
-        name: "tiny",
-        size_mb: 75,
-        description: "Fastest, lowest accuracy",
-        english_only: false,
-    },
-    ModelInfo {
-        name: "tiny.en",
-        size_mb: 39,
-        description: "Fastest, lowest accuracy",
-        english_only: true,
-    },
-    // Base models
-    ModelInfo {
-        name: "base",
-        size_mb: 142,
-        description: "Good balance (default)",
-        english_only: false,
-    },
-    ModelInfo {
-        name: "base.en",
-        size_mb: 142,
-        description: "Good balance (default)",
-        english_only: true,
-    },
-    // Small models
-    ModelInfo {
-        name: "small",
-        size_mb: 466,
-        description: "Better accuracy",
-        english_only: false,
-    },
-    ModelInfo {
-        name: "small.en",
-        size_mb: 466,
-        description: "Better accuracy",
-        english_only: true,
-    },
-    // Medium models
-    ModelInfo {
-        name: "medium",
-        size_mb: 1500,
-        description: "High accuracy",
-        english_only: false,
-    },
-    ModelInfo {
-        name: "medium.en",
-        size_mb: 1500,
-        description: "High accuracy",
-        english_only: true,
-    },
-    // Large models
-    ModelInfo {
-        name: "large-v3",
-        size_mb: 3100,
-        description: "Best accuracy",
-        english_only: false,
-    },
-    ModelInfo {
-        name: "large-v3-turbo",
-        size_mb: 1600,
-        description: "Fast + accurate (recommended for GPU)",
-        english_only: false,
-    },
-];
-
-// =============================================================================
-// Parakeet Model Definitions
-// =============================================================================
-
-/// Parakeet model information for display and download
-struct ParakeetModelInfo {
-    name: &'static str,
-    size_mb: u32,
-    description: &'static str,
-    files: &'static [(&'static str, u64)], // (filename, expected_size_bytes)
-    huggingface_repo: &'static str,
-}
-
-const PARAKEET_MODELS: &[ParakeetModelInfo] = &[
-    ParakeetModelInfo {
-        name: "parakeet-tdt-0.6b-v3",
-        size_mb: 2600,
-        description: "TDT model with punctuation (recommended)",
-        files: &[
-            ("encoder-model.onnx", 43_825_971),
-            ("encoder-model.onnx.data", 2_620_260_352),
-            ("decoder_joint-model.onnx", 76_023_939),
-            ("vocab.txt", 96_179),
-            ("config.json", 97),
-        ],
-        huggingface_repo: "istupakov/parakeet-tdt-0.6b-v3-onnx",
-    },
-    ParakeetModelInfo {
-        name: "parakeet-tdt-0.6b-v3-int8",
-        size_mb: 670,
-        description: "TDT quantized, smaller/faster",
-        files: &[
-            ("encoder-model.int8.onnx", 683_671_552),
-            ("decoder_joint-model.int8.onnx", 19_087_667),
-            ("vocab.txt", 96_179),
-            ("config.json", 97),
-        ],
-        huggingface_repo: "istupakov/parakeet-tdt-0.6b-v3-onnx",
-    },
-];
-
 // =============================================================================
 // Whisper Model Functions
 // =============================================================================
 
 /// Check if a model name is valid (Whisper models)
 pub fn is_valid_model(name: &str) -> bool {
-    MODELS.iter().any(|m| m.name == name)
+    registry()
+        .models(TranscriptionEngine::Whisper)
+        .any(|m| m.name == name)
 }
 
 /// Get list of valid model names (for error messages)
 pub fn valid_model_names() -> Vec<&'static str> {
-    MODELS.iter().map(|m| m.name).collect()
+    registry().names(TranscriptionEngine::Whisper)
 }
 
 /// Run interactive model selection (single menu with all models)
@@ -154,19 +41,21 @@ pub async fn interactive_select() -> anyhow::Result<()> {
     let current_parakeet_model = config.parakeet.as_ref().map(|p| p.model.as_str());
 
     let parakeet_available = cfg!(feature = "parakeet");
-    let whisper_count = MODELS.len();
-    let parakeet_count = PARAKEET_MODELS.len();
+    let whisper_models: Vec<&ModelEntry> =
+        registry().models(TranscriptionEngine::Whisper).collect();
+    let parakeet_models: Vec<&ModelEntry> =
+        registry().models(TranscriptionEngine::Parakeet).collect();
+    let whisper_count = whisper_models.len();
+    let parakeet_count = parakeet_models.len();
     let total_count = whisper_count + if parakeet_available { parakeet_count } else { 0 };
 
     // --- Whisper Section ---
     println!("--- Whisper (OpenAI, 99+ languages) ---\n");
 
-    for (i, model) in MODELS.iter().enumerate() {
-        let filename = get_model_filename(model.name);
-        let model_path = models_dir.join(&filename);
-        let installed = model_path.exists();
+    for (i, model) in whisper_models.iter().enumerate() {
+        let installed = model.install_path(&models_dir).exists();
 
-        let is_current = is_whisper_engine && model.name == current_whisper_model;
+        let is_current = is_whisper_engine && model.name == *current_whisper_model;
         let star = if is_current { "*" } else { " " };
 
         let status = if installed {
@@ -175,10 +64,14 @@ pub async fn interactive_select() -> anyhow::Result<()> {
             ""
         };
 
-        let lang = if model.english_only { "en" } else { "multi" };
+        let lang = if model.languages.is_empty() {
+            "multi".to_string()
+        } else {
+            model.languages.join(",")
+        };
 
         println!(
-            " {}[{:>2}] {:<16} ({:>4} MB) {} - {} {}",
+            " {}[{:>2}] {:<20} ({:>4} MB) {} - {} {}",
             star,
             i + 1,
             model.name,
@@ -193,11 +86,12 @@ pub async fn interactive_select() -> anyhow::Result<()> {
     println!("\n--- Parakeet (NVIDIA FastConformer, English) ---\n");
 
     if parakeet_available {
-        for (i, model) in PARAKEET_MODELS.iter().enumerate() {
-            let model_path = models_dir.join(model.name);
+        for (i, model) in parakeet_models.iter().enumerate() {
+            let model_path = model.install_path(&models_dir);
             let installed = model_path.exists() && validate_parakeet_model(&model_path).is_ok();
 
-            let is_current = is_parakeet_engine && current_parakeet_model == Some(model.name);
+            let is_current =
+                is_parakeet_engine && current_parakeet_model == Some(model.name.as_str());
             let star = if is_current { "*" } else { " " };
 
             let status = if installed {
@@ -239,11 +133,11 @@ pub async fn interactive_select() -> anyhow::Result<()> {
     // Route to appropriate handler based on selection
     if selection <= whisper_count {
         // Whisper model selected
-        handle_whisper_selection(selection).await
+        handle_whisper_selection(whisper_models[selection - 1]).await
     } else if parakeet_available && selection <= total_count {
         // Parakeet model selected
         let parakeet_index = selection - whisper_count;
-        handle_parakeet_selection(parakeet_index).await
+        handle_parakeet_selection(parakeet_models[parakeet_index - 1]).await
     } else {
         println!("\nInvalid selection.");
         Ok(())
@@ -251,17 +145,9 @@ pub async fn interactive_select() -> anyhow::Result<()> {
 }
 
 /// Handle Whisper model selection (download/config)
-async fn handle_whisper_selection(selection: usize) -> anyhow::Result<()> {
+async fn handle_whisper_selection(model: &ModelEntry) -> anyhow::Result<()> {
     let models_dir = Config::models_dir();
-
-    if selection == 0 || selection > MODELS.len() {
-        println!("\nCancelled.");
-        return Ok(());
-    }
-
-    let model = &MODELS[selection - 1];
-    let filename = get_model_filename(model.name);
-    let model_path = models_dir.join(&filename);
+    let model_path = model.install_path(&models_dir);
 
     // Check if already installed
     if model_path.exists() {
@@ -280,7 +166,7 @@ async fn handle_whisper_selection(selection: usize) -> anyhow::Result<()> {
         match choice {
             "" | "1" => {
                 // Set as default without re-downloading
-                update_config_model(model.name)?;
+                update_config_model(&model.name)?;
                 restart_daemon_if_running().await;
                 return Ok(());
             }
@@ -295,26 +181,19 @@ async fn handle_whisper_selection(selection: usize) -> anyhow::Result<()> {
     }
 
     // Download the model
-    download_model(model.name)?;
+    download_entry(model)?;
 
     // Update config and restart daemon
-    update_config_model(model.name)?;
+    update_config_model(&model.name)?;
     restart_daemon_if_running().await;
 
     Ok(())
 }
 
 /// Handle Parakeet model selection (download/config)
-async fn handle_parakeet_selection(selection: usize) -> anyhow::Result<()> {
+async fn handle_parakeet_selection(model: &ModelEntry) -> anyhow::Result<()> {
     let models_dir = Config::models_dir();
-
-    if selection == 0 || selection > PARAKEET_MODELS.len() {
-        println!("\nCancelled.");
-        return Ok(());
-    }
-
-    let model = &PARAKEET_MODELS[selection - 1];
-    let model_path = models_dir.join(model.name);
+    let model_path = model.install_path(&models_dir);
 
     // Check if already installed
     if model_path.exists() && validate_parakeet_model(&model_path).is_ok() {
@@ -333,7 +212,7 @@ async fn handle_parakeet_selection(selection: usize) -> anyhow::Result<()> {
         match choice {
             "" | "1" => {
                 // Set as default without re-downloading
-                update_config_parakeet(model.name)?;
+                update_config_parakeet(&model.name)?;
                 restart_daemon_if_running().await;
                 return Ok(());
             }
@@ -348,10 +227,10 @@ async fn handle_parakeet_selection(selection: usize) -> anyhow::Result<()> {
     }
 
     // Download the model
-    download_parakeet_model_by_info(model)?;
+    download_entry(model)?;
 
     // Update config and restart daemon
-    update_config_parakeet(model.name)?;
+    update_config_parakeet(&model.name)?;
     restart_daemon_if_running().await;
 
     Ok(())
@@ -389,54 +268,433 @@ async fn restart_daemon_if_running() {
 }
 
 // =============================================================================
-// Whisper Download Functions
+// Download Functions
 // =============================================================================
 
 /// Download a specific Whisper model using curl
 pub fn download_model(model_name: &str) -> anyhow::Result<()> {
+    let model = registry()
+        .get(model_name)
+        .filter(|m| m.engine == TranscriptionEngine::Whisper)
+        .ok_or_else(|| anyhow::anyhow!("Unknown Whisper model: {}", model_name))?;
+
+    download_entry(model)
+}
+
+/// Download all files of a registry model
+///
+/// Interrupted downloads are resumed. Each file is checked against the
+/// SHA-256 pinned in the registry and its expected size when known. A hash
+/// served by the same host as the file can't catch a tampered download, so
+/// files without a pinned hash are downloaded with a warning, and the hash
+/// they had is recorded for `--verify`.
+pub fn download_entry(model: &ModelEntry) -> anyhow::Result<()> {
     let models_dir = Config::models_dir();
-    let filename = get_model_filename(model_name);
-    let model_path = models_dir.join(&filename);
+
+    let unpinned: Vec<&str> = model
+        .files
+        .iter()
+        .filter(|f| f.sha256.is_none())
+        .map(|f| f.name.as_str())
+        .collect();
+    if !unpinned.is_empty() {
+        print_warning(&format!(
+            "No pinned SHA-256 for {} in the model registry: downloading without verification",
+            unpinned.join(", ")
+        ));
+        print_warning("A corrupted or tampered file will not be detected");
+    }
 
     // Ensure directory exists
     std::fs::create_dir_all(&models_dir)?;
 
-    let url = get_model_url(model_name);
-
-    println!("\nDownloading {}...", model_name);
-    println!("URL: {}", url);
-
-    // Use curl for downloading - it handles progress display and redirects
-    let status = Command::new("curl")
-        .args([
-            "-L",             // Follow redirects
-            "--progress-bar", // Show progress bar
-            "-o",
-            model_path.to_str().unwrap_or("model.bin"),
-            &url,
-        ])
-        .status();
-
-    match status {
-        Ok(exit_status) if exit_status.success() => {
-            print_success(&format!("Saved to {:?}", model_path));
-            Ok(())
+    println!("\nDownloading {} ({} MB)...\n", model.name, model.size_mb);
+
+    let mut checksums = Vec::new();
+    for file in &model.files {
+        let relative_path = model.relative_path(file);
+        let file_path = models_dir.join(&relative_path);
+        if let Some(parent) = file_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        // Multi-file models keep complete files so a failed download can be retried cheaply
+        if model.engine == TranscriptionEngine::Parakeet && file_path.exists() {
+            let size = std::fs::metadata(&file_path).map(|m| m.len()).unwrap_or(0);
+            if file.size.is_none_or(|expected| size == expected) {
+                println!("  {} already exists, skipping", file.name);
+                continue;
+            }
+            println!("  {} is incomplete, downloading again", file.name);
+            let _ = std::fs::remove_file(&file_path);
+        }
+
+        let url = model.file_url(file);
+        println!("Downloading {}...", file.name);
+        println!("URL: {}", url);
+
+        let expected = file.sha256.as_deref();
+        match download::download_file(&url, &file_path, expected) {
+            Ok(sha256) => {
+                if expected.is_none() {
+                    print_info(&format!("Recorded SHA-256 of {}: {}", file.name, sha256));
+                }
+                checksums.push((relative_path, sha256));
+            }
+            Err(e) => {
+                print_failure(&e.to_string());
+                anyhow::bail!("Download failed for {}", file.name)
+            }
+        }
+
+        if let Some(expected_size) = file.size {
+            let size = std::fs::metadata(&file_path).map(|m| m.len()).unwrap_or(0);
+            if size != expected_size {
+                let _ = std::fs::remove_file(&file_path);
+                print_failure(&format!(
+                    "{}: expected {} bytes, got {}",
+                    file.name, expected_size, size
+                ));
+                anyhow::bail!("Download failed for {}", file.name)
+            }
         }
-        Ok(exit_status) => {
+    }
+
+    let model_path = model.install_path(&models_dir);
+    match model.engine {
+        TranscriptionEngine::Whisper => {
+            if !download::is_ggml_model(&model_path) {
+                let _ = std::fs::remove_file(&model_path);
+                print_failure("Downloaded file is not a Whisper model (check the URL)");
+                anyhow::bail!("Download failed")
+            }
+        }
+        TranscriptionEngine::Parakeet => validate_parakeet_model(&model_path)?,
+        // External engines validate their own model files
+        TranscriptionEngine::External => {}
+    }
+
+    let checksums: Vec<(&str, &str)> = checksums
+        .iter()
+        .map(|(path, sha256)| (path.as_str(), sha256.as_str()))
+        .collect();
+    record_checksums(&models_dir, &checksums)?;
+
+    if unpinned.is_empty() {
+        print_success("Checksum verified");
+    }
+    print_success(&format!("Saved to {:?}", model_path));
+    Ok(())
+}
+
+/// Record checksums of newly installed files in the models directory manifest
+fn record_checksums(models_dir: &Path, files: &[(&str, &str)]) -> anyhow::Result<()> {
+    let mut manifest = ChecksumManifest::load(models_dir);
+    for (relative_path, sha256) in files {
+        manifest.set(relative_path, sha256);
+    }
+    manifest.save()
+}
+
+/// Install a model obtained out-of-band (no network access needed)
+///
+/// Accepts a Whisper GGML file, a Parakeet model directory, or a tar archive
+/// containing either. The model is validated before anything is copied.
+pub fn import_model(source: &Path, expected_sha256: Option<&str>) -> anyhow::Result<()> {
+    if !source.exists() {
+        anyhow::bail!("No such file or directory: {:?}", source);
+    }
+    if let Some(expected) = expected_sha256 {
+        if source.is_dir() {
+            anyhow::bail!("--sha256 applies to a single file or archive, not a directory");
+        }
+        if !download::is_sha256(expected) {
+            anyhow::bail!("Invalid SHA-256: {}", expected);
+        }
+        let actual = download::sha256_file(source)?;
+        if !actual.eq_ignore_ascii_case(expected) {
+            print_failure(&format!("Checksum mismatch for {:?}", source));
+            println!("    expected: {}", expected);
+            println!("    actual:   {}", actual);
+            anyhow::bail!("Checksum mismatch");
+        }
+        print_success("Checksum verified");
+    }
+
+    let models_dir = Config::models_dir();
+    std::fs::create_dir_all(&models_dir)?;
+
+    if source.is_dir() {
+        return import_parakeet_dir(source, &file_name(source)?, &models_dir);
+    }
+
+    if is_archive(source) {
+        let extract_dir = tempfile::tempdir()?;
+        println!("Extracting {:?}...", source);
+        let status = Command::new("tar")
+            .arg("-xf")
+            .arg(source)
+            .arg("-C")
+            .arg(extract_dir.path())
+            .status()
+            .map_err(|e| anyhow::anyhow!("Failed to run tar: {}", e))?;
+        if !status.success() {
+            anyhow::bail!("Failed to extract {:?}", source);
+        }
+        return import_extracted(extract_dir.path(), &archive_stem(source), &models_dir);
+    }
+
+    import_whisper_file(source, &models_dir)
+}
+
+/// Archive extensions accepted by `--import`
+const ARCHIVE_EXTENSIONS: &[&str] = &[".tar", ".tar.gz", ".tgz", ".tar.xz", ".tar.zst", ".tar.bz2"];
+
+/// Check whether a path looks like a tar archive
+fn is_archive(path: &Path) -> bool {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    ARCHIVE_EXTENSIONS.iter().any(|ext| name.ends_with(ext))
+}
+
+/// Archive file name without its extension (e.g., "parakeet-tdt-0.6b-v3")
+fn archive_stem(path: &Path) -> String {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    ARCHIVE_EXTENSIONS
+        .iter()
+        .find(|ext| name.to_lowercase().ends_with(*ext))
+        .map(|ext| name[..name.len() - ext.len()].to_string())
+        .unwrap_or(name)
+}
+
+/// Final path component as a string
+fn file_name(path: &Path) -> anyhow::Result<String> {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .ok_or_else(|| anyhow::anyhow!("Invalid model path: {:?}", path))
+}
+
+/// Import the model found in an extracted archive
+fn import_extracted(dir: &Path, archive_name: &str, models_dir: &Path) -> anyhow::Result<()> {
+    // Parakeet files packed without a top-level directory are named after the archive
+    if validate_parakeet_model(dir).is_ok() {
+        return import_parakeet_dir(dir, archive_name, models_dir);
+    }
+
+    let entries: Vec<_> = std::fs::read_dir(dir)?
+        .flatten()
+        .map(|e| e.path())
+        .collect();
+    let dirs: Vec<_> = entries.iter().filter(|p| p.is_dir()).collect();
+    let bins: Vec<_> = entries
+        .iter()
+        .filter(|p| p.is_file() && p.extension().is_some_and(|e| e == "bin"))
+        .collect();
+
+    match (dirs.as_slice(), bins.as_slice()) {
+        ([model_dir], []) => import_parakeet_dir(model_dir, &file_name(model_dir)?, models_dir),
+        ([], [model_file]) => import_whisper_file(model_file, models_dir),
+        _ => anyhow::bail!(
+            "Archive must contain exactly one Whisper .bin file or one Parakeet model directory"
+        ),
+    }
+}
+
+/// Validate and install a Whisper GGML model file
+fn import_whisper_file(source: &Path, models_dir: &Path) -> anyhow::Result<()> {
+    if !download::is_ggml_model(source) {
+        print_failure(&format!("{:?} is not a Whisper GGML model", source));
+        anyhow::bail!("Invalid model file");
+    }
+
+    let filename = file_name(source)?;
+    let dest = models_dir.join(&filename);
+    let known = registry().find_by_file(&filename);
+
+    // Known models with a registry checksum must match it
+    if let Some(expected) = known.and_then(|m| m.files[0].sha256.as_deref()) {
+        let actual = download::sha256_file(source)?;
+        if !actual.eq_ignore_ascii_case(expected) {
             print_failure(&format!(
-                "Download failed: curl exited with code {}",
-                exit_status.code().unwrap_or(-1)
+                "{} does not match the registry checksum",
+                filename
             ));
-            // Clean up partial download
-            let _ = std::fs::remove_file(&model_path);
-            anyhow::bail!("Download failed")
+            println!("    expected: {}", expected);
+            println!("    actual:   {}", actual);
+            anyhow::bail!("Checksum mismatch");
+        }
+    }
+
+    println!("Installing {} to {:?}...", filename, models_dir);
+    let sha256 = download::install_file(source, &dest)?;
+    record_checksums(models_dir, &[(filename.as_str(), sha256.as_str())])?;
+
+    // Refer to the model by its short name if it's one of the known models
+    let model_name = known.map(|m| m.name.clone()).unwrap_or(filename);
+    print_success(&format!("Imported Whisper model '{}'", model_name));
+    println!("\n  Use it with: voxtype setup model --set {}", model_name);
+    Ok(())
+}
+
+/// Validate and install a Parakeet model directory as `models_dir/<name>`
+fn import_parakeet_dir(source: &Path, name: &str, models_dir: &Path) -> anyhow::Result<()> {
+    validate_parakeet_model(source)?;
+
+    // Known models must have the expected file sizes
+    let known = registry()
+        .get(name)
+        .filter(|m| m.engine == TranscriptionEngine::Parakeet);
+    if let Some(model) = known {
+        for file in &model.files {
+            let Some(expected_size) = file.size else {
+                continue;
+            };
+            let size = std::fs::metadata(source.join(&file.name))
+                .map(|m| m.len())
+                .ok();
+            if size.is_some_and(|size| size != expected_size) {
+                print_failure(&format!(
+                    "{}: expected {} bytes, found {}",
+                    file.name,
+                    expected_size,
+                    size.unwrap_or(0)
+                ));
+                anyhow::bail!("Incomplete or corrupted Parakeet model");
+            }
+        }
+    }
+
+    let dest = models_dir.join(name);
+    std::fs::create_dir_all(&dest)?;
+    println!("Installing {} to {:?}...", name, dest);
+
+    let mut checksums = Vec::new();
+    for entry in std::fs::read_dir(source)?.flatten() {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let filename = entry.file_name().to_string_lossy().into_owned();
+        let sha256 = download::install_file(&path, &dest.join(&filename))?;
+        checksums.push((format!("{}/{}", name, filename), sha256));
+    }
+
+    let checksums: Vec<(&str, &str)> = checksums
+        .iter()
+        .map(|(path, sha256)| (path.as_str(), sha256.as_str()))
+        .collect();
+    record_checksums(models_dir, &checksums)?;
+
+    print_success(&format!("Imported Parakeet model '{}'", name));
+    println!(
+        "\n  Use it with: engine = \"parakeet\" and [parakeet] model = \"{}\"",
+        name
+    );
+    Ok(())
+}
+
+/// Re-check installed models against their recorded checksums
+pub fn verify_installed() -> anyhow::Result<()> {
+    println!("Verifying Installed Models\n");
+    println!("==========================\n");
+
+    let models_dir = Config::models_dir();
+    let manifest = ChecksumManifest::load(&models_dir);
+
+    // Everything that is recorded, plus any model files installed before checksums existed
+    let mut files: Vec<String> = manifest
+        .entries()
+        .map(|(path, _)| path.to_string())
+        .collect();
+    if let Ok(entries) = std::fs::read_dir(&models_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if path.is_file() && name.ends_with(".bin") {
+                files.push(name);
+            } else if path.is_dir() && validate_parakeet_model(&path).is_ok() {
+                for file in std::fs::read_dir(&path).into_iter().flatten().flatten() {
+                    if file.path().is_file() {
+                        files.push(format!("{}/{}", name, file.file_name().to_string_lossy()));
+                    }
+                }
+            }
         }
-        Err(e) => {
-            print_failure(&format!("Failed to run curl: {}", e));
-            print_info("Please ensure curl is installed (e.g., 'sudo pacman -S curl')");
-            anyhow::bail!("curl not available: {}", e)
+    }
+    files.sort();
+    files.dedup();
+
+    if files.is_empty() {
+        println!("  No models installed.");
+        return Ok(());
+    }
+
+    let mut failures = 0;
+    let mut unrecorded = 0;
+    let mut unpinned = 0;
+    for file in &files {
+        // Checksums pinned in the registry take precedence over locally recorded ones
+        let pinned = registry_sha256(file);
+        let expected = pinned.or_else(|| manifest.get(file));
+        match download::verify_file(&models_dir, expected, file)? {
+            VerifyStatus::Ok if pinned.is_some() => print_success(file),
+            VerifyStatus::Ok => {
+                print_success(&format!(
+                    "{} (matches the checksum recorded at install)",
+                    file
+                ));
+                unpinned += 1;
+            }
+            VerifyStatus::Mismatch { expected, actual } => {
+                print_failure(&format!("{} (checksum mismatch)", file));
+                println!("    expected: {}", expected);
+                println!("    actual:   {}", actual);
+                failures += 1;
+            }
+            VerifyStatus::Unrecorded => {
+                print_warning(&format!("{} (no recorded checksum)", file));
+                unrecorded += 1;
+            }
+            VerifyStatus::Missing => {
+                print_failure(&format!("{} (missing)", file));
+                failures += 1;
+            }
         }
     }
+
+    if unpinned > 0 {
+        println!();
+        print_info("Files without a pinned checksum in the model registry are only checked");
+        print_info("for changes since they were installed, not against a published hash.");
+    }
+    if unrecorded > 0 {
+        println!();
+        print_info("Files without a checksum were installed before verification existed.");
+        print_info("Re-download or re-import them to record one.");
+    }
+    if failures > 0 {
+        println!();
+        print_info("Re-download corrupted models with: voxtype setup model");
+        anyhow::bail!("{} model file(s) failed verification", failures);
+    }
+
+    Ok(())
+}
+
+/// SHA-256 the registry lists for a file (path relative to the models directory)
+fn registry_sha256(relative_path: &str) -> Option<&'static str> {
+    registry().all().iter().find_map(|model| {
+        model
+            .files
+            .iter()
+            .find(|f| model.relative_path(f) == relative_path)
+            .and_then(|f| f.sha256.as_deref())
+    })
 }
 
 /// Set a specific model as the default (must already be downloaded)
@@ -497,9 +755,12 @@ pub fn list_installed() {
 
     let mut found = false;
 
-    for model in MODELS {
-        let filename = get_model_filename(model.name);
-        let model_path = models_dir.join(&filename);
+    let models = registry()
+        .all()
+        .iter()
+        .filter(|m| m.engine == TranscriptionEngine::Whisper);
+    for model in models {
+        let model_path = model.install_path(&models_dir);
 
         if model_path.exists() {
             let size = std::fs::metadata(&model_path)
@@ -597,12 +858,14 @@ fn update_model_in_config(config: &str, model_name: &str) -> String {
 
 /// Check if a model name is a Parakeet model
 pub fn is_parakeet_model(name: &str) -> bool {
-    PARAKEET_MODELS.iter().any(|m| m.name == name)
+    registry()
+        .models(TranscriptionEngine::Parakeet)
+        .any(|m| m.name == name)
 }
 
 /// Get list of valid Parakeet model names
 pub fn valid_parakeet_model_names() -> Vec<&'static str> {
-    PARAKEET_MODELS.iter().map(|m| m.name).collect()
+    registry().names(TranscriptionEngine::Parakeet)
 }
 
 /// Validate that a Parakeet model directory has the required files
@@ -638,75 +901,12 @@ pub fn validate_parakeet_model(path: &Path) -> anyhow::Result<()> {
 
 /// Download a Parakeet model by name (public API for run_setup)
 pub fn download_parakeet_model(model_name: &str) -> anyhow::Result<()> {
-    let model = PARAKEET_MODELS
-        .iter()
-        .find(|m| m.name == model_name)
+    let model = registry()
+        .get(model_name)
+        .filter(|m| m.engine == TranscriptionEngine::Parakeet)
         .ok_or_else(|| anyhow::anyhow!("Unknown Parakeet model: {}", model_name))?;
 
-    download_parakeet_model_by_info(model)
-}
-
-/// Download a Parakeet model using its info struct
-fn download_parakeet_model_by_info(model: &ParakeetModelInfo) -> anyhow::Result<()> {
-    let models_dir = Config::models_dir();
-    let model_path = models_dir.join(model.name);
-
-    // Create model directory
-    std::fs::create_dir_all(&model_path)?;
-
-    println!("\nDownloading {} ({} MB)...\n", model.name, model.size_mb);
-
-    for (filename, _expected_size) in model.files {
-        let file_path = model_path.join(filename);
-
-        if file_path.exists() {
-            println!("  {} already exists, skipping", filename);
-            continue;
-        }
-
-        let url = format!(
-            "https://huggingface.co/{}/resolve/main/{}",
-            model.huggingface_repo, filename
-        );
-
-        println!("Downloading {}...", filename);
-
-        let status = Command::new("curl")
-            .args([
-                "-L",
-                "--progress-bar",
-                "-o",
-                file_path.to_str().unwrap_or("file"),
-                &url,
-            ])
-            .status();
-
-        match status {
-            Ok(exit_status) if exit_status.success() => {
-                // Success, continue
-            }
-            Ok(exit_status) => {
-                print_failure(&format!(
-                    "Download failed: curl exited with code {}",
-                    exit_status.code().unwrap_or(-1)
-                ));
-                // Clean up partial download
-                let _ = std::fs::remove_file(&file_path);
-                anyhow::bail!("Download failed for {}", filename)
-            }
-            Err(e) => {
-                print_failure(&format!("Failed to run curl: {}", e));
-                print_info("Please ensure curl is installed (e.g., 'sudo pacman -S curl')");
-                anyhow::bail!("curl not available: {}", e)
-            }
-        }
-    }
-
-    // Validate all files are present
-    validate_parakeet_model(&model_path)?;
-    print_success(&format!("Model '{}' downloaded to {:?}", model.name, model_path));
-
-    Ok(())
+    download_entry(model)
 }
 
 /// Update config to use Parakeet engine and a specific model (with status messages)
@@ -839,8 +1039,8 @@ pub fn list_installed_parakeet() {
 
     let mut found = false;
 
-    for model in PARAKEET_MODELS {
-        let model_path = models_dir.join(model.name);
+    for model in registry().models(TranscriptionEngine::Parakeet) {
+        let model_path = model.install_path(&models_dir);
 
         if model_path.exists() && validate_parakeet_model(&model_path).is_ok() {
             let size = std::fs::read_dir(&model_path)
@@ -870,6 +1070,7 @@ pub fn list_installed_parakeet() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model_registry::ModelRegistry;
 
     #[test]
     fn test_update_model_in_config_basic() {
@@ -954,7 +1155,8 @@ language = "en"
 
     #[test]
     fn test_models_list_contains_expected_models() {
-        let model_names: Vec<&str> = MODELS.iter().map(|m| m.name).collect();
+        let registry = ModelRegistry::builtin();
+        let model_names = registry.names(TranscriptionEngine::Whisper);
         // Multilingual models
         assert!(model_names.contains(&"tiny"));
         assert!(model_names.contains(&"base"));
@@ -972,7 +1174,8 @@ language = "en"
 
     #[test]
     fn test_model_info_sizes_are_reasonable() {
-        for model in MODELS {
+        let registry = ModelRegistry::builtin();
+        for model in registry.models(TranscriptionEngine::Whisper) {
             // All models should have positive size
             assert!(model.size_mb > 0, "Model {} has invalid size", model.name);
             // Tiny models should be smallest, large should be biggest
@@ -1013,7 +1216,68 @@ language = "en"
         let names = valid_model_names();
         assert!(names.contains(&"tiny.en"));
         assert!(names.contains(&"large-v3-turbo"));
-        assert_eq!(names.len(), MODELS.len());
+        assert_eq!(
+            names.len(),
+            registry().models(TranscriptionEngine::Whisper).count()
+        );
+    }
+
+    #[test]
+    fn test_archive_detection() {
+        assert!(is_archive(Path::new("/tmp/parakeet-tdt-0.6b-v3.tar.gz")));
+        assert!(is_archive(Path::new("model.TGZ")));
+        assert!(!is_archive(Path::new("ggml-base.en.bin")));
+        assert_eq!(
+            archive_stem(Path::new("/tmp/parakeet-tdt-0.6b-v3.tar.gz")),
+            "parakeet-tdt-0.6b-v3"
+        );
+        assert_eq!(archive_stem(Path::new("model.tar")), "model");
+    }
+
+    #[test]
+    fn test_import_whisper_file_records_checksum() {
+        let source_dir = tempfile::tempdir().unwrap();
+        let models_dir = tempfile::tempdir().unwrap();
+        let source = source_dir.path().join("ggml-base.en.bin");
+        std::fs::write(&source, b"lmgg-model-data").unwrap();
+
+        import_whisper_file(&source, models_dir.path()).unwrap();
+
+        assert!(models_dir.path().join("ggml-base.en.bin").exists());
+        assert!(!models_dir.path().join("ggml-base.en.bin.part").exists());
+        let manifest = ChecksumManifest::load(models_dir.path());
+        assert!(manifest.get("ggml-base.en.bin").is_some());
+    }
+
+    #[test]
+    fn test_import_rejects_non_model_file() {
+        let source_dir = tempfile::tempdir().unwrap();
+        let models_dir = tempfile::tempdir().unwrap();
+        let source = source_dir.path().join("ggml-base.en.bin");
+        std::fs::write(&source, b"<html>Not Found</html>").unwrap();
+
+        assert!(import_whisper_file(&source, models_dir.path()).is_err());
+        assert!(!models_dir.path().join("ggml-base.en.bin").exists());
+    }
+
+    #[test]
+    fn test_import_extracted_parakeet_without_top_dir() {
+        let extracted = tempfile::tempdir().unwrap();
+        let models_dir = tempfile::tempdir().unwrap();
+        for file in [
+            "encoder-model.onnx",
+            "decoder_joint-model.onnx",
+            "vocab.txt",
+        ] {
+            std::fs::write(extracted.path().join(file), "x").unwrap();
+        }
+
+        import_extracted(extracted.path(), "my-parakeet", models_dir.path()).unwrap();
+
+        let dest = models_dir.path().join("my-parakeet");
+        assert!(validate_parakeet_model(&dest).is_ok());
+        let manifest = ChecksumManifest::load(models_dir.path());
+        assert!(manifest.get("my-parakeet/vocab.txt").is_some());
     }
 
     // =========================================================================
@@ -1022,14 +1286,16 @@ language = "en"
 
     #[test]
     fn test_parakeet_models_list_contains_expected_models() {
-        let model_names: Vec<&str> = PARAKEET_MODELS.iter().map(|m| m.name).collect();
+        let registry = ModelRegistry::builtin();
+        let model_names = registry.names(TranscriptionEngine::Parakeet);
         assert!(model_names.contains(&"parakeet-tdt-0.6b-v3"));
         assert!(model_names.contains(&"parakeet-tdt-0.6b-v3-int8"));
     }
 
     #[test]
     fn test_parakeet_model_info_sizes_are_reasonable() {
-        for model in PARAKEET_MODELS {
+        let registry = ModelRegistry::builtin();
+        for model in registry.models(TranscriptionEngine::Parakeet) {
             // All models should have positive size
             assert!(model.size_mb > 0, "Model {} has invalid size", model.name);
             // Full model should be larger than quantized
@@ -1044,7 +1310,8 @@ language = "en"
 
     #[test]
     fn test_parakeet_models_have_files() {
-        for model in PARAKEET_MODELS {
+        let registry = ModelRegistry::builtin();
+        for model in registry.models(TranscriptionEngine::Parakeet) {
             assert!(
                 !model.files.is_empty(),
                 "Model {} should have file definitions",
@@ -1052,7 +1319,7 @@ language = "en"
             );
             // All TDT models should have vocab.txt
             assert!(
-                model.files.iter().any(|(f, _)| *f == "vocab.txt"),
+                model.files.iter().any(|f| f.name == "vocab.txt"),
                 "Model {} should have vocab.txt",
                 model.name
             );
@@ -1077,7 +1344,10 @@ language = "en"
         let names = valid_parakeet_model_names();
         assert!(names.contains(&"parakeet-tdt-0.6b-v3"));
         assert!(names.contains(&"parakeet-tdt-0.6b-v3-int8"));
-        assert_eq!(names.len(), PARAKEET_MODELS.len());
+        assert_eq!(
+            names.len(),
+            registry().models(TranscriptionEngine::Parakeet).count()
+        );
     }
 
     #[test]
@@ -3,6 +3,7 @@
 //! Defines the states for the push-to-talk workflow:
 //! Idle → Recording → Transcribing → Outputting → Idle
 
+use crate::transcribe::TranscriptionOverrides;
 use std::time::Instant;
 
 /// Audio samples collected during recording (f32, mono, 16kHz)
@@ -20,6 +21,8 @@ pub enum State {
         started_at: Instant,
         /// Optional model override for this recording
         model_override: Option<String>,
+        /// Language/prompt/translate overrides for this recording
+        overrides: TranscriptionOverrides,
     },
 
     /// Hotkey released, transcribing audio
@@ -105,6 +108,7 @@ mod tests {
         let state = State::Recording {
             started_at: Instant::now(),
             model_override: None,
+            overrides: TranscriptionOverrides::default(),
         };
         assert!(state.is_recording());
         assert!(!state.is_idle());
@@ -125,6 +129,7 @@ mod tests {
         let state = State::Recording {
             started_at: Instant::now(),
             model_override: None,
+            overrides: TranscriptionOverrides::default(),
         };
         assert!(format!("{}", state).starts_with("Recording"));
     }
@@ -0,0 +1,576 @@
+//! Code dictation mode
+//!
+//! Formats dictation for source code instead of prose:
+//! - Casing commands: "camel case get value" → "getValue", also pascal,
+//!   snake, kebab, constant and all caps
+//! - "no space" joins the words around it: "get no space value" → "getvalue"
+//! - Symbol words: "x equals y dot len" → "x = y.len"
+//! - Spelling with the NATO alphabet: "spell cap alpha bravo one" → "Ab1"
+//!
+//! Punctuation Whisper adds on its own at the end of a phrase (".", ",", "?")
+//! is dropped, since symbols are spoken explicitly. Punctuation written inside
+//! a token ("3.5", "std::env") is kept. Commands are English.
+
+use crate::config::CodeModeConfig;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+/// Built-in symbol words
+/// Spaces in a symbol are kept around it: "equals" gives "x = y", "dot" gives "y.len"
+const SYMBOLS: &[(&str, &str)] = &[
+    ("dot", "."),
+    ("comma", ", "),
+    ("colon", ": "),
+    ("double colon", "::"),
+    ("semicolon", ";"),
+    ("equals", " = "),
+    ("double equals", " == "),
+    ("triple equals", " === "),
+    ("not equals", " != "),
+    ("plus", " + "),
+    ("minus", " - "),
+    ("times", " * "),
+    ("divided by", " / "),
+    ("plus equals", " += "),
+    ("minus equals", " -= "),
+    ("greater than", " > "),
+    ("less than", " < "),
+    ("greater or equal", " >= "),
+    ("less or equal", " <= "),
+    ("and and", " && "),
+    ("or or", " || "),
+    ("arrow", " -> "),
+    ("fat arrow", " => "),
+    ("open paren", "("),
+    ("close paren", ")"),
+    ("open bracket", "["),
+    ("close bracket", "]"),
+    ("open brace", "{"),
+    ("close brace", "}"),
+    ("open angle", "<"),
+    ("close angle", ">"),
+    ("underscore", "_"),
+    ("dash", "-"),
+    ("star", "*"),
+    ("slash", "/"),
+    ("backslash", "\\"),
+    ("pipe", "|"),
+    ("ampersand", "&"),
+    ("bang", "!"),
+    ("question mark", "?"),
+    ("hash", "#"),
+    ("at sign", "@"),
+    ("dollar", "$"),
+    ("percent", "%"),
+    ("caret", "^"),
+    ("tilde", "~"),
+    ("backtick", "`"),
+    ("quote", "\""),
+    ("single quote", "'"),
+    ("space", " "),
+    ("tab", "\t"),
+    ("new line", "\n"),
+];
+
+/// Casing commands
+const CASES: &[(&str, Case)] = &[
+    ("camel case", Case::Camel),
+    ("pascal case", Case::Pascal),
+    ("snake case", Case::Snake),
+    ("kebab case", Case::Kebab),
+    ("constant case", Case::Constant),
+    ("screaming snake case", Case::Constant),
+    ("all caps", Case::AllCaps),
+];
+
+const NO_SPACE: &str = "no space";
+const SPELL: &[&str] = &["spell", "spelling"];
+const CAPITAL: &[&str] = &["cap", "capital"];
+
+/// NATO alphabet, plus digits for spelling
+const SPELLING: &[(&str, char)] = &[
+    ("alpha", 'a'),
+    ("alfa", 'a'),
+    ("bravo", 'b'),
+    ("charlie", 'c'),
+    ("delta", 'd'),
+    ("echo", 'e'),
+    ("foxtrot", 'f'),
+    ("golf", 'g'),
+    ("hotel", 'h'),
+    ("india", 'i'),
+    ("juliet", 'j'),
+    ("juliett", 'j'),
+    ("kilo", 'k'),
+    ("lima", 'l'),
+    ("mike", 'm'),
+    ("november", 'n'),
+    ("oscar", 'o'),
+    ("papa", 'p'),
+    ("quebec", 'q'),
+    ("romeo", 'r'),
+    ("sierra", 's'),
+    ("tango", 't'),
+    ("uniform", 'u'),
+    ("victor", 'v'),
+    ("whiskey", 'w'),
+    ("whisky", 'w'),
+    ("x ray", 'x'),
+    ("xray", 'x'),
+    ("yankee", 'y'),
+    ("zulu", 'z'),
+    ("zero", '0'),
+    ("one", '1'),
+    ("two", '2'),
+    ("three", '3'),
+    ("four", '4'),
+    ("five", '5'),
+    ("six", '6'),
+    ("seven", '7'),
+    ("eight", '8'),
+    ("nine", '9'),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Case {
+    Camel,
+    Pascal,
+    Snake,
+    Kebab,
+    Constant,
+    AllCaps,
+}
+
+impl Case {
+    fn apply(self, words: &[String]) -> String {
+        let words: Vec<String> = words
+            .iter()
+            .map(|w| w.replace(['\'', '’'], "").to_lowercase())
+            .collect();
+        match self {
+            Case::Camel => words
+                .iter()
+                .enumerate()
+                .map(|(i, w)| if i == 0 { w.clone() } else { capitalize(w) })
+                .collect(),
+            Case::Pascal => words.iter().map(|w| capitalize(w)).collect(),
+            Case::Snake => words.join("_"),
+            Case::Kebab => words.join("-"),
+            Case::Constant => words.join("_").to_uppercase(),
+            Case::AllCaps => words.join(" ").to_uppercase(),
+        }
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// A spoken command recognized in code mode
+#[derive(Debug, Clone)]
+enum Command {
+    Case(Case),
+    NoSpace,
+    Spell,
+    Symbol(String),
+}
+
+/// Something in the transcription: a word or a symbol Whisper wrote
+#[derive(Debug, Clone)]
+enum Item {
+    Word {
+        text: String,
+        /// Whisper put punctuation after the word, ending a phrase
+        ends_phrase: bool,
+    },
+    Symbol {
+        text: String,
+        space_before: bool,
+        space_after: bool,
+    },
+}
+
+impl Item {
+    fn word(&self) -> Option<&str> {
+        match self {
+            Item::Word { text, .. } => Some(text),
+            Item::Symbol { .. } => None,
+        }
+    }
+}
+
+/// Output piece, joined with spaces between adjacent words
+enum Piece {
+    Word(String),
+    Symbol(String),
+    Glue,
+}
+
+/// Formats dictation as code
+pub struct CodeFormatter {
+    /// Command phrases as lowercase words, longest first
+    commands: Vec<(Vec<String>, Command)>,
+}
+
+impl CodeFormatter {
+    pub fn new(config: &CodeModeConfig) -> Self {
+        let mut symbols: BTreeMap<String, String> = SYMBOLS
+            .iter()
+            .map(|(phrase, symbol)| (phrase.to_string(), symbol.to_string()))
+            .collect();
+        // Configured symbols add to or replace the built-in ones;
+        // an empty symbol removes a built-in phrase
+        for (phrase, symbol) in &config.symbols {
+            symbols.insert(phrase.to_lowercase(), symbol.clone());
+        }
+
+        let mut commands: Vec<(Vec<String>, Command)> = symbols
+            .into_iter()
+            .filter(|(_, symbol)| !symbol.is_empty())
+            .map(|(phrase, symbol)| (phrase, Command::Symbol(symbol)))
+            .chain(
+                CASES
+                    .iter()
+                    .map(|(phrase, case)| (phrase.to_string(), Command::Case(*case))),
+            )
+            .chain(std::iter::once((NO_SPACE.to_string(), Command::NoSpace)))
+            .chain(SPELL.iter().map(|w| (w.to_string(), Command::Spell)))
+            .map(|(phrase, command)| (split_phrase(&phrase), command))
+            .filter(|(words, _)| !words.is_empty())
+            .collect();
+        commands.sort_by_key(|(words, _)| std::cmp::Reverse(words.len()));
+
+        Self { commands }
+    }
+
+    /// Format a transcription as code
+    pub fn format(&self, text: &str) -> String {
+        let items = lex(text);
+        let mut pieces = Vec::new();
+        let mut i = 0;
+        while i < items.len() {
+            let Some((len, command)) = self.command_at(&items, i) else {
+                match &items[i] {
+                    Item::Word { text, .. } => pieces.push(Piece::Word(text.clone())),
+                    Item::Symbol {
+                        text,
+                        space_before,
+                        space_after,
+                    } => {
+                        let before = if *space_before { " " } else { "" };
+                        let after = if *space_after { " " } else { "" };
+                        pieces.push(Piece::Symbol(format!("{}{}{}", before, text, after)));
+                    }
+                }
+                i += 1;
+                continue;
+            };
+            i += len;
+
+            match command {
+                Command::Symbol(symbol) => pieces.push(Piece::Symbol(symbol.clone())),
+                Command::NoSpace => pieces.push(Piece::Glue),
+                Command::Case(case) => {
+                    let (words, len) = self.phrase_words(&items, i);
+                    if !words.is_empty() {
+                        pieces.push(Piece::Word(case.apply(&words)));
+                    }
+                    i += len;
+                }
+                Command::Spell => {
+                    let (spelled, len) = spell(&items, i);
+                    if !spelled.is_empty() {
+                        pieces.push(Piece::Word(spelled));
+                    }
+                    i += len;
+                }
+            }
+        }
+        join(&pieces)
+    }
+
+    /// Longest command starting at item i
+    fn command_at(&self, items: &[Item], i: usize) -> Option<(usize, &Command)> {
+        self.commands
+            .iter()
+            .find(|(words, _)| matches_phrase(items, i, words))
+            .map(|(words, command)| (words.len(), command))
+    }
+
+    /// Words after a casing command, up to the next command or punctuation
+    fn phrase_words(&self, items: &[Item], start: usize) -> (Vec<String>, usize) {
+        let mut words = Vec::new();
+        let mut i = start;
+        while i < items.len() && self.command_at(items, i).is_none() {
+            let Item::Word { text, ends_phrase } = &items[i] else {
+                break;
+            };
+            words.push(text.clone());
+            i += 1;
+            if *ends_phrase {
+                break;
+            }
+        }
+        (words, i - start)
+    }
+}
+
+/// Lowercase words of a phrase; hyphens count as spaces
+fn split_phrase(phrase: &str) -> Vec<String> {
+    phrase
+        .split(|c: char| c.is_whitespace() || c == '-')
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Whether the words of a phrase match the items at i
+/// Only the last word may be followed by punctuation
+fn matches_phrase(items: &[Item], i: usize, words: &[String]) -> bool {
+    words.len() <= items.len().saturating_sub(i)
+        && words
+            .iter()
+            .enumerate()
+            .all(|(k, word)| match &items[i + k] {
+                Item::Word { text, ends_phrase } => {
+                    text.to_lowercase() == *word && (!ends_phrase || k + 1 == words.len())
+                }
+                Item::Symbol { .. } => false,
+            })
+}
+
+/// Letters and digits spelled after "spell", with "cap" for capitals
+fn spell(items: &[Item], start: usize) -> (String, usize) {
+    let mut spelled = String::new();
+    let mut capital = false;
+    let mut i = start;
+    while i < items.len() {
+        let Some(word) = items[i].word().map(str::to_lowercase) else {
+            break;
+        };
+        let ends_phrase = matches!(
+            items[i],
+            Item::Word {
+                ends_phrase: true,
+                ..
+            }
+        );
+
+        if CAPITAL.contains(&word.as_str()) && !ends_phrase {
+            capital = true;
+            i += 1;
+            continue;
+        }
+        let letter = SPELLING
+            .iter()
+            .map(|(name, c)| (split_phrase(name), *c))
+            .find(|(words, _)| matches_phrase(items, i, words));
+        let (len, c) = match letter {
+            Some((words, c)) => (words.len(), c),
+            // Digits Whisper already wrote ("spell alpha 1")
+            None if word.chars().all(|c| c.is_ascii_digit()) => {
+                spelled.push_str(&word);
+                i += 1;
+                if ends_phrase {
+                    break;
+                }
+                continue;
+            }
+            None => break,
+        };
+        if capital {
+            spelled.extend(c.to_uppercase());
+        } else {
+            spelled.push(c);
+        }
+        capital = false;
+        i += len;
+        if matches!(
+            items[i - 1],
+            Item::Word {
+                ends_phrase: true,
+                ..
+            }
+        ) {
+            break;
+        }
+    }
+    (spelled, i - start)
+}
+
+/// Split a transcription into words and symbols
+/// Sentence punctuation Whisper adds is dropped and ends the phrase before it,
+/// unless it sits between two tokens ("3.5", "a.b"), where it is kept as written;
+/// hyphens joining words ("snake-case") count as spaces
+fn lex(text: &str) -> Vec<Item> {
+    static TOKEN: OnceLock<Regex> = OnceLock::new();
+    let re = TOKEN.get_or_init(|| {
+        Regex::new(
+            r"(\s*)(?:([\p{L}\p{N}]+(?:['’][\p{L}\p{N}]+)*)|([.,?!;:…]+)|([^\s\p{L}\p{N}]+))",
+        )
+        .expect("valid code token regex")
+    });
+
+    let mut items: Vec<Item> = Vec::new();
+    let mut last_end = 0;
+    for caps in re.captures_iter(text) {
+        let space_before = !caps[1].is_empty();
+        let whole = caps.get(0).expect("match");
+        let space_after = text[whole.end()..].starts_with(char::is_whitespace);
+        if let Some(word) = caps.get(2) {
+            items.push(Item::Word {
+                text: word.as_str().to_string(),
+                ends_phrase: false,
+            });
+        } else if let Some(punctuation) = caps.get(3) {
+            let written = !space_before && !space_after && whole.end() < text.len();
+            if written && !items.is_empty() {
+                items.push(Item::Symbol {
+                    text: punctuation.as_str().to_string(),
+                    space_before: false,
+                    space_after: false,
+                });
+            } else if let Some(Item::Word { ends_phrase, .. }) = items.last_mut() {
+                *ends_phrase = true;
+            }
+        } else if let Some(symbol) = caps.get(4) {
+            let joins_words = symbol.as_str() == "-"
+                && !space_before
+                && !space_after
+                && matches!(items.last(), Some(Item::Word { .. }));
+            if !joins_words {
+                items.push(Item::Symbol {
+                    text: symbol.as_str().to_string(),
+                    space_before: space_before && last_end > 0,
+                    space_after,
+                });
+            }
+        }
+        last_end = whole.end();
+    }
+    items
+}
+
+/// Join output pieces: a space between adjacent words unless glued,
+/// symbols bring their own spacing
+fn join(pieces: &[Piece]) -> String {
+    let mut output = String::new();
+    let mut previous_word = false;
+    let mut glue = false;
+    for piece in pieces {
+        match piece {
+            Piece::Word(word) => {
+                if previous_word && !glue {
+                    output.push(' ');
+                }
+                output.push_str(word);
+                previous_word = true;
+                glue = false;
+            }
+            Piece::Symbol(symbol) => {
+                let symbol = if glue || output.is_empty() || output.ends_with(' ') {
+                    symbol.trim_start_matches(' ')
+                } else {
+                    symbol.as_str()
+                };
+                output.push_str(symbol);
+                previous_word = false;
+                glue = false;
+            }
+            Piece::Glue => glue = true,
+        }
+    }
+    output.trim_end_matches(' ').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(text: &str) -> String {
+        CodeFormatter::new(&CodeModeConfig::default()).format(text)
+    }
+
+    #[test]
+    fn test_casing_commands() {
+        assert_eq!(format("camel case get value"), "getValue");
+        assert_eq!(format("Pascal case user account."), "UserAccount");
+        assert_eq!(format("snake case user id"), "user_id");
+        assert_eq!(format("kebab-case main menu"), "main-menu");
+        assert_eq!(format("constant case max retries"), "MAX_RETRIES");
+        assert_eq!(format("all caps todo"), "TODO");
+        assert_eq!(format("camel case don't stop"), "dontStop");
+    }
+
+    #[test]
+    fn test_casing_stops_at_commands_and_punctuation() {
+        assert_eq!(
+            format("snake case user id equals camel case get user id"),
+            "user_id = getUserId"
+        );
+        assert_eq!(format("snake case first name, last"), "first_name last");
+    }
+
+    #[test]
+    fn test_symbols() {
+        assert_eq!(format("x equals y dot len"), "x = y.len");
+        assert_eq!(format("foo open paren a comma b close paren"), "foo(a, b)");
+        assert_eq!(format("self dot items dot push"), "self.items.push");
+        assert_eq!(format("std double colon env"), "std::env");
+        assert_eq!(format("a fat arrow b"), "a => b");
+        assert_eq!(format("equals x"), "= x");
+    }
+
+    #[test]
+    fn test_no_space() {
+        assert_eq!(format("get no space value"), "getvalue");
+        assert_eq!(
+            format("camel case get no space snake case user id"),
+            "getuser_id"
+        );
+    }
+
+    #[test]
+    fn test_spelling() {
+        assert_eq!(format("spell alpha bravo charlie"), "abc");
+        assert_eq!(format("spell cap x-ray yankee one"), "Xy1");
+        assert_eq!(format("spell victor two equals five"), "v2 = five");
+        assert_eq!(format("spell x ray"), "x");
+        assert_eq!(format("Spell cap kilo eight sierra."), "K8s");
+    }
+
+    #[test]
+    fn test_whisper_punctuation_dropped() {
+        assert_eq!(format("Camel case get value."), "getValue");
+        assert_eq!(format("x equals y, plus z."), "x = y + z");
+    }
+
+    #[test]
+    fn test_written_symbols_kept() {
+        assert_eq!(format("x = 5"), "x = 5");
+        assert_eq!(format("foo_bar"), "foo_bar");
+        assert_eq!(format("3.5"), "3.5");
+        assert_eq!(format("std::env"), "std::env");
+        assert_eq!(format("a.b"), "a.b");
+        assert_eq!(format("x equals y.len."), "x = y.len");
+        assert_eq!(format("snake case max len equals 3.5"), "max_len = 3.5");
+        assert_eq!(format("at 3:30."), "at 3:30");
+    }
+
+    #[test]
+    fn test_configured_symbols() {
+        let mut config = CodeModeConfig::default();
+        config
+            .symbols
+            .insert("walrus".to_string(), " := ".to_string());
+        config.symbols.insert("dash".to_string(), String::new());
+        let formatter = CodeFormatter::new(&config);
+        assert_eq!(formatter.format("x walrus y"), "x := y");
+        assert_eq!(formatter.format("dash"), "dash");
+    }
+}
@@ -0,0 +1,442 @@
+//! Voice editing commands
+//!
+//! Recognizes spoken commands like "select all", "undo" or "press enter"
+//! and turns a transcription into a sequence of text and key actions.
+//!
+//! Most commands only count when spoken on their own: the command must be a
+//! whole sentence or clause ("Hello world. Select all."), so that dictating
+//! "I want to undo the change" types the words instead of pressing Ctrl+Z.
+//! "new line" and "new paragraph" work anywhere.
+
+use crate::config::VoiceCommandsConfig;
+use crate::output::action::{Key, KeyCombo, Modifier, OutputAction};
+
+/// What a recognized command does
+#[derive(Debug, Clone, PartialEq)]
+enum Effect {
+    /// Press keys in sequence
+    Keys(Vec<KeyCombo>),
+    /// Insert fixed text
+    Text(&'static str),
+    /// Remove the preceding dictated text
+    DeleteThat,
+}
+
+/// A command phrase and its effect
+#[derive(Debug, Clone)]
+struct Command {
+    /// Normalized words of the phrase
+    words: Vec<String>,
+    effect: Effect,
+    /// Only recognized as a whole sentence or clause
+    standalone: bool,
+}
+
+/// Built-in standalone commands: phrase → key combination
+const BUILTIN_KEYS: &[(&str, &str)] = &[
+    ("select all", "ctrl+a"),
+    ("undo", "ctrl+z"),
+    ("undo that", "ctrl+z"),
+    ("redo", "ctrl+shift+z"),
+    ("redo that", "ctrl+shift+z"),
+    ("copy that", "ctrl+c"),
+    ("cut that", "ctrl+x"),
+    ("paste that", "ctrl+v"),
+    ("delete word", "ctrl+backspace"),
+];
+
+/// Punctuation that ends a sentence or clause
+const CLAUSE_END: &[char] = &['.', ',', '!', '?', ';', ':'];
+
+/// Recognizer for voice editing commands
+pub struct VoiceCommands {
+    /// Commands, longest phrase first
+    commands: Vec<Command>,
+    /// Recognize "press <key>" (built-in)
+    press: bool,
+}
+
+impl VoiceCommands {
+    /// Build the recognizer from configuration
+    /// Invalid key combinations in custom commands are skipped with a warning
+    pub fn new(config: &VoiceCommandsConfig) -> Self {
+        let mut commands = Vec::new();
+
+        if config.builtin {
+            for (phrase, keys) in BUILTIN_KEYS {
+                let combo = keys.parse().expect("valid built-in key combination");
+                commands.push(Command {
+                    words: words(phrase),
+                    effect: Effect::Keys(vec![combo]),
+                    standalone: true,
+                });
+            }
+            for phrase in ["delete that", "scratch that"] {
+                commands.push(Command {
+                    words: words(phrase),
+                    effect: Effect::DeleteThat,
+                    standalone: true,
+                });
+            }
+            commands.push(Command {
+                words: words("new line"),
+                effect: Effect::Text("\n"),
+                standalone: false,
+            });
+            commands.push(Command {
+                words: words("new paragraph"),
+                effect: Effect::Text("\n\n"),
+                standalone: false,
+            });
+        }
+
+        for (phrase, keys) in &config.custom {
+            let combos: Result<Vec<KeyCombo>, String> =
+                keys.split_whitespace().map(str::parse).collect();
+            match combos {
+                Ok(combos) if !combos.is_empty() && !words(phrase).is_empty() => {
+                    // Custom commands replace built-ins with the same phrase
+                    let phrase_words = words(phrase);
+                    commands.retain(|c| c.words != phrase_words);
+                    commands.push(Command {
+                        words: phrase_words,
+                        effect: Effect::Keys(combos),
+                        standalone: true,
+                    });
+                }
+                Ok(_) => tracing::warn!("Skipping empty voice command {:?}", phrase),
+                Err(e) => tracing::warn!("Skipping voice command {:?}: {}", phrase, e),
+            }
+        }
+
+        commands.sort_by_key(|c| std::cmp::Reverse(c.words.len()));
+
+        Self {
+            commands,
+            press: config.builtin,
+        }
+    }
+
+    /// Split a transcription into text and key actions
+    ///
+    /// `process` is applied to each piece of dictated text (not to text
+    /// inserted by commands). `previous_output_chars` is the length of the
+    /// previous dictation, erased by "delete that" when nothing precedes it.
+    pub fn parse(
+        &self,
+        text: &str,
+        previous_output_chars: usize,
+        process: impl Fn(&str) -> String,
+    ) -> Vec<OutputAction> {
+        let tokens = tokenize(text);
+        let normalized: Vec<String> = tokens
+            .iter()
+            .map(|&(s, e)| normalize(&text[s..e]))
+            .collect();
+
+        let mut actions = Vec::new();
+        let mut text_start = 0;
+        let mut i = 0;
+
+        while i < tokens.len() {
+            let Some((len, effect, standalone)) = self.match_at(&normalized, i) else {
+                i += 1;
+                continue;
+            };
+
+            if standalone && !is_standalone(text, &tokens, i, len) {
+                i += 1;
+                continue;
+            }
+
+            // Dictated text before the command
+            let before = text[text_start..tokens[i].0].trim();
+            if !before.is_empty() {
+                actions.push(OutputAction::Text(process(before)));
+            }
+
+            match effect {
+                Effect::Keys(combos) => {
+                    actions.extend(combos.into_iter().map(OutputAction::Key));
+                }
+                Effect::Text(inserted) => actions.push(OutputAction::Text(inserted.to_string())),
+                Effect::DeleteThat => {
+                    if matches!(actions.last(), Some(OutputAction::Text(_))) {
+                        actions.pop();
+                    } else if actions.is_empty() {
+                        let backspace = KeyCombo::key(Key::Backspace);
+                        actions.extend(
+                            std::iter::repeat_n(backspace, previous_output_chars)
+                                .map(OutputAction::Key),
+                        );
+                    }
+                }
+            }
+
+            // Skip the command words, including their trailing punctuation
+            text_start = tokens[i + len - 1].1;
+            i += len;
+        }
+
+        let rest = text[text_start..].trim();
+        if !rest.is_empty() {
+            actions.push(OutputAction::Text(process(rest)));
+        }
+
+        merge_text(actions)
+    }
+
+    /// Find the command starting at token `i`
+    /// Returns (number of tokens, effect, standalone)
+    fn match_at(&self, words: &[String], i: usize) -> Option<(usize, Effect, bool)> {
+        for command in &self.commands {
+            let end = i + command.words.len();
+            if end <= words.len() && words[i..end] == command.words[..] {
+                return Some((
+                    command.words.len(),
+                    command.effect.clone(),
+                    command.standalone,
+                ));
+            }
+        }
+
+        if self.press && words[i] == "press" {
+            return parse_press(&words[i + 1..])
+                .map(|(len, combo)| (len + 1, Effect::Keys(vec![combo]), true));
+        }
+
+        None
+    }
+}
+
+/// Parse "<modifiers...> <key>" after "press"
+/// Returns the number of words used and the key combination
+fn parse_press(words: &[String]) -> Option<(usize, KeyCombo)> {
+    let mut modifiers = Vec::new();
+    let mut i = 0;
+    while let Some(modifier) = words.get(i).and_then(|w| Modifier::parse(w)) {
+        modifiers.push(modifier);
+        i += 1;
+    }
+
+    // Two-word key names first ("page up"), then single words
+    if let Some(key) = words
+        .get(i..i + 2)
+        .and_then(|pair| Key::parse(&pair.join("")))
+    {
+        if !matches!(key, Key::Char(_) | Key::F(_)) {
+            return Some((i + 2, KeyCombo::with(&modifiers, key)));
+        }
+    }
+    let key = Key::parse(words.get(i)?)?;
+    Some((i + 1, KeyCombo::with(&modifiers, key)))
+}
+
+/// Whether the command at tokens[i..i+len] is a whole sentence or clause
+fn is_standalone(text: &str, tokens: &[(usize, usize)], i: usize, len: usize) -> bool {
+    let starts_clause = i == 0 || {
+        let (s, e) = tokens[i - 1];
+        text[s..e].ends_with(CLAUSE_END)
+    };
+    let last = i + len - 1;
+    let ends_clause = last == tokens.len() - 1 || {
+        let (s, e) = tokens[last];
+        text[s..e].ends_with(CLAUSE_END)
+    };
+    starts_clause && ends_clause
+}
+
+/// Merge adjacent text actions and drop empty ones
+fn merge_text(actions: Vec<OutputAction>) -> Vec<OutputAction> {
+    let mut merged: Vec<OutputAction> = Vec::with_capacity(actions.len());
+    for action in actions {
+        match action {
+            OutputAction::Text(text) => {
+                if text.is_empty() {
+                    continue;
+                }
+                if let Some(OutputAction::Text(prev)) = merged.last_mut() {
+                    // Keep a space between dictated pieces, but not around newlines
+                    if !prev.ends_with('\n') && !text.starts_with('\n') {
+                        prev.push(' ');
+                    }
+                    prev.push_str(&text);
+                    continue;
+                }
+                merged.push(OutputAction::Text(text));
+            }
+            key => merged.push(key),
+        }
+    }
+    merged
+}
+
+/// Byte ranges of whitespace-separated tokens
+fn tokenize(text: &str) -> Vec<(usize, usize)> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    for (idx, c) in text.char_indices() {
+        match (c.is_whitespace(), start) {
+            (true, Some(s)) => {
+                tokens.push((s, idx));
+                start = None;
+            }
+            (false, None) => start = Some(idx),
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        tokens.push((s, text.len()));
+    }
+    tokens
+}
+
+/// Lowercase alphanumeric characters only
+fn normalize(token: &str) -> String {
+    token
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Normalized words of a phrase
+fn words(phrase: &str) -> Vec<String> {
+    phrase
+        .split_whitespace()
+        .map(normalize)
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn commands() -> VoiceCommands {
+        VoiceCommands::new(&VoiceCommandsConfig {
+            enabled: true,
+            builtin: true,
+            custom: BTreeMap::new(),
+        })
+    }
+
+    fn parse(text: &str) -> Vec<OutputAction> {
+        commands().parse(text, 0, |t| t.to_string())
+    }
+
+    fn key(combo: &str) -> OutputAction {
+        OutputAction::Key(combo.parse().unwrap())
+    }
+
+    fn text(t: &str) -> OutputAction {
+        OutputAction::Text(t.to_string())
+    }
+
+    #[test]
+    fn test_plain_text() {
+        assert_eq!(parse("Hello world."), vec![text("Hello world.")]);
+        assert!(parse("").is_empty());
+    }
+
+    #[test]
+    fn test_standalone_command() {
+        assert_eq!(parse("Select all."), vec![key("ctrl+a")]);
+        assert_eq!(parse("undo"), vec![key("ctrl+z")]);
+        assert_eq!(
+            parse("Hello world. Select all."),
+            vec![text("Hello world."), key("ctrl+a")]
+        );
+    }
+
+    #[test]
+    fn test_command_inside_sentence_is_text() {
+        assert_eq!(
+            parse("I want to undo the change."),
+            vec![text("I want to undo the change.")]
+        );
+        assert_eq!(
+            parse("please select all files"),
+            vec![text("please select all files")]
+        );
+    }
+
+    #[test]
+    fn test_press_key() {
+        assert_eq!(parse("Press enter."), vec![key("enter")]);
+        assert_eq!(parse("Press control shift T."), vec![key("ctrl+shift+t")]);
+        assert_eq!(parse("Press page down."), vec![key("pagedown")]);
+        assert_eq!(
+            parse("Send it. Press enter."),
+            vec![text("Send it."), key("enter")]
+        );
+        assert_eq!(parse("Press the button."), vec![text("Press the button.")]);
+    }
+
+    #[test]
+    fn test_new_line_anywhere() {
+        assert_eq!(
+            parse("line one new line line two"),
+            vec![text("line one\nline two")]
+        );
+        assert_eq!(parse("new paragraph"), vec![text("\n\n")]);
+    }
+
+    #[test]
+    fn test_delete_that_removes_preceding_text() {
+        assert_eq!(parse("Hello there. Delete that. Hi."), vec![text("Hi.")]);
+    }
+
+    #[test]
+    fn test_delete_that_erases_previous_dictation() {
+        let actions = commands().parse("Delete that.", 3, |t| t.to_string());
+        assert_eq!(
+            actions,
+            vec![key("backspace"), key("backspace"), key("backspace")]
+        );
+
+        let actions = commands().parse("Scratch that", 0, |t| t.to_string());
+        assert!(actions.is_empty());
+    }
+
+    #[test]
+    fn test_process_applies_to_dictated_text_only() {
+        let actions = commands().parse("hello new line world", 0, |t| t.to_uppercase());
+        assert_eq!(actions, vec![text("HELLO\nWORLD")]);
+    }
+
+    #[test]
+    fn test_custom_commands() {
+        let mut custom = BTreeMap::new();
+        custom.insert("save file".to_string(), "ctrl+s".to_string());
+        custom.insert("undo".to_string(), "ctrl+shift+u".to_string());
+        custom.insert("broken".to_string(), "ctrl+nokey".to_string());
+        let commands = VoiceCommands::new(&VoiceCommandsConfig {
+            enabled: true,
+            builtin: true,
+            custom,
+        });
+
+        let parse = |t: &str| commands.parse(t, 0, |t| t.to_string());
+        assert_eq!(parse("Save file."), vec![key("ctrl+s")]);
+        assert_eq!(parse("Undo."), vec![key("ctrl+shift+u")]);
+        assert_eq!(parse("Broken."), vec![text("Broken.")]);
+    }
+
+    #[test]
+    fn test_builtin_disabled() {
+        let mut custom = BTreeMap::new();
+        custom.insert("close tab".to_string(), "ctrl+w".to_string());
+        let commands = VoiceCommands::new(&VoiceCommandsConfig {
+            enabled: true,
+            builtin: false,
+            custom,
+        });
+
+        let parse = |t: &str| commands.parse(t, 0, |t| t.to_string());
+        assert_eq!(parse("Close tab."), vec![key("ctrl+w")]);
+        assert_eq!(parse("Undo."), vec![text("Undo.")]);
+        assert_eq!(parse("Press enter."), vec![text("Press enter.")]);
+    }
+}
@@ -0,0 +1,408 @@
+//! Filler word and disfluency removal
+//!
+//! Removes hesitations ("um", "uh"), discourse markers set off by commas
+//! ("you know", "I mean") and stutter repeats ("I I think", "we should we
+//! should go"), then repairs the punctuation and capitalization left behind:
+//! "Um, I I think, you know, it works." → "I think it works."
+//!
+//! Filler lists are per language. English, German, French and Spanish have
+//! built-in lists, which `[text.disfluency]` can replace; repeat collapsing
+//! works in every language.
+
+use super::punctuation::normalize_language;
+use crate::config::DisfluencyConfig;
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Built-in lists for one language
+struct Builtin {
+    language: &'static str,
+    /// Removed wherever they appear
+    fillers: &'static [&'static str],
+    /// Removed when set off by commas or ending a sentence
+    markers: &'static [&'static str],
+    /// Words that are often repeated on purpose ("that that", "nous nous")
+    keep_repeats: &'static [&'static str],
+}
+
+static BUILTIN: &[Builtin] = &[
+    Builtin {
+        language: "en",
+        fillers: &[
+            "um", "umm", "uh", "uhh", "uhm", "er", "erm", "ah", "hmm", "hm", "mm", "mmm",
+        ],
+        markers: &["you know", "i mean", "like", "you see", "basically"],
+        keep_repeats: &[
+            "that", "had", "is", "very", "really", "no", "bye", "ha", "yeah", "so", "blah", "well",
+        ],
+    },
+    Builtin {
+        language: "de",
+        fillers: &["äh", "ähm", "ähh", "öh", "öhm", "hm", "hmm", "mhm"],
+        markers: &["weißt du", "sozusagen", "quasi", "halt", "ne"],
+        keep_repeats: &["die", "der", "das", "sehr", "nein", "ja", "so"],
+    },
+    Builtin {
+        language: "fr",
+        fillers: &["euh", "heu", "euhm", "hum", "hmm", "bah", "ben"],
+        markers: &["tu vois", "tu sais", "genre", "en fait", "du coup", "voilà"],
+        keep_repeats: &["nous", "vous", "très", "non", "oui", "si", "bon"],
+    },
+    Builtin {
+        language: "es",
+        fillers: &["eh", "ehm", "em", "mmm", "hmm"],
+        markers: &["o sea", "sabes", "este", "pues", "bueno"],
+        keep_repeats: &["muy", "no", "sí", "ya"],
+    },
+];
+
+/// Filler phrases for one language, as lowercase words
+#[derive(Debug, Clone, Default)]
+struct Lists {
+    fillers: Vec<Vec<String>>,
+    markers: Vec<Vec<String>>,
+    keep_repeats: Vec<String>,
+}
+
+/// Removes fillers and stutter repeats from transcriptions
+pub struct DisfluencyRemover {
+    languages: HashMap<String, Lists>,
+    collapse_repeats: bool,
+}
+
+impl DisfluencyRemover {
+    pub fn new(config: &DisfluencyConfig) -> Self {
+        let phrases = |list: &[&str]| -> Vec<Vec<String>> {
+            list.iter().filter_map(|p| phrase_words(p)).collect()
+        };
+
+        let mut languages: HashMap<String, Lists> = BUILTIN
+            .iter()
+            .map(|b| {
+                let lists = Lists {
+                    fillers: phrases(b.fillers),
+                    markers: phrases(b.markers),
+                    keep_repeats: b.keep_repeats.iter().map(|w| w.to_string()).collect(),
+                };
+                (b.language.to_string(), lists)
+            })
+            .collect();
+
+        // Configured lists replace the built-in list for their language
+        for (language, fillers) in &config.fillers {
+            let lists = languages.entry(normalize_language(language)).or_default();
+            lists.fillers = fillers.iter().filter_map(|p| phrase_words(p)).collect();
+        }
+        for (language, markers) in &config.discourse_markers {
+            let lists = languages.entry(normalize_language(language)).or_default();
+            lists.markers = markers.iter().filter_map(|p| phrase_words(p)).collect();
+        }
+
+        Self {
+            languages,
+            collapse_repeats: config.collapse_repeats,
+        }
+    }
+
+    /// Remove fillers and repeats from text in the given language
+    pub fn remove(&self, text: &str, language: &str) -> String {
+        let empty = Lists::default();
+        let lists = self
+            .languages
+            .get(&normalize_language(language))
+            .unwrap_or(&empty);
+
+        let mut tokens = remove_fillers(tokenize(text), lists);
+        if self.collapse_repeats {
+            collapse_repeats(&mut tokens, &lists.keep_repeats);
+        }
+
+        let mut output: String = tokens
+            .iter()
+            .map(|t| format!("{}{}{}{}", t.space, t.lead, t.core, t.trail))
+            .collect();
+        // Keep trailing whitespace (e.g. a newline) as in the input
+        output.push_str(&text[text.trim_end().len()..]);
+        output
+    }
+}
+
+/// Lowercase words of a filler phrase, or None if it is blank
+fn phrase_words(phrase: &str) -> Option<Vec<String>> {
+    let words: Vec<String> = phrase
+        .split_whitespace()
+        .map(|w| w.to_lowercase())
+        .collect();
+    (!words.is_empty()).then_some(words)
+}
+
+/// A whitespace-separated chunk of text
+#[derive(Debug, Clone)]
+struct Token {
+    /// Whitespace before the chunk
+    space: String,
+    /// Punctuation before the word ("(", "¿")
+    lead: String,
+    /// The word itself
+    core: String,
+    /// Punctuation after the word (",", "?", "...")
+    trail: String,
+}
+
+impl Token {
+    fn word(&self) -> String {
+        self.core.to_lowercase()
+    }
+
+    fn ends_sentence(&self) -> bool {
+        self.trail.contains(['.', '?', '!', '…'])
+    }
+
+    fn is_capitalized(&self) -> bool {
+        self.core.starts_with(char::is_uppercase)
+    }
+
+    fn capitalize(&mut self) {
+        let mut chars = self.core.chars();
+        if let Some(first) = chars.next() {
+            self.core = first.to_uppercase().chain(chars).collect();
+        }
+    }
+}
+
+fn tokenize(text: &str) -> Vec<Token> {
+    static CHUNK: OnceLock<Regex> = OnceLock::new();
+    let re = CHUNK.get_or_init(|| Regex::new(r"(\s*)(\S+)").expect("valid chunk regex"));
+    re.captures_iter(text)
+        .map(|caps| {
+            let chunk = &caps[2];
+            let is_word = |c: char| c.is_alphanumeric();
+            let start = chunk.find(is_word).unwrap_or(chunk.len());
+            let end = chunk.rfind(is_word).map_or(start, |i| {
+                i + chunk[i..].chars().next().map_or(0, char::len_utf8)
+            });
+            Token {
+                space: caps[1].to_string(),
+                lead: chunk[..start].to_string(),
+                core: chunk[start..end].to_string(),
+                trail: chunk[end..].to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Whitespace to keep when the words between two spaces are removed:
+/// none at the start of the text, otherwise preferring line breaks
+fn merge_space(removed: String, next: String) -> String {
+    if removed.is_empty() || (removed.contains('\n') && !next.contains('\n')) {
+        removed
+    } else {
+        next
+    }
+}
+
+/// Length of the longest phrase matching the tokens at i
+/// Words inside a phrase must not be separated by punctuation
+fn phrase_at(tokens: &[Token], i: usize, phrases: &[Vec<String>]) -> Option<usize> {
+    phrases
+        .iter()
+        .filter(|phrase| {
+            phrase.len() <= tokens.len() - i
+                && phrase.iter().enumerate().all(|(k, word)| {
+                    let token = &tokens[i + k];
+                    token.word() == *word
+                        && (k == 0 || token.lead.is_empty())
+                        && (k + 1 == phrase.len() || token.trail.is_empty())
+                })
+        })
+        .map(|phrase| phrase.len())
+        .max()
+}
+
+fn remove_fillers(tokens: Vec<Token>, lists: &Lists) -> Vec<Token> {
+    let mut kept: Vec<Token> = Vec::with_capacity(tokens.len());
+    // Whitespace and opening punctuation of removed words, for the next kept word
+    let mut pending_space: Option<String> = None;
+    let mut pending_lead = String::new();
+    let mut capitalize_next = false;
+
+    let mut i = 0;
+    while i < tokens.len() {
+        let sentence_start = kept.last().is_none_or(Token::ends_sentence);
+        let filler = phrase_at(&tokens, i, &lists.fillers);
+        let marker = phrase_at(&tokens, i, &lists.markers).filter(|len| {
+            // Only set off by commas: "I, like, love it" but not "I like it"
+            let last = &tokens[i + len - 1];
+            let before = sentence_start || kept.last().is_some_and(|t| t.trail.ends_with(','));
+            let after = i + len == tokens.len() || !last.trail.is_empty();
+            before && after
+        });
+
+        let Some(len) = filler.max(marker) else {
+            let mut token = tokens[i].clone();
+            if let Some(space) = pending_space.take() {
+                token.space = merge_space(space, token.space);
+            }
+            token.lead = std::mem::take(&mut pending_lead) + token.lead.as_str();
+            if capitalize_next {
+                token.capitalize();
+                capitalize_next = false;
+            }
+            kept.push(token);
+            i += 1;
+            continue;
+        };
+
+        let first = &tokens[i];
+        let last = &tokens[i + len - 1];
+        pending_space.get_or_insert_with(|| first.space.clone());
+        pending_lead.push_str(&first.lead);
+
+        if sentence_start {
+            capitalize_next |= first.is_capitalized();
+        } else if let Some(previous) = kept.last_mut() {
+            if last.ends_sentence() {
+                // "it works, um." → "it works."
+                let end: String = last.trail.chars().filter(|c| *c != ',').collect();
+                previous.trail = format!("{}{}", previous.trail.trim_end_matches(','), end);
+            } else if last.trail.contains(',') && previous.trail.ends_with(',') {
+                // "I think, um, we" → "I think we"
+                previous.trail.pop();
+            }
+        }
+        i += len;
+    }
+    kept
+}
+
+/// Collapse repeated words and phrases: "I I think" → "I think",
+/// "we should we should go" → "we should go"
+fn collapse_repeats(tokens: &mut Vec<Token>, keep: &[String]) {
+    let mut i = 0;
+    while i < tokens.len() {
+        let repeat = (1..=3).rev().find(|&n| {
+            if i + 2 * n > tokens.len() {
+                return false;
+            }
+            let (a, b) = (&tokens[i..i + n], &tokens[i + n..i + 2 * n]);
+            let same = a
+                .iter()
+                .zip(b)
+                .all(|(x, y)| x.core.chars().any(char::is_alphabetic) && x.word() == y.word());
+            // A repeat is one stretch of speech: no sentence or clause break
+            // inside it, except the break after a false start ("I- I", "I, I")
+            let joined = a[..n - 1]
+                .iter()
+                .chain(&b[..n - 1])
+                .all(|t| t.trail.is_empty())
+                && b.iter().all(|t| t.lead.is_empty())
+                && matches!(a[n - 1].trail.as_str(), "" | "," | "-" | "—" | "..." | "…");
+            let intended = n == 1 && keep.contains(&a[0].word());
+            same && joined && !intended
+        });
+
+        match repeat {
+            Some(n) => {
+                let removed: Vec<Token> = tokens.drain(i..i + n).collect();
+                let next = &mut tokens[i];
+                next.space = merge_space(removed[0].space.clone(), std::mem::take(&mut next.space));
+                next.lead = removed[0].lead.clone();
+                if removed[0].is_capitalized() {
+                    next.capitalize();
+                }
+            }
+            None => i += 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn remove(text: &str) -> String {
+        DisfluencyRemover::new(&DisfluencyConfig::default()).remove(text, "en")
+    }
+
+    #[test]
+    fn test_fillers_removed() {
+        assert_eq!(remove("I um think so"), "I think so");
+        assert_eq!(remove("Um, I think so."), "I think so.");
+        assert_eq!(
+            remove("I think, uh, we should go."),
+            "I think we should go."
+        );
+        assert_eq!(remove("It works, um."), "It works.");
+        assert_eq!(remove("Yes. Uh, no."), "Yes. No.");
+        assert_eq!(remove("um"), "");
+    }
+
+    #[test]
+    fn test_markers_only_when_set_off() {
+        assert_eq!(remove("It's, you know, really fast."), "It's really fast.");
+        assert_eq!(remove("You know the answer."), "You know the answer.");
+        assert_eq!(remove("I, like, love it."), "I love it.");
+        assert_eq!(remove("I like it."), "I like it.");
+        assert_eq!(remove("I mean, it works."), "It works.");
+    }
+
+    #[test]
+    fn test_repeats_collapsed() {
+        assert_eq!(remove("I I think so"), "I think so");
+        assert_eq!(remove("the the the cat"), "the cat");
+        assert_eq!(remove("We- we should go."), "We should go.");
+        assert_eq!(remove("we should we should go"), "we should go");
+        assert_eq!(
+            remove("I said that that was fine"),
+            "I said that that was fine"
+        );
+        assert_eq!(remove("It ends. Ends here."), "It ends. Ends here.");
+    }
+
+    #[test]
+    fn test_repeats_can_be_kept() {
+        let config = DisfluencyConfig {
+            collapse_repeats: false,
+            ..Default::default()
+        };
+        let remover = DisfluencyRemover::new(&config);
+        assert_eq!(remover.remove("I I um think", "en"), "I I think");
+    }
+
+    #[test]
+    fn test_language_lists() {
+        let remover = DisfluencyRemover::new(&DisfluencyConfig::default());
+        assert_eq!(
+            remover.remove("Euh, je pense, tu vois, que oui.", "fr"),
+            "Je pense que oui."
+        );
+        assert_eq!(remover.remove("Ähm, das ist gut.", "de-AT"), "Das ist gut.");
+        // "um" is only a filler in English
+        assert_eq!(remover.remove("ich um mich", "de"), "ich um mich");
+        // No built-in fillers, but repeats still collapse
+        assert_eq!(remover.remove("ja ja ja", "ja"), "ja");
+    }
+
+    #[test]
+    fn test_configured_lists_replace_builtin() {
+        let mut config = DisfluencyConfig::default();
+        config
+            .fillers
+            .insert("en".to_string(), vec!["so yeah".to_string()]);
+        config
+            .discourse_markers
+            .insert("en".to_string(), vec!["right".to_string()]);
+        let remover = DisfluencyRemover::new(&config);
+        assert_eq!(
+            remover.remove("Um, so yeah it works, right?", "en"),
+            "Um, it works?"
+        );
+    }
+
+    #[test]
+    fn test_whitespace_preserved() {
+        assert_eq!(remove("line one um\nline two\n"), "line one\nline two\n");
+        assert_eq!(remove("line one\num line two"), "line one\nline two");
+    }
+}
@@ -0,0 +1,228 @@
+//! Command grammar matching
+//!
+//! Matches a transcription against a fixed set of command phrases and
+//! returns the identifier of the best match. Used for constrained voice
+//! commands where only a known set of utterances makes sense.
+//!
+//! Matching works with every engine: the transcription is normalized and
+//! compared to each phrase by edit distance, tolerating small
+//! recognition errors ("close the window" still matches "close window").
+//! For Whisper, the phrases are also added to the initial prompt so that
+//! decoding is biased toward them.
+
+use super::vocabulary::{estimate_tokens, levenshtein};
+use crate::config::GrammarConfig;
+
+/// Result of a successful grammar match
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandMatch {
+    /// Command identifier from the grammar
+    pub id: String,
+    /// Phrase that matched
+    pub phrase: String,
+    /// Similarity score (1.0 = exact match)
+    pub score: f32,
+}
+
+/// Matcher for a configured command grammar
+pub struct CommandGrammar {
+    /// (command id, original phrase, normalized phrase words)
+    phrases: Vec<(String, String, Vec<String>)>,
+    threshold: f32,
+}
+
+impl CommandGrammar {
+    /// Build a matcher from grammar configuration
+    pub fn new(config: &GrammarConfig) -> Self {
+        let phrases = config
+            .commands
+            .iter()
+            .flat_map(|(id, phrases)| {
+                phrases.iter().filter_map(move |phrase| {
+                    let words = words(phrase);
+                    (!words.is_empty()).then(|| (id.clone(), phrase.clone(), words))
+                })
+            })
+            .collect();
+
+        Self {
+            phrases,
+            threshold: config.threshold.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Whether the grammar has no phrases
+    pub fn is_empty(&self) -> bool {
+        self.phrases.is_empty()
+    }
+
+    /// Initial prompt listing the phrases after `base`, to bias recognition toward them
+    ///
+    /// Phrases that would take the prompt past `max_tokens` (approximately)
+    /// are left out; the base prompt is always kept.
+    pub fn prompt(&self, base: Option<&str>, max_tokens: usize) -> Option<String> {
+        let mut prompt = base.map(str::trim).unwrap_or_default().to_string();
+        for (_, phrase, _) in &self.phrases {
+            let candidate = if prompt.is_empty() {
+                format!("{}.", phrase)
+            } else {
+                format!("{} {}.", prompt, phrase)
+            };
+            if estimate_tokens(&candidate) > max_tokens {
+                break;
+            }
+            prompt = candidate;
+        }
+        (!prompt.is_empty()).then_some(prompt)
+    }
+
+    /// Match a transcription against the grammar
+    ///
+    /// Returns the best-scoring command at or above the threshold. Ties go to
+    /// the command listed first (commands are ordered by identifier).
+    pub fn match_command(&self, text: &str) -> Option<CommandMatch> {
+        let spoken = words(text);
+        if spoken.is_empty() {
+            return None;
+        }
+
+        let mut best: Option<CommandMatch> = None;
+        for (id, phrase, phrase_words) in &self.phrases {
+            let score = score(&spoken, phrase_words);
+            if score >= self.threshold && best.as_ref().is_none_or(|b| score > b.score) {
+                best = Some(CommandMatch {
+                    id: id.clone(),
+                    phrase: phrase.clone(),
+                    score,
+                });
+            }
+        }
+        best
+    }
+}
+
+/// Lowercase words with punctuation stripped
+fn words(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(|w| {
+            w.chars()
+                .filter(|c| c.is_alphanumeric() || *c == '\'')
+                .flat_map(char::to_lowercase)
+                .collect::<String>()
+        })
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+/// Similarity between spoken words and a phrase
+///
+/// Compares characters of the whole utterance, so small misrecognitions and
+/// split or merged words ("log out" vs "logout") still score high.
+fn score(spoken: &[String], phrase: &[String]) -> f32 {
+    let a: Vec<char> = spoken.join(" ").chars().collect();
+    let b: Vec<char> = phrase.join(" ").chars().collect();
+    let max_len = a.len().max(b.len());
+    if max_len == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein(&a, &b) as f32 / max_len as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn grammar(threshold: f32) -> CommandGrammar {
+        let mut commands = BTreeMap::new();
+        commands.insert(
+            "close".to_string(),
+            vec!["close window".to_string(), "close this".to_string()],
+        );
+        commands.insert("maximize".to_string(), vec!["maximize".to_string()]);
+        commands.insert("next_tab".to_string(), vec!["next tab".to_string()]);
+        CommandGrammar::new(&GrammarConfig {
+            commands,
+            threshold,
+            bias_prompt: true,
+        })
+    }
+
+    #[test]
+    fn test_exact_match() {
+        let m = grammar(0.75).match_command("close window").unwrap();
+        assert_eq!(m.id, "close");
+        assert_eq!(m.phrase, "close window");
+        assert_eq!(m.score, 1.0);
+    }
+
+    #[test]
+    fn test_ignores_case_and_punctuation() {
+        let m = grammar(0.75).match_command(" Next tab.").unwrap();
+        assert_eq!(m.id, "next_tab");
+        assert_eq!(m.score, 1.0);
+    }
+
+    #[test]
+    fn test_fuzzy_match() {
+        let m = grammar(0.75).match_command("Maximise").unwrap();
+        assert_eq!(m.id, "maximize");
+        assert!(m.score < 1.0);
+
+        let m = grammar(0.75).match_command("close windows").unwrap();
+        assert_eq!(m.id, "close");
+    }
+
+    #[test]
+    fn test_no_match_below_threshold() {
+        assert!(grammar(0.75)
+            .match_command("open the pod bay doors")
+            .is_none());
+        assert!(grammar(0.75).match_command("").is_none());
+        assert!(grammar(0.75).match_command("...").is_none());
+    }
+
+    #[test]
+    fn test_threshold_is_respected() {
+        assert!(grammar(1.0).match_command("Maximise").is_none());
+        assert!(grammar(0.5).match_command("Maximise").is_some());
+    }
+
+    #[test]
+    fn test_prompt_lists_phrases() {
+        assert_eq!(
+            grammar(0.75).prompt(None, 224).as_deref(),
+            Some("close window. close this. maximize. next tab.")
+        );
+    }
+
+    #[test]
+    fn test_prompt_keeps_base_within_budget() {
+        let g = grammar(0.75);
+        assert_eq!(
+            g.prompt(Some("Hyprland, Waybar. "), 224).as_deref(),
+            Some("Hyprland, Waybar. close window. close this. maximize. next tab.")
+        );
+        // "Hyprland, Waybar. close window. close this." is 15 tokens by the estimate
+        assert_eq!(
+            g.prompt(Some("Hyprland, Waybar."), 15).as_deref(),
+            Some("Hyprland, Waybar. close window. close this.")
+        );
+        assert_eq!(
+            g.prompt(Some("Hyprland, Waybar."), 1).as_deref(),
+            Some("Hyprland, Waybar.")
+        );
+    }
+
+    #[test]
+    fn test_empty_grammar() {
+        let g = CommandGrammar::new(&GrammarConfig {
+            commands: BTreeMap::new(),
+            threshold: 0.75,
+            bias_prompt: true,
+        });
+        assert!(g.is_empty());
+        assert!(g.prompt(None, 224).is_none());
+        assert!(g.match_command("anything").is_none());
+    }
+}
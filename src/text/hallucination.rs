@@ -0,0 +1,158 @@
+//! Whisper hallucination filter
+//!
+//! On silence or noise, Whisper tends to produce text it saw a lot in
+//! training: video outros ("Thanks for watching!"), subtitle credits and
+//! non-speech tags like "[BLANK_AUDIO]" or "(music)". The filter removes
+//! such tags, and drops the transcription when nothing else is left or when
+//! it is only a known phrase.
+
+use crate::config::HallucinationFilterConfig;
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Phrases Whisper produces on silence, dropped when they are the whole transcription
+const BUILTIN_PHRASES: &[&str] = &[
+    "thanks for watching",
+    "thank you for watching",
+    "thank you so much for watching",
+    "thanks for watching and see you next time",
+    "please subscribe",
+    "like and subscribe",
+    "don't forget to like and subscribe",
+    "see you next time",
+    "see you in the next video",
+    "you",
+    "subtitles by the amara org community",
+    "transcribed by https otter ai",
+    "untertitel der amara org community",
+    "untertitelung des zdf 2020",
+    "vielen dank fürs zuschauen",
+    "sous titrage société radio canada",
+    "merci d'avoir regardé cette vidéo",
+    "subtítulos realizados por la comunidad de amara org",
+    "gracias por ver el video",
+];
+
+/// Non-speech tags, removed wherever they appear ("[BLANK_AUDIO]", "(music)")
+const TAGS: &[&str] = &[
+    "blank_audio",
+    "blank audio",
+    "no speech",
+    "silence",
+    "music",
+    "music playing",
+    "applause",
+    "laughter",
+    "laughs",
+    "inaudible",
+    "noise",
+    "background noise",
+    "sound",
+    "coughs",
+    "sighs",
+    "typing",
+    "keyboard clicking",
+];
+
+/// Drops known Whisper hallucinations
+pub struct HallucinationFilter {
+    /// Normalized phrases dropped when they are the whole transcription
+    phrases: Vec<String>,
+}
+
+impl HallucinationFilter {
+    pub fn new(config: &HallucinationFilterConfig) -> Self {
+        let phrases = BUILTIN_PHRASES
+            .iter()
+            .map(|p| p.to_string())
+            .chain(config.phrases.iter().cloned())
+            .map(|p| normalize(&p))
+            .filter(|p| !p.is_empty())
+            .collect();
+        Self { phrases }
+    }
+
+    /// Remove non-speech tags, then drop the text if only a known phrase is left
+    /// Returns an empty string for a dropped transcription
+    pub fn filter(&self, text: &str) -> String {
+        static TAG: OnceLock<Regex> = OnceLock::new();
+        let tag = TAG.get_or_init(|| {
+            Regex::new(r"[\[(*♪]+\s*([^\])*♪]*?)\s*[\])*♪]+").expect("valid tag regex")
+        });
+
+        let without_tags = tag.replace_all(text, |caps: &regex::Captures| {
+            let inner = caps[1].to_lowercase();
+            if inner.is_empty() || TAGS.contains(&inner.as_str()) {
+                String::new()
+            } else {
+                caps[0].to_string()
+            }
+        });
+        // Close the gap a removed tag leaves, keeping line breaks
+        let without_tags = without_tags
+            .split(' ')
+            .filter(|w| !w.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let normalized = normalize(&without_tags);
+        if normalized.is_empty() || self.phrases.contains(&normalized) {
+            String::new()
+        } else {
+            without_tags
+        }
+    }
+}
+
+/// Lowercase words without punctuation, for comparing phrases
+fn normalize(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !(c.is_alphanumeric() || c == '\'' || c == '’'))
+        .filter(|w| !w.is_empty())
+        .map(|w| w.replace('’', "'"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(text: &str) -> String {
+        HallucinationFilter::new(&HallucinationFilterConfig::default()).filter(text)
+    }
+
+    #[test]
+    fn test_drops_known_phrases() {
+        assert_eq!(filter("Thanks for watching!"), "");
+        assert_eq!(filter(" you"), "");
+        assert_eq!(filter("Subtitles by the Amara.org community"), "");
+        assert_eq!(
+            filter("Thank you for the review."),
+            "Thank you for the review."
+        );
+    }
+
+    #[test]
+    fn test_removes_tags() {
+        assert_eq!(filter("[BLANK_AUDIO]"), "");
+        assert_eq!(filter("(music) ♪ ♪"), "");
+        assert_eq!(filter("Hello [Music] world."), "Hello world.");
+        assert_eq!(
+            filter("See section (a) and [1]."),
+            "See section (a) and [1]."
+        );
+        assert_eq!(filter("*laughs* Thank you."), "Thank you.");
+    }
+
+    #[test]
+    fn test_configured_phrases() {
+        let config = HallucinationFilterConfig {
+            enabled: true,
+            phrases: vec!["Okay.".to_string()],
+        };
+        let filter = HallucinationFilter::new(&config);
+        assert_eq!(filter.filter("okay"), "");
+        assert_eq!(filter.filter("Okay, let's go."), "Okay, let's go.");
+    }
+}
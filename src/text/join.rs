@@ -0,0 +1,232 @@
+//! Smart joining of consecutive dictations
+//!
+//! Every dictation is transcribed on its own, so back-to-back recordings come
+//! out as "Hello world.And then" or "I think. We should" with a capital in the
+//! middle of a sentence. The joiner remembers the end of the previous output
+//! and adjusts the start of the next one so continuous dictation reads as one
+//! text:
+//! - A space is inserted between the two, unless the next one starts with
+//!   closing punctuation or the previous one ended with a space or line break
+//! - The first letter is capitalized after a sentence end, and lowercased
+//!   (if Whisper capitalized it) when continuing a sentence
+//! - Punctuation already typed isn't repeated ("Hello." + ". Then")
+
+use crate::config::SmartJoinConfig;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How many characters of the previous output are kept
+const TAIL_CHARS: usize = 8;
+
+/// Punctuation that attaches to the preceding text
+const CLOSING: &[char] = &[
+    '.', ',', ';', ':', '!', '?', ')', ']', '}', '…', '»', '”', '’', '%', '।', '。', '，',
+];
+
+/// Characters that end a sentence
+const SENTENCE_END: &[char] = &['.', '!', '?', '…', '。', '！', '？'];
+
+/// Characters after which no space is needed
+const OPENING: &[char] = &['(', '[', '{', '«', '“', '‘', '/', '-', '¿', '¡'];
+
+/// Words that keep their capital when continuing a sentence
+const ALWAYS_CAPITALIZED: &[&str] = &[
+    "I", "I'm", "I've", "I'll", "I'd", "I’m", "I’ve", "I’ll", "I’d",
+];
+
+/// End of the previous output
+#[derive(Debug, Clone)]
+struct Tail {
+    text: String,
+    at: Instant,
+}
+
+/// Joins consecutive dictations into continuous text
+pub struct SmartJoiner {
+    enabled: bool,
+    timeout: Duration,
+    capitalization: bool,
+    tail: Mutex<Option<Tail>>,
+}
+
+impl SmartJoiner {
+    pub fn new(config: &SmartJoinConfig) -> Self {
+        Self {
+            enabled: config.enabled,
+            timeout: Duration::from_secs(config.timeout_secs),
+            capitalization: config.capitalization,
+            tail: Mutex::new(None),
+        }
+    }
+
+    /// Adjust text to continue the previous output
+    /// Returns the text unchanged when disabled or the previous output timed out
+    pub fn join(&self, text: &str) -> String {
+        self.join_at(text, Instant::now())
+    }
+
+    /// Remember text that was output, so the next dictation can continue it
+    pub fn record(&self, output: &str) {
+        self.record_at(output, Instant::now())
+    }
+
+    /// Forget the previous output (e.g., after key presses moved the cursor)
+    pub fn reset(&self) {
+        *self.tail.lock().unwrap() = None;
+    }
+
+    fn record_at(&self, output: &str, at: Instant) {
+        if !self.enabled || output.is_empty() {
+            return;
+        }
+        let skip = output.chars().count().saturating_sub(TAIL_CHARS);
+        *self.tail.lock().unwrap() = Some(Tail {
+            text: output.chars().skip(skip).collect(),
+            at,
+        });
+    }
+
+    fn join_at(&self, text: &str, now: Instant) -> String {
+        if !self.enabled || text.is_empty() {
+            return text.to_string();
+        }
+        let tail = self.tail.lock().unwrap().clone();
+        let Some(tail) = tail.filter(|t| now.duration_since(t.at) <= self.timeout) else {
+            return text.to_string();
+        };
+        join_after(&tail.text, text, self.capitalization)
+    }
+}
+
+/// Adjust the start of `text` to follow `previous`
+fn join_after(previous: &str, text: &str, capitalization: bool) -> String {
+    let Some(last) = previous.chars().last() else {
+        return text.to_string();
+    };
+    let trimmed = text.trim_start_matches([' ', '\t']);
+
+    // Don't repeat punctuation the previous output already ends with
+    let trimmed = match trimmed.chars().next() {
+        Some(c) if CLOSING.contains(&c) && c == last => {
+            trimmed[c.len_utf8()..].trim_start_matches([' ', '\t'])
+        }
+        _ => trimmed,
+    };
+
+    let mut body = trimmed.to_string();
+    // Sentence end: the last punctuation, looking past closing quotes and brackets
+    let sentence_ended = previous
+        .trim_end()
+        .chars()
+        .rev()
+        .find(|c| !matches!(c, ')' | ']' | '"' | '\'' | '”' | '’' | '»'))
+        .is_none_or(|c| SENTENCE_END.contains(&c))
+        || previous.ends_with('\n');
+    if capitalization {
+        body = if sentence_ended {
+            capitalize_first(&body)
+        } else {
+            lowercase_first_word(&body)
+        };
+    }
+
+    let attaches = body.starts_with(CLOSING)
+        || body.starts_with(char::is_whitespace)
+        || last.is_whitespace()
+        || OPENING.contains(&last);
+    if attaches || body.is_empty() {
+        body
+    } else {
+        format!(" {}", body)
+    }
+}
+
+fn capitalize_first(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) if first.is_lowercase() => first.to_uppercase().chain(chars).collect(),
+        _ => text.to_string(),
+    }
+}
+
+/// Lowercase a first word that is only capitalized because it started the
+/// transcription; acronyms ("NASA") and "I" are kept
+fn lowercase_first_word(text: &str) -> String {
+    let end = text
+        .find(|c: char| !(c.is_alphanumeric() || c == '\'' || c == '’'))
+        .unwrap_or(text.len());
+    let word = &text[..end];
+    let mut chars = word.chars();
+    let Some(first) = chars.next() else {
+        return text.to_string();
+    };
+    let rest_lowercase = chars.all(|c| !c.is_uppercase());
+    if !first.is_uppercase() || !rest_lowercase || ALWAYS_CAPITALIZED.contains(&word) {
+        return text.to_string();
+    }
+    first
+        .to_lowercase()
+        .chain(text[first.len_utf8()..].chars())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn joiner() -> SmartJoiner {
+        SmartJoiner::new(&SmartJoinConfig {
+            enabled: true,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_inserts_space_and_capitalizes() {
+        assert_eq!(join_after("Hello world.", "And then", true), " And then");
+        assert_eq!(join_after("Hello world.", "and then", true), " And then");
+        assert_eq!(
+            join_after("I think", "We should go.", true),
+            " we should go."
+        );
+        assert_eq!(join_after("I think", "I should go.", true), " I should go.");
+        assert_eq!(join_after("I think", "NASA is", true), " NASA is");
+        assert_eq!(join_after("I think", "We should", false), " We should");
+    }
+
+    #[test]
+    fn test_attaching_punctuation() {
+        assert_eq!(join_after("Hello world", ", and then", true), ", and then");
+        assert_eq!(join_after("Hello world.", ". Then", true), " Then");
+        assert_eq!(join_after("Hello world.\n", "then", true), "Then");
+        assert_eq!(join_after("Hello (", "world", true), "world");
+        assert_eq!(join_after("He said \"stop.\"", "then", true), " Then");
+    }
+
+    #[test]
+    fn test_timeout_and_reset() {
+        let joiner = joiner();
+        let start = Instant::now();
+        assert_eq!(joiner.join_at("Hello", start), "Hello");
+
+        joiner.record_at("Well, hello world.", start);
+        assert_eq!(
+            joiner.join_at("and then", start + Duration::from_secs(5)),
+            " And then"
+        );
+        assert_eq!(
+            joiner.join_at("and then", start + Duration::from_secs(600)),
+            "and then"
+        );
+
+        joiner.reset();
+        assert_eq!(joiner.join_at("and then", start), "and then");
+    }
+
+    #[test]
+    fn test_disabled() {
+        let joiner = SmartJoiner::new(&SmartJoinConfig::default());
+        joiner.record("Hello world.");
+        assert_eq!(joiner.join("and then"), "and then");
+    }
+}
@@ -1,19 +1,92 @@
 //! Text processing module
 //!
 //! Provides post-transcription text transformations including:
-//! - Spoken punctuation conversion (e.g., "period" → ".")
+//! - Whisper hallucination filtering (e.g., "Thanks for watching!" on silence)
+//! - Filler word and stutter removal (e.g., "um, I I think" → "I think")
+//! - Inverse text normalization (e.g., "twenty three percent" → "23%")
+//! - Spoken punctuation conversion (e.g., "period" → ".", "virgule" → ",")
+//! - Code dictation (e.g., "camel case get value" → "getValue")
+//! - Custom vocabulary correction (near-miss spellings of domain terms)
 //! - Custom word replacements
-
-use crate::config::TextConfig;
+//! - Ordered literal/regex replacement rules
+//! - Snippet expansion (trigger phrases → boilerplate with placeholders)
+//! - Voice editing commands (text and key actions)
+//! - Command grammar matching (constrained voice commands)
+
+pub mod code;
+pub mod commands;
+pub mod disfluency;
+pub mod grammar;
+pub mod hallucination;
+pub mod join;
+pub mod normalize;
+pub mod punctuation;
+pub mod rules;
+pub mod snippets;
+pub mod vocabulary;
+
+use crate::config::{PipelineStageKind, TextConfig};
+use crate::output::action::OutputAction;
+use code::CodeFormatter;
+use commands::VoiceCommands;
+use disfluency::DisfluencyRemover;
+use hallucination::HallucinationFilter;
+use normalize::Normalizer;
 use regex::Regex;
+use rules::CompiledRule;
+use snippets::{SnippetExpander, SnippetLibrary};
 use std::collections::HashMap;
+use vocabulary::{Vocabulary, VocabularyCorrector};
+
+/// Per-transcription settings for text processing
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProcessContext<'a> {
+    /// Transcription language (detected or overridden), if known
+    pub language: Option<&'a str>,
+    /// Inverse text normalization on/off from the active profile
+    pub normalize: Option<bool>,
+    /// Disfluency removal on/off from the active profile
+    pub disfluency: Option<bool>,
+    /// Code dictation on/off from the active profile
+    pub code: Option<bool>,
+    /// Spoken punctuation on/off from the active profile
+    pub spoken_punctuation: Option<bool>,
+}
 
 /// Text processor that applies transformations to transcribed text
 pub struct TextProcessor {
+    /// Whether the hallucination filter is enabled
+    hallucination_filter: bool,
+    /// Filter for text Whisper produces on silence
+    hallucination: HallucinationFilter,
+    /// Whether filler word removal is enabled
+    disfluency: bool,
+    /// Filler word and stutter remover
+    disfluency_remover: DisfluencyRemover,
+    /// Whether inverse text normalization is enabled
+    normalize: bool,
+    /// Inverse text normalizer for spoken numbers, dates and units
+    normalizer: Normalizer,
+    /// Whether code dictation is enabled
+    code: bool,
+    /// Code dictation formatter (casing commands, symbols, spelling)
+    code_formatter: CodeFormatter,
     /// Whether spoken punctuation is enabled
     spoken_punctuation: bool,
+    /// Spoken punctuation language that overrides the transcription language
+    punctuation_language: Option<String>,
+    /// Spoken punctuation language when the transcription language is unknown
+    language: String,
     /// Custom word replacements (lowercase key → replacement value)
     replacements: HashMap<String, String>,
+    /// Fuzzy corrector for custom vocabulary terms
+    vocabulary: Option<VocabularyCorrector>,
+    /// Ordered replacement rules from [[text.rules]]
+    rules: Vec<CompiledRule>,
+    /// Snippet expander for trigger phrases (if any snippets are defined)
+    snippets: Option<SnippetExpander>,
+    /// Voice editing command recognizer (if enabled)
+    commands: Option<VoiceCommands>,
 }
 
 impl TextProcessor {
@@ -27,88 +100,228 @@ impl TextProcessor {
             .collect();
 
         Self {
+            hallucination_filter: config.hallucination_filter.enabled,
+            hallucination: HallucinationFilter::new(&config.hallucination_filter),
+            disfluency: config.disfluency.enabled,
+            disfluency_remover: DisfluencyRemover::new(&config.disfluency),
+            normalize: config.normalize.enabled,
+            normalizer: Normalizer::new(&config.normalize),
+            code: config.code.enabled,
+            code_formatter: CodeFormatter::new(&config.code),
             spoken_punctuation: config.spoken_punctuation,
+            punctuation_language: config.punctuation_language.clone(),
+            language: punctuation::DEFAULT_LANGUAGE.to_string(),
             replacements,
+            vocabulary: None,
+            rules: rules::compile_rules(&config.rules),
+            snippets: None,
+            commands: config
+                .commands
+                .enabled
+                .then(|| VoiceCommands::new(&config.commands)),
         }
     }
 
+    /// Enable fuzzy correction of vocabulary terms
+    pub fn with_vocabulary(mut self, vocabulary: &Vocabulary, threshold: f32) -> Self {
+        self.vocabulary = if vocabulary.is_empty() {
+            None
+        } else {
+            Some(VocabularyCorrector::new(vocabulary, threshold))
+        };
+        self
+    }
+
+    /// Enable snippet expansion
+    pub fn with_snippets(mut self, library: &SnippetLibrary) -> Self {
+        self.snippets = if library.is_empty() {
+            None
+        } else {
+            Some(SnippetExpander::new(library))
+        };
+        self
+    }
+
+    /// Set the configured transcription language, used for filler removal,
+    /// normalization and spoken punctuation when a transcription doesn't report its language
+    pub fn with_language(mut self, language: &str) -> Self {
+        self.language = language.to_string();
+        self
+    }
+
     /// Process text by applying all enabled transformations
     pub fn process(&self, text: &str) -> String {
-        let mut result = text.to_string();
+        self.process_with(text, ProcessContext::default())
+    }
 
-        // Apply spoken punctuation first (so user replacements can override if needed)
-        if self.spoken_punctuation {
-            result = self.apply_spoken_punctuation(&result);
-        }
+    /// Process text with per-transcription settings (detected language,
+    /// profile overrides)
+    pub fn process_with(&self, text: &str, context: ProcessContext) -> String {
+        self.run(text, context, |_, _| {})
+    }
 
-        // Apply custom replacements
-        if !self.replacements.is_empty() {
-            result = self.apply_replacements(&result);
+    /// Process text and recognize voice editing commands
+    /// Returns the text and key actions to output; dictated text between
+    /// commands is processed like [`process_with`](Self::process_with)
+    pub fn process_actions(
+        &self,
+        text: &str,
+        context: ProcessContext,
+        previous_output_chars: usize,
+    ) -> Vec<OutputAction> {
+        self.process_actions_with(text, previous_output_chars, |t| {
+            self.process_with(t, context)
+        })
+    }
+
+    /// Recognize voice editing commands, processing dictated text with `process`
+    pub fn process_actions_with(
+        &self,
+        text: &str,
+        previous_output_chars: usize,
+        process: impl Fn(&str) -> String,
+    ) -> Vec<OutputAction> {
+        match &self.commands {
+            Some(commands) => commands.parse(text, previous_output_chars, process),
+            None => {
+                let processed = process(text);
+                if processed.is_empty() {
+                    Vec::new()
+                } else {
+                    vec![OutputAction::Text(processed)]
+                }
+            }
         }
+    }
 
-        result
+    /// Process text and record the output of every stage and rule
+    /// Returns (stage label, text after the stage) pairs, in order
+    pub fn trace(&self, text: &str, context: ProcessContext) -> Vec<(String, String)> {
+        let mut steps = Vec::new();
+        self.run(text, context, |label, output| {
+            steps.push((label.to_string(), output.to_string()))
+        });
+        steps
     }
 
-    /// Apply spoken punctuation conversions
-    fn apply_spoken_punctuation(&self, text: &str) -> String {
-        let mut result = text.to_string();
+    /// Spoken punctuation language: the configured override, then the
+    /// transcription language, then the configured transcription language
+    pub fn punctuation_language<'a>(&'a self, language: Option<&'a str>) -> &'a str {
+        self.punctuation_language
+            .as_deref()
+            .or(language)
+            .unwrap_or(&self.language)
+    }
 
-        // Order matters: longer phrases first to avoid partial matches
-        // Using word boundaries to avoid replacing parts of words
-        let punctuation_map: &[(&str, &str)] = &[
-            // Multi-word phrases first
-            ("question mark", "?"),
-            ("exclamation mark", "!"),
-            ("exclamation point", "!"),
-            ("open parenthesis", "("),
-            ("close parenthesis", ")"),
-            ("open paren", "("),
-            ("close paren", ")"),
-            ("open bracket", "["),
-            ("close bracket", "]"),
-            ("open brace", "{"),
-            ("close brace", "}"),
-            ("at sign", "@"),
-            ("at symbol", "@"),
-            ("dollar sign", "$"),
-            ("percent sign", "%"),
-            ("plus sign", "+"),
-            ("equals sign", "="),
-            ("forward slash", "/"),
-            ("single quote", "'"),
-            ("double quote", "\""),
-            ("new paragraph", "\n\n"),
-            ("new line", "\n"),
-            // Single words
-            ("period", "."),
-            ("comma", ","),
-            ("colon", ":"),
-            ("semicolon", ";"),
-            ("dash", "-"),
-            ("hyphen", "-"),
-            ("underscore", "_"),
-            ("hash", "#"),
-            ("hashtag", "#"),
-            ("percent", "%"),
-            ("ampersand", "&"),
-            ("asterisk", "*"),
-            ("plus", "+"),
-            ("equals", "="),
-            ("slash", "/"),
-            ("backslash", "\\"),
-            ("pipe", "|"),
-            ("tilde", "~"),
-            ("backtick", "`"),
-            ("tab", "\t"),
-        ];
+    /// Built-in stages in their default order, with whether each is enabled
+    ///
+    /// Hallucinations are dropped first, then fillers, so neither splits numbers
+    /// or phrases. Numbers are normalized before spoken punctuation, which would
+    /// otherwise take "point" and "percent" (or "virgule") as punctuation. Code
+    /// dictation has its own symbol words and replaces spoken punctuation.
+    /// Vocabulary fixes come before user replacements and rules, so those can
+    /// override anything above; snippets go last so their text is output as written.
+    fn default_stages(&self, context: ProcessContext) -> [(PipelineStageKind, bool); 9] {
+        let code = context.code.unwrap_or(self.code);
+        [
+            (
+                PipelineStageKind::HallucinationFilter,
+                self.hallucination_filter,
+            ),
+            (
+                PipelineStageKind::Disfluency,
+                context.disfluency.unwrap_or(self.disfluency),
+            ),
+            (
+                PipelineStageKind::Normalize,
+                context.normalize.unwrap_or(self.normalize),
+            ),
+            (PipelineStageKind::Code, code),
+            (
+                PipelineStageKind::SpokenPunctuation,
+                context
+                    .spoken_punctuation
+                    .unwrap_or(self.spoken_punctuation)
+                    && !code,
+            ),
+            (PipelineStageKind::Vocabulary, true),
+            (PipelineStageKind::Replacements, true),
+            (PipelineStageKind::Rules, true),
+            (PipelineStageKind::Snippets, true),
+        ]
+    }
 
-        for (phrase, symbol) in punctuation_map {
-            result = replace_phrase_case_insensitive(&result, phrase, symbol);
+    fn run(
+        &self,
+        text: &str,
+        context: ProcessContext,
+        mut observe: impl FnMut(&str, &str),
+    ) -> String {
+        let mut result = text.to_string();
+        for (stage, enabled) in self.default_stages(context) {
+            if enabled {
+                result = self.apply_stage(stage, &result, context, &mut observe);
+            }
         }
+        result
+    }
 
-        // Clean up spacing around punctuation
-        result = clean_punctuation_spacing(&result);
-
+    /// Apply one built-in stage, whatever its `enabled` setting in [text]
+    ///
+    /// Stages with nothing to do (no vocabulary, no rules, a language without
+    /// normalization rules) return the text unchanged without observing it.
+    /// External stages ("post_process", "command") are left to the pipeline.
+    pub fn apply_stage(
+        &self,
+        stage: PipelineStageKind,
+        text: &str,
+        context: ProcessContext,
+        observe: &mut dyn FnMut(&str, &str),
+    ) -> String {
+        let language = context.language.unwrap_or(&self.language);
+        let (label, result) = match stage {
+            PipelineStageKind::HallucinationFilter => (
+                "hallucination filter".to_string(),
+                self.hallucination.filter(text),
+            ),
+            PipelineStageKind::Disfluency => (
+                format!("disfluency ({})", language),
+                self.disfluency_remover.remove(text, language),
+            ),
+            PipelineStageKind::Normalize if self.normalizer.supports(language) => (
+                format!("normalize ({})", language),
+                self.normalizer.normalize(text, language),
+            ),
+            PipelineStageKind::Code => ("code".to_string(), self.code_formatter.format(text)),
+            PipelineStageKind::SpokenPunctuation => {
+                let language = self.punctuation_language(context.language);
+                (
+                    format!("spoken punctuation ({})", language),
+                    punctuation::tables().get(language).apply(text),
+                )
+            }
+            PipelineStageKind::Vocabulary => match self.vocabulary {
+                Some(ref corrector) => ("vocabulary".to_string(), corrector.correct(text)),
+                None => return text.to_string(),
+            },
+            PipelineStageKind::Replacements if !self.replacements.is_empty() => {
+                ("replacements".to_string(), self.apply_replacements(text))
+            }
+            PipelineStageKind::Rules => {
+                let mut result = text.to_string();
+                for rule in &self.rules {
+                    result = rule.apply(&result);
+                    observe(&format!("rule {}", rule.label), &result);
+                }
+                return result;
+            }
+            PipelineStageKind::Snippets => match self.snippets {
+                Some(ref snippets) => ("snippets".to_string(), snippets.expand(text)),
+                None => return text.to_string(),
+            },
+            _ => return text.to_string(),
+        };
+        observe(&label, &result);
         result
     }
 
@@ -138,55 +351,32 @@ fn replace_phrase_case_insensitive(text: &str, from: &str, to: &str) -> String {
     }
 }
 
-/// Clean up spacing around punctuation marks
-fn clean_punctuation_spacing(text: &str) -> String {
-    let mut result = text.to_string();
-
-    // Remove space before punctuation that shouldn't have it
-    for punct in ['.', ',', '?', '!', ':', ';', ')', ']', '}'] {
-        result = result.replace(&format!(" {}", punct), &punct.to_string());
-    }
-
-    // Remove space after opening brackets
-    for punct in ['(', '[', '{'] {
-        result = result.replace(&format!("{} ", punct), &punct.to_string());
-    }
-
-    // Remove space before opening brackets (for function calls, array access, etc.)
-    for punct in ['(', '[', '{'] {
-        result = result.replace(&format!(" {}", punct), &punct.to_string());
-    }
-
-    // Remove space before symbols that typically attach to the next word (email, hashtags, etc.)
-    for sym in ['#', '@', '$'] {
-        result = result.replace(&format!(" {}", sym), &sym.to_string());
-    }
-
-    // Remove space after symbols that typically attach to the next word
-    for sym in ['#', '@', '$'] {
-        result = result.replace(&format!("{} ", sym), &sym.to_string());
-    }
-
-    // Remove spaces around newlines and tabs
-    result = result.replace(" \n", "\n");
-    result = result.replace("\n ", "\n");
-    result = result.replace(" \t", "\t");
-    result = result.replace("\t ", "\t");
-
-    result
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::TextRule;
 
     fn make_config(spoken_punctuation: bool, replacements: &[(&str, &str)]) -> TextConfig {
         TextConfig {
             spoken_punctuation,
+            punctuation_language: None,
             replacements: replacements
                 .iter()
                 .map(|(k, v)| (k.to_string(), v.to_string()))
                 .collect(),
+            rules: Vec::new(),
+            commands: Default::default(),
+            normalize: Default::default(),
+            disfluency: Default::default(),
+            code: Default::default(),
+            hallucination_filter: Default::default(),
+        }
+    }
+
+    fn in_language(language: &str) -> ProcessContext<'_> {
+        ProcessContext {
+            language: Some(language),
+            ..Default::default()
         }
     }
 
@@ -198,6 +388,19 @@ mod tests {
         assert_eq!(processor.process("hello period"), "hello.");
         assert_eq!(processor.process("hello comma world"), "hello, world");
         assert_eq!(processor.process("what question mark"), "what?");
+
+        // A profile can turn it off, or on when [text] leaves it off
+        let off = ProcessContext {
+            spoken_punctuation: Some(false),
+            ..Default::default()
+        };
+        assert_eq!(processor.process_with("hello period", off), "hello period");
+        let processor = TextProcessor::new(&make_config(false, &[]));
+        let on = ProcessContext {
+            spoken_punctuation: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(processor.process_with("hello period", on), "hello.");
     }
 
     #[test]
@@ -218,6 +421,39 @@ mod tests {
         assert_eq!(processor.process("hello Period"), "hello.");
     }
 
+    #[test]
+    fn test_spoken_punctuation_language() {
+        let config = make_config(true, &[]);
+        let processor = TextProcessor::new(&config);
+
+        // Detected language selects the table
+        assert_eq!(
+            processor.process_with("Hallo Komma Welt Punkt", in_language("de")),
+            "Hallo, Welt."
+        );
+        assert_eq!(
+            processor.process_with("Ça va point d'interrogation", in_language("fr")),
+            "Ça va\u{00A0}?"
+        );
+
+        // Configured transcription language when none is detected
+        let processor = TextProcessor::new(&config).with_language("de");
+        assert_eq!(processor.process("Ende Punkt"), "Ende.");
+        assert_eq!(
+            processor.process_with("end period", in_language("en")),
+            "end."
+        );
+
+        // Explicit punctuation language wins over the detected one
+        let mut config = make_config(true, &[]);
+        config.punctuation_language = Some("fr".to_string());
+        let processor = TextProcessor::new(&config);
+        assert_eq!(
+            processor.process_with("oui virgule non", in_language("en")),
+            "oui, non"
+        );
+    }
+
     #[test]
     fn test_word_replacements() {
         let config = make_config(false, &[("vox type", "voxtype")]);
@@ -271,6 +507,18 @@ mod tests {
         assert_eq!(processor.process("user at sign example"), "user@example");
     }
 
+    #[test]
+    fn test_vocabulary_correction() {
+        let config = make_config(true, &[]);
+        let vocab = Vocabulary::new(["Kubernetes"]);
+        let processor = TextProcessor::new(&config).with_vocabulary(&vocab, 0.8);
+
+        assert_eq!(
+            processor.process("deploy to kubernetis period"),
+            "deploy to Kubernetes."
+        );
+    }
+
     #[test]
     fn test_newline_and_tab() {
         let config = make_config(true, &[]);
@@ -282,4 +530,164 @@ mod tests {
         );
         assert_eq!(processor.process("col one tab col two"), "col one\tcol two");
     }
+
+    #[test]
+    fn test_longer_replacement_wins_in_any_order() {
+        // Each HashMap iterates its replacements in its own order; the longer
+        // phrase must win over the words inside it every time
+        let replacements = [("vox", "Vox"), ("vox type", "voxtype"), ("type", "Type")];
+        for _ in 0..16 {
+            let processor = TextProcessor::new(&make_config(false, &replacements));
+            assert_eq!(
+                processor.process("vox type and vox, type"),
+                "voxtype and Vox, Type"
+            );
+        }
+    }
+
+    #[test]
+    fn test_rules_applied_in_order() {
+        let mut config = make_config(false, &[("vox type", "voxtype")]);
+        config.rules = vec![
+            TextRule {
+                name: Some("ticket".to_string()),
+                pattern: r"ticket (\d+)".to_string(),
+                replacement: "#$1".to_string(),
+                regex: true,
+                case_sensitive: false,
+                whole_word: true,
+                enabled: true,
+            },
+            TextRule {
+                name: None,
+                pattern: "#42".to_string(),
+                replacement: "#42 (voxtype)".to_string(),
+                regex: false,
+                case_sensitive: false,
+                whole_word: true,
+                enabled: true,
+            },
+        ];
+        let processor = TextProcessor::new(&config);
+
+        assert_eq!(
+            processor.process("vox type fixes ticket 42"),
+            "voxtype fixes #42 (voxtype)"
+        );
+    }
+
+    #[test]
+    fn test_fillers_removed_before_normalization() {
+        let mut config = make_config(true, &[]);
+        config.disfluency.enabled = true;
+        config.normalize.enabled = true;
+        let processor = TextProcessor::new(&config);
+
+        assert_eq!(
+            processor.process("Um, twenty uh three percent period"),
+            "23%."
+        );
+
+        // A profile can turn either stage off
+        let context = ProcessContext {
+            disfluency: Some(false),
+            normalize: Some(false),
+            ..Default::default()
+        };
+        assert_eq!(
+            processor.process_with("um twenty three", context),
+            "um twenty three"
+        );
+    }
+
+    #[test]
+    fn test_code_mode_replaces_spoken_punctuation() {
+        let config = make_config(true, &[("user id", "uid")]);
+        let processor = TextProcessor::new(&config);
+        let context = ProcessContext {
+            code: Some(true),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            processor.process_with("Snake case max size equals user id dot len.", context),
+            "max_size = uid.len"
+        );
+        assert_eq!(
+            processor.process("snake case name period"),
+            "snake case name."
+        );
+    }
+
+    #[test]
+    fn test_snippets_expand_after_rules() {
+        let library = SnippetLibrary::parse(
+            "[sig]\ntrigger = \"insert signature\"\ntext = \"Cheers, vox type\"",
+        )
+        .unwrap();
+        let processor = TextProcessor::new(&make_config(true, &[("vox type", "voxtype")]))
+            .with_snippets(&library);
+
+        assert_eq!(
+            processor.process("vox type rocks period insert signature"),
+            "voxtype rocks. Cheers, vox type"
+        );
+    }
+
+    #[test]
+    fn test_trace_records_each_stage() {
+        let mut config = make_config(true, &[]);
+        config.rules = vec![TextRule {
+            name: Some("shout".to_string()),
+            pattern: "hello".to_string(),
+            replacement: "HELLO".to_string(),
+            regex: false,
+            case_sensitive: false,
+            whole_word: true,
+            enabled: true,
+        }];
+        let processor = TextProcessor::new(&config);
+
+        let steps = processor.trace("hello period", ProcessContext::default());
+        assert_eq!(
+            steps,
+            vec![
+                ("spoken punctuation (en)".to_string(), "hello.".to_string()),
+                ("rule shout".to_string(), "HELLO.".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_process_actions_with_commands() {
+        let mut config = make_config(true, &[]);
+        config.commands.enabled = true;
+        let processor = TextProcessor::new(&config);
+
+        let actions = processor.process_actions(
+            "hello comma world. Select all.",
+            ProcessContext::default(),
+            0,
+        );
+        assert_eq!(
+            actions,
+            vec![
+                OutputAction::Text("hello, world.".to_string()),
+                OutputAction::Key("ctrl+a".parse().unwrap()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_process_actions_without_commands() {
+        let processor = TextProcessor::new(&make_config(false, &[]));
+
+        assert_eq!(
+            processor.process_actions("Select all.", ProcessContext::default(), 0),
+            vec![OutputAction::Text("Select all.".to_string())]
+        );
+        assert!(processor
+            .process_actions("", ProcessContext::default(), 0)
+            .is_empty());
+    }
 }
@@ -0,0 +1,2256 @@
+//! Inverse text normalization
+//!
+//! Rewrites spoken numbers in written form: "twenty three point five
+//! percent" → "23.5%", "march third twenty twenty six" → "March 3, 2026".
+//! Covers cardinals, decimals, ordinals, currencies, percentages, dates,
+//! times and units.
+//!
+//! Rules are per language. English, German, French and Spanish are built
+//! in; text in other languages passes through unchanged. The number grammar
+//! is shared, and each language supplies its words and a few flags for how
+//! numbers are put together (German "dreiundzwanzig", French
+//! "quatre-vingt-dix").
+
+use super::punctuation::normalize_language;
+use crate::config::{DateFormat, NormalizeConfig};
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Inverse text normalizer for the built-in languages
+pub struct Normalizer {
+    config: NormalizeConfig,
+}
+
+impl Normalizer {
+    pub fn new(config: &NormalizeConfig) -> Self {
+        Self {
+            config: config.clone(),
+        }
+    }
+
+    /// Whether text in this language is normalized: it has built-in rules
+    /// and is listed in `languages` (an empty list allows all)
+    pub fn supports(&self, language: &str) -> bool {
+        let code = normalize_language(language);
+        lexicon(&code).is_some()
+            && (self.config.languages.is_empty()
+                || self
+                    .config
+                    .languages
+                    .iter()
+                    .any(|l| normalize_language(l) == code))
+    }
+
+    /// Rewrite spoken numbers in written form
+    /// Text in unsupported languages is returned unchanged
+    pub fn normalize(&self, text: &str, language: &str) -> String {
+        if !self.supports(language) {
+            return text.to_string();
+        }
+        let Some(lex) = lexicon(&normalize_language(language)) else {
+            return text.to_string();
+        };
+
+        let scanner = Scanner {
+            text,
+            words: tokenize(text),
+            lex,
+            config: &self.config,
+        };
+        scanner.rewrite()
+    }
+}
+
+/// Built-in rules for a language code
+fn lexicon(code: &str) -> Option<&'static Lexicon> {
+    match code {
+        "en" => Some(&ENGLISH),
+        "de" => Some(&GERMAN),
+        "fr" => Some(&FRENCH),
+        "es" => Some(&SPANISH),
+        _ => None,
+    }
+}
+
+/// Number words and written forms for one language
+/// Words are lowercase; multi-word phrases are separated by single spaces
+struct Lexicon {
+    /// Cardinal words and their values ("three", "twenty", "quatre vingt")
+    cardinals: &'static [(&'static str, u64)],
+    /// Words multiplying the preceding number by 100 ("hundred", "cent")
+    hundreds: &'static [&'static str],
+    /// Scale words ("thousand", "million")
+    scales: &'static [(&'static str, u64)],
+    /// Words joining parts of a number ("and" in "one hundred and five")
+    connectors: &'static [&'static str],
+    /// Connectors may follow round tens ("treinta y cuatro", "vingt et un")
+    tens_connector: bool,
+    /// Words that only count as one before a hundred or scale ("a hundred")
+    articles: &'static [&'static str],
+    /// Words for a zero digit ("oh" in "twenty oh five")
+    zero_digits: &'static [&'static str],
+    /// 60 and 80 may be followed by 10-19 (French "soixante-dix")
+    vigesimal: bool,
+    /// Numbers may be written as one word (German "zweihundertdrei")
+    compounds: bool,
+    /// Years may be spoken as two pairs ("nineteen eighty four")
+    spoken_years: bool,
+    /// Irregular ordinals and their cardinal ("third" → "three")
+    ordinals: &'static [(&'static str, &'static str)],
+    /// Ordinal suffixes and what replaces them ("ieth" → "y" in "twentieth")
+    ordinal_suffixes: &'static [(&'static str, &'static str)],
+    /// Fixes for stems left by removing a suffix ("drit" → "drei")
+    ordinal_stems: &'static [(&'static str, &'static str)],
+    ordinal_style: OrdinalStyle,
+    /// Words introducing decimals and the separator written for them
+    decimal_words: &'static [(&'static str, &'static str)],
+    /// Decimal separator for currency amounts
+    decimal_separator: &'static str,
+    /// Thousands separator for numbers of five digits or more
+    group_separator: &'static str,
+    /// Space between a number and a unit or percent sign
+    unit_space: &'static str,
+    percent_words: &'static [&'static str],
+    /// Percent sign is separated from the number by `unit_space`
+    spaced_percent: bool,
+    currencies: &'static [Currency],
+    /// Words joining major and minor amounts ("five dollars and fifty cents")
+    amount_joiners: &'static [&'static str],
+    units: &'static [Unit],
+    /// Spoken month names and the written name, January first
+    months: [(&'static [&'static str], &'static str); 12],
+    date: DateStyle,
+    time: TimeStyle,
+}
+
+struct Currency {
+    words: &'static [&'static str],
+    symbol: &'static str,
+    /// Symbol before the amount ("$5") rather than after ("5 €")
+    prefix: bool,
+    minor_words: &'static [&'static str],
+    /// Symbol for minor amounts on their own ("50¢")
+    minor_symbol: Option<&'static str>,
+}
+
+struct Unit {
+    words: &'static [&'static str],
+    symbol: &'static str,
+    /// Symbol follows the number without a space ("90°")
+    attached: bool,
+}
+
+/// Unit written after the number with a space ("5 km")
+const fn unit(words: &'static [&'static str], symbol: &'static str) -> Unit {
+    Unit {
+        words,
+        symbol,
+        attached: false,
+    }
+}
+
+/// Unit written right after the number ("90°")
+const fn attached_unit(words: &'static [&'static str], symbol: &'static str) -> Unit {
+    Unit {
+        words,
+        symbol,
+        attached: true,
+    }
+}
+
+enum OrdinalStyle {
+    /// 1st, 2nd, 3rd, 4th
+    English,
+    /// A fixed suffix ("3." in German, "3.º" in Spanish)
+    Suffix(&'static str),
+    /// 1er, 2e
+    French,
+}
+
+struct DateStyle {
+    /// Day before month ("3. März") rather than month before day ("March 3")
+    day_first: bool,
+    /// Days must be spoken as ordinals ("march third", not "march three")
+    ordinal_days: bool,
+    /// Words between day, month and year ("tres de marzo de ...")
+    joiners: &'static [&'static str],
+    /// Alternative day-first form with an article and a joiner ("the third of march")
+    day_of: Option<(&'static str, &'static str)>,
+    /// Month names that are also common words; without a year they must be
+    /// capitalized to count as dates ("May 1st", not "may first")
+    ambiguous_months: &'static [&'static str],
+    /// Written first day of the month ("1er")
+    first_day: Option<&'static str>,
+    /// Long templates with {d} day, {m} month name, {y} year
+    long: &'static str,
+    day_month: &'static str,
+    month_year: &'static str,
+    /// Numeric template with {dd} day, {mm} month, {y} year
+    numeric: &'static str,
+}
+
+struct TimeStyle {
+    /// Words after the hour ("o'clock", "Uhr", "heures")
+    clock_words: &'static [&'static str],
+    /// Minutes may follow the clock word ("drei Uhr dreißig")
+    minutes_after_clock: bool,
+    /// Templates with {h} hour and {mm} two-digit minutes
+    hour_format: &'static str,
+    minute_format: &'static str,
+    /// Written a.m. and p.m. (12-hour clock)
+    meridiem: Option<(&'static str, &'static str)>,
+    /// Phrases before the hour and their minute offset ("half past" → 30)
+    relative: &'static [(&'static str, i64)],
+}
+
+static ENGLISH: Lexicon = Lexicon {
+    cardinals: &[
+        ("zero", 0),
+        ("one", 1),
+        ("two", 2),
+        ("three", 3),
+        ("four", 4),
+        ("five", 5),
+        ("six", 6),
+        ("seven", 7),
+        ("eight", 8),
+        ("nine", 9),
+        ("ten", 10),
+        ("eleven", 11),
+        ("twelve", 12),
+        ("thirteen", 13),
+        ("fourteen", 14),
+        ("fifteen", 15),
+        ("sixteen", 16),
+        ("seventeen", 17),
+        ("eighteen", 18),
+        ("nineteen", 19),
+        ("twenty", 20),
+        ("thirty", 30),
+        ("forty", 40),
+        ("fifty", 50),
+        ("sixty", 60),
+        ("seventy", 70),
+        ("eighty", 80),
+        ("ninety", 90),
+    ],
+    hundreds: &["hundred"],
+    scales: &[
+        ("thousand", 1_000),
+        ("million", 1_000_000),
+        ("billion", 1_000_000_000),
+        ("trillion", 1_000_000_000_000),
+    ],
+    connectors: &["and"],
+    tens_connector: false,
+    articles: &["a", "an"],
+    zero_digits: &["oh", "zero"],
+    vigesimal: false,
+    compounds: false,
+    spoken_years: true,
+    ordinals: &[
+        ("first", "one"),
+        ("second", "two"),
+        ("third", "three"),
+        ("fifth", "five"),
+        ("eighth", "eight"),
+        ("ninth", "nine"),
+        ("twelfth", "twelve"),
+    ],
+    ordinal_suffixes: &[("ieth", "y"), ("th", "")],
+    ordinal_stems: &[],
+    ordinal_style: OrdinalStyle::English,
+    decimal_words: &[("point", ".")],
+    decimal_separator: ".",
+    group_separator: ",",
+    unit_space: " ",
+    percent_words: &["percent", "per cent"],
+    spaced_percent: false,
+    currencies: &[
+        Currency {
+            words: &["dollar", "dollars", "bucks"],
+            symbol: "$",
+            prefix: true,
+            minor_words: &["cent", "cents"],
+            minor_symbol: Some("¢"),
+        },
+        Currency {
+            words: &["euro", "euros"],
+            symbol: "€",
+            prefix: true,
+            minor_words: &["cent", "cents"],
+            minor_symbol: None,
+        },
+        Currency {
+            words: &["pound sterling", "pounds sterling", "quid"],
+            symbol: "£",
+            prefix: true,
+            minor_words: &["pence", "penny"],
+            minor_symbol: None,
+        },
+        Currency {
+            words: &["yen"],
+            symbol: "¥",
+            prefix: true,
+            minor_words: &[],
+            minor_symbol: None,
+        },
+        Currency {
+            words: &["rupee", "rupees"],
+            symbol: "₹",
+            prefix: true,
+            minor_words: &["paise"],
+            minor_symbol: None,
+        },
+    ],
+    amount_joiners: &["and"],
+    units: &[
+        unit(&["kilometers per hour", "kilometres per hour"], "km/h"),
+        unit(&["miles per hour"], "mph"),
+        unit(
+            &["kilometers", "kilometres", "kilometer", "kilometre"],
+            "km",
+        ),
+        unit(
+            &["centimeters", "centimetres", "centimeter", "centimetre"],
+            "cm",
+        ),
+        unit(
+            &["millimeters", "millimetres", "millimeter", "millimetre"],
+            "mm",
+        ),
+        unit(&["meters", "metres", "meter", "metre"], "m"),
+        unit(&["kilograms", "kilogram", "kilos", "kilo"], "kg"),
+        unit(&["milligrams", "milligram"], "mg"),
+        unit(&["grams", "gram"], "g"),
+        unit(
+            &["milliliters", "millilitres", "milliliter", "millilitre"],
+            "mL",
+        ),
+        unit(&["liters", "litres", "liter", "litre"], "L"),
+        attached_unit(
+            &["degrees celsius", "degree celsius", "degrees centigrade"],
+            "°C",
+        ),
+        attached_unit(&["degrees fahrenheit", "degree fahrenheit"], "°F"),
+        attached_unit(&["degrees", "degree"], "°"),
+        unit(&["kilobytes", "kilobyte"], "KB"),
+        unit(&["megabytes", "megabyte"], "MB"),
+        unit(&["gigabytes", "gigabyte"], "GB"),
+        unit(&["terabytes", "terabyte"], "TB"),
+        unit(&["kilohertz"], "kHz"),
+        unit(&["megahertz"], "MHz"),
+        unit(&["gigahertz"], "GHz"),
+        unit(&["hertz"], "Hz"),
+        unit(&["kilowatts", "kilowatt"], "kW"),
+        unit(&["watts", "watt"], "W"),
+        unit(&["volts", "volt"], "V"),
+        unit(&["milliseconds", "millisecond"], "ms"),
+    ],
+    months: [
+        (&["january"], "January"),
+        (&["february"], "February"),
+        (&["march"], "March"),
+        (&["april"], "April"),
+        (&["may"], "May"),
+        (&["june"], "June"),
+        (&["july"], "July"),
+        (&["august"], "August"),
+        (&["september"], "September"),
+        (&["october"], "October"),
+        (&["november"], "November"),
+        (&["december"], "December"),
+    ],
+    date: DateStyle {
+        day_first: false,
+        ordinal_days: true,
+        joiners: &[],
+        day_of: Some(("the", "of")),
+        ambiguous_months: &["march", "may", "august"],
+        first_day: None,
+        long: "{m} {d}, {y}",
+        day_month: "{m} {d}",
+        month_year: "{m} {y}",
+        numeric: "{mm}/{dd}/{y}",
+    },
+    time: TimeStyle {
+        clock_words: &["o'clock", "oclock"],
+        minutes_after_clock: false,
+        hour_format: "{h}:00",
+        minute_format: "{h}:{mm}",
+        meridiem: Some(("a.m.", "p.m.")),
+        relative: &[
+            ("half past", 30),
+            ("quarter past", 15),
+            ("quarter after", 15),
+            ("quarter to", -15),
+            ("quarter of", -15),
+        ],
+    },
+};
+
+static GERMAN: Lexicon = Lexicon {
+    cardinals: &[
+        ("null", 0),
+        ("eins", 1),
+        ("zwei", 2),
+        ("drei", 3),
+        ("vier", 4),
+        ("fünf", 5),
+        ("sechs", 6),
+        ("sieben", 7),
+        ("acht", 8),
+        ("neun", 9),
+        ("zehn", 10),
+        ("elf", 11),
+        ("zwölf", 12),
+        ("dreizehn", 13),
+        ("vierzehn", 14),
+        ("fünfzehn", 15),
+        ("sechzehn", 16),
+        ("siebzehn", 17),
+        ("achtzehn", 18),
+        ("neunzehn", 19),
+        ("zwanzig", 20),
+        ("dreißig", 30),
+        ("dreissig", 30),
+        ("vierzig", 40),
+        ("fünfzig", 50),
+        ("sechzig", 60),
+        ("siebzig", 70),
+        ("achtzig", 80),
+        ("neunzig", 90),
+    ],
+    hundreds: &["hundert"],
+    scales: &[
+        ("tausend", 1_000),
+        ("million", 1_000_000),
+        ("millionen", 1_000_000),
+        ("milliarde", 1_000_000_000),
+        ("milliarden", 1_000_000_000),
+    ],
+    connectors: &["und"],
+    tens_connector: false,
+    articles: &["ein", "eine", "einen", "einer"],
+    zero_digits: &["null"],
+    vigesimal: false,
+    compounds: true,
+    spoken_years: false,
+    ordinals: &[],
+    ordinal_suffixes: &[
+        ("sten", ""),
+        ("ster", ""),
+        ("stes", ""),
+        ("stem", ""),
+        ("ste", ""),
+        ("ten", ""),
+        ("ter", ""),
+        ("tes", ""),
+        ("tem", ""),
+        ("te", ""),
+    ],
+    ordinal_stems: &[
+        ("er", "eins"),
+        ("drit", "drei"),
+        ("sieb", "sieben"),
+        ("ach", "acht"),
+    ],
+    ordinal_style: OrdinalStyle::Suffix("."),
+    decimal_words: &[("komma", ",")],
+    decimal_separator: ",",
+    group_separator: ".",
+    unit_space: " ",
+    percent_words: &["prozent"],
+    spaced_percent: true,
+    currencies: &[
+        Currency {
+            words: &["euro", "euros"],
+            symbol: "€",
+            prefix: false,
+            minor_words: &["cent", "cents"],
+            minor_symbol: None,
+        },
+        Currency {
+            words: &["dollar", "dollars"],
+            symbol: "$",
+            prefix: false,
+            minor_words: &["cent", "cents"],
+            minor_symbol: None,
+        },
+        Currency {
+            words: &["franken"],
+            symbol: "CHF",
+            prefix: false,
+            minor_words: &["rappen"],
+            minor_symbol: None,
+        },
+        Currency {
+            words: &["pfund"],
+            symbol: "£",
+            prefix: false,
+            minor_words: &["pence"],
+            minor_symbol: None,
+        },
+    ],
+    amount_joiners: &["und"],
+    units: &[
+        unit(&["kilometer pro stunde", "stundenkilometer"], "km/h"),
+        unit(&["kilometer", "kilometern"], "km"),
+        unit(&["zentimeter", "zentimetern"], "cm"),
+        unit(&["millimeter", "millimetern"], "mm"),
+        unit(&["meter", "metern"], "m"),
+        unit(&["kilogramm", "kilo"], "kg"),
+        unit(&["milligramm"], "mg"),
+        unit(&["gramm"], "g"),
+        unit(&["milliliter", "millilitern"], "ml"),
+        unit(&["liter", "litern"], "l"),
+        unit(&["grad celsius"], "°C"),
+        attached_unit(&["grad"], "°"),
+        unit(&["kilobyte"], "KB"),
+        unit(&["megabyte"], "MB"),
+        unit(&["gigabyte"], "GB"),
+        unit(&["terabyte"], "TB"),
+        unit(&["kilohertz"], "kHz"),
+        unit(&["megahertz"], "MHz"),
+        unit(&["gigahertz"], "GHz"),
+        unit(&["hertz"], "Hz"),
+        unit(&["kilowatt"], "kW"),
+        unit(&["watt"], "W"),
+        unit(&["volt"], "V"),
+        unit(&["millisekunden", "millisekunde"], "ms"),
+    ],
+    months: [
+        (&["januar", "jänner"], "Januar"),
+        (&["februar", "feber"], "Februar"),
+        (&["märz", "maerz"], "März"),
+        (&["april"], "April"),
+        (&["mai"], "Mai"),
+        (&["juni"], "Juni"),
+        (&["juli"], "Juli"),
+        (&["august"], "August"),
+        (&["september"], "September"),
+        (&["oktober"], "Oktober"),
+        (&["november"], "November"),
+        (&["dezember"], "Dezember"),
+    ],
+    date: DateStyle {
+        day_first: true,
+        ordinal_days: true,
+        joiners: &[],
+        day_of: None,
+        ambiguous_months: &[],
+        first_day: None,
+        long: "{d}. {m} {y}",
+        day_month: "{d}. {m}",
+        month_year: "{m} {y}",
+        numeric: "{dd}.{mm}.{y}",
+    },
+    time: TimeStyle {
+        clock_words: &["uhr"],
+        minutes_after_clock: true,
+        hour_format: "{h} Uhr",
+        minute_format: "{h}:{mm} Uhr",
+        meridiem: None,
+        relative: &[],
+    },
+};
+
+static FRENCH: Lexicon = Lexicon {
+    cardinals: &[
+        ("zéro", 0),
+        ("un", 1),
+        ("une", 1),
+        ("deux", 2),
+        ("trois", 3),
+        ("quatre", 4),
+        ("cinq", 5),
+        ("six", 6),
+        ("sept", 7),
+        ("huit", 8),
+        ("neuf", 9),
+        ("dix", 10),
+        ("onze", 11),
+        ("douze", 12),
+        ("treize", 13),
+        ("quatorze", 14),
+        ("quinze", 15),
+        ("seize", 16),
+        ("vingt", 20),
+        ("trente", 30),
+        ("quarante", 40),
+        ("cinquante", 50),
+        ("soixante", 60),
+        ("septante", 70),
+        ("huitante", 80),
+        ("octante", 80),
+        ("nonante", 90),
+        ("quatre vingt", 80),
+        ("quatre vingts", 80),
+    ],
+    hundreds: &["cent", "cents"],
+    scales: &[
+        ("mille", 1_000),
+        ("million", 1_000_000),
+        ("millions", 1_000_000),
+        ("milliard", 1_000_000_000),
+        ("milliards", 1_000_000_000),
+    ],
+    connectors: &["et"],
+    tens_connector: true,
+    articles: &[],
+    zero_digits: &["zéro"],
+    vigesimal: true,
+    compounds: false,
+    spoken_years: false,
+    ordinals: &[("premier", "un"), ("première", "un")],
+    ordinal_suffixes: &[("ième", "e"), ("ième", "")],
+    ordinal_stems: &[("cinqu", "cinq"), ("neuv", "neuf")],
+    ordinal_style: OrdinalStyle::French,
+    decimal_words: &[("virgule", ",")],
+    decimal_separator: ",",
+    group_separator: "\u{202F}",
+    unit_space: "\u{00A0}",
+    percent_words: &["pour cent", "pourcent"],
+    spaced_percent: true,
+    currencies: &[
+        Currency {
+            words: &["euro", "euros"],
+            symbol: "€",
+            prefix: false,
+            minor_words: &["centime", "centimes", "cent", "cents"],
+            minor_symbol: None,
+        },
+        Currency {
+            words: &["dollar", "dollars"],
+            symbol: "$",
+            prefix: false,
+            minor_words: &["cent", "cents"],
+            minor_symbol: None,
+        },
+        Currency {
+            words: &["franc suisse", "francs suisses"],
+            symbol: "CHF",
+            prefix: false,
+            minor_words: &["centime", "centimes"],
+            minor_symbol: None,
+        },
+        Currency {
+            words: &["livre sterling", "livres sterling"],
+            symbol: "£",
+            prefix: false,
+            minor_words: &["penny", "pence"],
+            minor_symbol: None,
+        },
+    ],
+    amount_joiners: &["et"],
+    units: &[
+        unit(
+            &[
+                "kilomètres heure",
+                "kilomètres par heure",
+                "kilomètre heure",
+                "kilomètre par heure",
+            ],
+            "km/h",
+        ),
+        unit(
+            &["kilomètres", "kilomètre", "kilometres", "kilometre"],
+            "km",
+        ),
+        unit(&["centimètres", "centimètre"], "cm"),
+        unit(&["millimètres", "millimètre"], "mm"),
+        unit(&["mètres", "mètre", "metres", "metre"], "m"),
+        unit(&["kilogrammes", "kilogramme", "kilos", "kilo"], "kg"),
+        unit(&["milligrammes", "milligramme"], "mg"),
+        unit(&["grammes", "gramme"], "g"),
+        unit(&["millilitres", "millilitre"], "ml"),
+        unit(&["litres", "litre"], "l"),
+        unit(&["degrés celsius", "degré celsius"], "°C"),
+        attached_unit(&["degrés", "degré"], "°"),
+        unit(&["kilooctets", "kilooctet"], "ko"),
+        unit(&["mégaoctets", "mégaoctet"], "Mo"),
+        unit(&["gigaoctets", "gigaoctet"], "Go"),
+        unit(&["téraoctets", "téraoctet"], "To"),
+        unit(&["kilohertz"], "kHz"),
+        unit(&["mégahertz"], "MHz"),
+        unit(&["gigahertz"], "GHz"),
+        unit(&["hertz"], "Hz"),
+        unit(&["kilowatts", "kilowatt"], "kW"),
+        unit(&["watts", "watt"], "W"),
+        unit(&["volts", "volt"], "V"),
+        unit(&["millisecondes", "milliseconde"], "ms"),
+    ],
+    months: [
+        (&["janvier"], "janvier"),
+        (&["février", "fevrier"], "février"),
+        (&["mars"], "mars"),
+        (&["avril"], "avril"),
+        (&["mai"], "mai"),
+        (&["juin"], "juin"),
+        (&["juillet"], "juillet"),
+        (&["août", "aout"], "août"),
+        (&["septembre"], "septembre"),
+        (&["octobre"], "octobre"),
+        (&["novembre"], "novembre"),
+        (&["décembre", "decembre"], "décembre"),
+    ],
+    date: DateStyle {
+        day_first: true,
+        ordinal_days: false,
+        joiners: &[],
+        day_of: None,
+        ambiguous_months: &[],
+        first_day: Some("1er"),
+        long: "{d} {m} {y}",
+        day_month: "{d} {m}",
+        month_year: "{m} {y}",
+        numeric: "{dd}/{mm}/{y}",
+    },
+    time: TimeStyle {
+        clock_words: &["heures", "heure"],
+        minutes_after_clock: true,
+        hour_format: "{h} h",
+        minute_format: "{h} h {mm}",
+        meridiem: None,
+        relative: &[],
+    },
+};
+
+static SPANISH: Lexicon = Lexicon {
+    cardinals: &[
+        ("cero", 0),
+        ("uno", 1),
+        ("dos", 2),
+        ("tres", 3),
+        ("cuatro", 4),
+        ("cinco", 5),
+        ("seis", 6),
+        ("siete", 7),
+        ("ocho", 8),
+        ("nueve", 9),
+        ("diez", 10),
+        ("once", 11),
+        ("doce", 12),
+        ("trece", 13),
+        ("catorce", 14),
+        ("quince", 15),
+        ("dieciséis", 16),
+        ("dieciseis", 16),
+        ("diecisiete", 17),
+        ("dieciocho", 18),
+        ("diecinueve", 19),
+        ("veinte", 20),
+        ("veintiuno", 21),
+        ("veintiún", 21),
+        ("veintidós", 22),
+        ("veintidos", 22),
+        ("veintitrés", 23),
+        ("veintitres", 23),
+        ("veinticuatro", 24),
+        ("veinticinco", 25),
+        ("veintiséis", 26),
+        ("veintiseis", 26),
+        ("veintisiete", 27),
+        ("veintiocho", 28),
+        ("veintinueve", 29),
+        ("treinta", 30),
+        ("cuarenta", 40),
+        ("cincuenta", 50),
+        ("sesenta", 60),
+        ("setenta", 70),
+        ("ochenta", 80),
+        ("noventa", 90),
+        ("cien", 100),
+        ("ciento", 100),
+        ("doscientos", 200),
+        ("doscientas", 200),
+        ("trescientos", 300),
+        ("trescientas", 300),
+        ("cuatrocientos", 400),
+        ("cuatrocientas", 400),
+        ("quinientos", 500),
+        ("quinientas", 500),
+        ("seiscientos", 600),
+        ("seiscientas", 600),
+        ("setecientos", 700),
+        ("setecientas", 700),
+        ("ochocientos", 800),
+        ("ochocientas", 800),
+        ("novecientos", 900),
+        ("novecientas", 900),
+    ],
+    hundreds: &[],
+    scales: &[
+        ("mil", 1_000),
+        ("millón", 1_000_000),
+        ("millon", 1_000_000),
+        ("millones", 1_000_000),
+    ],
+    connectors: &["y"],
+    tens_connector: true,
+    articles: &["un", "una"],
+    zero_digits: &["cero"],
+    vigesimal: false,
+    compounds: false,
+    spoken_years: false,
+    ordinals: &[
+        ("primero", "uno"),
+        ("primer", "uno"),
+        ("primera", "uno"),
+        ("segundo", "dos"),
+        ("segunda", "dos"),
+        ("tercero", "tres"),
+        ("tercer", "tres"),
+        ("tercera", "tres"),
+        ("cuarto", "cuatro"),
+        ("cuarta", "cuatro"),
+        ("quinto", "cinco"),
+        ("quinta", "cinco"),
+        ("sexto", "seis"),
+        ("sexta", "seis"),
+        ("séptimo", "siete"),
+        ("séptima", "siete"),
+        ("octavo", "ocho"),
+        ("octava", "ocho"),
+        ("noveno", "nueve"),
+        ("novena", "nueve"),
+        ("décimo", "diez"),
+        ("décima", "diez"),
+    ],
+    ordinal_suffixes: &[],
+    ordinal_stems: &[],
+    ordinal_style: OrdinalStyle::Suffix(".º"),
+    decimal_words: &[("coma", ","), ("punto", ".")],
+    decimal_separator: ",",
+    group_separator: ".",
+    unit_space: " ",
+    percent_words: &["por ciento"],
+    spaced_percent: true,
+    currencies: &[
+        Currency {
+            words: &["euro", "euros"],
+            symbol: "€",
+            prefix: false,
+            minor_words: &["céntimo", "céntimos", "centimo", "centimos"],
+            minor_symbol: None,
+        },
+        Currency {
+            words: &["dólar", "dólares", "dolar", "dolares"],
+            symbol: "$",
+            prefix: true,
+            minor_words: &["centavo", "centavos"],
+            minor_symbol: None,
+        },
+        Currency {
+            words: &["peso", "pesos"],
+            symbol: "$",
+            prefix: true,
+            minor_words: &["centavo", "centavos"],
+            minor_symbol: None,
+        },
+        Currency {
+            words: &["libra esterlina", "libras esterlinas"],
+            symbol: "£",
+            prefix: false,
+            minor_words: &["penique", "peniques"],
+            minor_symbol: None,
+        },
+    ],
+    amount_joiners: &["con", "y"],
+    units: &[
+        unit(&["kilómetros por hora", "kilometros por hora"], "km/h"),
+        unit(
+            &["kilómetros", "kilómetro", "kilometros", "kilometro"],
+            "km",
+        ),
+        unit(&["centímetros", "centímetro"], "cm"),
+        unit(&["milímetros", "milímetro"], "mm"),
+        unit(&["metros", "metro"], "m"),
+        unit(&["kilogramos", "kilogramo", "kilos", "kilo"], "kg"),
+        unit(&["miligramos", "miligramo"], "mg"),
+        unit(&["gramos", "gramo"], "g"),
+        unit(&["mililitros", "mililitro"], "ml"),
+        unit(&["litros", "litro"], "l"),
+        unit(
+            &["grados celsius", "grados centígrados", "grado celsius"],
+            "°C",
+        ),
+        attached_unit(&["grados", "grado"], "°"),
+        unit(&["kilobytes"], "KB"),
+        unit(&["megabytes"], "MB"),
+        unit(&["gigabytes"], "GB"),
+        unit(&["terabytes"], "TB"),
+        unit(&["kilohercios"], "kHz"),
+        unit(&["megahercios"], "MHz"),
+        unit(&["gigahercios"], "GHz"),
+        unit(&["hercios", "hertz"], "Hz"),
+        unit(&["kilovatios", "kilovatio"], "kW"),
+        unit(&["vatios", "vatio"], "W"),
+        unit(&["voltios", "voltio"], "V"),
+        unit(&["milisegundos", "milisegundo"], "ms"),
+    ],
+    months: [
+        (&["enero"], "enero"),
+        (&["febrero"], "febrero"),
+        (&["marzo"], "marzo"),
+        (&["abril"], "abril"),
+        (&["mayo"], "mayo"),
+        (&["junio"], "junio"),
+        (&["julio"], "julio"),
+        (&["agosto"], "agosto"),
+        (&["septiembre", "setiembre"], "septiembre"),
+        (&["octubre"], "octubre"),
+        (&["noviembre"], "noviembre"),
+        (&["diciembre"], "diciembre"),
+    ],
+    date: DateStyle {
+        day_first: true,
+        ordinal_days: false,
+        joiners: &["de"],
+        day_of: None,
+        ambiguous_months: &[],
+        first_day: None,
+        long: "{d} de {m} de {y}",
+        day_month: "{d} de {m}",
+        month_year: "{m} de {y}",
+        numeric: "{dd}/{mm}/{y}",
+    },
+    time: TimeStyle {
+        clock_words: &[],
+        minutes_after_clock: false,
+        hour_format: "",
+        minute_format: "",
+        meridiem: None,
+        relative: &[],
+    },
+};
+
+impl Lexicon {
+    fn cardinal(&self, word: &str) -> Option<u64> {
+        self.cardinals
+            .iter()
+            .find(|(w, _)| *w == word)
+            .map(|(_, v)| *v)
+    }
+
+    fn scale(&self, word: &str) -> Option<u64> {
+        self.scales
+            .iter()
+            .find(|(w, _)| *w == word)
+            .map(|(_, v)| *v)
+    }
+
+    /// Number parts of a single cardinal word
+    fn word_atoms(&self, word: &str) -> Option<Vec<Atom>> {
+        if let Some(value) = self.cardinal(word) {
+            return Some(vec![Atom::Value(value)]);
+        }
+        if self.hundreds.contains(&word) {
+            return Some(vec![Atom::Hundred]);
+        }
+        if let Some(scale) = self.scale(word) {
+            return Some(vec![Atom::Scale(scale)]);
+        }
+        if self.articles.contains(&word) {
+            return Some(vec![Atom::Article]);
+        }
+        if self.connectors.contains(&word) {
+            return Some(vec![Atom::Connector]);
+        }
+        if self.compounds {
+            return self.decompose(word);
+        }
+        None
+    }
+
+    /// Number parts of an ordinal word ("twenty-third" → "third")
+    fn ordinal_atoms(&self, word: &str) -> Option<Vec<Atom>> {
+        let number_atoms = |stem: &str| {
+            self.word_atoms(stem).filter(|atoms| {
+                matches!(
+                    atoms.last(),
+                    Some(Atom::Value(_) | Atom::Hundred | Atom::Scale(_))
+                )
+            })
+        };
+
+        if let Some((_, cardinal)) = self.ordinals.iter().find(|(w, _)| *w == word) {
+            return number_atoms(cardinal);
+        }
+
+        for (suffix, replacement) in self.ordinal_suffixes {
+            let Some(stem) = word.strip_suffix(suffix) else {
+                continue;
+            };
+            if stem.is_empty() {
+                continue;
+            }
+            let stem = format!("{}{}", stem, replacement);
+            if let Some(atoms) = number_atoms(&stem) {
+                return Some(atoms);
+            }
+            for (from, to) in self.ordinal_stems {
+                if let Some(base) = stem.strip_suffix(from) {
+                    if let Some(atoms) = number_atoms(&format!("{}{}", base, to)) {
+                        return Some(atoms);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Split a compound number word ("dreihundertvierundzwanzig") into parts
+    /// Units before "und" are moved after their tens ("vierundzwanzig" → 20, 4)
+    fn decompose(&self, word: &str) -> Option<Vec<Atom>> {
+        let mut morphemes: Vec<(&str, Atom)> = self
+            .cardinals
+            .iter()
+            .filter(|(w, _)| !w.contains(' '))
+            .map(|(w, v)| (*w, Atom::Value(*v)))
+            .chain(self.hundreds.iter().map(|w| (*w, Atom::Hundred)))
+            .chain(self.scales.iter().map(|(w, s)| (*w, Atom::Scale(*s))))
+            .chain(self.connectors.iter().map(|w| (*w, Atom::Connector)))
+            .chain(std::iter::once(("ein", Atom::Value(1))))
+            .collect();
+        morphemes.sort_by_key(|(w, _)| std::cmp::Reverse(w.len()));
+
+        fn split(rest: &str, morphemes: &[(&str, Atom)], atoms: &mut Vec<Atom>) -> bool {
+            if rest.is_empty() {
+                return true;
+            }
+            for (morpheme, atom) in morphemes {
+                if let Some(tail) = rest.strip_prefix(morpheme) {
+                    atoms.push(*atom);
+                    if split(tail, morphemes, atoms) {
+                        return true;
+                    }
+                    atoms.pop();
+                }
+            }
+            false
+        }
+
+        let mut atoms = Vec::new();
+        if !split(word, &morphemes, &mut atoms) || atoms.len() < 2 {
+            return None;
+        }
+
+        let mut ordered = Vec::with_capacity(atoms.len());
+        let mut k = 0;
+        while k < atoms.len() {
+            match atoms[k..] {
+                [Atom::Value(unit), Atom::Connector, Atom::Value(tens), ..]
+                    if unit < 10 && tens % 10 == 0 && (20..=90).contains(&tens) =>
+                {
+                    ordered.push(Atom::Value(tens));
+                    ordered.push(Atom::Value(unit));
+                    k += 3;
+                }
+                _ => {
+                    ordered.push(atoms[k]);
+                    k += 1;
+                }
+            }
+        }
+        Some(ordered)
+    }
+
+    fn format_ordinal(&self, value: u64) -> String {
+        match self.ordinal_style {
+            OrdinalStyle::English => {
+                let suffix = match (value % 10, value % 100) {
+                    (_, 11..=13) => "th",
+                    (1, _) => "st",
+                    (2, _) => "nd",
+                    (3, _) => "rd",
+                    _ => "th",
+                };
+                format!("{}{}", value, suffix)
+            }
+            OrdinalStyle::Suffix(suffix) => format!("{}{}", value, suffix),
+            OrdinalStyle::French if value == 1 => "1er".to_string(),
+            OrdinalStyle::French => format!("{}e", value),
+        }
+    }
+}
+
+/// One part of a spoken number
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Atom {
+    Value(u64),
+    Hundred,
+    Scale(u64),
+    Connector,
+    Article,
+}
+
+/// Running value of a number being read word by word
+#[derive(Debug, Clone, Default)]
+struct Accumulator {
+    total: u64,
+    current: u64,
+    /// Next value must be below this ("twenty" allows up to nine more)
+    limit: Option<u64>,
+    /// Scales must decrease ("million ... thousand")
+    last_scale: Option<u64>,
+    scales: usize,
+    connectable: bool,
+    pending_connector: bool,
+    pending_article: bool,
+    started: bool,
+    zero: bool,
+    /// Only an article and multipliers so far ("a hundred", "thousand")
+    bare: bool,
+    last: Option<Atom>,
+}
+
+impl Accumulator {
+    /// Add a part, returning false if it can't continue the number
+    fn push(&mut self, atom: Atom, lex: &Lexicon) -> bool {
+        if self.zero {
+            return false;
+        }
+        let after_article = self.pending_article;
+        if after_article && !matches!(atom, Atom::Hundred | Atom::Scale(_)) {
+            return false;
+        }
+        self.pending_article = false;
+
+        match atom {
+            // "treinta y un": an article after a connector is the digit one
+            Atom::Article if self.pending_connector && lex.tens_connector => {
+                return self.push(Atom::Value(1), lex);
+            }
+            Atom::Article => {
+                if self.started {
+                    return false;
+                }
+                self.pending_article = true;
+                self.bare = true;
+            }
+            Atom::Connector => {
+                if !self.started || self.pending_connector || !self.connectable {
+                    return false;
+                }
+                self.pending_connector = true;
+            }
+            Atom::Value(0) => {
+                if self.started {
+                    return false;
+                }
+                self.zero = true;
+            }
+            Atom::Value(value) => {
+                if self.limit.is_some_and(|limit| value >= limit) {
+                    return false;
+                }
+                self.current += value;
+                let place = place_value(value);
+                self.limit = Some(if lex.vigesimal && (value == 60 || value == 80) {
+                    20
+                } else if value % place == 0 {
+                    place
+                } else {
+                    1
+                });
+                self.connectable =
+                    lex.tens_connector && value % 10 == 0 && (20..100).contains(&value);
+                self.pending_connector = false;
+                self.bare = false;
+            }
+            Atom::Hundred => {
+                if self.pending_connector || self.current >= 100 {
+                    return false;
+                }
+                if !self.started {
+                    self.bare = true;
+                }
+                self.current = self.current.max(1) * 100;
+                self.limit = Some(100);
+                self.connectable = true;
+            }
+            Atom::Scale(scale) => {
+                if self.pending_connector
+                    || self.last_scale.is_some_and(|last| scale >= last)
+                    || (self.started && self.current == 0 && !after_article)
+                {
+                    return false;
+                }
+                if !self.started {
+                    self.bare = true;
+                }
+                self.total += self.current.max(1) * scale;
+                self.current = 0;
+                self.last_scale = Some(scale);
+                self.scales += 1;
+                self.limit = Some(1000);
+                self.connectable = true;
+            }
+        }
+
+        self.started = true;
+        self.last = Some(atom);
+        true
+    }
+
+    fn complete(&self) -> bool {
+        self.started && !self.pending_connector && !self.pending_article
+    }
+
+    fn value(&self) -> u64 {
+        self.total + self.current
+    }
+}
+
+/// 1, 10, 100, ... for the leading digit of a value
+fn place_value(value: u64) -> u64 {
+    let mut place = 1;
+    while value / place >= 10 {
+        place *= 10;
+    }
+    place
+}
+
+/// Digits with a separator between thousands, for five digits or more
+fn group_digits(value: u64, separator: &str) -> String {
+    let digits = value.to_string();
+    if digits.len() < 5 {
+        return digits;
+    }
+    let mut grouped = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push_str(separator);
+        }
+        grouped.push(c);
+    }
+    grouped
+}
+
+/// A spoken (or written) number
+#[derive(Debug, Clone, Default)]
+struct Number {
+    value: u64,
+    /// Digits after the decimal separator
+    fraction: Option<String>,
+    decimal_separator: &'static str,
+    /// Digits as they appear in the text ("2024", "3.5")
+    literal: Option<String>,
+    ordinal: bool,
+    /// Large scale word kept after the digits ("2 million")
+    scale_word: Option<String>,
+    /// An article or multiplier only ("a hundred", "thousand")
+    bare: bool,
+    /// Length in words
+    len: usize,
+}
+
+impl Number {
+    /// A whole number without decimals or a kept scale word
+    fn is_integer(&self) -> bool {
+        self.fraction.is_none() && self.scale_word.is_none() && !self.bare
+    }
+
+    fn is_spoken(&self) -> bool {
+        self.literal.is_none()
+    }
+}
+
+/// A word in the text, with its byte range
+struct Word {
+    start: usize,
+    end: usize,
+    lower: String,
+    digits: bool,
+}
+
+fn tokenize(text: &str) -> Vec<Word> {
+    static WORD: OnceLock<Regex> = OnceLock::new();
+    let re = WORD.get_or_init(|| {
+        Regex::new(r"[0-9]+(?:[.,][0-9]+)*|\p{L}+(?:['’]\p{L}+)*").expect("valid word regex")
+    });
+    re.find_iter(text)
+        .map(|m| Word {
+            start: m.start(),
+            end: m.end(),
+            lower: m.as_str().to_lowercase().replace('’', "'"),
+            digits: m.as_str().starts_with(|c: char| c.is_ascii_digit()),
+        })
+        .collect()
+}
+
+/// A normalized span: replaces the text up to `end` and resumes at word `next`
+struct Match {
+    end: usize,
+    next: usize,
+    text: String,
+}
+
+struct Scanner<'a> {
+    text: &'a str,
+    words: Vec<Word>,
+    lex: &'static Lexicon,
+    config: &'a NormalizeConfig,
+}
+
+impl Scanner<'_> {
+    fn rewrite(&self) -> String {
+        let mut output = String::with_capacity(self.text.len());
+        let mut copied = 0;
+        let mut i = 0;
+        while i < self.words.len() {
+            match self.match_at(i) {
+                Some(m) => {
+                    output.push_str(&self.text[copied..self.words[i].start]);
+                    output.push_str(&m.text);
+                    copied = m.end;
+                    i = m.next;
+                }
+                None => i += 1,
+            }
+        }
+        output.push_str(&self.text[copied..]);
+        output
+    }
+
+    fn match_at(&self, i: usize) -> Option<Match> {
+        if self.config.times {
+            if let Some(m) = self.time_at(i) {
+                return Some(m);
+            }
+        }
+        if self.config.dates {
+            if let Some(m) = self.date_at(i) {
+                return Some(m);
+            }
+        }
+
+        let number = self.number_at(i)?;
+        if !number.ordinal {
+            if self.config.currency {
+                if let Some(m) = self.currency_at(i, &number) {
+                    return Some(m);
+                }
+            }
+            if self.config.percentages {
+                if let Some(m) = self.percent_at(i, &number) {
+                    return Some(m);
+                }
+            }
+            if self.config.units {
+                if let Some(m) = self.unit_at(i, &number) {
+                    return Some(m);
+                }
+            }
+        }
+        if self.config.numbers {
+            if let Some((year, len)) = self.spoken_year_at(i) {
+                return Some(self.span(i, len, year.to_string()));
+            }
+        }
+        self.plain_number(i, number)
+    }
+
+    // -- Words and phrases --
+
+    /// Whether word j continues the phrase before it (only spaces or
+    /// hyphens in between)
+    fn follows(&self, j: usize) -> bool {
+        if j == 0 || j >= self.words.len() {
+            return false;
+        }
+        let gap = &self.text[self.words[j - 1].end..self.words[j].start];
+        !gap.is_empty() && gap.chars().all(|c| c.is_whitespace() || c == '-')
+    }
+
+    /// Length in words of a phrase at word i, if it matches
+    fn phrase_len(&self, i: usize, phrase: &str) -> Option<usize> {
+        let mut len = 0;
+        for part in phrase.split(' ') {
+            let j = i + len;
+            if j >= self.words.len() || (len > 0 && !self.follows(j)) {
+                return None;
+            }
+            if self.words[j].lower != part {
+                return None;
+            }
+            len += 1;
+        }
+        Some(len)
+    }
+
+    /// Length of the longest phrase matching at word i
+    fn longest_phrase(&self, i: usize, phrases: &[&str]) -> Option<usize> {
+        phrases.iter().filter_map(|p| self.phrase_len(i, p)).max()
+    }
+
+    /// Same as `longest_phrase`, for a word continuing the current phrase
+    fn next_phrase(&self, j: usize, phrases: &[&str]) -> Option<usize> {
+        if self.follows(j) {
+            self.longest_phrase(j, phrases)
+        } else {
+            None
+        }
+    }
+
+    fn original(&self, i: usize) -> &str {
+        &self.text[self.words[i].start..self.words[i].end]
+    }
+
+    fn span(&self, i: usize, len: usize, text: String) -> Match {
+        Match {
+            end: self.words[i + len - 1].end,
+            next: i + len,
+            text,
+        }
+    }
+
+    // -- Numbers --
+
+    /// A single spoken digit ("five", "oh")
+    fn digit_at(&self, j: usize) -> Option<u64> {
+        let word = self.words.get(j)?.lower.as_str();
+        if self.lex.zero_digits.contains(&word) {
+            return Some(0);
+        }
+        self.lex.cardinal(word).filter(|v| *v < 10)
+    }
+
+    /// A spoken cardinal or ordinal starting at word i
+    fn cardinal_at(&self, i: usize) -> Option<Number> {
+        let mut acc = Accumulator::default();
+        let mut best: Option<(Accumulator, usize, bool)> = None;
+        let mut j = i;
+        while j < self.words.len() && (j == i || self.follows(j)) {
+            let multiword = self
+                .lex
+                .cardinals
+                .iter()
+                .filter(|(w, _)| w.contains(' '))
+                .filter_map(|(w, v)| self.phrase_len(j, w).map(|len| (*v, len)))
+                .max_by_key(|(_, len)| *len);
+            let word = self.words[j].lower.as_str();
+            let (atoms, len, ordinal) = if let Some((value, len)) = multiword {
+                (vec![Atom::Value(value)], len, false)
+            } else if let Some(atoms) = self.lex.word_atoms(word) {
+                (atoms, 1, false)
+            } else if let Some(atoms) = self.lex.ordinal_atoms(word) {
+                (atoms, 1, true)
+            } else {
+                break;
+            };
+
+            let mut next = acc.clone();
+            if !atoms.iter().all(|atom| next.push(*atom, self.lex)) {
+                break;
+            }
+            acc = next;
+            j += len;
+            if acc.complete() {
+                best = Some((acc.clone(), j, ordinal));
+            }
+            if ordinal {
+                break;
+            }
+        }
+
+        let (acc, end, ordinal) = best?;
+        let mut number = Number {
+            value: acc.value(),
+            ordinal,
+            bare: acc.bare,
+            len: end - i,
+            ..Default::default()
+        };
+        // "two million", "three hundred billion": keep the scale word
+        if let (false, Some(Atom::Scale(scale)), 1) = (ordinal, acc.last, acc.scales) {
+            if scale >= 1_000_000 && !number.bare {
+                number.value = acc.total / scale;
+                number.scale_word = Some(self.original(end - 1).to_string());
+            }
+        }
+        Some(number)
+    }
+
+    /// Digits after a decimal word ("five", "one two", "twenty five")
+    fn fraction_at(&self, j: usize) -> Option<(String, usize)> {
+        if !self.follows(j) {
+            return None;
+        }
+        let mut digits = String::new();
+        let mut k = j;
+        while k < self.words.len() && (k == j || self.follows(k)) {
+            match self.digit_at(k) {
+                Some(d) => {
+                    digits.push_str(&d.to_string());
+                    k += 1;
+                }
+                None => break,
+            }
+        }
+        if digits.is_empty() {
+            let number = self.cardinal_at(j)?;
+            if number.ordinal || !number.is_integer() {
+                return None;
+            }
+            digits = number.value.to_string();
+            k = j + number.len;
+        }
+        Some((digits, k - j))
+    }
+
+    fn decimal_word_at(&self, j: usize) -> Option<&'static str> {
+        let word = self.words.get(j)?.lower.as_str();
+        self.lex
+            .decimal_words
+            .iter()
+            .find(|(w, _)| *w == word)
+            .map(|(_, separator)| *separator)
+    }
+
+    /// A number starting at word i: digits, a spoken cardinal or ordinal,
+    /// or a decimal ("three point five", "point five")
+    fn number_at(&self, i: usize) -> Option<Number> {
+        let word = self.words.get(i)?;
+        if word.digits {
+            let raw = self.original(i);
+            let mut number = Number {
+                literal: Some(raw.to_string()),
+                len: 1,
+                ..Default::default()
+            };
+            match raw.parse() {
+                Ok(value) => number.value = value,
+                // Written decimals aren't whole numbers
+                Err(_) => number.fraction = Some(String::new()),
+            }
+            return Some(number);
+        }
+
+        if let Some(separator) = self.decimal_word_at(i) {
+            let (fraction, len) = self.fraction_at(i + 1)?;
+            return Some(Number {
+                fraction: Some(fraction),
+                decimal_separator: separator,
+                len: len + 1,
+                ..Default::default()
+            });
+        }
+
+        let mut number = self.cardinal_at(i)?;
+        if number.ordinal || !number.is_integer() {
+            return Some(number);
+        }
+        let j = i + number.len;
+        if let Some(separator) = self.decimal_word_at(j).filter(|_| self.follows(j)) {
+            if let Some((fraction, len)) = self.fraction_at(j + 1) {
+                number.fraction = Some(fraction);
+                number.decimal_separator = separator;
+                number.len += len + 1;
+
+                // "three point five million"
+                let k = i + number.len;
+                if self.follows(k) {
+                    if let Some(scale) = self.lex.scale(&self.words[k].lower) {
+                        if scale >= 1_000_000 {
+                            number.scale_word = Some(self.original(k).to_string());
+                            number.len += 1;
+                        }
+                    }
+                }
+            }
+        }
+        Some(number)
+    }
+
+    fn format_number(&self, number: &Number) -> String {
+        let mut text = match &number.literal {
+            Some(literal) => literal.clone(),
+            None => group_digits(number.value, self.lex.group_separator),
+        };
+        if let (None, Some(fraction)) = (&number.literal, &number.fraction) {
+            text.push_str(number.decimal_separator);
+            text.push_str(fraction);
+        }
+        if let Some(scale) = &number.scale_word {
+            text.push(' ');
+            text.push_str(scale);
+        }
+        text
+    }
+
+    /// A number on its own, unless it is part of a run of separate numbers
+    /// ("one two three") or a small number kept as a word
+    fn plain_number(&self, i: usize, number: Number) -> Option<Match> {
+        // "a hundred" and "thousand" read better as words, "hundredth" doesn't
+        if !number.is_spoken() || (number.bare && !(number.ordinal && number.len == 1)) {
+            return None;
+        }
+        let enabled = if number.ordinal {
+            self.config.ordinals
+        } else {
+            self.config.numbers
+        };
+        if !enabled {
+            return None;
+        }
+
+        // Consecutive numbers are ambiguous: leave the whole run as spoken
+        let mut end = i + number.len;
+        if self.follows(end) && self.number_at(end).is_some() {
+            while self.follows(end) {
+                match self.number_at(end) {
+                    Some(next) => end += next.len,
+                    None => break,
+                }
+            }
+            let text = self.text[self.words[i].start..self.words[end - 1].end].to_string();
+            return Some(self.span(i, end - i, text));
+        }
+
+        if self.config.keep_small_numbers
+            && number.len == 1
+            && number.value < 10
+            && number.fraction.is_none()
+        {
+            return None;
+        }
+
+        let text = if number.ordinal {
+            self.lex.format_ordinal(number.value)
+        } else {
+            self.format_number(&number)
+        };
+        Some(self.span(i, number.len, text))
+    }
+
+    /// A year spoken as two pairs ("nineteen eighty four", "twenty oh five")
+    fn spoken_year_at(&self, i: usize) -> Option<(u64, usize)> {
+        if !self.lex.spoken_years {
+            return None;
+        }
+        let century = self.cardinal_at(i)?;
+        if century.ordinal || !century.is_integer() || !(16..=20).contains(&century.value) {
+            return None;
+        }
+        let j = i + century.len;
+        if !self.follows(j) {
+            return None;
+        }
+        if self.lex.zero_digits.contains(&self.words[j].lower.as_str()) {
+            let digit = self.digit_at(j + 1).filter(|_| self.follows(j + 1))?;
+            return (digit > 0).then_some((century.value * 100 + digit, century.len + 2));
+        }
+        let rest = self.cardinal_at(j)?;
+        if rest.ordinal || !rest.is_integer() || !(10..=99).contains(&rest.value) {
+            return None;
+        }
+        Some((century.value * 100 + rest.value, century.len + rest.len))
+    }
+
+    // -- Currency, percentages, units --
+
+    fn currency_at(&self, i: usize, number: &Number) -> Option<Match> {
+        let j = i + number.len;
+        let currency = self
+            .lex
+            .currencies
+            .iter()
+            .find_map(|c| self.next_phrase(j, c.words).map(|len| (c, len)));
+
+        let Some((currency, len)) = currency else {
+            // Minor amount on its own ("fifty cents" → "50¢")
+            return self.lex.currencies.iter().find_map(|c| {
+                let symbol = c.minor_symbol?;
+                let len = self.next_phrase(j, c.minor_words)?;
+                number.is_integer().then(|| {
+                    self.span(
+                        i,
+                        number.len + len,
+                        format!("{}{}", self.format_number(number), symbol),
+                    )
+                })
+            });
+        };
+
+        let mut end = j + len;
+        let mut amount = self.format_number(number);
+
+        // "five dollars fifty", "five dollars and fifty cents"
+        if number.is_integer() {
+            let mut k = end;
+            let joined = self.next_phrase(k, self.lex.amount_joiners).is_some();
+            if joined {
+                k += 1;
+            }
+            if let Some(minor) = self.number_at(k).filter(|_| self.follows(k)) {
+                if minor.is_integer() && !minor.ordinal && (1..=99).contains(&minor.value) {
+                    let after = k + minor.len;
+                    let minor_word = self.next_phrase(after, currency.minor_words);
+                    if minor_word.is_some() || !joined {
+                        end = after + minor_word.unwrap_or(0);
+                        amount = format!(
+                            "{}{}{:02}",
+                            group_digits(number.value, self.lex.group_separator),
+                            self.lex.decimal_separator,
+                            minor.value
+                        );
+                    }
+                }
+            }
+        }
+
+        let text = if currency.prefix {
+            format!("{}{}", currency.symbol, amount)
+        } else {
+            format!("{}{}{}", amount, self.lex.unit_space, currency.symbol)
+        };
+        Some(self.span(i, end - i, text))
+    }
+
+    fn percent_at(&self, i: usize, number: &Number) -> Option<Match> {
+        let len = self.next_phrase(i + number.len, self.lex.percent_words)?;
+        let space = if self.lex.spaced_percent {
+            self.lex.unit_space
+        } else {
+            ""
+        };
+        let text = format!("{}{}%", self.format_number(number), space);
+        Some(self.span(i, number.len + len, text))
+    }
+
+    fn unit_at(&self, i: usize, number: &Number) -> Option<Match> {
+        let j = i + number.len;
+        let (unit, len) = self
+            .lex
+            .units
+            .iter()
+            .filter_map(|u| self.next_phrase(j, u.words).map(|len| (u, len)))
+            .max_by_key(|(_, len)| *len)?;
+        let space = if unit.attached {
+            ""
+        } else {
+            self.lex.unit_space
+        };
+        let text = format!("{}{}{}", self.format_number(number), space, unit.symbol);
+        Some(self.span(i, number.len + len, text))
+    }
+
+    // -- Dates --
+
+    fn month_at(&self, j: usize) -> Option<(usize, usize)> {
+        self.lex
+            .months
+            .iter()
+            .enumerate()
+            .find_map(|(m, (words, _))| self.longest_phrase(j, words).map(|len| (m, len)))
+    }
+
+    /// A day of the month (1-31)
+    fn day_at(&self, j: usize) -> Option<Number> {
+        self.number_at(j)
+            .filter(|n| n.is_integer() && (1..=31).contains(&n.value))
+    }
+
+    /// A year (1000-2999), possibly after a comma when `after_comma` is set
+    fn year_at(&self, k: usize, after_comma: bool) -> Option<(u64, usize)> {
+        if k == 0 || k >= self.words.len() {
+            return None;
+        }
+        let gap = &self.text[self.words[k - 1].end..self.words[k].start];
+        if !(self.follows(k) || (after_comma && gap.trim() == ",")) {
+            return None;
+        }
+        if let Some(year) = self.spoken_year_at(k) {
+            return Some(year);
+        }
+        self.number_at(k)
+            .filter(|n| !n.ordinal && n.is_integer() && (1000..=2999).contains(&n.value))
+            .map(|n| (n.value, n.len))
+    }
+
+    /// Joiner word between date parts ("de"), if any
+    fn date_joiner_at(&self, j: usize) -> usize {
+        if self.follows(j)
+            && self
+                .lex
+                .date
+                .joiners
+                .contains(&self.words[j].lower.as_str())
+        {
+            1
+        } else {
+            0
+        }
+    }
+
+    fn date_at(&self, i: usize) -> Option<Match> {
+        if self.lex.date.day_first {
+            self.day_month_at(i).or_else(|| self.month_year_at(i))
+        } else {
+            self.month_day_at(i)
+                .or_else(|| self.day_of_month_at(i))
+                .or_else(|| self.month_year_at(i))
+        }
+    }
+
+    /// "march third", "March 3rd, 2026"
+    fn month_day_at(&self, i: usize) -> Option<Match> {
+        let (month, len) = self.month_at(i)?;
+        let j = i + len;
+        if !self.follows(j) {
+            return None;
+        }
+        let day = self.day_at(j)?;
+        let k = j + day.len;
+        if let Some((year, ylen)) = self.year_at(k, true) {
+            let text = self.format_date(month, Some(day.value), Some(year));
+            return Some(self.span(i, k + ylen - i, text));
+        }
+
+        let style = &self.lex.date;
+        if style.ordinal_days && !day.ordinal && day.is_spoken() {
+            return None;
+        }
+        let ambiguous = style
+            .ambiguous_months
+            .contains(&self.words[i].lower.as_str());
+        if ambiguous && !self.original(i).starts_with(char::is_uppercase) {
+            return None;
+        }
+        let text = self.format_date(month, Some(day.value), None);
+        Some(self.span(i, k - i, text))
+    }
+
+    /// "the third of march"
+    fn day_of_month_at(&self, i: usize) -> Option<Match> {
+        let (article, joiner) = self.lex.date.day_of?;
+        if self.words[i].lower != article || !self.follows(i + 1) {
+            return None;
+        }
+        let day = self.day_at(i + 1).filter(|d| d.ordinal)?;
+        let j = i + 1 + day.len;
+        if self.next_phrase(j, &[joiner]).is_none() || !self.follows(j + 1) {
+            return None;
+        }
+        let (month, len) = self.month_at(j + 1)?;
+        let mut end = j + 1 + len;
+        let year = self.year_at(end, true);
+        if let Some((_, ylen)) = year {
+            end += ylen;
+        }
+        let text = self.format_date(month, Some(day.value), year.map(|(y, _)| y));
+        Some(self.span(i, end - i, text))
+    }
+
+    /// "dritter März", "trois mars deux mille vingt-six", "tres de marzo"
+    fn day_month_at(&self, i: usize) -> Option<Match> {
+        let day = self.day_at(i)?;
+        if self.lex.date.ordinal_days && !day.ordinal && day.is_spoken() {
+            return None;
+        }
+        let mut j = i + day.len;
+        j += self.date_joiner_at(j);
+        if !self.follows(j) {
+            return None;
+        }
+        let (month, len) = self.month_at(j)?;
+        let mut end = j + len;
+        let k = end + self.date_joiner_at(end);
+        let year = self.year_at(k, false);
+        if let Some((_, ylen)) = year {
+            end = k + ylen;
+        }
+        let text = self.format_date(month, Some(day.value), year.map(|(y, _)| y));
+        Some(self.span(i, end - i, text))
+    }
+
+    /// "march twenty twenty six"
+    fn month_year_at(&self, i: usize) -> Option<Match> {
+        let (month, len) = self.month_at(i)?;
+        let j = i + len;
+        let k = j + self.date_joiner_at(j);
+        let (year, ylen) = self.year_at(k, false)?;
+        let text = self.format_date(month, None, Some(year));
+        Some(self.span(i, k + ylen - i, text))
+    }
+
+    fn format_date(&self, month: usize, day: Option<u64>, year: Option<u64>) -> String {
+        let style = &self.lex.date;
+        let name = self.lex.months[month].1;
+        let day_text = |d: u64| match (d, style.first_day) {
+            (1, Some(first)) => first.to_string(),
+            _ => d.to_string(),
+        };
+        match (self.config.date_format, day, year) {
+            (DateFormat::Iso, Some(d), Some(y)) => format!("{:04}-{:02}-{:02}", y, month + 1, d),
+            (DateFormat::Numeric, Some(d), Some(y)) => style
+                .numeric
+                .replace("{dd}", &format!("{:02}", d))
+                .replace("{mm}", &format!("{:02}", month + 1))
+                .replace("{y}", &y.to_string()),
+            (_, Some(d), Some(y)) => style
+                .long
+                .replace("{d}", &day_text(d))
+                .replace("{m}", name)
+                .replace("{y}", &y.to_string()),
+            (_, Some(d), None) => style
+                .day_month
+                .replace("{d}", &day_text(d))
+                .replace("{m}", name),
+            (_, None, Some(y)) => style
+                .month_year
+                .replace("{m}", name)
+                .replace("{y}", &y.to_string()),
+            (_, None, None) => name.to_string(),
+        }
+    }
+
+    // -- Times --
+
+    /// A spoken hour (not digits, which are already written)
+    fn hour_at(&self, j: usize, max: u64) -> Option<Number> {
+        self.cardinal_at(j)
+            .filter(|n| !n.ordinal && n.is_integer() && n.value <= max)
+    }
+
+    /// Minutes after the hour ("thirty", "oh five")
+    fn minutes_at(&self, k: usize) -> Option<(u64, usize)> {
+        if !self.follows(k) {
+            return None;
+        }
+        if self.lex.zero_digits.contains(&self.words[k].lower.as_str()) && self.follows(k + 1) {
+            return self.digit_at(k + 1).filter(|d| *d > 0).map(|d| (d, 2));
+        }
+        self.cardinal_at(k)
+            .filter(|n| !n.ordinal && n.is_integer() && (1..=59).contains(&n.value))
+            .map(|n| (n.value, n.len))
+    }
+
+    /// "a.m." or "p.m." right after byte position `pos`
+    /// Returns the written form and the end of the match
+    fn meridiem_at(&self, pos: usize) -> Option<(String, usize)> {
+        static MERIDIEM: OnceLock<Regex> = OnceLock::new();
+        let (am, pm) = self.lex.time.meridiem?;
+        let re = MERIDIEM.get_or_init(|| {
+            Regex::new(r"^\s+(?:([aApP])\.\s?[mM](\.)?|([pP])[mM](\.)?|(A)M(\.)?)")
+                .expect("valid meridiem regex")
+        });
+        let rest = &self.text[pos..];
+        let caps = re.captures(rest)?;
+        let end = pos + caps.get(0)?.end();
+        if self.text[end..].starts_with(char::is_alphanumeric) {
+            return None;
+        }
+        let letter = caps
+            .get(1)
+            .or(caps.get(3))
+            .or(caps.get(5))?
+            .as_str()
+            .to_lowercase();
+        let dotted = caps.get(2).or(caps.get(4)).or(caps.get(6)).is_some();
+        let mut written = if letter == "a" { am } else { pm }.to_string();
+        // "pm." at the end of a sentence: its period completes "p.m."
+        if !dotted && self.text[end..].starts_with('.') {
+            written.pop();
+        }
+        Some((written, end))
+    }
+
+    fn time_at(&self, i: usize) -> Option<Match> {
+        let style = &self.lex.time;
+
+        // "half past three", "quarter to four"
+        if let Some((offset, len)) = style
+            .relative
+            .iter()
+            .filter_map(|(p, offset)| self.phrase_len(i, p).map(|len| (*offset, len)))
+            .max_by_key(|(_, len)| *len)
+        {
+            let j = i + len;
+            if self.follows(j) {
+                if let Some(hour) = self.hour_at(j, 12).filter(|h| h.value > 0) {
+                    let (h, m) = if offset >= 0 {
+                        (hour.value, offset as u64)
+                    } else {
+                        let h = if hour.value == 1 { 12 } else { hour.value - 1 };
+                        (h, (60 + offset) as u64)
+                    };
+                    let text = self.format_time(style.minute_format, h, m);
+                    return Some(self.finish_time(j + hour.len, text));
+                }
+            }
+        }
+
+        let hour = self.hour_at(i, 24)?;
+        let j = i + hour.len;
+
+        // "three o'clock", "drei Uhr dreißig"
+        if let Some(len) = self.next_phrase(j, style.clock_words) {
+            let k = j + len;
+            if style.minutes_after_clock {
+                if let Some((m, mlen)) = self.minutes_at(k) {
+                    let text = self.format_time(style.minute_format, hour.value, m);
+                    return Some(self.finish_time(k + mlen, text));
+                }
+            }
+            let text = self.format_time(style.hour_format, hour.value, 0);
+            return Some(self.finish_time(k, text));
+        }
+
+        // "three thirty p.m.", "seven a.m."
+        if style.meridiem.is_some() && (1..=12).contains(&hour.value) {
+            if let Some((m, mlen)) = self.minutes_at(j) {
+                let end = self.words[j + mlen - 1].end;
+                if self.meridiem_at(end).is_some() {
+                    let text = self.format_time(style.minute_format, hour.value, m);
+                    return Some(self.finish_time(j + mlen, text));
+                }
+            }
+            if self.meridiem_at(self.words[j - 1].end).is_some() {
+                return Some(self.finish_time(j, hour.value.to_string()));
+            }
+        }
+        None
+    }
+
+    fn format_time(&self, template: &str, hour: u64, minute: u64) -> String {
+        template
+            .replace("{h}", &hour.to_string())
+            .replace("{mm}", &format!("{:02}", minute))
+    }
+
+    /// Time match ending at word `next`, with a following a.m./p.m. if any
+    fn finish_time(&self, next: usize, text: String) -> Match {
+        let end = self.words[next - 1].end;
+        match self.meridiem_at(end) {
+            Some((meridiem, end)) => Match {
+                end,
+                next: self.words.partition_point(|w| w.start < end),
+                text: format!("{} {}", text, meridiem),
+            },
+            None => Match { end, next, text },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn normalize(text: &str, language: &str) -> String {
+        let config = NormalizeConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        Normalizer::new(&config).normalize(text, language)
+    }
+
+    fn en(text: &str) -> String {
+        normalize(text, "en")
+    }
+
+    #[test]
+    fn test_cardinals() {
+        assert_eq!(en("twenty three people"), "23 people");
+        assert_eq!(en("one hundred and five"), "105");
+        assert_eq!(en("a hundred and twelve"), "112");
+        assert_eq!(en("twenty-one"), "21");
+        assert_eq!(en("three thousand four hundred"), "3400");
+        assert_eq!(en("forty two thousand"), "42,000");
+        assert_eq!(en("two million"), "2 million");
+        assert_eq!(en("nineteen hundred"), "1900");
+    }
+
+    #[test]
+    fn test_small_numbers_kept() {
+        assert_eq!(en("two cats and one dog"), "two cats and one dog");
+        assert_eq!(en("the first time"), "the first time");
+        assert_eq!(en("a hundred"), "a hundred");
+
+        let config = NormalizeConfig {
+            keep_small_numbers: false,
+            ..Default::default()
+        };
+        let normalizer = Normalizer::new(&config);
+        assert_eq!(normalizer.normalize("two cats", "en"), "2 cats");
+    }
+
+    #[test]
+    fn test_number_runs_left_alone() {
+        assert_eq!(en("one two three"), "one two three");
+        assert_eq!(en("call me at three thirty"), "call me at three thirty");
+    }
+
+    #[test]
+    fn test_decimals() {
+        assert_eq!(en("three point one four"), "3.14");
+        assert_eq!(en("point five"), "0.5");
+        assert_eq!(en("two point five million"), "2.5 million");
+        assert_eq!(en("twenty points"), "20 points");
+    }
+
+    #[test]
+    fn test_ordinals() {
+        assert_eq!(en("the twenty third floor"), "the 23rd floor");
+        assert_eq!(en("his eleventh birthday"), "his 11th birthday");
+        assert_eq!(en("the twenty first century"), "the 21st century");
+        assert_eq!(en("the hundredth time"), "the 100th time");
+        assert_eq!(en("one second"), "one second");
+    }
+
+    #[test]
+    fn test_percent_and_currency() {
+        assert_eq!(en("twenty three point five percent"), "23.5%");
+        assert_eq!(en("a hundred percent"), "100%");
+        assert_eq!(en("five dollars"), "$5");
+        assert_eq!(en("five dollars and fifty cents"), "$5.50");
+        assert_eq!(en("twelve dollars ninety nine"), "$12.99");
+        assert_eq!(en("fifty cents"), "50¢");
+        assert_eq!(en("three million euros"), "€3 million");
+        assert_eq!(en("5 dollars"), "$5");
+    }
+
+    #[test]
+    fn test_units() {
+        assert_eq!(en("five kilometers"), "5 km");
+        assert_eq!(en("twenty degrees celsius"), "20°C");
+        assert_eq!(en("ninety degrees"), "90°");
+        assert_eq!(en("sixteen gigabytes of ram"), "16 GB of ram");
+        assert_eq!(en("fifty kilometers per hour"), "50 km/h");
+    }
+
+    #[test]
+    fn test_dates() {
+        assert_eq!(en("march third twenty twenty six"), "March 3, 2026");
+        assert_eq!(en("on March twenty third"), "on March 23");
+        assert_eq!(en("the fourth of july"), "July 4");
+        assert_eq!(en("January twenty twenty five"), "January 2025");
+        assert_eq!(en("you may first ask"), "you may first ask");
+        assert_eq!(en("born in nineteen eighty four"), "born in 1984");
+        assert_eq!(en("twenty oh five"), "2005");
+    }
+
+    #[test]
+    fn test_date_formats() {
+        let config = NormalizeConfig {
+            date_format: DateFormat::Iso,
+            ..Default::default()
+        };
+        let normalizer = Normalizer::new(&config);
+        assert_eq!(
+            normalizer.normalize("march third twenty twenty six", "en"),
+            "2026-03-03"
+        );
+        assert_eq!(normalizer.normalize("March third", "en"), "March 3");
+
+        let config = NormalizeConfig {
+            date_format: DateFormat::Numeric,
+            ..Default::default()
+        };
+        let normalizer = Normalizer::new(&config);
+        assert_eq!(
+            normalizer.normalize("march third twenty twenty six", "en"),
+            "03/03/2026"
+        );
+        assert_eq!(
+            normalizer.normalize("dritter März zweitausendsechsundzwanzig", "de"),
+            "03.03.2026"
+        );
+    }
+
+    #[test]
+    fn test_times() {
+        assert_eq!(en("at three thirty p.m."), "at 3:30 p.m.");
+        assert_eq!(en("at seven am."), "at seven am.");
+        assert_eq!(en("at seven AM."), "at 7 a.m.");
+        assert_eq!(en("at twelve oh five pm"), "at 12:05 p.m.");
+        assert_eq!(en("at three o'clock"), "at 3:00");
+        assert_eq!(en("half past four"), "4:30");
+        assert_eq!(en("quarter to one"), "12:45");
+    }
+
+    #[test]
+    fn test_config_doc_examples() {
+        // The examples in the NormalizeConfig field docs
+        assert_eq!(en("two cats"), "two cats");
+        assert_eq!(en("twenty three point five"), "23.5");
+        assert_eq!(en("twenty third"), "23rd");
+        assert_eq!(en("five dollars fifty"), "$5.50");
+        assert_eq!(en("ten percent"), "10%");
+        assert_eq!(en("march third twenty twenty six"), "March 3, 2026");
+        assert_eq!(en("three thirty p.m."), "3:30 p.m.");
+        assert_eq!(en("five kilometers"), "5 km");
+    }
+
+    #[test]
+    fn test_german() {
+        let de = |text| normalize(text, "de");
+        assert_eq!(de("dreiundzwanzig Personen"), "23 Personen");
+        assert_eq!(de("zweihundertfünfundvierzig"), "245");
+        assert_eq!(de("drei Komma fünf Prozent"), "3,5 %");
+        assert_eq!(de("zwölf Euro fünfzig"), "12,50 €");
+        assert_eq!(de("am dritten März"), "am 3. März");
+        assert_eq!(de("der zwanzigste Geburtstag"), "der 20. Geburtstag");
+        assert_eq!(de("um fünfzehn Uhr dreißig"), "um 15:30 Uhr");
+        assert_eq!(de("eine Million"), "eine Million");
+        assert_eq!(de("Achtung"), "Achtung");
+    }
+
+    #[test]
+    fn test_french() {
+        let fr = |text| normalize(text, "fr");
+        assert_eq!(fr("quatre-vingt-dix-sept"), "97");
+        assert_eq!(fr("soixante et onze"), "71");
+        assert_eq!(fr("deux cents euros"), "200\u{00A0}€");
+        assert_eq!(fr("le premier mai"), "le 1er mai");
+        assert_eq!(fr("trois mars deux mille vingt-six"), "3 mars 2026");
+        assert_eq!(fr("la vingt et unième fois"), "la 21e fois");
+        assert_eq!(fr("à huit heures quinze"), "à 8 h 15");
+        assert_eq!(fr("vingt pour cent"), "20\u{00A0}%");
+    }
+
+    #[test]
+    fn test_spanish() {
+        let es = |text| normalize(text, "es");
+        assert_eq!(es("treinta y cuatro"), "34");
+        assert_eq!(es("treinta y un años"), "31 años");
+        assert_eq!(es("mil novecientos noventa"), "1990");
+        assert_eq!(
+            es("tres de marzo de dos mil veintiséis"),
+            "3 de marzo de 2026"
+        );
+        assert_eq!(es("diez dólares"), "$10");
+    }
+
+    #[test]
+    fn test_categories_can_be_disabled() {
+        let config = NormalizeConfig {
+            currency: false,
+            ordinals: false,
+            ..Default::default()
+        };
+        let normalizer = Normalizer::new(&config);
+        assert_eq!(normalizer.normalize("five dollars", "en"), "five dollars");
+        assert_eq!(
+            normalizer.normalize("the twenty third floor", "en"),
+            "the twenty third floor"
+        );
+        assert_eq!(normalizer.normalize("twenty five", "en"), "25");
+    }
+
+    #[test]
+    fn test_unsupported_language() {
+        let config = NormalizeConfig {
+            languages: vec!["de".to_string()],
+            ..Default::default()
+        };
+        let normalizer = Normalizer::new(&config);
+        assert!(normalizer.supports("de-AT"));
+        assert!(!normalizer.supports("en"));
+        assert!(!normalizer.supports("ja"));
+        assert_eq!(normalizer.normalize("twenty five", "en"), "twenty five");
+        assert_eq!(normalizer.normalize("twenty five", "ja"), "twenty five");
+    }
+}
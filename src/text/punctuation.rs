@@ -0,0 +1,560 @@
+//! Spoken punctuation tables
+//!
+//! Maps spoken phrases ("comma", "virgule", "Komma") to symbols, per
+//! language. Built-in tables (`punctuation.toml`) cover English, French,
+//! German, Spanish, Italian, Portuguese and Dutch. Files in
+//! `~/.config/voxtype/punctuation/<lang>.toml` add phrases to a built-in
+//! table, or define a table for another language.
+//!
+//! Example user file (`~/.config/voxtype/punctuation/fr.toml`):
+//!
+//! ```toml
+//! [phrases]
+//! "guillemet simple" = "'"
+//! "point" = ""    # remove a built-in phrase
+//! ```
+//!
+//! Words that are also everyday words ("point", "Punkt", "punto") are listed
+//! under `final_phrases` and only replaced at the end of the text or before a
+//! line break or punctuation mark, so "le point de vue" keeps its "point".
+//!
+//! Tables can also carry typography rules, such as the non-breaking space
+//! French puts before `?`, `!`, `:` and `;`.
+
+use crate::config::Config;
+use regex::{NoExpand, Regex, RegexBuilder};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Built-in tables shipped with voxtype
+const BUILTIN_TABLES: &str = include_str!("punctuation.toml");
+
+/// Directory in the config directory holding user tables (`<lang>.toml`)
+pub const USER_TABLES_DIR: &str = "punctuation";
+
+/// Language used when the transcription language has no table
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+/// Default non-breaking space
+const NBSP: &str = "\u{00A0}";
+
+/// A spoken punctuation table as written in TOML
+#[derive(Debug, Clone, Default, Deserialize)]
+struct TableSpec {
+    /// Replace the built-in table instead of extending it (user files only)
+    #[serde(default)]
+    replace: bool,
+
+    /// Spoken phrase → symbol; an empty symbol removes the phrase
+    #[serde(default)]
+    phrases: BTreeMap<String, String>,
+
+    /// Phrases replaced only at the end of the text or before a line break
+    /// or punctuation mark; `phrases` takes precedence
+    #[serde(default)]
+    final_phrases: BTreeMap<String, String>,
+
+    /// Characters preceded by a non-breaking space
+    #[serde(default)]
+    nbsp_before: Option<String>,
+
+    /// Characters followed by a non-breaking space
+    #[serde(default)]
+    nbsp_after: Option<String>,
+
+    /// Space character used by nbsp_before/nbsp_after
+    #[serde(default)]
+    nbsp: Option<String>,
+}
+
+impl TableSpec {
+    /// Add phrases and settings from a user table
+    fn merge(&mut self, other: TableSpec) {
+        if other.replace {
+            *self = other;
+            return;
+        }
+        self.phrases.extend(other.phrases);
+        self.final_phrases.extend(other.final_phrases);
+        if other.nbsp_before.is_some() {
+            self.nbsp_before = other.nbsp_before;
+        }
+        if other.nbsp_after.is_some() {
+            self.nbsp_after = other.nbsp_after;
+        }
+        if other.nbsp.is_some() {
+            self.nbsp = other.nbsp;
+        }
+    }
+}
+
+/// Compiled spoken punctuation table for one language
+#[derive(Debug, Clone)]
+pub struct PunctuationTable {
+    /// (phrase matcher, symbol), longest phrase first
+    /// Final phrases capture what follows them as `end`
+    phrases: Vec<(Regex, String)>,
+    nbsp_before: Vec<char>,
+    nbsp_after: Vec<char>,
+    nbsp: String,
+}
+
+impl PunctuationTable {
+    fn compile(spec: &TableSpec) -> Self {
+        let final_phrases = spec
+            .final_phrases
+            .iter()
+            .filter(|(phrase, _)| !spec.phrases.contains_key(*phrase))
+            .map(|(phrase, symbol)| (phrase, symbol, true));
+        let mut phrases: Vec<(&String, &String, bool)> = spec
+            .phrases
+            .iter()
+            .map(|(phrase, symbol)| (phrase, symbol, false))
+            .chain(final_phrases)
+            .filter(|(phrase, symbol, _)| !phrase.trim().is_empty() && !symbol.is_empty())
+            .collect();
+        phrases.sort_by(|(a, _, _), (b, _, _)| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+
+        let phrases = phrases
+            .into_iter()
+            .filter_map(
+                |(phrase, symbol, at_end)| match phrase_regex(phrase, at_end) {
+                    Ok(re) => Some((re, symbol.clone())),
+                    Err(e) => {
+                        tracing::warn!("Skipping spoken punctuation {:?}: {}", phrase, e);
+                        None
+                    }
+                },
+            )
+            .collect();
+
+        Self {
+            phrases,
+            nbsp_before: spec.nbsp_before.as_deref().unwrap_or("").chars().collect(),
+            nbsp_after: spec.nbsp_after.as_deref().unwrap_or("").chars().collect(),
+            nbsp: spec.nbsp.clone().unwrap_or_else(|| NBSP.to_string()),
+        }
+    }
+
+    /// Number of spoken phrases in the table
+    pub fn len(&self) -> usize {
+        self.phrases.len()
+    }
+
+    /// Whether the table has no phrases
+    pub fn is_empty(&self) -> bool {
+        self.phrases.is_empty()
+    }
+
+    /// Replace spoken phrases with symbols and fix the spacing around them
+    pub fn apply(&self, text: &str) -> String {
+        let mut result = text.to_string();
+        for (re, symbol) in &self.phrases {
+            result = if re.capture_names().any(|name| name == Some("end")) {
+                re.replace_all(&result, |caps: &regex::Captures| {
+                    format!("{}{}", symbol, &caps["end"])
+                })
+                .into_owned()
+            } else {
+                re.replace_all(&result, NoExpand(symbol)).into_owned()
+            };
+        }
+
+        result = clean_punctuation_spacing(&result);
+
+        if !self.nbsp_before.is_empty() || !self.nbsp_after.is_empty() {
+            result = self.apply_nbsp(&result);
+        }
+
+        result
+    }
+
+    /// Put non-breaking spaces around the configured characters
+    /// (French: "Quoi ?" and "« bonjour »")
+    fn apply_nbsp(&self, text: &str) -> String {
+        let chars: Vec<char> = text.chars().collect();
+        let is_mark = |c: char| self.nbsp_before.contains(&c) || self.nbsp_after.contains(&c);
+        let mut out = String::with_capacity(text.len());
+
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+
+            // Marks directly followed by a word or slash are left alone,
+            // so URLs and times ("https://", "10:30") keep their form
+            let attached = chars
+                .get(i + 1)
+                .is_some_and(|n| n.is_alphanumeric() || *n == '/');
+
+            if self.nbsp_before.contains(&c) && !attached {
+                let len = out.trim_end_matches(' ').len();
+                out.truncate(len);
+                if out
+                    .chars()
+                    .last()
+                    .is_some_and(|p| !p.is_whitespace() && !is_mark(p))
+                {
+                    out.push_str(&self.nbsp);
+                }
+                out.push(c);
+                i += 1;
+            } else if self.nbsp_after.contains(&c) {
+                out.push(c);
+                i += 1;
+                while chars.get(i) == Some(&' ') {
+                    i += 1;
+                }
+                if chars
+                    .get(i)
+                    .is_some_and(|n| !n.is_whitespace() && !is_mark(*n))
+                {
+                    out.push_str(&self.nbsp);
+                }
+            } else {
+                out.push(c);
+                i += 1;
+            }
+        }
+
+        out
+    }
+}
+
+/// Spoken punctuation tables for all known languages
+#[derive(Debug, Clone)]
+pub struct PunctuationTables {
+    tables: HashMap<String, PunctuationTable>,
+}
+
+impl PunctuationTables {
+    /// Tables containing only the built-in languages
+    pub fn builtin() -> Self {
+        Self::compile(builtin_specs())
+    }
+
+    /// Built-in tables merged with the user's punctuation directory
+    pub fn load() -> Self {
+        let mut specs = builtin_specs();
+        if let Some(dir) = Self::user_dir() {
+            for (language, spec) in load_user_specs(&dir) {
+                specs.entry(language).or_default().merge(spec);
+            }
+        }
+        Self::compile(specs)
+    }
+
+    /// User tables directory (~/.config/voxtype/punctuation)
+    pub fn user_dir() -> Option<PathBuf> {
+        Config::config_dir().map(|dir| dir.join(USER_TABLES_DIR))
+    }
+
+    fn compile(specs: BTreeMap<String, TableSpec>) -> Self {
+        let tables = specs
+            .iter()
+            .map(|(language, spec)| (language.clone(), PunctuationTable::compile(spec)))
+            .collect();
+        Self { tables }
+    }
+
+    /// Table for a language code ("fr", "fr-CA", "pt_BR")
+    /// Languages without a table use English
+    pub fn get(&self, language: &str) -> &PunctuationTable {
+        self.tables
+            .get(&normalize_language(language))
+            .or_else(|| self.tables.get(DEFAULT_LANGUAGE))
+            .expect("built-in English punctuation table exists")
+    }
+
+    /// Whether a language has its own table
+    pub fn contains(&self, language: &str) -> bool {
+        self.tables.contains_key(&normalize_language(language))
+    }
+
+    /// Languages with a table, sorted
+    pub fn languages(&self) -> Vec<&str> {
+        let mut languages: Vec<&str> = self.tables.keys().map(String::as_str).collect();
+        languages.sort_unstable();
+        languages
+    }
+}
+
+/// Process-wide tables (built-in plus user files), loaded on first use
+pub fn tables() -> &'static PunctuationTables {
+    static TABLES: OnceLock<PunctuationTables> = OnceLock::new();
+    TABLES.get_or_init(PunctuationTables::load)
+}
+
+fn builtin_specs() -> BTreeMap<String, TableSpec> {
+    toml::from_str(BUILTIN_TABLES).expect("built-in punctuation tables are valid")
+}
+
+/// Read `<lang>.toml` files from the user tables directory
+/// Invalid files are skipped with a warning
+fn load_user_specs(dir: &Path) -> Vec<(String, TableSpec)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut specs = Vec::new();
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.extension().and_then(|e| e.to_str()) != Some("toml") {
+            continue;
+        }
+        let Some(language) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+
+        match std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|contents| toml::from_str::<TableSpec>(&contents).map_err(|e| e.to_string()))
+        {
+            Ok(spec) => specs.push((normalize_language(language), spec)),
+            Err(e) => tracing::warn!("Ignoring {:?}: {}", path, e),
+        }
+    }
+    specs
+}
+
+/// Primary language subtag, lowercase ("fr-CA" → "fr")
+pub(crate) fn normalize_language(language: &str) -> String {
+    language
+        .split(['-', '_'])
+        .next()
+        .unwrap_or(language)
+        .trim()
+        .to_lowercase()
+}
+
+/// Case-insensitive word-boundary matcher for a spoken phrase
+/// Spaces and hyphens are interchangeable, and apostrophes match ' and ’
+/// A final phrase must be followed by the end of the text, a line break or a
+/// punctuation mark, captured as `end` (the regex crate has no lookahead)
+fn phrase_regex(phrase: &str, is_final: bool) -> Result<Regex, regex::Error> {
+    let pattern = phrase
+        .split(|c: char| c.is_whitespace() || c == '-')
+        .filter(|word| !word.is_empty())
+        .map(|word| regex::escape(word).replace(['\'', '’'], "['’]"))
+        .collect::<Vec<_>>()
+        .join(r"[\s-]+");
+    let end = if is_final {
+        r"(?P<end>[ \t]*(?:\z|[\n.,;:!?…)»]))"
+    } else {
+        ""
+    };
+    RegexBuilder::new(&format!(r"\b{}\b{}", pattern, end))
+        .case_insensitive(true)
+        .build()
+}
+
+/// Clean up spacing around punctuation marks
+fn clean_punctuation_spacing(text: &str) -> String {
+    let mut result = text.to_string();
+
+    // Remove space before punctuation that shouldn't have it
+    for punct in ['.', ',', '?', '!', ':', ';', ')', ']', '}', '…'] {
+        result = result.replace(&format!(" {}", punct), &punct.to_string());
+    }
+
+    // Remove space after opening brackets
+    for punct in ['(', '[', '{'] {
+        result = result.replace(&format!("{} ", punct), &punct.to_string());
+    }
+
+    // Remove space before opening brackets (for function calls, array access, etc.)
+    for punct in ['(', '[', '{'] {
+        result = result.replace(&format!(" {}", punct), &punct.to_string());
+    }
+
+    // Remove space before symbols that typically attach to the next word (email, hashtags, etc.)
+    for sym in ['#', '@', '$'] {
+        result = result.replace(&format!(" {}", sym), &sym.to_string());
+    }
+
+    // Remove space after symbols that typically attach to the next word
+    for sym in ['#', '@', '$'] {
+        result = result.replace(&format!("{} ", sym), &sym.to_string());
+    }
+
+    // Remove space after inverted marks (Spanish "¿ qué" → "¿qué")
+    for sym in ['¿', '¡'] {
+        result = result.replace(&format!("{} ", sym), &sym.to_string());
+    }
+
+    // Remove spaces around newlines and tabs
+    result = result.replace(" \n", "\n");
+    result = result.replace("\n ", "\n");
+    result = result.replace(" \t", "\t");
+    result = result.replace("\t ", "\t");
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(language: &str, text: &str) -> String {
+        PunctuationTables::builtin().get(language).apply(text)
+    }
+
+    #[test]
+    fn test_builtin_tables_parse() {
+        let tables = PunctuationTables::builtin();
+        assert_eq!(
+            tables.languages(),
+            vec!["de", "en", "es", "fr", "it", "nl", "pt"]
+        );
+        for language in tables.languages() {
+            assert!(!tables.get(language).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_english() {
+        assert_eq!(
+            apply("en", "Hello comma world question mark"),
+            "Hello, world?"
+        );
+    }
+
+    #[test]
+    fn test_german() {
+        assert_eq!(
+            apply("de", "Hallo Komma wie geht es dir Fragezeichen"),
+            "Hallo, wie geht es dir?"
+        );
+        assert_eq!(apply("de", "Ende Punkt neue Zeile"), "Ende.\n");
+    }
+
+    #[test]
+    fn test_french_nbsp() {
+        assert_eq!(
+            apply("fr", "Bonjour virgule comment ça va point d'interrogation"),
+            "Bonjour, comment ça va\u{00A0}?"
+        );
+        assert_eq!(
+            apply("fr", "C'est génial point d'exclamation"),
+            "C'est génial\u{00A0}!"
+        );
+        assert_eq!(
+            apply(
+                "fr",
+                "Il a dit ouvrez les guillemets oui fermez les guillemets"
+            ),
+            "Il a dit «\u{00A0}oui\u{00A0}»"
+        );
+    }
+
+    #[test]
+    fn test_bare_period_words_only_at_the_end() {
+        assert_eq!(apply("fr", "le point de vue"), "le point de vue");
+        assert_eq!(apply("fr", "c'est fini point"), "c'est fini.");
+        assert_eq!(apply("fr", "fini point final merci"), "fini. merci");
+        // At the very end, the bare word is always taken as punctuation
+        assert_eq!(apply("de", "Das ist der Punkt"), "Das ist der.");
+        assert_eq!(
+            apply("de", "der Punkt ist wichtig"),
+            "der Punkt ist wichtig"
+        );
+        assert_eq!(apply("de", "gut Punkt am Ende weiter"), "gut. weiter");
+        assert_eq!(apply("de", "gut Punkt neue Zeile"), "gut.\n");
+        assert_eq!(
+            apply("es", "en punto de la mañana"),
+            "en punto de la mañana"
+        );
+        assert_eq!(apply("es", "hola punto y seguido adiós"), "hola. adiós");
+        assert_eq!(apply("it", "il punto di vista"), "il punto di vista");
+        assert_eq!(apply("it", "ciao punto fermo a presto"), "ciao. a presto");
+        assert_eq!(apply("it", "ciao punto"), "ciao.");
+    }
+
+    #[test]
+    fn test_french_phrase_variants() {
+        // Typographic apostrophe and hyphen/space variants
+        assert_eq!(apply("fr", "Quoi point d’interrogation"), "Quoi\u{00A0}?");
+        assert_eq!(apply("fr", "un point virgule deux"), "un\u{00A0}; deux");
+        assert_eq!(apply("fr", "un point-virgule deux"), "un\u{00A0}; deux");
+    }
+
+    #[test]
+    fn test_french_nbsp_leaves_urls_and_times() {
+        assert_eq!(apply("fr", "https://example.com"), "https://example.com");
+        assert_eq!(apply("fr", "à 10:30"), "à 10:30");
+        // Already spaced text is not doubled
+        assert_eq!(apply("fr", "Quoi\u{00A0}?"), "Quoi\u{00A0}?");
+        assert_eq!(apply("fr", "Quoi ?!"), "Quoi\u{00A0}?!");
+    }
+
+    #[test]
+    fn test_spanish_inverted_marks() {
+        assert_eq!(
+            apply("es", "abrir interrogación qué tal cerrar interrogación"),
+            "¿qué tal?"
+        );
+    }
+
+    #[test]
+    fn test_language_fallback_and_normalization() {
+        let tables = PunctuationTables::builtin();
+        assert!(tables.contains("fr-CA"));
+        assert!(tables.contains("PT_br"));
+        assert!(!tables.contains("ja"));
+        assert_eq!(tables.get("ja").apply("yes period"), "yes.");
+        assert_eq!(tables.get("fr_FR").apply("oui point"), "oui.");
+    }
+
+    #[test]
+    fn test_user_table_merge() {
+        let mut spec = builtin_specs().remove("fr").unwrap();
+        let user: TableSpec = toml::from_str(
+            r#"
+            nbsp = "\u202F"
+            [phrases]
+            "point" = ""
+            "tiret long" = "—"
+            "#,
+        )
+        .unwrap();
+        spec.merge(user);
+        let table = PunctuationTable::compile(&spec);
+        assert_eq!(table.apply("le point"), "le point");
+        assert_eq!(table.apply("a tiret long b"), "a — b");
+        assert_eq!(table.apply("Quoi point d'interrogation"), "Quoi\u{202F}?");
+    }
+
+    #[test]
+    fn test_user_table_replace() {
+        let mut spec = builtin_specs().remove("en").unwrap();
+        let user: TableSpec = toml::from_str(
+            r#"
+            replace = true
+            [phrases]
+            "full stop" = "."
+            "#,
+        )
+        .unwrap();
+        spec.merge(user);
+        let table = PunctuationTable::compile(&spec);
+        assert_eq!(table.len(), 1);
+        assert_eq!(table.apply("done full stop period"), "done. period");
+    }
+
+    #[test]
+    fn test_load_user_specs() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("sv.toml"),
+            "[phrases]\n\"kommatecken\" = \",\"\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("broken.toml"), "phrases = 3").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let specs = load_user_specs(dir.path());
+        assert_eq!(specs.len(), 1);
+        assert_eq!(specs[0].0, "sv");
+        assert_eq!(specs[0].1.phrases.get("kommatecken").unwrap(), ",");
+    }
+}
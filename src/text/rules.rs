@@ -0,0 +1,258 @@
+//! Ordered replacement rules
+//!
+//! Rules from `[[text.rules]]` are applied in the order they are listed.
+//! A rule's pattern is either literal text or a regular expression with
+//! capture groups (`$1`, `${name}` in the replacement).
+
+use crate::config::TextRule;
+use regex::{Captures, NoExpand, Regex, RegexBuilder};
+
+/// A compiled `[[text.rules]]` entry
+#[derive(Debug, Clone)]
+pub struct CompiledRule {
+    /// Label for logs and `voxtype text test`
+    pub label: String,
+    regex: Regex,
+    replacement: String,
+    /// Expand capture group references in the replacement (regex rules only)
+    expand: bool,
+    /// Skip regex matches that start or end inside a word
+    whole_word: bool,
+}
+
+impl CompiledRule {
+    /// Compile a rule, returning an error for an invalid regex
+    pub fn compile(rule: &TextRule) -> Result<Self, regex::Error> {
+        // Regex rules check word boundaries on each match instead, since
+        // the pattern's first and last characters aren't known up front
+        let pattern = if rule.regex {
+            rule.pattern.clone()
+        } else {
+            literal_pattern(&rule.pattern, rule.whole_word)
+        };
+
+        let regex = RegexBuilder::new(&pattern)
+            .case_insensitive(!rule.case_sensitive)
+            .build()?;
+
+        Ok(Self {
+            label: rule.label(),
+            regex,
+            replacement: rule.replacement.clone(),
+            expand: rule.regex,
+            whole_word: rule.regex && rule.whole_word,
+        })
+    }
+
+    /// Apply the rule to all matches in the text
+    pub fn apply(&self, text: &str) -> String {
+        if self.whole_word {
+            self.regex
+                .replace_all(text, |caps: &Captures| {
+                    let m = caps.get(0).expect("match");
+                    let mut replaced = String::new();
+                    if is_whole_word(text, m.start(), m.end()) {
+                        caps.expand(&self.replacement, &mut replaced);
+                    } else {
+                        replaced.push_str(m.as_str());
+                    }
+                    replaced
+                })
+                .into_owned()
+        } else if self.expand {
+            self.regex
+                .replace_all(text, self.replacement.as_str())
+                .into_owned()
+        } else {
+            self.regex
+                .replace_all(text, NoExpand(&self.replacement))
+                .into_owned()
+        }
+    }
+}
+
+/// Compile all enabled rules, in order
+/// Invalid rules are skipped with a warning
+pub fn compile_rules(rules: &[TextRule]) -> Vec<CompiledRule> {
+    rules
+        .iter()
+        .filter(|rule| rule.enabled)
+        .filter_map(|rule| match CompiledRule::compile(rule) {
+            Ok(compiled) => Some(compiled),
+            Err(e) => {
+                tracing::warn!("Skipping text rule {}: {}", rule.label(), e);
+                None
+            }
+        })
+        .collect()
+}
+
+fn is_word_char(c: Option<char>) -> bool {
+    c.is_some_and(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Whether text[start..end] doesn't cut into a word at either end
+/// Like the boundaries of literal rules, only checked next to word characters
+fn is_whole_word(text: &str, start: usize, end: usize) -> bool {
+    let matched = &text[start..end];
+    let starts_word = is_word_char(matched.chars().next());
+    let ends_word = is_word_char(matched.chars().next_back());
+    let cuts_start = starts_word && is_word_char(text[..start].chars().next_back());
+    let cuts_end = ends_word && is_word_char(text[end..].chars().next());
+    !cuts_start && !cuts_end
+}
+
+/// Regex for a literal pattern
+/// Word boundaries are only added next to word characters, so patterns
+/// like "c++" or "@here" still match when whole_word is set
+fn literal_pattern(literal: &str, whole_word: bool) -> String {
+    let escaped = regex::escape(literal);
+    if !whole_word {
+        return escaped;
+    }
+
+    let start = if is_word_char(literal.chars().next()) {
+        r"\b"
+    } else {
+        ""
+    };
+    let end = if is_word_char(literal.chars().last()) {
+        r"\b"
+    } else {
+        ""
+    };
+    format!("{}{}{}", start, escaped, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(pattern: &str, replacement: &str) -> TextRule {
+        TextRule {
+            name: None,
+            pattern: pattern.to_string(),
+            replacement: replacement.to_string(),
+            regex: false,
+            case_sensitive: false,
+            whole_word: true,
+            enabled: true,
+        }
+    }
+
+    fn apply(rule: &TextRule, text: &str) -> String {
+        CompiledRule::compile(rule).unwrap().apply(text)
+    }
+
+    #[test]
+    fn test_literal_rule() {
+        let r = rule("vox type", "voxtype");
+        assert_eq!(apply(&r, "I use Vox Type daily"), "I use voxtype daily");
+    }
+
+    #[test]
+    fn test_literal_rule_whole_word() {
+        let r = rule("cat", "dog");
+        assert_eq!(apply(&r, "cat concatenate"), "dog concatenate");
+
+        let r = TextRule {
+            whole_word: false,
+            ..rule("cat", "dog")
+        };
+        assert_eq!(apply(&r, "cat concatenate"), "dog condogenate");
+    }
+
+    #[test]
+    fn test_literal_rule_with_symbols() {
+        let r = rule("c plus plus", "C++");
+        assert_eq!(apply(&r, "I write c plus plus"), "I write C++");
+
+        let r = rule("C++", "Rust");
+        assert_eq!(apply(&r, "I write C++ code"), "I write Rust code");
+    }
+
+    #[test]
+    fn test_literal_replacement_is_not_expanded() {
+        let r = rule("price", "$1");
+        assert_eq!(apply(&r, "the price"), "the $1");
+    }
+
+    #[test]
+    fn test_case_sensitive_rule() {
+        let r = TextRule {
+            case_sensitive: true,
+            ..rule("Rust", "RUST")
+        };
+        assert_eq!(apply(&r, "Rust rust"), "RUST rust");
+    }
+
+    #[test]
+    fn test_regex_rule_with_captures() {
+        let r = TextRule {
+            regex: true,
+            ..rule(r"ticket (\d+)", "#$1")
+        };
+        assert_eq!(apply(&r, "fixes ticket 42"), "fixes #42");
+
+        let r = TextRule {
+            regex: true,
+            ..rule(
+                r"(?P<user>\w+) at (?P<host>\w+) dot com",
+                "${user}@${host}.com",
+            )
+        };
+        assert_eq!(
+            apply(&r, "mail bob at example dot com"),
+            "mail bob@example.com"
+        );
+    }
+
+    #[test]
+    fn test_regex_rule_whole_word() {
+        let r = TextRule {
+            regex: true,
+            ..rule(r"cat(s?)", "dog$1")
+        };
+        assert_eq!(apply(&r, "cats concatenate cat"), "dogs concatenate dog");
+
+        // Boundaries only apply next to word characters
+        let r = TextRule {
+            regex: true,
+            ..rule(r"\$(\d+)", "$1 dollars")
+        };
+        assert_eq!(apply(&r, "costs $5 now"), "costs 5 dollars now");
+        assert_eq!(apply(&r, "costs $50a"), "costs $50a");
+
+        let r = TextRule {
+            regex: true,
+            ..rule(r"#\d+", "issue")
+        };
+        assert_eq!(apply(&r, "see #12, not #12a"), "see issue, not #12a");
+    }
+
+    #[test]
+    fn test_invalid_regex() {
+        let r = TextRule {
+            regex: true,
+            ..rule("(unclosed", "x")
+        };
+        assert!(CompiledRule::compile(&r).is_err());
+        assert!(compile_rules(&[r]).is_empty());
+    }
+
+    #[test]
+    fn test_compile_rules_skips_disabled() {
+        let rules = vec![
+            rule("one", "1"),
+            TextRule {
+                enabled: false,
+                ..rule("two", "2")
+            },
+            rule("three", "3"),
+        ];
+        let compiled = compile_rules(&rules);
+        assert_eq!(compiled.len(), 2);
+        assert_eq!(compiled[0].label, "\"one\"");
+        assert_eq!(compiled[1].label, "\"three\"");
+    }
+}
@@ -0,0 +1,395 @@
+//! Snippet expansion
+//!
+//! A snippet library maps trigger phrases to text (email signatures, standard
+//! replies, code skeletons). When a transcription contains a trigger, it is
+//! replaced by the snippet text with its placeholders filled in:
+//! - `{date}`, `{time}`, `{datetime}` or `{date:FORMAT}` (strftime format)
+//! - `{clipboard}`: current clipboard contents
+//! - `{env:NAME}`: environment variable
+//!
+//! The library is a TOML file, one table per snippet:
+//!
+//! ```toml
+//! [signature]
+//! trigger = "insert signature"
+//! text = """
+//! Best regards,
+//! Jane"""
+//! ```
+
+use crate::config::SnippetsConfig;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::{mpsc, OnceLock};
+use std::time::Duration;
+
+/// Maximum time to wait for the clipboard owner to send its contents
+const CLIPBOARD_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// A trigger phrase and the text it expands to
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Snippet {
+    /// Spoken phrase that inserts the snippet (case-insensitive)
+    pub trigger: String,
+    /// Text to insert, may contain placeholders and span multiple lines
+    pub text: String,
+}
+
+/// Named snippets, as stored in the snippets file
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct SnippetLibrary {
+    snippets: BTreeMap<String, Snippet>,
+}
+
+impl SnippetLibrary {
+    /// Load the snippets file, returning an empty library if it doesn't exist
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => Self::parse(&contents),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Load the snippets configured in `[snippets]`
+    ///
+    /// Returns None when snippets are disabled or the file can't be read.
+    pub fn from_config(config: &SnippetsConfig) -> Option<Self> {
+        if !config.enabled {
+            return None;
+        }
+        let path = config.resolve_path()?;
+        match Self::load(&path) {
+            Ok(library) => Some(library),
+            Err(e) => {
+                tracing::warn!("Failed to read snippets file {:?}: {}", path, e);
+                None
+            }
+        }
+    }
+
+    /// Parse snippets file contents
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        toml::from_str(contents).map_err(|e| e.to_string())
+    }
+
+    /// Write the snippets file, creating parent directories as needed
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let body = toml::to_string(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        std::fs::write(path, format!("# Voxtype snippets\n\n{}", body))
+    }
+
+    /// Add or replace a snippet, returning the snippet it replaced
+    pub fn insert(&mut self, name: &str, snippet: Snippet) -> Option<Snippet> {
+        self.snippets.insert(name.to_string(), snippet)
+    }
+
+    /// Remove a snippet by name, returning false if it wasn't present
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.snippets.remove(name).is_some()
+    }
+
+    /// Snippets by name, in name order
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Snippet)> {
+        self.snippets.iter()
+    }
+
+    /// Number of snippets
+    pub fn len(&self) -> usize {
+        self.snippets.len()
+    }
+
+    /// Check whether the library has no snippets
+    pub fn is_empty(&self) -> bool {
+        self.snippets.is_empty()
+    }
+}
+
+/// Replaces trigger phrases with snippet text
+pub struct SnippetExpander {
+    /// Trigger pattern and snippet text, longest trigger first
+    snippets: Vec<(Regex, String)>,
+}
+
+impl SnippetExpander {
+    pub fn new(library: &SnippetLibrary) -> Self {
+        let mut snippets: Vec<(&str, &str)> = library
+            .iter()
+            .map(|(_, s)| (s.trigger.trim(), s.text.as_str()))
+            .filter(|(trigger, _)| !trigger.is_empty())
+            .collect();
+        snippets.sort_by_key(|(trigger, _)| std::cmp::Reverse(trigger.len()));
+
+        let snippets = snippets
+            .into_iter()
+            .filter_map(|(trigger, text)| {
+                // Whisper may capitalize the trigger, put commas between its
+                // words and end it with a period
+                let words: Vec<String> = trigger.split_whitespace().map(regex::escape).collect();
+                let pattern = format!(r"(?i)\b{}\b[.!?]?", words.join(r"[\s,]+"));
+                match Regex::new(&pattern) {
+                    Ok(re) => Some((re, text.to_string())),
+                    Err(e) => {
+                        tracing::warn!("Invalid snippet trigger {:?}: {}", trigger, e);
+                        None
+                    }
+                }
+            })
+            .collect();
+
+        Self { snippets }
+    }
+
+    /// Expand triggers in text, filling placeholders from the system
+    pub fn expand(&self, text: &str) -> String {
+        self.expand_with(text, &mut system_placeholder)
+    }
+
+    /// Expand triggers in text, filling placeholders with `resolve`
+    /// Placeholders `resolve` doesn't know are left as written
+    pub fn expand_with(
+        &self,
+        text: &str,
+        resolve: &mut dyn FnMut(&str, Option<&str>) -> Option<String>,
+    ) -> String {
+        let mut result = text.to_string();
+        for (trigger, template) in &self.snippets {
+            if trigger.is_match(&result) {
+                let expansion = fill_placeholders(template, resolve);
+                result = trigger
+                    .replace_all(&result, regex::NoExpand(&expansion))
+                    .into_owned();
+            }
+        }
+        result
+    }
+}
+
+/// Replace `{name}` and `{name:argument}` placeholders
+fn fill_placeholders(
+    template: &str,
+    resolve: &mut dyn FnMut(&str, Option<&str>) -> Option<String>,
+) -> String {
+    static PLACEHOLDER: OnceLock<Regex> = OnceLock::new();
+    let re = PLACEHOLDER.get_or_init(|| {
+        Regex::new(r"\{([a-z]+)(?::([^{}\n]*))?\}").expect("valid placeholder regex")
+    });
+
+    re.replace_all(template, |caps: &regex::Captures| {
+        let argument = caps.get(2).map(|m| m.as_str());
+        resolve(&caps[1], argument).unwrap_or_else(|| caps[0].to_string())
+    })
+    .into_owned()
+}
+
+/// Fill a placeholder from the clock, clipboard or environment
+fn system_placeholder(name: &str, argument: Option<&str>) -> Option<String> {
+    match (name, argument) {
+        ("date", None) => format_local_time("%Y-%m-%d"),
+        ("time", None) => format_local_time("%H:%M"),
+        ("datetime", None) => format_local_time("%Y-%m-%d %H:%M"),
+        ("date" | "time" | "datetime", Some(format)) => format_local_time(format),
+        ("clipboard", None) => read_clipboard(),
+        // Unset variables expand to nothing, like in a shell
+        ("env", Some(variable)) => Some(std::env::var(variable).unwrap_or_default()),
+        _ => None,
+    }
+}
+
+/// Format the current local time with a strftime format
+fn format_local_time(format: &str) -> Option<String> {
+    let format = std::ffi::CString::new(format).ok()?;
+    let mut buffer = [0u8; 256];
+    // SAFETY: localtime_r fills the zeroed tm we own, and strftime writes at
+    // most buffer.len() bytes into our buffer
+    let written = unsafe {
+        let now = libc::time(std::ptr::null_mut());
+        let mut tm: libc::tm = std::mem::zeroed();
+        if libc::localtime_r(&now, &mut tm).is_null() {
+            return None;
+        }
+        libc::strftime(
+            buffer.as_mut_ptr() as *mut libc::c_char,
+            buffer.len(),
+            format.as_ptr(),
+            &tm,
+        )
+    };
+    Some(String::from_utf8_lossy(&buffer[..written]).into_owned())
+}
+
+/// Read the clipboard with wl-paste (Wayland) or xclip (X11)
+fn read_clipboard() -> Option<String> {
+    let readers: [(&str, &[&str]); 2] = [
+        ("wl-paste", &["--no-newline"]),
+        ("xclip", &["-selection", "clipboard", "-o"]),
+    ];
+    for (program, args) in readers {
+        if let Some(stdout) = read_output(program, args) {
+            return Some(String::from_utf8_lossy(&stdout).into_owned());
+        }
+    }
+    tracing::warn!("Could not read clipboard for snippet (install wl-clipboard or xclip)");
+    Some(String::new())
+}
+
+/// Run a clipboard reader, returning its stdout if it succeeds in time
+/// A clipboard owner that never answers would otherwise hold up the transcription
+fn read_output(program: &str, args: &[&str]) -> Option<Vec<u8>> {
+    let mut child = match Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            tracing::debug!("{} unavailable: {}", program, e);
+            return None;
+        }
+    };
+
+    let mut stdout = child.stdout.take()?;
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut data = Vec::new();
+        let _ = tx.send(stdout.read_to_end(&mut data).map(|_| data));
+    });
+
+    match rx.recv_timeout(CLIPBOARD_TIMEOUT) {
+        Ok(Ok(data)) => match child.wait() {
+            Ok(status) if status.success() => Some(data),
+            _ => None,
+        },
+        Ok(Err(e)) => {
+            tracing::debug!("{} failed: {}", program, e);
+            let _ = child.kill();
+            let _ = child.wait();
+            None
+        }
+        Err(_) => {
+            tracing::debug!("{} {} timed out", program, args.join(" "));
+            // Killing the reader closes its stdout, which ends the reading thread
+            let _ = child.kill();
+            let _ = child.wait();
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn library() -> SnippetLibrary {
+        SnippetLibrary::parse(
+            r#"
+[signature]
+trigger = "insert signature"
+text = """
+Best regards,
+Jane ({env:TEAM})"""
+
+[reply]
+trigger = "standard reply"
+text = "Thanks, I'll get back to you by {date:%A}."
+
+[function]
+trigger = "rust function"
+text = "fn {clipboard}() {\n}"
+"#,
+        )
+        .unwrap()
+    }
+
+    fn resolve(name: &str, argument: Option<&str>) -> Option<String> {
+        match (name, argument) {
+            ("env", Some("TEAM")) => Some("Platform".to_string()),
+            ("date", Some("%A")) => Some("Friday".to_string()),
+            ("clipboard", None) => Some("parse_args".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_parse_library() {
+        let library = library();
+        assert_eq!(library.len(), 3);
+        let names: Vec<&String> = library.iter().map(|(name, _)| name).collect();
+        assert_eq!(names, ["function", "reply", "signature"]);
+        assert!(SnippetLibrary::parse("[broken]\ntext = 1").is_err());
+    }
+
+    #[test]
+    fn test_expand_trigger() {
+        let expander = SnippetExpander::new(&library());
+        assert_eq!(
+            expander.expand_with("Thanks for the update. Insert signature.", &mut resolve),
+            "Thanks for the update. Best regards,\nJane (Platform)"
+        );
+        assert_eq!(
+            expander.expand_with("Standard, reply", &mut resolve),
+            "Thanks, I'll get back to you by Friday."
+        );
+        assert_eq!(
+            expander.expand_with("no trigger here", &mut resolve),
+            "no trigger here"
+        );
+    }
+
+    #[test]
+    fn test_placeholders() {
+        let expander = SnippetExpander::new(&library());
+        // Braces that aren't placeholders are kept
+        assert_eq!(
+            expander.expand_with("rust function", &mut resolve),
+            "fn parse_args() {\n}"
+        );
+        assert_eq!(
+            fill_placeholders("{unknown} {env:MISSING} $1", &mut resolve),
+            "{unknown} {env:MISSING} $1"
+        );
+        assert_eq!(format_local_time("%Y").unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("snippets.toml");
+        let mut library = library();
+        assert!(library.remove("reply"));
+        assert!(!library.remove("reply"));
+        library.insert(
+            "addr",
+            Snippet {
+                trigger: "my address".to_string(),
+                text: "1 Main St\nSpringfield".to_string(),
+            },
+        );
+        library.save(&path).unwrap();
+        assert_eq!(SnippetLibrary::load(&path).unwrap(), library);
+        assert!(SnippetLibrary::load(&dir.path().join("missing.toml"))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_read_output_times_out() {
+        assert_eq!(read_output("echo", &["hi"]).as_deref(), Some(&b"hi\n"[..]));
+        assert_eq!(read_output("false", &[]), None);
+
+        let started = std::time::Instant::now();
+        assert_eq!(read_output("sleep", &["10"]), None);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,453 @@
+//! Custom vocabulary support
+//!
+//! A vocabulary is a plain-text list of domain terms (one per line, `#` starts
+//! a comment). Terms are used in two places:
+//! - Injected into the Whisper initial prompt to bias recognition
+//! - Fuzzy-matched against the transcription to fix near-miss spellings
+
+use crate::config::VocabularyConfig;
+use std::path::Path;
+
+/// Terms shorter than this (normalized) are only case-corrected, never fuzzy-matched
+/// One edit is too much for short terms: "rust" is close to "trust" and "crust"
+const MIN_FUZZY_TERM_LEN: usize = 6;
+
+/// A list of custom vocabulary terms
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Vocabulary {
+    terms: Vec<String>,
+}
+
+impl Vocabulary {
+    /// Create a vocabulary from a list of terms (duplicates are dropped)
+    pub fn new<I, S>(terms: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut vocab = Self::default();
+        for term in terms {
+            vocab.add(&term.into());
+        }
+        vocab
+    }
+
+    /// Load the vocabulary file, returning an empty vocabulary if it doesn't exist
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => Ok(Self::parse(&contents)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Load the vocabulary configured in `[vocabulary]`
+    ///
+    /// Returns None when vocabulary support is disabled or the file can't be read.
+    pub fn from_config(config: &VocabularyConfig) -> Option<Self> {
+        if !config.enabled {
+            return None;
+        }
+        let path = config.resolve_path()?;
+        match Self::load(&path) {
+            Ok(vocab) => Some(vocab),
+            Err(e) => {
+                tracing::warn!("Failed to read vocabulary file {:?}: {}", path, e);
+                None
+            }
+        }
+    }
+
+    /// Parse vocabulary file contents
+    pub fn parse(contents: &str) -> Self {
+        Self::new(
+            contents
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#')),
+        )
+    }
+
+    /// Write the vocabulary file, creating parent directories as needed
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut contents = String::from("# Voxtype vocabulary, one term per line\n");
+        for term in &self.terms {
+            contents.push_str(term);
+            contents.push('\n');
+        }
+        std::fs::write(path, contents)
+    }
+
+    /// Add a term, returning false if it is empty or already present
+    pub fn add(&mut self, term: &str) -> bool {
+        let term = term.trim();
+        if term.is_empty() || self.contains(term) {
+            return false;
+        }
+        self.terms.push(term.to_string());
+        true
+    }
+
+    /// Remove a term (case-insensitive), returning false if it wasn't present
+    pub fn remove(&mut self, term: &str) -> bool {
+        let term = term.trim().to_lowercase();
+        let before = self.terms.len();
+        self.terms.retain(|t| t.to_lowercase() != term);
+        self.terms.len() != before
+    }
+
+    /// Check whether a term is present (case-insensitive)
+    pub fn contains(&self, term: &str) -> bool {
+        let term = term.trim().to_lowercase();
+        self.terms.iter().any(|t| t.to_lowercase() == term)
+    }
+
+    /// All terms in file order
+    pub fn terms(&self) -> &[String] {
+        &self.terms
+    }
+
+    /// Check whether the vocabulary has no terms
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Build an initial prompt with vocabulary terms appended to `base`
+    ///
+    /// Terms are added in file order until the approximate token budget is
+    /// reached. Returns the base prompt unchanged if no term fits.
+    pub fn build_prompt(&self, base: Option<&str>, max_tokens: usize) -> Option<String> {
+        let base = base.map(str::trim).filter(|b| !b.is_empty());
+
+        let mut prompt = base.map(str::to_string).unwrap_or_default();
+        let mut added = 0;
+        for term in &self.terms {
+            let separator = match (added, prompt.is_empty()) {
+                (0, true) => "",
+                (0, false) => " ",
+                _ => ", ",
+            };
+            let candidate = format!("{}{}{}", prompt, separator, term);
+            // +1 accounts for the closing period
+            if estimate_tokens(&candidate) + 1 > max_tokens {
+                break;
+            }
+            prompt = candidate;
+            added += 1;
+        }
+
+        if added == 0 {
+            return base.map(str::to_string);
+        }
+        prompt.push('.');
+        Some(prompt)
+    }
+}
+
+/// Most prompt tokens Whisper uses; it drops the start of longer prompts
+pub const WHISPER_MAX_PROMPT_TOKENS: usize = 224;
+
+/// Rough token estimate for Whisper's BPE tokenizer
+///
+/// Domain terms tokenize poorly, so this errs on the high side (~3 chars per token).
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(3)
+}
+
+/// Corrects near-miss spellings of vocabulary terms in transcribed text
+pub struct VocabularyCorrector {
+    /// (term as written, normalized form, word count)
+    terms: Vec<(String, String, usize)>,
+    /// Minimum similarity for a fuzzy match
+    threshold: f32,
+}
+
+impl VocabularyCorrector {
+    /// Create a corrector for the given vocabulary
+    pub fn new(vocabulary: &Vocabulary, threshold: f32) -> Self {
+        let terms = vocabulary
+            .terms()
+            .iter()
+            .filter_map(|term| {
+                let normalized = normalize(term);
+                if normalized.is_empty() {
+                    return None;
+                }
+                let words = term.split_whitespace().count().max(1);
+                Some((term.clone(), normalized, words))
+            })
+            .collect();
+
+        Self {
+            terms,
+            threshold: threshold.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Replace word sequences that closely match a vocabulary term
+    pub fn correct(&self, text: &str) -> String {
+        if self.terms.is_empty() {
+            return text.to_string();
+        }
+
+        let tokens = tokenize(text);
+        let normalized: Vec<String> = tokens
+            .iter()
+            .map(|&(s, e)| normalize(&text[s..e]))
+            .collect();
+
+        let mut result = String::with_capacity(text.len());
+        let mut last = 0;
+        let mut i = 0;
+
+        while i < tokens.len() {
+            let Some((term, len)) = self.best_match(&normalized[i..]) else {
+                i += 1;
+                continue;
+            };
+
+            let (start, first_end) = tokens[i];
+            let (last_start, end) = tokens[i + len - 1];
+
+            result.push_str(&text[last..start]);
+            result.push_str(leading_punctuation(&text[start..first_end]));
+            result.push_str(term);
+            result.push_str(trailing_punctuation(&text[last_start..end]));
+            last = end;
+            i += len;
+        }
+
+        result.push_str(&text[last..]);
+        result
+    }
+
+    /// Find the best term matching a window starting at the first word
+    ///
+    /// Returns the term and the number of words it replaces.
+    fn best_match(&self, words: &[String]) -> Option<(&str, usize)> {
+        let mut best: Option<(&str, usize, f32)> = None;
+
+        for (term, term_norm, term_words) in &self.terms {
+            let fuzzy = term_norm.chars().count() >= MIN_FUZZY_TERM_LEN;
+            let min_len = if fuzzy {
+                term_words.saturating_sub(1).max(1)
+            } else {
+                *term_words
+            };
+            let max_len = if fuzzy { term_words + 1 } else { *term_words };
+
+            for len in min_len..=max_len.min(words.len()) {
+                let window = &words[..len];
+                if window.iter().any(|w| w.is_empty()) {
+                    break;
+                }
+                let joined: String = window.concat();
+                let score = if fuzzy {
+                    similarity(&joined, term_norm)
+                } else if joined == *term_norm {
+                    1.0
+                } else {
+                    0.0
+                };
+
+                if score < self.threshold {
+                    continue;
+                }
+                let better = match best {
+                    None => true,
+                    Some((_, best_len, best_score)) => {
+                        score > best_score || (score == best_score && len > best_len)
+                    }
+                };
+                if better {
+                    best = Some((term.as_str(), len, score));
+                }
+            }
+        }
+
+        best.map(|(term, len, _)| (term, len))
+    }
+}
+
+/// Byte ranges of whitespace-separated tokens
+fn tokenize(text: &str) -> Vec<(usize, usize)> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    for (idx, ch) in text.char_indices() {
+        match (ch.is_whitespace(), start) {
+            (true, Some(s)) => {
+                tokens.push((s, idx));
+                start = None;
+            }
+            (false, None) => start = Some(idx),
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        tokens.push((s, text.len()));
+    }
+    tokens
+}
+
+/// Lowercase alphanumeric characters only
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Non-alphanumeric prefix of a token (e.g., an opening quote)
+fn leading_punctuation(token: &str) -> &str {
+    let end = token
+        .find(|c: char| c.is_alphanumeric())
+        .unwrap_or(token.len());
+    &token[..end]
+}
+
+/// Non-alphanumeric suffix of a token (e.g., a trailing comma or period)
+fn trailing_punctuation(token: &str) -> &str {
+    let start = token
+        .char_indices()
+        .rev()
+        .find(|(_, c)| c.is_alphanumeric())
+        .map(|(i, c)| i + c.len_utf8())
+        .unwrap_or(0);
+    &token[start..]
+}
+
+/// Normalized Levenshtein similarity (1.0 = identical)
+fn similarity(a: &str, b: &str) -> f32 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let max_len = a.len().max(b.len());
+    if max_len == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein(&a, &b) as f32 / max_len as f32
+}
+
+/// Levenshtein edit distance over any sequence (characters, words)
+pub(crate) fn levenshtein<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+
+    for (i, ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_skips_comments_and_duplicates() {
+        let vocab = Vocabulary::parse("# header\nKubernetes\n\n  PyTorch  \nkubernetes\n");
+        assert_eq!(vocab.terms(), &["Kubernetes", "PyTorch"]);
+    }
+
+    #[test]
+    fn test_add_remove() {
+        let mut vocab = Vocabulary::default();
+        assert!(vocab.add("Tokio"));
+        assert!(!vocab.add("tokio"));
+        assert!(!vocab.add("   "));
+        assert!(vocab.contains("TOKIO"));
+        assert!(vocab.remove("tokio"));
+        assert!(!vocab.remove("tokio"));
+        assert!(vocab.is_empty());
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("vocabulary.txt");
+
+        assert!(Vocabulary::load(&path).unwrap().is_empty());
+
+        let vocab = Vocabulary::new(["Voxtype", "Hyprland"]);
+        vocab.save(&path).unwrap();
+        assert_eq!(Vocabulary::load(&path).unwrap(), vocab);
+    }
+
+    #[test]
+    fn test_build_prompt() {
+        let vocab = Vocabulary::new(["Voxtype", "Hyprland"]);
+        assert_eq!(
+            vocab.build_prompt(None, 100).as_deref(),
+            Some("Voxtype, Hyprland.")
+        );
+        assert_eq!(
+            vocab.build_prompt(Some("Technical notes."), 100).as_deref(),
+            Some("Technical notes. Voxtype, Hyprland.")
+        );
+    }
+
+    #[test]
+    fn test_build_prompt_respects_token_budget() {
+        let vocab = Vocabulary::new(["Voxtype", "Hyprland"]);
+        // "Voxtype" is ~3 tokens, plus the period
+        assert_eq!(vocab.build_prompt(None, 4).as_deref(), Some("Voxtype."));
+        assert_eq!(vocab.build_prompt(None, 1), None);
+        assert_eq!(vocab.build_prompt(Some("Base"), 1).as_deref(), Some("Base"));
+    }
+
+    #[test]
+    fn test_correct_near_miss() {
+        let vocab = Vocabulary::new(["Kubernetes", "PostgreSQL"]);
+        let corrector = VocabularyCorrector::new(&vocab, 0.8);
+
+        assert_eq!(
+            corrector.correct("deploy it to kubernetis, then restart"),
+            "deploy it to Kubernetes, then restart"
+        );
+        assert_eq!(
+            corrector.correct("the postgre sql database"),
+            "the PostgreSQL database"
+        );
+    }
+
+    #[test]
+    fn test_correct_preserves_unrelated_text() {
+        let vocab = Vocabulary::new(["Kubernetes"]);
+        let corrector = VocabularyCorrector::new(&vocab, 0.8);
+
+        let text = "line one\nline two, with  spacing.";
+        assert_eq!(corrector.correct(text), text);
+    }
+
+    #[test]
+    fn test_short_terms_only_case_corrected() {
+        let vocab = Vocabulary::new(["GTK"]);
+        let corrector = VocabularyCorrector::new(&vocab, 0.5);
+
+        assert_eq!(corrector.correct("uses gtk."), "uses GTK.");
+        assert_eq!(corrector.correct("uses gtx"), "uses gtx");
+
+        // Common words one edit away from a short term are left alone
+        let vocab = Vocabulary::new(["Rust"]);
+        let corrector = VocabularyCorrector::new(&vocab, 0.8);
+        assert_eq!(corrector.correct("I trust you"), "I trust you");
+        assert_eq!(corrector.correct("rusty crust"), "rusty crust");
+        assert_eq!(corrector.correct("written in rust"), "written in Rust");
+    }
+
+    #[test]
+    fn test_similarity() {
+        assert_eq!(similarity("abc", "abc"), 1.0);
+        assert_eq!(similarity("", ""), 1.0);
+        assert!(similarity("kubernetis", "kubernetes") >= 0.9);
+        assert!(similarity("cat", "kubernetes") < 0.5);
+    }
+}
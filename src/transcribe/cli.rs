@@ -6,14 +6,16 @@
 //!
 //! The whisper-cli binary must be installed separately or built from whisper.cpp.
 
-use super::Transcriber;
-use crate::config::{Config, WhisperConfig};
+use super::whisper::resolve_model_path;
+use super::{Transcriber, TranscriptionOverrides};
+use crate::config::WhisperConfig;
 use crate::error::TranscribeError;
 use serde::Deserialize;
 use std::path::PathBuf;
 use std::process::{Command, Stdio};
 
 /// CLI-based transcriber using whisper-cli subprocess
+#[derive(Clone)]
 pub struct CliTranscriber {
     /// Path to whisper-cli binary
     cli_path: PathBuf,
@@ -234,6 +236,29 @@ impl Transcriber for CliTranscriber {
 
         Ok(text)
     }
+
+    fn transcribe_with_overrides(
+        &self,
+        samples: &[f32],
+        overrides: &TranscriptionOverrides,
+    ) -> Result<String, TranscribeError> {
+        if overrides.is_empty() {
+            return self.transcribe(samples);
+        }
+
+        let mut effective = self.clone();
+        if let Some(ref language) = overrides.language {
+            // whisper-cli takes a single language, use the primary one
+            effective.language = language.primary().to_string();
+        }
+        if let Some(ref prompt) = overrides.initial_prompt {
+            effective.initial_prompt = Some(prompt.clone());
+        }
+        if let Some(translate) = overrides.translate {
+            effective.translate = translate;
+        }
+        effective.transcribe(samples)
+    }
 }
 
 /// Resolve whisper-cli path
@@ -276,66 +301,6 @@ fn resolve_cli_path(configured_path: Option<&str>) -> Result<PathBuf, Transcribe
     ))
 }
 
-/// Resolve model name to file path
-fn resolve_model_path(model: &str) -> Result<PathBuf, TranscribeError> {
-    // If it's already an absolute path, use it directly
-    let path = PathBuf::from(model);
-    if path.is_absolute() && path.exists() {
-        return Ok(path);
-    }
-
-    // Map model names to file names
-    let model_filename = match model {
-        "tiny" => "ggml-tiny.bin",
-        "tiny.en" => "ggml-tiny.en.bin",
-        "base" => "ggml-base.bin",
-        "base.en" => "ggml-base.en.bin",
-        "small" => "ggml-small.bin",
-        "small.en" => "ggml-small.en.bin",
-        "medium" => "ggml-medium.bin",
-        "medium.en" => "ggml-medium.en.bin",
-        "large" | "large-v1" => "ggml-large-v1.bin",
-        "large-v2" => "ggml-large-v2.bin",
-        "large-v3" => "ggml-large-v3.bin",
-        "large-v3-turbo" => "ggml-large-v3-turbo.bin",
-        other if other.ends_with(".bin") => other,
-        other => {
-            return Err(TranscribeError::ModelNotFound(format!(
-                "Unknown model: '{}'. Valid models: tiny, base, small, medium, large-v3, large-v3-turbo",
-                other
-            )));
-        }
-    };
-
-    // Look in the data directory
-    let models_dir = Config::models_dir();
-    let model_path = models_dir.join(model_filename);
-
-    if model_path.exists() {
-        return Ok(model_path);
-    }
-
-    // Also check current directory
-    let cwd_path = PathBuf::from(model_filename);
-    if cwd_path.exists() {
-        return Ok(cwd_path);
-    }
-
-    // Also check ./models/
-    let local_models_path = PathBuf::from("models").join(model_filename);
-    if local_models_path.exists() {
-        return Ok(local_models_path);
-    }
-
-    Err(TranscribeError::ModelNotFound(format!(
-        "Model '{}' not found. Looked in:\n  - {}\n  - {}\n  - {}",
-        model,
-        model_path.display(),
-        cwd_path.display(),
-        local_models_path.display()
-    )))
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,611 @@
+//! External speech-to-text engine over a JSON-lines protocol
+//!
+//! Runs any recognizer as a long-lived child process. Voxtype and the engine
+//! exchange one JSON object per line over stdin/stdout; the engine's stderr
+//! is forwarded to the debug log. See docs/EXTERNAL_ENGINE.md for the full
+//! protocol.
+//!
+//! Protocol summary:
+//! 1. Voxtype sends `hello` (protocol version, audio format, model)
+//! 2. Engine answers `ready` with its name and capabilities
+//! 3. For each recording: `start`, one or more `audio` frames
+//!    (base64 16-bit little-endian PCM, mono, 16kHz), then `end`
+//! 4. Engine answers `result` (text and optional segments) or `error`,
+//!    optionally preceded by `partial` results
+//! 5. Voxtype sends `shutdown` before stopping the engine
+//!
+//! The process is restarted on the next transcription if it exits or stops
+//! responding.
+
+use super::{Transcriber, TranscriptionOverrides};
+use crate::config::{ExternalConfig, LanguageConfig};
+use crate::error::TranscribeError;
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Protocol version spoken by this implementation
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Audio encoding of `audio` frames
+pub const AUDIO_ENCODING: &str = "pcm_s16le_base64";
+
+/// Samples per `audio` frame (one second at 16kHz)
+const FRAME_SAMPLES: usize = 16000;
+
+/// Message sent from voxtype to the engine
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Request<'a> {
+    Hello {
+        protocol: u32,
+        sample_rate: u32,
+        channels: u16,
+        encoding: &'static str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        model: Option<&'a str>,
+    },
+    Start {
+        id: u64,
+        language: &'a LanguageConfig,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        initial_prompt: Option<&'a str>,
+        translate: bool,
+    },
+    Audio {
+        id: u64,
+        data: String,
+    },
+    End {
+        id: u64,
+    },
+    Shutdown,
+}
+
+/// Message sent from the engine to voxtype
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Response {
+    Ready {
+        #[serde(default)]
+        name: Option<String>,
+        #[serde(default)]
+        protocol: Option<u32>,
+        #[serde(default)]
+        capabilities: Capabilities,
+    },
+    Partial {
+        id: u64,
+        #[serde(default)]
+        text: String,
+    },
+    Result {
+        id: u64,
+        #[serde(default)]
+        text: String,
+        #[serde(default)]
+        segments: Vec<Segment>,
+    },
+    Error {
+        #[serde(default)]
+        id: Option<u64>,
+        message: String,
+    },
+    /// Unknown message types are ignored for forward compatibility
+    #[serde(other)]
+    Unknown,
+}
+
+/// Features the engine reports in its `ready` message
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Supported language codes (empty = any)
+    #[serde(default)]
+    pub languages: Vec<String>,
+    /// Engine uses `initial_prompt`
+    #[serde(default)]
+    pub initial_prompt: bool,
+    /// Engine can translate to English
+    #[serde(default)]
+    pub translate: bool,
+    /// Engine sends `partial` results while audio arrives
+    #[serde(default)]
+    pub partial_results: bool,
+}
+
+/// A timed piece of the transcription
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct Segment {
+    /// Start time in seconds
+    #[serde(default)]
+    pub start: f32,
+    /// End time in seconds
+    #[serde(default)]
+    pub end: f32,
+    pub text: String,
+}
+
+/// A running engine process that completed the handshake
+struct EngineProcess {
+    child: Child,
+    stdin: ChildStdin,
+    lines: Receiver<String>,
+    name: String,
+    capabilities: Capabilities,
+}
+
+impl EngineProcess {
+    /// Spawn the engine and wait for its `ready` message
+    fn spawn(config: &ExternalConfig) -> Result<Self, TranscribeError> {
+        let mut child = Command::new(&config.command)
+            .args(&config.args)
+            .envs(&config.env)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| {
+                TranscribeError::InitFailed(format!(
+                    "Failed to start external engine '{}': {}",
+                    config.command, e
+                ))
+            })?;
+
+        let stdin = child.stdin.take().ok_or_else(|| {
+            TranscribeError::InitFailed("External engine stdin not available".to_string())
+        })?;
+        let stdout = child.stdout.take().ok_or_else(|| {
+            TranscribeError::InitFailed("External engine stdout not available".to_string())
+        })?;
+
+        // Read stdout on a thread so responses can be awaited with a timeout
+        let (tx, lines) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else { break };
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        if let Some(stderr) = child.stderr.take() {
+            let command = config.command.clone();
+            std::thread::spawn(move || {
+                for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                    tracing::debug!("[{}] {}", command, line);
+                }
+            });
+        }
+
+        let mut process = Self {
+            child,
+            stdin,
+            lines,
+            name: config.command.clone(),
+            capabilities: Capabilities::default(),
+        };
+
+        process.send(&Request::Hello {
+            protocol: PROTOCOL_VERSION,
+            sample_rate: 16000,
+            channels: 1,
+            encoding: AUDIO_ENCODING,
+            model: config.model.as_deref(),
+        })?;
+
+        let deadline = Instant::now() + Duration::from_secs(config.startup_timeout_secs);
+        loop {
+            match process
+                .recv(deadline)
+                .map_err(TranscribeError::InitFailed)?
+            {
+                Response::Ready {
+                    name,
+                    protocol,
+                    capabilities,
+                } => {
+                    if let Some(protocol) = protocol.filter(|p| *p != PROTOCOL_VERSION) {
+                        return Err(TranscribeError::InitFailed(format!(
+                            "External engine speaks protocol {}, voxtype supports {}",
+                            protocol, PROTOCOL_VERSION
+                        )));
+                    }
+                    if let Some(name) = name {
+                        process.name = name;
+                    }
+                    process.capabilities = capabilities;
+                    return Ok(process);
+                }
+                Response::Error { message, .. } => {
+                    return Err(TranscribeError::InitFailed(format!(
+                        "External engine failed to start: {}",
+                        message
+                    )));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Write one message to the engine
+    fn send(&mut self, request: &Request) -> Result<(), TranscribeError> {
+        let line = serde_json::to_string(request)
+            .map_err(|e| TranscribeError::InferenceFailed(e.to_string()))?;
+        writeln!(self.stdin, "{}", line)
+            .and_then(|_| self.stdin.flush())
+            .map_err(|e| {
+                TranscribeError::InferenceFailed(format!(
+                    "Failed to write to external engine: {}",
+                    e
+                ))
+            })
+    }
+
+    /// Wait for the next message from the engine
+    fn recv(&self, deadline: Instant) -> Result<Response, String> {
+        loop {
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            let line = match self.lines.recv_timeout(timeout) {
+                Ok(line) => line,
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(format!("External engine '{}' timed out", self.name))
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(format!("External engine '{}' exited", self.name))
+                }
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(&line) {
+                Ok(response) => return Ok(response),
+                Err(e) => tracing::warn!(
+                    "Ignoring invalid message from external engine: {} ({:?})",
+                    e,
+                    line
+                ),
+            }
+        }
+    }
+
+    /// Check whether the process is still running
+    fn is_alive(&mut self) -> bool {
+        matches!(self.child.try_wait(), Ok(None))
+    }
+}
+
+impl Drop for EngineProcess {
+    fn drop(&mut self) {
+        let _ = self.send(&Request::Shutdown);
+
+        // Give the engine a moment to exit cleanly before killing it
+        for _ in 0..20 {
+            if !matches!(self.child.try_wait(), Ok(None)) {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Transcriber backed by an external engine process
+pub struct ExternalTranscriber {
+    config: ExternalConfig,
+    process: Mutex<Option<EngineProcess>>,
+    next_id: AtomicU64,
+}
+
+impl ExternalTranscriber {
+    /// Start the external engine and complete the handshake
+    pub fn new(config: &ExternalConfig) -> Result<Self, TranscribeError> {
+        if config.command.trim().is_empty() {
+            return Err(TranscribeError::ConfigError(
+                "[external] command is not set".to_string(),
+            ));
+        }
+
+        let process = EngineProcess::spawn(config)?;
+        tracing::info!(
+            "External engine '{}' ready (capabilities: {:?})",
+            process.name,
+            process.capabilities
+        );
+
+        Ok(Self {
+            config: config.clone(),
+            process: Mutex::new(Some(process)),
+            next_id: AtomicU64::new(1),
+        })
+    }
+
+    /// Send one recording to the engine and wait for its result
+    fn run(
+        &self,
+        process: &mut EngineProcess,
+        samples: &[f32],
+        overrides: &TranscriptionOverrides,
+    ) -> Result<String, TranscribeError> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let language = overrides.language.as_ref().unwrap_or(&self.config.language);
+        let initial_prompt = overrides
+            .initial_prompt
+            .as_deref()
+            .or(self.config.initial_prompt.as_deref());
+        let translate = overrides.translate.unwrap_or(self.config.translate);
+
+        warn_unsupported(&process.capabilities, language, initial_prompt, translate);
+
+        process.send(&Request::Start {
+            id,
+            language,
+            initial_prompt,
+            translate,
+        })?;
+        for frame in samples.chunks(FRAME_SAMPLES) {
+            process.send(&Request::Audio {
+                id,
+                data: encode_pcm_s16le(frame),
+            })?;
+        }
+        process.send(&Request::End { id })?;
+
+        let deadline = Instant::now() + Duration::from_secs(self.config.timeout_secs);
+        loop {
+            match process
+                .recv(deadline)
+                .map_err(TranscribeError::InferenceFailed)?
+            {
+                Response::Result {
+                    id: result_id,
+                    text,
+                    segments,
+                } if result_id == id => return Ok(result_text(text, &segments)),
+                Response::Error {
+                    id: error_id,
+                    message,
+                } if error_id.is_none_or(|error_id| error_id == id) => {
+                    return Err(TranscribeError::InferenceFailed(format!(
+                        "External engine error: {}",
+                        message
+                    )));
+                }
+                Response::Partial {
+                    id: partial_id,
+                    text,
+                } if partial_id == id => {
+                    tracing::trace!("Partial result: {:?}", text);
+                }
+                other => tracing::debug!("Ignoring external engine message: {:?}", other),
+            }
+        }
+    }
+}
+
+impl Transcriber for ExternalTranscriber {
+    fn transcribe(&self, samples: &[f32]) -> Result<String, TranscribeError> {
+        self.transcribe_with_overrides(samples, &TranscriptionOverrides::default())
+    }
+
+    fn transcribe_with_overrides(
+        &self,
+        samples: &[f32],
+        overrides: &TranscriptionOverrides,
+    ) -> Result<String, TranscribeError> {
+        let mut guard = self.process.lock().unwrap();
+
+        // Restart the engine if it exited since the last transcription
+        if !guard.as_mut().is_some_and(EngineProcess::is_alive) {
+            tracing::info!("Starting external engine '{}'", self.config.command);
+            *guard = None;
+            *guard = Some(EngineProcess::spawn(&self.config)?);
+        }
+        let process = guard.as_mut().expect("engine process was just started");
+
+        let start = Instant::now();
+        let result = self.run(process, samples, overrides);
+        if result.is_err() {
+            // The engine may be stuck or out of sync; start a fresh one next time
+            *guard = None;
+        } else {
+            tracing::info!(
+                "External transcription completed in {:.2}s",
+                start.elapsed().as_secs_f32()
+            );
+        }
+        result
+    }
+}
+
+/// Log request options the engine said it doesn't support
+fn warn_unsupported(
+    capabilities: &Capabilities,
+    language: &LanguageConfig,
+    initial_prompt: Option<&str>,
+    translate: bool,
+) {
+    if !capabilities.languages.is_empty() && !language.is_auto() {
+        for lang in language.as_vec() {
+            if !capabilities.languages.contains(&lang) {
+                tracing::warn!("External engine does not list language '{}'", lang);
+            }
+        }
+    }
+    if initial_prompt.is_some() && !capabilities.initial_prompt {
+        tracing::debug!("External engine does not support initial_prompt, it will be ignored");
+    }
+    if translate && !capabilities.translate {
+        tracing::warn!("External engine does not support translation");
+    }
+}
+
+/// Final text of a result, falling back to the joined segments
+fn result_text(text: String, segments: &[Segment]) -> String {
+    if text.trim().is_empty() && !segments.is_empty() {
+        segments
+            .iter()
+            .map(|s| s.text.trim())
+            .filter(|t| !t.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    } else {
+        text.trim().to_string()
+    }
+}
+
+/// Encode f32 samples as base64 16-bit little-endian PCM
+fn encode_pcm_s16le(samples: &[f32]) -> String {
+    let mut bytes = Vec::with_capacity(samples.len() * 2);
+    for &sample in samples {
+        let value = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    base64_encode(&bytes)
+}
+
+/// Standard base64 encoding with padding
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = ((b[0] as u32) << 16) | ((b[1] as u32) << 8) | b[2] as u32;
+        out.push(ALPHABET[(n >> 18) as usize & 63] as char);
+        out.push(ALPHABET[(n >> 12) as usize & 63] as char);
+        out.push(if chunk.len() > 1 {
+            ALPHABET[(n >> 6) as usize & 63] as char
+        } else {
+            '='
+        });
+        out.push(if chunk.len() > 2 {
+            ALPHABET[n as usize & 63] as char
+        } else {
+            '='
+        });
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64_encode() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn test_encode_pcm_s16le() {
+        // 1.0 -> 0x7fff, -1.0 -> 0x8001, 0.0 -> 0x0000
+        assert_eq!(
+            encode_pcm_s16le(&[1.0, -1.0, 0.0]),
+            base64_encode(&[0xff, 0x7f, 0x01, 0x80, 0, 0])
+        );
+    }
+
+    #[test]
+    fn test_request_serialization() {
+        let language = LanguageConfig::Single("en".to_string());
+        let start = serde_json::to_string(&Request::Start {
+            id: 3,
+            language: &language,
+            initial_prompt: None,
+            translate: false,
+        })
+        .unwrap();
+        assert_eq!(
+            start,
+            r#"{"type":"start","id":3,"language":"en","translate":false}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&Request::Shutdown).unwrap(),
+            r#"{"type":"shutdown"}"#
+        );
+    }
+
+    #[test]
+    fn test_response_parsing() {
+        let ready: Response = serde_json::from_str(
+            r#"{"type":"ready","name":"my-asr","protocol":1,"capabilities":{"languages":["en"],"translate":true}}"#,
+        )
+        .unwrap();
+        match ready {
+            Response::Ready {
+                name, capabilities, ..
+            } => {
+                assert_eq!(name.as_deref(), Some("my-asr"));
+                assert_eq!(capabilities.languages, vec!["en"]);
+                assert!(capabilities.translate);
+                assert!(!capabilities.initial_prompt);
+            }
+            other => panic!("unexpected response: {:?}", other),
+        }
+
+        let result: Response = serde_json::from_str(
+            r#"{"type":"result","id":1,"text":"","segments":[{"start":0.0,"end":1.0,"text":" hello "},{"start":1.0,"end":2.0,"text":"world"}]}"#,
+        )
+        .unwrap();
+        match result {
+            Response::Result { segments, text, .. } => {
+                assert_eq!(result_text(text, &segments), "hello world");
+            }
+            other => panic!("unexpected response: {:?}", other),
+        }
+
+        let unknown: Response = serde_json::from_str(r#"{"type":"progress","value":5}"#).unwrap();
+        assert!(matches!(unknown, Response::Unknown));
+    }
+
+    #[test]
+    fn test_missing_command_is_config_error() {
+        let result = ExternalTranscriber::new(&ExternalConfig::default());
+        assert!(matches!(result, Err(TranscribeError::ConfigError(_))));
+    }
+
+    #[test]
+    fn test_round_trip_with_shell_engine() {
+        // A minimal engine: answers the handshake, then one result per `end`
+        let script = r#"
+            read -r hello
+            echo '{"type":"ready","name":"sh-engine","capabilities":{"languages":["en"]}}'
+            while read -r line; do
+                case "$line" in
+                    *'"type":"end"'*)
+                        id=$(echo "$line" | sed 's/.*"id":\([0-9]*\).*/\1/')
+                        echo '{"type":"partial","id":'"$id"',"text":"hel"}'
+                        echo '{"type":"result","id":'"$id"',"text":"hello"}'
+                        ;;
+                    *'"type":"shutdown"'*) exit 0 ;;
+                esac
+            done
+        "#;
+        let config = ExternalConfig {
+            command: "sh".to_string(),
+            args: vec!["-c".to_string(), script.to_string()],
+            startup_timeout_secs: 5,
+            timeout_secs: 5,
+            ..Default::default()
+        };
+
+        let transcriber = ExternalTranscriber::new(&config).unwrap();
+        assert_eq!(transcriber.transcribe(&[0.0; 20000]).unwrap(), "hello");
+        // The process is reused for the next recording
+        assert_eq!(transcriber.transcribe(&[0.1; 100]).unwrap(), "hello");
+    }
+}
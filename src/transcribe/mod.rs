@@ -5,9 +5,11 @@
 //! - Remote OpenAI-compatible Whisper API (whisper.cpp server, OpenAI, etc.)
 //! - CLI subprocess using whisper-cli (fallback for glibc 2.42+ compatibility)
 //! - Subprocess isolation for GPU memory release
+//! - Any external recognizer speaking the JSON-lines engine protocol
 //! - Optionally NVIDIA Parakeet via ONNX Runtime (when `parakeet` feature is enabled)
 
 pub mod cli;
+pub mod external;
 pub mod remote;
 pub mod subprocess;
 pub mod whisper;
@@ -16,7 +18,9 @@ pub mod worker;
 #[cfg(feature = "parakeet")]
 pub mod parakeet;
 
-use crate::config::{Config, TranscriptionEngine, WhisperConfig, WhisperMode};
+use crate::config::{
+    Config, LanguageConfig, Profile, TranscriptionEngine, WhisperConfig, WhisperMode,
+};
 use crate::error::TranscribeError;
 use crate::setup::gpu;
 
@@ -37,6 +41,90 @@ pub trait Transcriber: Send + Sync {
     fn prepare(&self) {
         // Default: no-op
     }
+
+    /// Transcribe with per-recording overrides (language, initial prompt, translate)
+    ///
+    /// Default implementation ignores the overrides, for engines that have no
+    /// notion of language selection or prompting.
+    fn transcribe_with_overrides(
+        &self,
+        samples: &[f32],
+        overrides: &TranscriptionOverrides,
+    ) -> Result<String, TranscribeError> {
+        if !overrides.is_empty() {
+            tracing::warn!(
+                "Transcription engine does not support language/prompt/translate overrides, ignoring"
+            );
+        }
+        self.transcribe(samples)
+    }
+
+    /// Prepare for a transcription that will use the given overrides
+    ///
+    /// Default implementation ignores the overrides and calls `prepare()`.
+    fn prepare_with_overrides(&self, overrides: &TranscriptionOverrides) {
+        let _ = overrides;
+        self.prepare();
+    }
+
+    /// Language of the most recent transcription, if the engine knows it
+    ///
+    /// Used to pick language-specific text processing such as spoken
+    /// punctuation. Default implementation returns None.
+    fn last_language(&self) -> Option<String> {
+        None
+    }
+}
+
+/// Per-recording overrides for transcription settings
+///
+/// Populated from `voxtype record start --language/--initial-prompt/--translate`
+/// and from the active profile, then applied on top of the [whisper] config
+/// for a single recording.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TranscriptionOverrides {
+    /// Language override (single code, "auto", or allowed set)
+    pub language: Option<LanguageConfig>,
+    /// Initial prompt override
+    pub initial_prompt: Option<String>,
+    /// Translate-to-English override
+    pub translate: Option<bool>,
+}
+
+impl TranscriptionOverrides {
+    /// Check if no override is set
+    pub fn is_empty(&self) -> bool {
+        self.language.is_none() && self.initial_prompt.is_none() && self.translate.is_none()
+    }
+
+    /// Fill unset fields from a profile (explicit CLI flags take precedence)
+    pub fn with_profile_defaults(mut self, profile: &Profile) -> Self {
+        if self.language.is_none() {
+            self.language = profile.language.clone();
+        }
+        if self.initial_prompt.is_none() {
+            self.initial_prompt = profile.initial_prompt.clone();
+        }
+        if self.translate.is_none() {
+            self.translate = profile.translate;
+        }
+        self
+    }
+
+    /// Return a copy of the Whisper config with these overrides applied
+    pub fn apply_to(&self, config: &WhisperConfig) -> WhisperConfig {
+        let mut config = config.clone();
+        if let Some(ref language) = self.language {
+            config.language = language.clone();
+        }
+        if let Some(ref prompt) = self.initial_prompt {
+            config.initial_prompt = Some(prompt.clone());
+        }
+        if let Some(translate) = self.translate {
+            config.translate = translate;
+        }
+        config
+    }
 }
 
 /// Factory function to create transcriber based on configured engine
@@ -57,6 +145,16 @@ pub fn create_transcriber(config: &Config) -> Result<Box<dyn Transcriber>, Trans
             "Parakeet engine requested but voxtype was not compiled with --features parakeet"
                 .to_string(),
         )),
+        TranscriptionEngine::External => {
+            let external_config = config.external.as_ref().ok_or_else(|| {
+                TranscribeError::InitFailed(
+                    "External engine selected but [external] config section is missing".to_string(),
+                )
+            })?;
+            Ok(Box::new(external::ExternalTranscriber::new(
+                external_config,
+            )?))
+        }
     }
 }
 
@@ -104,3 +202,55 @@ pub fn create_transcriber_with_config_path(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overrides_default_is_empty() {
+        assert!(TranscriptionOverrides::default().is_empty());
+    }
+
+    #[test]
+    fn test_overrides_apply_to_config() {
+        let config = WhisperConfig {
+            language: LanguageConfig::Single("en".to_string()),
+            initial_prompt: Some("base prompt".to_string()),
+            translate: false,
+            ..Default::default()
+        };
+        let overrides = TranscriptionOverrides {
+            language: Some(LanguageConfig::Single("de".to_string())),
+            initial_prompt: None,
+            translate: Some(true),
+        };
+
+        let applied = overrides.apply_to(&config);
+        assert_eq!(applied.language, LanguageConfig::Single("de".to_string()));
+        assert_eq!(applied.initial_prompt, Some("base prompt".to_string()));
+        assert!(applied.translate);
+    }
+
+    #[test]
+    fn test_overrides_cli_takes_precedence_over_profile() {
+        let profile = Profile {
+            language: Some(LanguageConfig::Single("fr".to_string())),
+            initial_prompt: Some("profile prompt".to_string()),
+            translate: Some(true),
+            ..Default::default()
+        };
+        let overrides = TranscriptionOverrides {
+            language: Some(LanguageConfig::Single("de".to_string())),
+            ..Default::default()
+        }
+        .with_profile_defaults(&profile);
+
+        assert_eq!(
+            overrides.language,
+            Some(LanguageConfig::Single("de".to_string()))
+        );
+        assert_eq!(overrides.initial_prompt, Some("profile prompt".to_string()));
+        assert_eq!(overrides.translate, Some(true));
+    }
+}
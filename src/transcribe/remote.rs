@@ -6,7 +6,7 @@
 //! Note: Remote APIs don't support language arrays. When a language array is
 //! configured, the first/primary language is used.
 
-use super::Transcriber;
+use super::{Transcriber, TranscriptionOverrides};
 use crate::config::{LanguageConfig, WhisperConfig};
 use crate::error::TranscribeError;
 use std::io::Cursor;
@@ -14,7 +14,7 @@ use std::time::Duration;
 use ureq::serde_json;
 
 /// Remote transcriber using OpenAI-compatible Whisper API
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct RemoteTranscriber {
     /// Base endpoint URL (e.g., "http://192.168.1.100:8080")
     endpoint: String,
@@ -24,6 +24,8 @@ pub struct RemoteTranscriber {
     language: LanguageConfig,
     /// Whether to translate to English
     translate: bool,
+    /// Optional prompt to guide the transcription (sent as `prompt`)
+    initial_prompt: Option<String>,
     /// Optional API key for authentication
     api_key: Option<String>,
     /// Request timeout
@@ -96,6 +98,7 @@ impl RemoteTranscriber {
             model,
             language: config.language.clone(),
             translate: config.translate,
+            initial_prompt: config.initial_prompt.clone(),
             api_key,
             timeout,
         })
@@ -167,6 +170,14 @@ impl RemoteTranscriber {
             body.extend_from_slice(b"\r\n");
         }
 
+        // Add prompt field (if configured)
+        if let Some(ref prompt) = self.initial_prompt {
+            body.extend_from_slice(format!("--{}\r\n", boundary).as_bytes());
+            body.extend_from_slice(b"Content-Disposition: form-data; name=\"prompt\"\r\n\r\n");
+            body.extend_from_slice(prompt.as_bytes());
+            body.extend_from_slice(b"\r\n");
+        }
+
         // Add response_format field
         body.extend_from_slice(format!("--{}\r\n", boundary).as_bytes());
         body.extend_from_slice(b"Content-Disposition: form-data; name=\"response_format\"\r\n\r\n");
@@ -259,6 +270,35 @@ impl Transcriber for RemoteTranscriber {
 
         Ok(text)
     }
+
+    fn transcribe_with_overrides(
+        &self,
+        samples: &[f32],
+        overrides: &TranscriptionOverrides,
+    ) -> Result<String, TranscribeError> {
+        if overrides.is_empty() {
+            return self.transcribe(samples);
+        }
+
+        let mut effective = self.clone();
+        if let Some(ref language) = overrides.language {
+            if language.is_multiple() {
+                tracing::warn!(
+                    "Remote backend doesn't support language arrays. Using primary language '{}' from {:?}",
+                    language.primary(),
+                    language.as_vec()
+                );
+            }
+            effective.language = language.clone();
+        }
+        if let Some(ref prompt) = overrides.initial_prompt {
+            effective.initial_prompt = Some(prompt.clone());
+        }
+        if let Some(translate) = overrides.translate {
+            effective.translate = translate;
+        }
+        effective.transcribe(samples)
+    }
 }
 
 #[cfg(test)]
@@ -345,6 +385,36 @@ mod tests {
         assert!(body_str.contains("json"));
     }
 
+    #[test]
+    fn test_multipart_body_includes_prompt() {
+        let config = WhisperConfig {
+            mode: Some(crate::config::WhisperMode::Remote),
+            remote_endpoint: Some("http://localhost:8080".to_string()),
+            initial_prompt: Some("Kubernetes and Rust".to_string()),
+            ..Default::default()
+        };
+
+        let transcriber = RemoteTranscriber::new(&config).unwrap();
+        let (_, body) = transcriber.build_multipart_body(&[0u8; 10]);
+        let body_str = String::from_utf8_lossy(&body);
+
+        assert!(body_str.contains("name=\"prompt\""));
+        assert!(body_str.contains("Kubernetes and Rust"));
+    }
+
+    #[test]
+    fn test_multipart_body_omits_prompt_when_unset() {
+        let config = WhisperConfig {
+            mode: Some(crate::config::WhisperMode::Remote),
+            remote_endpoint: Some("http://localhost:8080".to_string()),
+            ..Default::default()
+        };
+
+        let transcriber = RemoteTranscriber::new(&config).unwrap();
+        let (_, body) = transcriber.build_multipart_body(&[0u8; 10]);
+        assert!(!String::from_utf8_lossy(&body).contains("name=\"prompt\""));
+    }
+
     #[test]
     fn test_translate_false_uses_transcriptions_endpoint() {
         let config = WhisperConfig {
@@ -17,7 +17,7 @@
 //! - Perceived latency is just transcription time, not model load + transcription
 
 use super::worker::READY_SIGNAL;
-use super::Transcriber;
+use super::{Transcriber, TranscriptionOverrides};
 use crate::config::WhisperConfig;
 use crate::error::TranscribeError;
 use std::io::{BufRead, BufReader, Read, Write};
@@ -55,8 +55,8 @@ pub struct SubprocessTranscriber {
     config: WhisperConfig,
     /// Path to the config file (if any)
     config_path: Option<std::path::PathBuf>,
-    /// Pre-spawned worker (from prepare())
-    prepared_worker: Mutex<Option<PreparedWorker>>,
+    /// Pre-spawned worker (from prepare()), with the overrides it was spawned for
+    prepared_worker: Mutex<Option<(PreparedWorker, TranscriptionOverrides)>>,
 }
 
 impl SubprocessTranscriber {
@@ -80,8 +80,12 @@ impl SubprocessTranscriber {
     }
 
     /// Build the command to spawn a worker
-    fn build_worker_command(&self) -> Result<Command, TranscribeError> {
+    fn build_worker_command(
+        &self,
+        overrides: &TranscriptionOverrides,
+    ) -> Result<Command, TranscribeError> {
         let exe_path = Self::get_executable_path()?;
+        let config = overrides.apply_to(&self.config);
 
         let mut cmd = Command::new(&exe_path);
         cmd.arg("transcribe-worker")
@@ -95,15 +99,22 @@ impl SubprocessTranscriber {
         }
 
         // Pass essential config via command-line arguments
-        cmd.arg("--model").arg(&self.config.model);
+        cmd.arg("--model").arg(&config.model);
         // Serialize language config as comma-separated string for CLI
         // Single: "en", Auto: "auto", Multiple: "en,fr,de"
-        let language_str = self.config.language.as_vec().join(",");
+        let language_str = config.language.as_vec().join(",");
         cmd.arg("--language").arg(&language_str);
-        if self.config.translate {
+        // Pass translate explicitly both ways, so an override can also
+        // disable translation enabled in the config file
+        if config.translate {
             cmd.arg("--translate");
+        } else {
+            cmd.arg("--no-translate");
+        }
+        if let Some(ref prompt) = config.initial_prompt {
+            cmd.arg("--initial-prompt").arg(prompt);
         }
-        if let Some(threads) = self.config.threads {
+        if let Some(threads) = config.threads {
             cmd.arg("--threads").arg(threads.to_string());
         }
 
@@ -111,8 +122,11 @@ impl SubprocessTranscriber {
     }
 
     /// Spawn a worker process and wait for it to be ready
-    fn spawn_and_wait_ready(&self) -> Result<PreparedWorker, TranscribeError> {
-        let mut cmd = self.build_worker_command()?;
+    fn spawn_and_wait_ready(
+        &self,
+        overrides: &TranscriptionOverrides,
+    ) -> Result<PreparedWorker, TranscribeError> {
+        let mut cmd = self.build_worker_command(overrides)?;
 
         let mut child = cmd.spawn().map_err(|e| {
             TranscribeError::InitFailed(format!("Failed to spawn transcribe-worker: {}", e))
@@ -207,13 +221,17 @@ impl SubprocessTranscriber {
 
 impl Transcriber for SubprocessTranscriber {
     fn prepare(&self) {
+        self.prepare_with_overrides(&TranscriptionOverrides::default());
+    }
+
+    fn prepare_with_overrides(&self, overrides: &TranscriptionOverrides) {
         tracing::debug!("Preparing subprocess transcriber (spawning worker)...");
         let start = std::time::Instant::now();
 
-        match self.spawn_and_wait_ready() {
+        match self.spawn_and_wait_ready(overrides) {
             Ok(worker) => {
                 let mut guard = self.prepared_worker.lock().unwrap();
-                *guard = Some(worker);
+                *guard = Some((worker, overrides.clone()));
                 tracing::info!(
                     "Worker prepared in {:.2}s (model loaded while recording)",
                     start.elapsed().as_secs_f32()
@@ -226,6 +244,14 @@ impl Transcriber for SubprocessTranscriber {
     }
 
     fn transcribe(&self, samples: &[f32]) -> Result<String, TranscribeError> {
+        self.transcribe_with_overrides(samples, &TranscriptionOverrides::default())
+    }
+
+    fn transcribe_with_overrides(
+        &self,
+        samples: &[f32],
+        overrides: &TranscriptionOverrides,
+    ) -> Result<String, TranscribeError> {
         if samples.is_empty() {
             return Err(TranscribeError::AudioFormat(
                 "Empty audio buffer".to_string(),
@@ -235,21 +261,32 @@ impl Transcriber for SubprocessTranscriber {
         let duration_secs = samples.len() as f32 / 16000.0;
 
         // Try to use prepared worker, or spawn a new one
+        // A worker prepared with different overrides can't be reused, since
+        // language/prompt/translate are passed on its command line
         let mut prepared = self.prepared_worker.lock().unwrap();
         let mut worker = match prepared.take() {
-            Some(w) => {
+            Some((w, prepared_overrides)) if &prepared_overrides == overrides => {
                 tracing::debug!(
                     "Using pre-spawned worker for {:.2}s of audio",
                     duration_secs
                 );
                 w
             }
+            Some((mut stale, _)) => {
+                tracing::debug!(
+                    "Prepared worker has different overrides, spawning new one for {:.2}s of audio",
+                    duration_secs
+                );
+                let _ = stale.child.kill();
+                let _ = stale.child.wait();
+                self.spawn_and_wait_ready(overrides)?
+            }
             None => {
                 tracing::debug!(
                     "No prepared worker, spawning new one for {:.2}s of audio",
                     duration_secs
                 );
-                self.spawn_and_wait_ready()?
+                self.spawn_and_wait_ready(overrides)?
             }
         };
         drop(prepared); // Release lock
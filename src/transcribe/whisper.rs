@@ -7,10 +7,12 @@
 //! - Auto-detect: Let Whisper detect from all ~99 supported languages
 //! - Constrained auto-detect: Detect from a user-specified subset of languages
 
-use super::Transcriber;
-use crate::config::{Config, LanguageConfig, WhisperConfig};
+use super::{Transcriber, TranscriptionOverrides};
+use crate::config::{Config, LanguageConfig, TranscriptionEngine, WhisperConfig};
 use crate::error::TranscribeError;
+use crate::model_registry::{registry, ModelRegistry};
 use std::path::PathBuf;
+use std::sync::Mutex;
 use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
 
 /// Whisper-based transcriber
@@ -27,6 +29,8 @@ pub struct WhisperTranscriber {
     context_window_optimization: bool,
     /// Initial prompt to provide context for transcription
     initial_prompt: Option<String>,
+    /// Language of the most recent transcription (detected or configured)
+    last_language: Mutex<Option<String>>,
 }
 
 impl WhisperTranscriber {
@@ -56,6 +60,7 @@ impl WhisperTranscriber {
             threads,
             context_window_optimization: config.context_window_optimization,
             initial_prompt: config.initial_prompt.clone(),
+            last_language: Mutex::new(None),
         })
     }
 
@@ -122,6 +127,26 @@ impl WhisperTranscriber {
 
 impl Transcriber for WhisperTranscriber {
     fn transcribe(&self, samples: &[f32]) -> Result<String, TranscribeError> {
+        self.transcribe_with_overrides(samples, &TranscriptionOverrides::default())
+    }
+
+    fn last_language(&self) -> Option<String> {
+        self.last_language.lock().ok().and_then(|last| last.clone())
+    }
+
+    fn transcribe_with_overrides(
+        &self,
+        samples: &[f32],
+        overrides: &TranscriptionOverrides,
+    ) -> Result<String, TranscribeError> {
+        // Per-recording overrides take precedence over the loaded configuration
+        let language = overrides.language.as_ref().unwrap_or(&self.language);
+        let translate = overrides.translate.unwrap_or(self.translate);
+        let initial_prompt = overrides
+            .initial_prompt
+            .as_ref()
+            .or(self.initial_prompt.as_ref());
+
         if samples.is_empty() {
             return Err(TranscribeError::AudioFormat(
                 "Empty audio buffer".to_string(),
@@ -144,18 +169,18 @@ impl Transcriber for WhisperTranscriber {
             .map_err(|e| TranscribeError::InferenceFailed(e.to_string()))?;
 
         // Determine language based on configuration mode
-        let selected_language: Option<String> = if self.language.is_auto() {
+        let selected_language: Option<String> = if language.is_auto() {
             // Unconstrained auto-detection: let Whisper detect from all languages
             tracing::debug!("Using unconstrained language auto-detection");
             None
-        } else if self.language.is_multiple() {
+        } else if language.is_multiple() {
             // Constrained auto-detection: detect from allowed set only
-            let allowed = self.language.as_vec();
+            let allowed = language.as_vec();
             tracing::debug!("Using constrained language detection from: {:?}", allowed);
             Some(self.select_language_from_allowed(&mut state, samples, &allowed)?)
         } else {
             // Single language: use it directly
-            let lang = self.language.primary().to_string();
+            let lang = language.primary().to_string();
             tracing::debug!("Using specified language: {}", lang);
             Some(lang)
         };
@@ -169,7 +194,7 @@ impl Transcriber for WhisperTranscriber {
             None => params.set_language(None),
         }
 
-        params.set_translate(self.translate);
+        params.set_translate(translate);
         params.set_n_threads(self.threads as i32);
 
         // Disable output we don't need
@@ -183,7 +208,7 @@ impl Transcriber for WhisperTranscriber {
         params.set_suppress_nst(true);
 
         // Set initial prompt if configured
-        if let Some(prompt) = &self.initial_prompt {
+        if let Some(prompt) = initial_prompt {
             params.set_initial_prompt(prompt);
             tracing::debug!("Using initial prompt: {:?}", prompt);
         }
@@ -214,6 +239,19 @@ impl Transcriber for WhisperTranscriber {
             .full(params, samples)
             .map_err(|e| TranscribeError::InferenceFailed(e.to_string()))?;
 
+        // Remember the output language for language-specific text processing
+        // (translation always produces English)
+        let output_language = if translate {
+            Some("en".to_string())
+        } else {
+            selected_language.or_else(|| {
+                whisper_rs::get_lang_str(state.full_lang_id_from_state()).map(str::to_string)
+            })
+        };
+        if let Ok(mut last) = self.last_language.lock() {
+            *last = output_language;
+        }
+
         // Collect all segments using iterator API
         let mut text = String::new();
         for segment in state.as_iter() {
@@ -241,54 +279,46 @@ impl Transcriber for WhisperTranscriber {
 }
 
 /// Resolve model name to file path
-fn resolve_model_path(model: &str) -> Result<PathBuf, TranscribeError> {
+pub(crate) fn resolve_model_path(model: &str) -> Result<PathBuf, TranscribeError> {
     // If it's already an absolute path, use it directly
     let path = PathBuf::from(model);
     if path.is_absolute() && path.exists() {
         return Ok(path);
     }
 
-    // Map model names to file names
-    let model_filename = match model {
-        "tiny" => "ggml-tiny.bin",
-        "tiny.en" => "ggml-tiny.en.bin",
-        "base" => "ggml-base.bin",
-        "base.en" => "ggml-base.en.bin",
-        "small" => "ggml-small.bin",
-        "small.en" => "ggml-small.en.bin",
-        "medium" => "ggml-medium.bin",
-        "medium.en" => "ggml-medium.en.bin",
-        "large" | "large-v1" => "ggml-large-v1.bin",
-        "large-v2" => "ggml-large-v2.bin",
-        "large-v3" => "ggml-large-v3.bin",
-        "large-v3-turbo" => "ggml-large-v3-turbo.bin",
+    // Map model names to file names via the model registry
+    let model_filename = if let Some(entry) = registry().get(model) {
+        entry.primary_file().to_string()
+    } else if model.ends_with(".bin") {
         // If it looks like a filename, use it as-is
-        other if other.ends_with(".bin") => other,
-        // Otherwise, assume it's a model name and add prefix/suffix
-        other => {
-            return Err(TranscribeError::ModelNotFound(format!(
-                "Unknown model: '{}'. Valid models: tiny, base, small, medium, large-v3, large-v3-turbo",
-                other
-            )));
-        }
+        model.to_string()
+    } else {
+        return Err(TranscribeError::ModelNotFound(format!(
+            "Unknown model: '{}'. Valid models: {}\nAdd custom models to {}",
+            model,
+            registry().names(TranscriptionEngine::Whisper).join(", "),
+            ModelRegistry::user_manifest_path()
+                .map(|p| p.display().to_string())
+                .unwrap_or_else(|| "models.toml".to_string())
+        )));
     };
 
     // Look in the data directory
     let models_dir = Config::models_dir();
-    let model_path = models_dir.join(model_filename);
+    let model_path = models_dir.join(&model_filename);
 
     if model_path.exists() {
         return Ok(model_path);
     }
 
     // Also check current directory
-    let cwd_path = PathBuf::from(model_filename);
+    let cwd_path = PathBuf::from(&model_filename);
     if cwd_path.exists() {
         return Ok(cwd_path);
     }
 
     // Also check ./models/
-    let local_models_path = PathBuf::from("models").join(model_filename);
+    let local_models_path = PathBuf::from("models").join(&model_filename);
     if local_models_path.exists() {
         return Ok(local_models_path);
     }
@@ -329,30 +359,21 @@ fn calculate_audio_ctx(duration_secs: f32) -> Option<i32> {
 
 /// Get the filename for a model
 pub fn get_model_filename(model: &str) -> String {
-    match model {
-        "tiny" => "ggml-tiny.bin",
-        "tiny.en" => "ggml-tiny.en.bin",
-        "base" => "ggml-base.bin",
-        "base.en" => "ggml-base.en.bin",
-        "small" => "ggml-small.bin",
-        "small.en" => "ggml-small.en.bin",
-        "medium" => "ggml-medium.bin",
-        "medium.en" => "ggml-medium.en.bin",
-        "large-v3" => "ggml-large-v3.bin",
-        "large-v3-turbo" => "ggml-large-v3-turbo.bin",
-        other => other,
-    }
-    .to_string()
+    registry()
+        .get(model)
+        .map(|entry| entry.primary_file().to_string())
+        .unwrap_or_else(|| model.to_string())
 }
 
 /// Get the download URL for a model
 pub fn get_model_url(model: &str) -> String {
-    let filename = get_model_filename(model);
-
-    format!(
-        "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/{}",
-        filename
-    )
+    match registry().get(model) {
+        Some(entry) => entry.file_url(&entry.files[0]),
+        None => format!(
+            "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/{}",
+            model
+        ),
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,89 @@
+//! Focused window lookup through a user command
+//!
+//! For compositors without built-in support (or X11 with `xdotool`), the
+//! command prints the focused window as a JSON object:
+//!
+//! ```json
+//! {"app_id": "org.gnome.Terminal", "class": "Gnome-terminal", "title": "~"}
+//! ```
+//!
+//! All fields are optional. Printing nothing means no window has focus.
+
+use super::{WindowInfo, WindowInfoProvider};
+use crate::error::WindowError;
+use std::process::Stdio;
+use tokio::process::Command;
+
+/// Focused window from a shell command
+pub struct CommandProvider {
+    command: String,
+}
+
+impl CommandProvider {
+    pub fn new(command: &str) -> Self {
+        Self {
+            command: command.to_string(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl WindowInfoProvider for CommandProvider {
+    async fn active_window(&self) -> Result<Option<WindowInfo>, WindowError> {
+        let output = Command::new("sh")
+            .args(["-c", &self.command])
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .output()
+            .await
+            .map_err(|e| WindowError::Command(e.to_string()))?;
+        if !output.status.success() {
+            return Err(WindowError::Command(format!(
+                "'{}' exited with {}",
+                self.command, output.status
+            )));
+        }
+        parse_output(&String::from_utf8_lossy(&output.stdout))
+    }
+
+    fn name(&self) -> &'static str {
+        "command"
+    }
+}
+
+fn parse_output(stdout: &str) -> Result<Option<WindowInfo>, WindowError> {
+    let stdout = stdout.trim();
+    if stdout.is_empty() {
+        return Ok(None);
+    }
+    serde_json::from_str(stdout)
+        .map(Some)
+        .map_err(|e| WindowError::InvalidReply("command", e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_command_provider() {
+        let provider = CommandProvider::new(r#"echo '{"class": "Code", "title": "main.rs"}'"#);
+        let window = provider.active_window().await.unwrap().unwrap();
+        assert_eq!(window.app(), Some("Code"));
+        assert_eq!(window.title.as_deref(), Some("main.rs"));
+
+        assert_eq!(
+            CommandProvider::new("true").active_window().await.unwrap(),
+            None
+        );
+        assert!(CommandProvider::new("exit 1")
+            .active_window()
+            .await
+            .is_err());
+        assert!(CommandProvider::new("echo nope")
+            .active_window()
+            .await
+            .is_err());
+    }
+}
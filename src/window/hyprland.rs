@@ -0,0 +1,140 @@
+//! Hyprland focused window lookup
+//!
+//! Sends `j/activewindow` to the request socket at
+//! `$XDG_RUNTIME_DIR/hypr/$HYPRLAND_INSTANCE_SIGNATURE/.socket.sock`
+//! (`/tmp/hypr/...` on older releases).
+
+use super::{WindowInfo, WindowInfoProvider};
+use crate::error::WindowError;
+use serde::Deserialize;
+use std::path::PathBuf;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::UnixStream;
+
+const NAME: &str = "Hyprland";
+
+/// Focused window from Hyprland's IPC socket
+pub struct HyprlandProvider {
+    /// Socket path; found from the environment when None
+    socket: Option<PathBuf>,
+}
+
+impl HyprlandProvider {
+    pub fn new() -> Self {
+        Self { socket: None }
+    }
+
+    /// Use a specific request socket
+    pub fn with_socket(socket: PathBuf) -> Self {
+        Self {
+            socket: Some(socket),
+        }
+    }
+
+    fn socket_path(&self) -> Option<PathBuf> {
+        if let Some(ref socket) = self.socket {
+            return Some(socket.clone());
+        }
+        let signature = std::env::var("HYPRLAND_INSTANCE_SIGNATURE").ok()?;
+        let mut candidates = Vec::new();
+        if let Ok(runtime) = std::env::var("XDG_RUNTIME_DIR") {
+            candidates.push(PathBuf::from(runtime).join("hypr"));
+        }
+        candidates.push(PathBuf::from("/tmp/hypr"));
+        candidates
+            .into_iter()
+            .map(|dir| dir.join(&signature).join(".socket.sock"))
+            .find(|path| path.exists())
+    }
+}
+
+impl Default for HyprlandProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl WindowInfoProvider for HyprlandProvider {
+    async fn active_window(&self) -> Result<Option<WindowInfo>, WindowError> {
+        let path = self.socket_path().ok_or(WindowError::NoSocket(NAME))?;
+        let ipc = |e: std::io::Error| WindowError::Ipc(NAME, e.to_string());
+
+        let mut stream = UnixStream::connect(&path).await.map_err(ipc)?;
+        stream.write_all(b"j/activewindow").await.map_err(ipc)?;
+        let mut reply = Vec::new();
+        stream.read_to_end(&mut reply).await.map_err(ipc)?;
+        parse_active_window(&reply)
+    }
+
+    fn name(&self) -> &'static str {
+        "hyprland"
+    }
+}
+
+#[derive(Deserialize)]
+struct ActiveWindow {
+    #[serde(default)]
+    class: Option<String>,
+    #[serde(default)]
+    title: Option<String>,
+}
+
+/// Parse the `j/activewindow` reply; `{}` means no window has focus
+/// Hyprland reports the app id of native windows as their class
+fn parse_active_window(reply: &[u8]) -> Result<Option<WindowInfo>, WindowError> {
+    let window: ActiveWindow = serde_json::from_slice(reply)
+        .map_err(|e| WindowError::InvalidReply(NAME, e.to_string()))?;
+    let class = window.class.filter(|c| !c.is_empty());
+    if class.is_none() && window.title.is_none() {
+        return Ok(None);
+    }
+    Ok(Some(WindowInfo {
+        app_id: class.clone(),
+        class,
+        title: window.title,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_active_window() {
+        let reply = br#"{"address": "0x55d1", "class": "kitty", "title": "~/src", "pid": 42}"#;
+        let window = parse_active_window(reply).unwrap().unwrap();
+        assert_eq!(window.app_id.as_deref(), Some("kitty"));
+        assert_eq!(window.class.as_deref(), Some("kitty"));
+        assert_eq!(window.title.as_deref(), Some("~/src"));
+
+        assert_eq!(parse_active_window(b"{}").unwrap(), None);
+        assert!(parse_active_window(b"unknown request").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_request() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".socket.sock");
+        let listener = tokio::net::UnixListener::bind(&path).unwrap();
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 14];
+            stream.read_exact(&mut request).await.unwrap();
+            assert_eq!(&request, b"j/activewindow");
+            stream
+                .write_all(br#"{"class": "firefox", "title": "Gmail"}"#)
+                .await
+                .unwrap();
+        });
+
+        let window = HyprlandProvider::with_socket(path)
+            .active_window()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(window.app(), Some("firefox"));
+        assert_eq!(window.title.as_deref(), Some("Gmail"));
+        server.await.unwrap();
+    }
+}
@@ -0,0 +1,336 @@
+//! Focused window lookup for automatic profile selection
+//!
+//! When a recording starts without `--profile`, the daemon asks where the
+//! focus is and uses the profile of the first matching
+//! `[[auto_profile.rules]]` entry. Window info comes from:
+//! - Hyprland: `j/activewindow` on the IPC socket
+//! - Sway: the focused node of the i3 IPC tree
+//! - niri: a `FocusedWindow` request on the IPC socket
+//! - command: a user command printing `{"app_id", "class", "title"}` as JSON
+//!
+//! `provider = "auto"` picks the compositor from its environment variables.
+
+pub mod command;
+pub mod hyprland;
+pub mod niri;
+pub mod sway;
+
+use crate::config::{AutoProfileConfig, WindowProvider, WindowRule};
+use crate::error::WindowError;
+use regex::{Regex, RegexBuilder};
+use serde::Deserialize;
+use std::time::Duration;
+
+/// The focused window
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct WindowInfo {
+    /// Wayland app id
+    #[serde(default)]
+    pub app_id: Option<String>,
+    /// X11 class (WM_CLASS) of XWayland windows
+    #[serde(default)]
+    pub class: Option<String>,
+    /// Window title
+    #[serde(default)]
+    pub title: Option<String>,
+}
+
+impl WindowInfo {
+    /// Application name for scripts: the app id, else the class
+    pub fn app(&self) -> Option<&str> {
+        self.app_id.as_deref().or(self.class.as_deref())
+    }
+}
+
+/// Source of focused window information
+#[async_trait::async_trait]
+pub trait WindowInfoProvider: Send + Sync {
+    /// The focused window, or None when no window has focus
+    async fn active_window(&self) -> Result<Option<WindowInfo>, WindowError>;
+
+    /// Human-readable name for logging
+    fn name(&self) -> &'static str;
+}
+
+/// Create the configured provider
+/// Returns None if "auto" finds no supported compositor, or "command" has no command
+pub fn create_provider(config: &AutoProfileConfig) -> Option<Box<dyn WindowInfoProvider>> {
+    match config.provider {
+        WindowProvider::Auto => detect_provider(),
+        WindowProvider::Hyprland => Some(Box::new(hyprland::HyprlandProvider::new())),
+        WindowProvider::Sway => Some(Box::new(sway::SwayProvider::new())),
+        WindowProvider::Niri => Some(Box::new(niri::NiriProvider::new())),
+        WindowProvider::Command => match config.command {
+            Some(ref command) => Some(Box::new(command::CommandProvider::new(command))),
+            None => {
+                tracing::warn!("auto_profile provider \"command\" needs a command");
+                None
+            }
+        },
+    }
+}
+
+/// Pick the provider for the running compositor
+fn detect_provider() -> Option<Box<dyn WindowInfoProvider>> {
+    let set = |name: &str| std::env::var_os(name).is_some_and(|v| !v.is_empty());
+    if set("HYPRLAND_INSTANCE_SIGNATURE") {
+        Some(Box::new(hyprland::HyprlandProvider::new()))
+    } else if set("SWAYSOCK") {
+        Some(Box::new(sway::SwayProvider::new()))
+    } else if set("NIRI_SOCKET") {
+        Some(Box::new(niri::NiriProvider::new()))
+    } else {
+        None
+    }
+}
+
+/// A rule with its patterns compiled
+struct CompiledWindowRule {
+    app_id: Option<Regex>,
+    class: Option<Regex>,
+    title: Option<Regex>,
+    profile: String,
+}
+
+impl CompiledWindowRule {
+    fn compile(rule: &WindowRule) -> Result<Self, regex::Error> {
+        let whole = |pattern: &str| {
+            RegexBuilder::new(&format!("^(?:{})$", pattern))
+                .case_insensitive(true)
+                .build()
+        };
+        Ok(Self {
+            app_id: rule.app_id.as_deref().map(whole).transpose()?,
+            class: rule.class.as_deref().map(whole).transpose()?,
+            title: rule.title.as_deref().map(Regex::new).transpose()?,
+            profile: rule.profile.clone(),
+        })
+    }
+
+    /// Every pattern matches (and there is at least one)
+    fn matches(&self, window: &WindowInfo) -> bool {
+        let field = |pattern: &Option<Regex>, value: &Option<String>| match pattern {
+            Some(pattern) => value.as_deref().is_some_and(|v| pattern.is_match(v)),
+            None => true,
+        };
+        (self.app_id.is_some() || self.class.is_some() || self.title.is_some())
+            && field(&self.app_id, &window.app_id)
+            && field(&self.class, &window.class)
+            && field(&self.title, &window.title)
+    }
+}
+
+/// Picks a profile for the focused window
+pub struct ProfileSelector {
+    provider: Box<dyn WindowInfoProvider>,
+    rules: Vec<CompiledWindowRule>,
+    timeout: Duration,
+}
+
+impl ProfileSelector {
+    /// Compile the rules; invalid rules are skipped with a warning
+    pub fn new(
+        provider: Box<dyn WindowInfoProvider>,
+        rules: &[WindowRule],
+        timeout: Duration,
+    ) -> Self {
+        let rules = rules
+            .iter()
+            .filter_map(|rule| match CompiledWindowRule::compile(rule) {
+                Ok(rule) => Some(rule),
+                Err(e) => {
+                    tracing::warn!("Skipping auto_profile rule for '{}': {}", rule.profile, e);
+                    None
+                }
+            })
+            .collect();
+        Self {
+            provider,
+            rules,
+            timeout,
+        }
+    }
+
+    /// Selector for `[auto_profile]`, or None if disabled, without rules or without a provider
+    pub fn from_config(config: &AutoProfileConfig) -> Option<Self> {
+        if !config.enabled || config.rules.is_empty() {
+            return None;
+        }
+        let provider = create_provider(config);
+        if provider.is_none() {
+            tracing::warn!("auto_profile: no window info provider available");
+        }
+        Some(Self::new(
+            provider?,
+            &config.rules,
+            Duration::from_millis(config.timeout_ms),
+        ))
+    }
+
+    /// Name of the window info provider
+    pub fn provider_name(&self) -> &'static str {
+        self.provider.name()
+    }
+
+    /// Look up the focused window; failures are logged and give None
+    pub async fn active_window(&self) -> Option<WindowInfo> {
+        match tokio::time::timeout(self.timeout, self.provider.active_window()).await {
+            Ok(Ok(window)) => window,
+            Ok(Err(e)) => {
+                tracing::warn!("Focused window lookup failed: {}", e);
+                None
+            }
+            Err(_) => {
+                tracing::warn!(
+                    "Focused window lookup via {} timed out after {} ms",
+                    self.provider.name(),
+                    self.timeout.as_millis()
+                );
+                None
+            }
+        }
+    }
+
+    /// Profile of the first rule matching the window
+    pub fn select(&self, window: &WindowInfo) -> Option<&str> {
+        self.rules
+            .iter()
+            .find(|rule| rule.matches(window))
+            .map(|rule| rule.profile.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Provider reporting a fixed window, optionally after a delay
+    struct FakeProvider {
+        window: Option<WindowInfo>,
+        delay: Duration,
+    }
+
+    #[async_trait::async_trait]
+    impl WindowInfoProvider for FakeProvider {
+        async fn active_window(&self) -> Result<Option<WindowInfo>, WindowError> {
+            tokio::time::sleep(self.delay).await;
+            Ok(self.window.clone())
+        }
+
+        fn name(&self) -> &'static str {
+            "fake"
+        }
+    }
+
+    fn window(app_id: Option<&str>, class: Option<&str>, title: &str) -> WindowInfo {
+        WindowInfo {
+            app_id: app_id.map(str::to_string),
+            class: class.map(str::to_string),
+            title: Some(title.to_string()),
+        }
+    }
+
+    fn rule(
+        app_id: Option<&str>,
+        class: Option<&str>,
+        title: Option<&str>,
+        profile: &str,
+    ) -> WindowRule {
+        WindowRule {
+            app_id: app_id.map(str::to_string),
+            class: class.map(str::to_string),
+            title: title.map(str::to_string),
+            profile: profile.to_string(),
+        }
+    }
+
+    fn selector(window: Option<WindowInfo>, delay: Duration) -> ProfileSelector {
+        ProfileSelector::new(
+            Box::new(FakeProvider { window, delay }),
+            &[
+                rule(Some("kitty|foot|Alacritty"), None, None, "terminal"),
+                rule(None, Some("Slack"), None, "chat"),
+                rule(Some("firefox"), None, Some("Gmail"), "email"),
+                rule(None, None, Some("(unclosed"), "broken"),
+                rule(None, None, None, "never"),
+                rule(None, None, Some(" - Discord$"), "chat"),
+            ],
+            Duration::from_millis(100),
+        )
+    }
+
+    #[test]
+    fn test_rules() {
+        let selector = selector(None, Duration::ZERO);
+        let select = |w: WindowInfo| selector.select(&w).map(str::to_string);
+
+        // App id and class match whole names, ignoring case
+        assert_eq!(
+            select(window(Some("foot"), None, "~")).as_deref(),
+            Some("terminal")
+        );
+        assert_eq!(
+            select(window(Some("alacritty"), None, "~")).as_deref(),
+            Some("terminal")
+        );
+        assert_eq!(select(window(Some("footclient"), None, "~")), None);
+        assert_eq!(
+            select(window(None, Some("slack"), "general")).as_deref(),
+            Some("chat")
+        );
+
+        // Every pattern of a rule must match; titles match anywhere
+        assert_eq!(
+            select(window(
+                Some("firefox"),
+                None,
+                "Inbox - Gmail - Mozilla Firefox"
+            ))
+            .as_deref(),
+            Some("email")
+        );
+        assert_eq!(select(window(Some("firefox"), None, "Rust docs")), None);
+        assert_eq!(
+            select(window(Some("vesktop"), None, "#general - Discord")).as_deref(),
+            Some("chat")
+        );
+
+        // The invalid rule is skipped, the empty one never matches
+        assert_eq!(selector.rules.len(), 5);
+        assert_eq!(select(WindowInfo::default()), None);
+    }
+
+    #[tokio::test]
+    async fn test_selector_with_fake_provider() {
+        let terminal = window(Some("kitty"), None, "vim");
+        let fake = selector(Some(terminal.clone()), Duration::ZERO);
+        let window = fake.active_window().await.unwrap();
+        assert_eq!(window, terminal);
+        assert_eq!(window.app(), Some("kitty"));
+        assert_eq!(fake.select(&window), Some("terminal"));
+
+        // Nothing focused, or a provider that hangs
+        assert_eq!(selector(None, Duration::ZERO).active_window().await, None);
+        let slow = selector(Some(terminal), Duration::from_secs(10));
+        assert_eq!(slow.active_window().await, None);
+    }
+
+    #[test]
+    fn test_from_config() {
+        let mut config = AutoProfileConfig {
+            provider: WindowProvider::Command,
+            command: Some("echo {}".to_string()),
+            ..Default::default()
+        };
+        assert!(ProfileSelector::from_config(&config).is_none());
+
+        config.rules = vec![rule(Some("kitty"), None, None, "terminal")];
+        let selector = ProfileSelector::from_config(&config).unwrap();
+        assert_eq!(selector.provider_name(), "command");
+
+        config.command = None;
+        assert!(ProfileSelector::from_config(&config).is_none());
+        config.enabled = false;
+        assert!(ProfileSelector::from_config(&config).is_none());
+    }
+}
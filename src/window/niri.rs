@@ -0,0 +1,133 @@
+//! niri focused window lookup
+//!
+//! Sends the `"FocusedWindow"` request as a JSON line to `$NIRI_SOCKET` and
+//! reads a single-line reply: `{"Ok":{"FocusedWindow":{...}}}`, with `null`
+//! when no window has focus.
+
+use super::{WindowInfo, WindowInfoProvider};
+use crate::error::WindowError;
+use serde_json::Value;
+use std::path::PathBuf;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::UnixStream;
+
+const NAME: &str = "niri";
+
+/// Focused window from niri's IPC socket
+pub struct NiriProvider {
+    /// Socket path; `$NIRI_SOCKET` when None
+    socket: Option<PathBuf>,
+}
+
+impl NiriProvider {
+    pub fn new() -> Self {
+        Self { socket: None }
+    }
+
+    /// Use a specific IPC socket
+    pub fn with_socket(socket: PathBuf) -> Self {
+        Self {
+            socket: Some(socket),
+        }
+    }
+
+    fn socket_path(&self) -> Option<PathBuf> {
+        self.socket
+            .clone()
+            .or_else(|| std::env::var_os("NIRI_SOCKET").map(PathBuf::from))
+    }
+}
+
+impl Default for NiriProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl WindowInfoProvider for NiriProvider {
+    async fn active_window(&self) -> Result<Option<WindowInfo>, WindowError> {
+        let path = self.socket_path().ok_or(WindowError::NoSocket(NAME))?;
+        let ipc = |e: std::io::Error| WindowError::Ipc(NAME, e.to_string());
+
+        let mut stream = UnixStream::connect(&path).await.map_err(ipc)?;
+        stream
+            .write_all(b"\"FocusedWindow\"\n")
+            .await
+            .map_err(ipc)?;
+        let mut reply = String::new();
+        BufReader::new(stream)
+            .read_line(&mut reply)
+            .await
+            .map_err(ipc)?;
+        parse_reply(&reply)
+    }
+
+    fn name(&self) -> &'static str {
+        "niri"
+    }
+}
+
+/// Parse a FocusedWindow reply
+fn parse_reply(reply: &str) -> Result<Option<WindowInfo>, WindowError> {
+    let invalid = |message: String| WindowError::InvalidReply(NAME, message);
+    let reply: Value = serde_json::from_str(reply).map_err(|e| invalid(e.to_string()))?;
+    if let Some(err) = reply.get("Err") {
+        return Err(WindowError::Ipc(NAME, err.to_string()));
+    }
+    let window = &reply["Ok"]["FocusedWindow"];
+    if window.is_null() {
+        return Ok(None);
+    }
+    serde_json::from_value::<WindowInfo>(window.clone())
+        .map(Some)
+        .map_err(|e| invalid(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_reply() {
+        let reply = r#"{"Ok":{"FocusedWindow":{"id":3,"title":"Inbox","app_id":"thunderbird","pid":7,"workspace_id":1,"is_focused":true}}}"#;
+        let window = parse_reply(reply).unwrap().unwrap();
+        assert_eq!(window.app_id.as_deref(), Some("thunderbird"));
+        assert_eq!(window.class, None);
+        assert_eq!(window.title.as_deref(), Some("Inbox"));
+
+        assert_eq!(
+            parse_reply(r#"{"Ok":{"FocusedWindow":null}}"#).unwrap(),
+            None
+        );
+        assert!(parse_reply(r#"{"Err":"unknown request"}"#).is_err());
+        assert!(parse_reply("").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_request() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("niri.sock");
+        let listener = tokio::net::UnixListener::bind(&path).unwrap();
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut stream = BufReader::new(stream);
+            let mut request = String::new();
+            stream.read_line(&mut request).await.unwrap();
+            assert_eq!(request, "\"FocusedWindow\"\n");
+            stream
+                .get_mut()
+                .write_all(b"{\"Ok\":{\"FocusedWindow\":{\"app_id\":\"foot\",\"title\":\"~\"}}}\n")
+                .await
+                .unwrap();
+        });
+
+        let window = NiriProvider::with_socket(path)
+            .active_window()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(window.app(), Some("foot"));
+        server.await.unwrap();
+    }
+}
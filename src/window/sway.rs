@@ -0,0 +1,168 @@
+//! Sway focused window lookup
+//!
+//! Sends GET_TREE over the i3 IPC socket at `$SWAYSOCK` and walks the tree
+//! for the focused node. Messages are framed as `i3-ipc`, payload length and
+//! message type (both u32, native endian), then the JSON payload.
+
+use super::{WindowInfo, WindowInfoProvider};
+use crate::error::WindowError;
+use serde_json::Value;
+use std::path::PathBuf;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::UnixStream;
+
+const NAME: &str = "Sway";
+const MAGIC: &[u8; 6] = b"i3-ipc";
+const GET_TREE: u32 = 4;
+
+/// Focused window from Sway's IPC socket
+pub struct SwayProvider {
+    /// Socket path; `$SWAYSOCK` when None
+    socket: Option<PathBuf>,
+}
+
+impl SwayProvider {
+    pub fn new() -> Self {
+        Self { socket: None }
+    }
+
+    /// Use a specific IPC socket
+    pub fn with_socket(socket: PathBuf) -> Self {
+        Self {
+            socket: Some(socket),
+        }
+    }
+
+    fn socket_path(&self) -> Option<PathBuf> {
+        self.socket
+            .clone()
+            .or_else(|| std::env::var_os("SWAYSOCK").map(PathBuf::from))
+    }
+}
+
+impl Default for SwayProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl WindowInfoProvider for SwayProvider {
+    async fn active_window(&self) -> Result<Option<WindowInfo>, WindowError> {
+        let path = self.socket_path().ok_or(WindowError::NoSocket(NAME))?;
+        let ipc = |e: std::io::Error| WindowError::Ipc(NAME, e.to_string());
+
+        let mut stream = UnixStream::connect(&path).await.map_err(ipc)?;
+        let mut request = MAGIC.to_vec();
+        request.extend_from_slice(&0u32.to_ne_bytes());
+        request.extend_from_slice(&GET_TREE.to_ne_bytes());
+        stream.write_all(&request).await.map_err(ipc)?;
+
+        let mut header = [0u8; 14];
+        stream.read_exact(&mut header).await.map_err(ipc)?;
+        if &header[..6] != MAGIC {
+            return Err(WindowError::InvalidReply(
+                NAME,
+                "bad IPC header".to_string(),
+            ));
+        }
+        let len = u32::from_ne_bytes(header[6..10].try_into().unwrap()) as usize;
+        let mut payload = vec![0u8; len];
+        stream.read_exact(&mut payload).await.map_err(ipc)?;
+
+        let tree: Value = serde_json::from_slice(&payload)
+            .map_err(|e| WindowError::InvalidReply(NAME, e.to_string()))?;
+        Ok(focused_window(&tree))
+    }
+
+    fn name(&self) -> &'static str {
+        "sway"
+    }
+}
+
+/// Find the focused window in a GET_TREE reply
+/// Workspaces and outputs can be focused too; only views (with a pid) count
+fn focused_window(node: &Value) -> Option<WindowInfo> {
+    if node["focused"].as_bool() == Some(true) && node.get("pid").is_some() {
+        let string = |value: &Value| value.as_str().map(str::to_string);
+        return Some(WindowInfo {
+            app_id: string(&node["app_id"]),
+            class: string(&node["window_properties"]["class"]),
+            title: string(&node["name"]),
+        });
+    }
+    ["nodes", "floating_nodes"]
+        .iter()
+        .filter_map(|key| node[*key].as_array())
+        .flatten()
+        .find_map(focused_window)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree() -> Value {
+        serde_json::json!({
+            "type": "root", "focused": false, "nodes": [{
+                "type": "output", "focused": false, "nodes": [{
+                    "type": "workspace", "focused": false,
+                    "nodes": [
+                        {"type": "con", "focused": false, "pid": 10, "app_id": "foot", "name": "~"}
+                    ],
+                    "floating_nodes": [{
+                        "type": "floating_con", "focused": true, "pid": 11, "app_id": null,
+                        "name": "Slack | general",
+                        "window_properties": {"class": "Slack", "title": "Slack | general"}
+                    }]
+                }]
+            }]
+        })
+    }
+
+    #[test]
+    fn test_focused_window() {
+        let window = focused_window(&tree()).unwrap();
+        assert_eq!(window.app_id, None);
+        assert_eq!(window.class.as_deref(), Some("Slack"));
+        assert_eq!(window.app(), Some("Slack"));
+        assert_eq!(window.title.as_deref(), Some("Slack | general"));
+
+        // An empty focused workspace is not a window
+        let empty =
+            serde_json::json!({"nodes": [{"type": "workspace", "focused": true, "nodes": []}]});
+        assert_eq!(focused_window(&empty), None);
+    }
+
+    #[tokio::test]
+    async fn test_ipc_framing() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sway-ipc.sock");
+        let listener = tokio::net::UnixListener::bind(&path).unwrap();
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut header = [0u8; 14];
+            stream.read_exact(&mut header).await.unwrap();
+            assert_eq!(&header[..6], MAGIC);
+            assert_eq!(
+                u32::from_ne_bytes(header[10..14].try_into().unwrap()),
+                GET_TREE
+            );
+
+            let payload = serde_json::to_vec(&tree()).unwrap();
+            let mut reply = MAGIC.to_vec();
+            reply.extend_from_slice(&(payload.len() as u32).to_ne_bytes());
+            reply.extend_from_slice(&GET_TREE.to_ne_bytes());
+            reply.extend_from_slice(&payload);
+            stream.write_all(&reply).await.unwrap();
+        });
+
+        let window = SwayProvider::with_socket(path)
+            .active_window()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(window.class.as_deref(), Some("Slack"));
+        server.await.unwrap();
+    }
+}